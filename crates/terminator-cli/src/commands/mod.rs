@@ -1,2 +1,4 @@
 pub mod init;
+pub mod ocr;
 pub mod setup;
+pub mod snapshot;
@@ -0,0 +1,71 @@
+use anyhow::Result;
+use clap::Args;
+use terminator::{Desktop, OcrElement};
+
+#[derive(Debug, Args)]
+pub struct OcrCommand {
+    /// Process name of the application to read (e.g. "notepad")
+    #[arg(long)]
+    process: String,
+
+    /// Print the full OCR result (lines and words with screen bounds) as JSON
+    #[arg(long)]
+    json: bool,
+}
+
+impl OcrCommand {
+    pub async fn execute(&self) -> Result<()> {
+        let desktop = Desktop::new(false, false)?;
+        let pid = terminator::find_pid_for_process(&desktop, &self.process)?;
+        let result = desktop.ocr_window_with_bounds(pid)?;
+
+        if self.json {
+            println!("{}", serde_json::to_string_pretty(&result)?);
+        } else {
+            for line in format_lines(&result) {
+                println!("{line}");
+            }
+        }
+        Ok(())
+    }
+}
+
+/// One `x,y wxh  text` row per recognized line, in reading order
+fn format_lines(result: &OcrElement) -> Vec<String> {
+    result
+        .children
+        .iter()
+        .flatten()
+        .filter_map(|line| {
+            let text = line.text.as_deref()?;
+            Some(match line.bounds {
+                Some((x, y, w, h)) => format!("{x:.0},{y:.0} {w:.0}x{h:.0}\t{text}"),
+                None => format!("?\t{text}"),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_format_lines_lists_text_with_bounds() {
+        let result: OcrElement = serde_json::from_value(json!({
+            "role": "OcrResult",
+            "text": "File Edit\nUntitled",
+            "children": [
+                { "role": "OcrLine", "text": "File Edit", "bounds": [10.0, 20.4, 80.0, 16.0] },
+                { "role": "OcrLine", "text": "Untitled" }
+            ]
+        }))
+        .unwrap();
+
+        assert_eq!(
+            format_lines(&result),
+            ["10,20 80x16\tFile Edit", "?\tUntitled"]
+        );
+    }
+}
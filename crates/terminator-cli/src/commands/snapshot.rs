@@ -0,0 +1,163 @@
+use anyhow::{Context, Result};
+use clap::Args;
+use colored::*;
+use serde_json::Value;
+use std::fs;
+use std::path::PathBuf;
+use terminator::ui_tree_diff::{remove_ids, simple_ui_tree_diff};
+use terminator::{Desktop, TreeBuildConfig};
+
+#[derive(Debug, Args)]
+pub struct SnapshotCommand {
+    /// Process name of the application to capture (e.g. "notepad")
+    #[arg(long)]
+    process: String,
+
+    /// Only capture the window whose title contains this text
+    #[arg(long)]
+    title: Option<String>,
+
+    /// Save the captured tree to this file as a baseline
+    #[arg(long, conflicts_with = "compare", required_unless_present = "compare")]
+    out: Option<PathBuf>,
+
+    /// Compare the captured tree against this baseline file
+    #[arg(long)]
+    compare: Option<PathBuf>,
+
+    /// Number of changed lines tolerated before the comparison fails
+    #[arg(long, default_value_t = 0)]
+    threshold: usize,
+
+    /// Maximum tree depth to capture
+    #[arg(long)]
+    max_depth: Option<usize>,
+}
+
+impl SnapshotCommand {
+    pub async fn execute(&self) -> Result<()> {
+        let tree = self.capture()?;
+
+        if let Some(out) = &self.out {
+            fs::write(out, serde_json::to_string_pretty(&tree)?)
+                .with_context(|| format!("Failed to write snapshot to {}", out.display()))?;
+            println!(
+                "{} Saved UI snapshot of '{}' to {}",
+                "✓".green(),
+                self.process,
+                out.display()
+            );
+            return Ok(());
+        }
+
+        let Some(baseline_path) = &self.compare else {
+            return Ok(());
+        };
+        let baseline: Value = serde_json::from_str(
+            &fs::read_to_string(baseline_path)
+                .with_context(|| format!("Failed to read baseline {}", baseline_path.display()))?,
+        )
+        .with_context(|| format!("Baseline {} is not valid JSON", baseline_path.display()))?;
+
+        let Some(diff) = diff_snapshots(&baseline, &tree)? else {
+            println!(
+                "{} UI matches baseline {}",
+                "✓".green(),
+                baseline_path.display()
+            );
+            return Ok(());
+        };
+
+        for line in diff.lines() {
+            if line.starts_with('+') {
+                println!("{}", line.green());
+            } else {
+                println!("{}", line.red());
+            }
+        }
+
+        let changed = diff.lines().count();
+        if changed > self.threshold {
+            anyhow::bail!(
+                "{changed} changed lines exceed the threshold of {}",
+                self.threshold
+            );
+        }
+        println!(
+            "{} {changed} changed lines, within the threshold of {}",
+            "⚠".yellow(),
+            self.threshold
+        );
+        Ok(())
+    }
+
+    fn capture(&self) -> Result<Value> {
+        let desktop = Desktop::new(false, false)?;
+        let pid = terminator::find_pid_for_process(&desktop, &self.process)?;
+        let config = TreeBuildConfig {
+            max_depth: self.max_depth,
+            ..Default::default()
+        };
+        let tree = desktop.get_window_tree(pid, self.title.as_deref(), Some(config))?;
+        Ok(serde_json::to_value(tree)?)
+    }
+}
+
+/// Line diff between two snapshots, ignoring element ids and bounds,
+/// which change between runs without the UI changing.
+fn diff_snapshots(baseline: &Value, current: &Value) -> Result<Option<String>> {
+    let baseline = serde_json::to_string(&remove_bounds(&remove_ids(baseline)))?;
+    let current = serde_json::to_string(&remove_bounds(&remove_ids(current)))?;
+    simple_ui_tree_diff(&baseline, &current).map_err(anyhow::Error::msg)
+}
+
+fn remove_bounds(value: &Value) -> Value {
+    match value {
+        Value::Array(items) => Value::Array(items.iter().map(remove_bounds).collect()),
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .filter(|(key, _)| key.as_str() != "bounds")
+                .map(|(key, val)| (key.clone(), remove_bounds(val)))
+                .collect(),
+        ),
+        _ => value.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_diff_ignores_ids_and_bounds() {
+        let baseline = json!({
+            "id": "1",
+            "attributes": { "role": "Window", "name": "App", "bounds": [0, 0, 800, 600] },
+            "children": [{ "id": "2", "attributes": { "role": "Button", "name": "OK" } }]
+        });
+        let moved = json!({
+            "id": "7",
+            "attributes": { "role": "Window", "name": "App", "bounds": [50, 50, 800, 600] },
+            "children": [{ "id": "8", "attributes": { "role": "Button", "name": "OK" } }]
+        });
+        assert!(diff_snapshots(&baseline, &moved).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_diff_reports_changed_elements() {
+        let baseline = json!({
+            "attributes": { "role": "Window", "name": "App" },
+            "children": [{ "attributes": { "role": "Button", "name": "OK" } }]
+        });
+        let renamed = json!({
+            "attributes": { "role": "Window", "name": "App" },
+            "children": [{ "attributes": { "role": "Button", "name": "Cancel" } }]
+        });
+        let diff = diff_snapshots(&baseline, &renamed).unwrap().unwrap();
+        assert!(diff.lines().any(|l| l.starts_with('-') && l.contains("OK")));
+        assert!(diff
+            .lines()
+            .any(|l| l.starts_with('+') && l.contains("Cancel")));
+    }
+}
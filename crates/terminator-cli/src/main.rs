@@ -235,6 +235,10 @@ enum Commands {
     Setup(commands::setup::SetupCommand),
     /// Create a new TypeScript workflow project
     Init(commands::init::InitCommand),
+    /// Capture a window's UI tree as a baseline, or compare against one
+    Snapshot(commands::snapshot::SnapshotCommand),
+    /// Read a window's text with OCR and print it with screen coordinates
+    Ocr(commands::ocr::OcrCommand),
 }
 
 fn main() {
@@ -297,6 +301,30 @@ fn main() {
                     }
                 });
         }
+        Commands::Snapshot(snapshot_cmd) => {
+            tokio::runtime::Builder::new_multi_thread()
+                .enable_all()
+                .build()
+                .unwrap()
+                .block_on(async {
+                    if let Err(e) = snapshot_cmd.execute().await {
+                        eprintln!("❌ Snapshot failed: {e}");
+                        std::process::exit(1);
+                    }
+                });
+        }
+        Commands::Ocr(ocr_cmd) => {
+            tokio::runtime::Builder::new_multi_thread()
+                .enable_all()
+                .build()
+                .unwrap()
+                .block_on(async {
+                    if let Err(e) = ocr_cmd.execute().await {
+                        eprintln!("❌ OCR failed: {e}");
+                        std::process::exit(1);
+                    }
+                });
+        }
     }
 }
 
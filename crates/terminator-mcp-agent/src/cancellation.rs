@@ -1,3 +1,5 @@
+use axum::body::Body;
+use futures::StreamExt;
 use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
@@ -166,6 +168,75 @@ impl Default for RequestManager {
     }
 }
 
+/// Runs a cleanup action once an HTTP response is finished with.
+///
+/// The action gets `true` if the response was delivered in full, and `false`
+/// if it was dropped first, which is how a client disconnect shows up to the
+/// server: hyper drops the in-flight handler and response body.
+pub struct ResponseGuard {
+    on_done: Option<Box<dyn FnOnce(bool) + Send>>,
+    delivered: bool,
+}
+
+impl ResponseGuard {
+    pub fn new(on_done: impl FnOnce(bool) + Send + 'static) -> Self {
+        Self {
+            on_done: Some(Box::new(on_done)),
+            delivered: false,
+        }
+    }
+
+    /// Mark the response as fully delivered
+    pub fn delivered(&mut self) {
+        self.delivered = true;
+    }
+}
+
+impl Drop for ResponseGuard {
+    fn drop(&mut self) {
+        if let Some(on_done) = self.on_done.take() {
+            on_done(self.delivered);
+        }
+    }
+}
+
+/// Wrap a response body so `guard` and `held` (e.g. a concurrency permit) live
+/// until the body has been streamed to the end or dropped.
+///
+/// Streaming responses (SSE) keep running after the handler returns, so this is
+/// what ties a request's lifetime to the connection rather than to the handler.
+pub fn guard_body<T: Send + 'static>(body: Body, guard: ResponseGuard, held: T) -> Body {
+    let stream = futures::stream::unfold(
+        (body.into_data_stream(), guard, held),
+        |(mut stream, mut guard, held)| async move {
+            match stream.next().await {
+                Some(chunk) => Some((chunk, (stream, guard, held))),
+                None => {
+                    guard.delivered();
+                    None
+                }
+            }
+        },
+    );
+    Body::from_stream(stream)
+}
+
+/// JSON-RPC id of a request body, if it is a single request expecting a response
+pub fn jsonrpc_request_id(body: &[u8]) -> Option<serde_json::Value> {
+    let message: serde_json::Value = serde_json::from_slice(body).ok()?;
+    message.get("method")?;
+    message.get("id").filter(|id| !id.is_null()).cloned()
+}
+
+/// `notifications/cancelled` message telling the MCP service to stop the request with `id`
+pub fn cancelled_notification(id: &serde_json::Value, reason: &str) -> serde_json::Value {
+    serde_json::json!({
+        "jsonrpc": "2.0",
+        "method": "notifications/cancelled",
+        "params": { "requestId": id, "reason": reason }
+    })
+}
+
 /// Helper function to wrap an async operation with cancellation support
 pub async fn with_cancellation<F, T>(
     context: &RequestContext,
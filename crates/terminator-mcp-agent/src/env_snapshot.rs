@@ -0,0 +1,73 @@
+use crate::log_redaction::{LogRedactor, REDACTED};
+use serde_json::{json, Map, Value};
+
+/// Key fragments (case-insensitive) whose values are always hidden, whatever they contain
+pub const SENSITIVE_KEY_FRAGMENTS: &[&str] = &[
+    "password",
+    "passwd",
+    "secret",
+    "token",
+    "api_key",
+    "apikey",
+    "credential",
+    "private_key",
+    "authorization",
+    "cookie",
+];
+
+/// Whether a variable or env key names something that should never be recorded
+pub fn is_sensitive_key(key: &str) -> bool {
+    let key = key.to_lowercase();
+    SENSITIVE_KEY_FRAGMENTS
+        .iter()
+        .any(|fragment| key.contains(fragment))
+}
+
+/// `value` with sensitive keys masked at any depth and every remaining string
+/// passed through `redactor`
+pub fn redact(value: &Value, redactor: &LogRedactor) -> Value {
+    match value {
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .map(|(key, value)| {
+                    let value = if is_sensitive_key(key) {
+                        json!(REDACTED)
+                    } else {
+                        redact(value, redactor)
+                    };
+                    (key.clone(), value)
+                })
+                .collect(),
+        ),
+        Value::Array(items) => Value::Array(items.iter().map(|v| redact(v, redactor)).collect()),
+        Value::String(s) => json!(redactor.redact(s)),
+        other => other.clone(),
+    }
+}
+
+/// Redacted record of what a workflow run had in scope when it finished.
+///
+/// `context` is the sequence executor's execution context: `variables` holds its
+/// top-level entries (variable defaults, inputs and selectors) and `env` holds the
+/// env bag that scripts and `set_env` steps read and write. The `{step_id}_result`
+/// and `{step_id}_status` entries written for `step_ids` are left out of `variables`,
+/// since the sequence result already carries every step's result.
+pub fn snapshot(context: &Map<String, Value>, step_ids: &[&str], redactor: &LogRedactor) -> Value {
+    let is_step_entry = |key: &str| {
+        step_ids.iter().any(|id| {
+            key.strip_prefix(id)
+                .is_some_and(|suffix| suffix == "_result" || suffix == "_status")
+        })
+    };
+    let variables: Map<String, Value> = context
+        .iter()
+        .filter(|(key, _)| key.as_str() != "env" && !is_step_entry(key))
+        .map(|(key, value)| (key.clone(), value.clone()))
+        .collect();
+    let env = context.get("env").cloned().unwrap_or_else(|| json!({}));
+
+    json!({
+        "variables": redact(&Value::Object(variables), redactor),
+        "env": redact(&env, redactor),
+    })
+}
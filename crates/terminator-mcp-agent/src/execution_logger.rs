@@ -690,7 +690,9 @@ pub fn generate_typescript_snippet(
         "open_application" => generate_open_application_snippet(args),
         "navigate_browser" => generate_navigate_browser_snippet(args),
         "get_window_tree" => generate_get_window_tree_snippet(args),
-        "capture_screenshot" => generate_capture_screenshot_snippet(args),
+        "capture_screenshot" | "capture_element_screenshot" => {
+            generate_capture_screenshot_snippet(args)
+        }
         "run_command" => generate_run_command_snippet(args),
         "mouse_drag" => generate_mouse_drag_snippet(args),
         "scroll_element" => generate_scroll_snippet(args),
@@ -881,6 +883,22 @@ fn generate_verification_code(args: &Value) -> String {
         }
     };
 
+    // invoke_element's wait_for_selector
+    if let Some(wait_selector) = args
+        .get("wait_for_selector")
+        .and_then(|v| v.as_str())
+        .filter(|s| !s.is_empty())
+    {
+        let wait_timeout = args
+            .get("wait_timeout_ms")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(5000);
+        verification_code.push_str(&format!(
+            "\n// Wait for the result of the action\nawait desktop.locator({}).waitFor({{ timeout: {} }});",
+            build_locator(wait_selector), wait_timeout
+        ));
+    }
+
     if !verify_exists.is_empty() {
         verification_code.push_str(&format!(
             "\n// Verify element exists after action\nawait desktop.locator({}).waitFor({{ timeout: {} }});",
@@ -2524,6 +2542,23 @@ mod tests {
         assert_eq!(snippet, "await desktop.delay(2000);");
     }
 
+    #[test]
+    fn test_invoke_snippet_waits_for_selector() {
+        let args = json!({
+            "process": "notepad",
+            "selector": "role:Button|name:Save",
+            "wait_for_selector": "role:Text|name:Saved",
+            "wait_timeout_ms": 8000
+        });
+        let result = json!({"status": "executed_without_error"});
+        let ts = generate_typescript_snippet("invoke_element", &args, Ok(&result));
+
+        assert!(ts.contains(".invoke("));
+        assert!(ts.contains(
+            "await desktop.locator(\"process:notepad >> role:Text|name:Saved\").waitFor({ timeout: 8000 });"
+        ));
+    }
+
     #[test]
     fn test_generate_typescript_snippet_raw() {
         let args = json!({
@@ -175,6 +175,19 @@ pub fn build_element_not_found_error(
         return McpError::invalid_params("Windows UI Automation API failure", Some(error_details));
     }
 
+    if let Some(AutomationError::ElevationRequired(message)) =
+        original_error.downcast_ref::<AutomationError>()
+    {
+        let error_details = json!({
+            "error_type": "ElevationRequired",
+            "message": message,
+            "selector": primary_selector,
+            "suggestion": "A UAC prompt is on screen and UI Automation can't see past it. Approve or dismiss it out-of-band (or run the agent elevated), then retry."
+        });
+
+        return McpError::invalid_params("Elevation required", Some(error_details));
+    }
+
     let selectors_tried = get_selectors_tried_all(primary_selector, alternatives, fallback);
     let error_payload = json!({
         "error_type": "ElementNotFound",
@@ -494,6 +507,8 @@ pub async fn maybe_attach_tree(
     result_json: &mut Value,
     found_element: Option<&terminator::UIElement>,
     include_all_bounds: bool,
+    include_relationships: bool,
+    stable_ids: bool,
 ) -> Option<UiaBoundsCache> {
     // Check if tree should be included
     if !include_tree_after_action {
@@ -554,22 +569,17 @@ pub async fn maybe_attach_tree(
         .filter(|s| *s != "true")
         .map(|s| s.to_string());
 
+    let property_mode = if detailed {
+        terminator::platforms::PropertyLoadingMode::Complete
+    } else {
+        terminator::platforms::PropertyLoadingMode::Fast
+    };
     let tree_config = terminator::platforms::TreeBuildConfig {
-        property_mode: if detailed {
-            terminator::platforms::PropertyLoadingMode::Complete
-        } else {
-            terminator::platforms::PropertyLoadingMode::Fast
-        },
-        timeout_per_operation_ms: Some(100),
-        yield_every_n_elements: Some(25),
-        batch_size: Some(25),
-        max_depth: tree_max_depth,
         include_all_bounds,
-        ui_settle_delay_ms: None,
-        format_output: true, // Let SDK handle formatting
-        show_overlay: false,
-        overlay_display_mode: None,
         from_selector: from_selector_opt.clone(),
+        include_relationships,
+        stable_ids,
+        ..attached_tree_config(property_mode, tree_max_depth)
     };
 
     // Use SDK's async method which handles from_selector internally
@@ -639,6 +649,26 @@ pub async fn maybe_attach_tree(
 pub struct UiDiffResult {
     pub diff: String,
     pub has_changes: bool,
+    /// Saved crop of the visually changed region, when requested and pixels changed
+    pub visual_diff: Option<Value>,
+}
+
+/// Save the changed region of a visual diff and describe it for the tool response
+fn visual_diff_json(visual_diff: &terminator::VisualDiff) -> Value {
+    terminator::screenshot_logger::init();
+    let prefix = terminator::screenshot_logger::generate_prefix(Some("mcp"), "ui_diff");
+    let saved = terminator::screenshot_logger::save_screenshot(
+        &visual_diff.screenshot,
+        &prefix,
+        "visual_diff",
+        None,
+    );
+    let (x, y, width, height) = visual_diff.region;
+    json!({
+        "screenshot_path": saved.map(|s| s.path.to_string_lossy().to_string()),
+        "region": { "x": x, "y": y, "width": width, "height": height },
+        "screen_bounds": visual_diff.screen_bounds,
+    })
 }
 
 /// Find element and execute action with optional UI diff capture
@@ -655,11 +685,12 @@ pub async fn find_and_execute_with_ui_diff<F, Fut, T>(
     alternatives: Option<&str>,
     fallback_selectors: Option<&str>,
     timeout_ms: Option<u64>,
-    retries: Option<u32>,
+    retry: &crate::utils::RetryPolicy,
     action: F,
     ui_diff_before_after: bool,
     tree_max_depth: Option<usize>,
     include_detailed_attributes: Option<bool>,
+    capture_visual_diff: bool,
     _tree_output_format: TreeOutputFormat, // Now handled by backend (always compact YAML)
 ) -> Result<((T, UIElement), String, Option<UiDiffResult>), anyhow::Error>
 where
@@ -677,7 +708,7 @@ where
                 alternatives,
                 fallback_selectors,
                 timeout_ms,
-                retries,
+                retry,
                 action,
             )
             .await?;
@@ -690,14 +721,16 @@ where
         primary_selector
     );
 
-    let retry_count = retries.unwrap_or(0);
+    let retry_count = retry.retries;
     let mut last_error: Option<anyhow::Error> = None;
 
     // Build UiDiffOptions for the backend
     let diff_options = terminator::UiDiffOptions {
         max_depth: tree_max_depth,
         settle_delay_ms: Some(1500),
+        settle: None,
         include_detailed_attributes,
+        capture_visual_diff,
     };
 
     for attempt in 0..=retry_count {
@@ -722,35 +755,40 @@ where
                         let mcp_diff = ui_diff.map(|d| UiDiffResult {
                             diff: d.diff,
                             has_changes: d.has_changes,
+                            visual_diff: d.visual_diff.as_ref().map(visual_diff_json),
                         });
                         return Ok(((result, returned_element), successful_selector, mcp_diff));
                     }
                     Err(e) => {
+                        let should_retry = retry.should_retry(attempt, &e);
                         last_error = Some(e.into());
-                        if attempt < retry_count {
-                            tracing::warn!(
-                                "[ui_diff] Action failed on attempt {}/{}. Retrying... Error: {}",
-                                attempt + 1,
-                                retry_count + 1,
-                                last_error.as_ref().unwrap()
-                            );
-                            tokio::time::sleep(Duration::from_millis(250)).await;
-                            continue;
+                        if !should_retry {
+                            break;
                         }
+                        tracing::warn!(
+                            "[ui_diff] Action failed on attempt {}/{}. Retrying... Error: {}",
+                            attempt + 1,
+                            retry_count + 1,
+                            last_error.as_ref().unwrap()
+                        );
+                        tokio::time::sleep(retry.delay(attempt, 250)).await;
                     }
                 }
             }
             Err(e) => {
+                let should_retry = retry.should_retry(attempt, &e);
                 last_error = Some(e.into());
-                if attempt < retry_count {
-                    tracing::warn!(
-                        "[ui_diff] Find element failed on attempt {}/{}. Retrying... Error: {}",
-                        attempt + 1,
-                        retry_count + 1,
-                        last_error.as_ref().unwrap()
-                    );
-                    // No need to sleep here, as find_element_with_fallbacks already has a timeout.
+                if !should_retry {
+                    break;
                 }
+                tracing::warn!(
+                    "[ui_diff] Find element failed on attempt {}/{}. Retrying... Error: {}",
+                    attempt + 1,
+                    retry_count + 1,
+                    last_error.as_ref().unwrap()
+                );
+                // No default delay here, as find_element_with_fallbacks already has a timeout.
+                tokio::time::sleep(retry.delay(attempt, 0)).await;
             }
         }
     }
@@ -764,6 +802,102 @@ where
     }))
 }
 
+/// Tree settings shared by the trees attached to tool and step results: short
+/// per-element timeouts, cooperative yielding and SDK-formatted output
+fn attached_tree_config(
+    property_mode: terminator::platforms::PropertyLoadingMode,
+    max_depth: Option<usize>,
+) -> terminator::platforms::TreeBuildConfig {
+    terminator::platforms::TreeBuildConfig {
+        property_mode,
+        timeout_per_operation_ms: Some(100),
+        yield_every_n_elements: Some(25),
+        batch_size: Some(25),
+        max_depth,
+        include_all_bounds: false,
+        ui_settle_delay_ms: None,
+        format_output: true,
+        show_overlay: false,
+        overlay_display_mode: None,
+        from_selector: None,
+        include_shortcuts: false,
+        include_relationships: false,
+        stable_ids: false,
+        parallel_subtrees: None,
+    }
+}
+
+/// Depth limit for `execute_sequence`'s `capture_trees` when none is given
+pub const DEFAULT_CAPTURE_TREE_MAX_DEPTH: usize = 10;
+
+/// Compact YAML tree of the window that currently has focus, for step-by-step
+/// tree capture in `execute_sequence`
+pub async fn capture_focused_window_tree(
+    desktop: &Desktop,
+    max_depth: usize,
+) -> Result<String, String> {
+    // focused_element and process_id are blocking UIA calls
+    let focused_desktop = desktop.clone();
+    let pid = tokio::task::spawn_blocking(move || {
+        focused_desktop
+            .focused_element()
+            .and_then(|element| element.process_id())
+    })
+    .await
+    .map_err(|e| format!("Could not find the focused window: {e}"))?
+    .map_err(|e| format!("Could not find the focused window: {e}"))?;
+
+    let tree_config = attached_tree_config(
+        terminator::platforms::PropertyLoadingMode::Fast,
+        Some(max_depth),
+    );
+
+    let result = desktop
+        .get_window_tree_result_async(pid, None, Some(tree_config))
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(result
+        .formatted
+        .unwrap_or_else(|| format_ui_node_as_compact_yaml(&result.tree, 0).formatted))
+}
+
+/// Attach trees captured around a sequence step to its result as `ui_trees`.
+/// A failed capture is reported as `{"error": ...}` in its slot; when both trees
+/// were captured the diff between them is added too.
+pub fn attach_step_trees(
+    step_result: &mut Value,
+    before: Option<Result<String, String>>,
+    after: Option<Result<String, String>>,
+) {
+    let Some(obj) = step_result.as_object_mut() else {
+        return;
+    };
+    if before.is_none() && after.is_none() {
+        return;
+    }
+
+    let slot = |tree: &Result<String, String>| match tree {
+        Ok(tree) => json!(tree),
+        Err(e) => json!({ "error": e }),
+    };
+    let mut trees = serde_json::Map::new();
+    if let Some(before) = &before {
+        trees.insert("before".to_string(), slot(before));
+    }
+    if let Some(after) = &after {
+        trees.insert("after".to_string(), slot(after));
+    }
+    if let (Some(Ok(before)), Some(Ok(after))) = (&before, &after) {
+        let diff = match terminator::ui_tree_diff::simple_ui_tree_diff(before, after) {
+            Ok(Some(diff)) => json!(diff),
+            Ok(None) => json!("No UI changes detected"),
+            Err(e) => json!({ "error": e }),
+        };
+        trees.insert("diff".to_string(), diff);
+    }
+    obj.insert("ui_trees".to_string(), Value::Object(trees));
+}
+
 pub fn should_add_focus_check(tool_calls: &[ToolCall], current_index: usize) -> bool {
     // Add focus check if:
     // 1. It's the first UI interaction
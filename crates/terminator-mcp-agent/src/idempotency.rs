@@ -0,0 +1,179 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tokio::sync::watch;
+
+/// How long a completed idempotency key is remembered
+pub const DEFAULT_IDEMPOTENCY_TTL: Duration = Duration::from_secs(120);
+
+/// Tools that change UI state and so must not run twice when a client retries
+pub const IDEMPOTENT_INPUT_TOOLS: &[&str] = &[
+    "click_element",
+    "invoke_element",
+    "type_into_element",
+    "press_key",
+    "press_key_global",
+    "mouse_drag",
+    "scroll_element",
+    "select_option",
+    "set_selected",
+    "set_value",
+    "activate_element",
+];
+
+/// Store key for a tool call, if it is an input tool carrying an `idempotency_key`.
+///
+/// Keys are scoped by tool so reusing a key for a different tool still executes it.
+pub fn key_for(tool_name: &str, arguments: &serde_json::Value) -> Option<String> {
+    if !IDEMPOTENT_INPUT_TOOLS.contains(&tool_name) {
+        return None;
+    }
+    let key = arguments.get("idempotency_key")?.as_str()?.trim();
+    (!key.is_empty()).then(|| format!("{tool_name}:{key}"))
+}
+
+/// Whether a result came from running the action or from an earlier call with the same key
+#[derive(Debug, Clone, PartialEq)]
+pub enum Outcome<T> {
+    Executed(T),
+    Replayed(T),
+}
+
+impl<T> Outcome<T> {
+    pub fn into_inner(self) -> T {
+        match self {
+            Outcome::Executed(value) | Outcome::Replayed(value) => value,
+        }
+    }
+}
+
+enum Entry<T> {
+    /// The first call is still executing; the sender is dropped when it finishes
+    Running(watch::Receiver<()>),
+    Done {
+        result: T,
+        completed_at: Instant,
+    },
+}
+
+/// Recently completed input actions, keyed by client-supplied idempotency key.
+///
+/// A retry of a completed action gets the original result back without running it
+/// again. A retry that arrives while the original is still executing (the usual case
+/// after a client-side timeout) waits for it instead of starting a second run.
+/// Only successful results are remembered, so a failed action can be retried.
+pub struct IdempotencyStore<T> {
+    ttl: Duration,
+    entries: Mutex<HashMap<String, Entry<T>>>,
+}
+
+impl<T: Clone> IdempotencyStore<T> {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Run `action` unless a call with the same key already completed within the TTL.
+    pub async fn execute<E, Fut>(
+        &self,
+        key: &str,
+        action: impl FnOnce() -> Fut,
+    ) -> Result<Outcome<T>, E>
+    where
+        Fut: Future<Output = Result<T, E>>,
+    {
+        self.execute_if(key, action, |_| true).await
+    }
+
+    /// Like [`IdempotencyStore::execute`], but only remembers results for which
+    /// `succeeded` returns true. Others are handed back without being stored, so a
+    /// result that reports failure in-band can be retried like an `Err`.
+    pub async fn execute_if<E, Fut>(
+        &self,
+        key: &str,
+        action: impl FnOnce() -> Fut,
+        succeeded: impl FnOnce(&T) -> bool,
+    ) -> Result<Outcome<T>, E>
+    where
+        Fut: Future<Output = Result<T, E>>,
+    {
+        let done = loop {
+            let mut running = {
+                let mut entries = self.entries.lock().unwrap();
+                let now = Instant::now();
+                entries.retain(|_, entry| match entry {
+                    Entry::Running(_) => true,
+                    Entry::Done { completed_at, .. } => now - *completed_at < self.ttl,
+                });
+                match entries.get(key) {
+                    Some(Entry::Done { result, .. }) => {
+                        return Ok(Outcome::Replayed(result.clone()));
+                    }
+                    Some(Entry::Running(running)) => running.clone(),
+                    None => {
+                        let (done, running) = watch::channel(());
+                        entries.insert(key.to_string(), Entry::Running(running));
+                        break done;
+                    }
+                }
+            };
+            // Wait for the first call to finish, then look again
+            let _ = running.changed().await;
+        };
+
+        let guard = RunningGuard {
+            store: self,
+            key,
+            _done: done,
+        };
+        let result = action().await?;
+        if !succeeded(&result) {
+            return Ok(Outcome::Executed(result));
+        }
+        self.entries.lock().unwrap().insert(
+            key.to_string(),
+            Entry::Done {
+                result: result.clone(),
+                completed_at: Instant::now(),
+            },
+        );
+        drop(guard);
+        Ok(Outcome::Executed(result))
+    }
+
+    /// Number of keys currently remembered or running
+    pub fn len(&self) -> usize {
+        self.entries.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<T: Clone> Default for IdempotencyStore<T> {
+    fn default() -> Self {
+        Self::new(DEFAULT_IDEMPOTENCY_TTL)
+    }
+}
+
+/// Clears a running entry that never completed (the action failed or was cancelled)
+/// and wakes any duplicates waiting on it
+struct RunningGuard<'a, T> {
+    store: &'a IdempotencyStore<T>,
+    key: &'a str,
+    _done: watch::Sender<()>,
+}
+
+impl<T> Drop for RunningGuard<'_, T> {
+    fn drop(&mut self) {
+        if let Ok(mut entries) = self.store.entries.lock() {
+            if matches!(entries.get(self.key), Some(Entry::Running(_))) {
+                entries.remove(self.key);
+            }
+        }
+    }
+}
@@ -2,28 +2,35 @@ pub mod cancellation;
 pub mod child_process;
 pub mod duration_parser;
 pub mod elicitation;
+pub mod env_snapshot;
 pub mod event_pipe;
 pub mod execution_logger;
 pub mod expression_eval;
 pub mod helpers;
+pub mod idempotency;
 pub mod log_pipe;
+pub mod log_redaction;
 pub mod mcp_types;
 pub mod omniparser;
 pub mod output_parser;
 pub mod posthog;
 pub mod prompt;
+pub mod request_gate;
+pub mod response_limit;
 pub mod scripting_engine;
 pub mod sentry;
 pub mod server;
 pub mod server_sequence;
 pub mod telemetry;
 pub mod tool_logging;
+pub mod tool_timing;
 pub mod tools;
 pub mod transpiler;
 pub mod tree_formatter;
 pub mod utils;
 pub mod vision;
 pub mod workflow_typescript;
+pub mod ws_transport;
 
 // Re-export ui_tree_diff from terminator crate (single source of truth)
 pub use terminator::ui_tree_diff;
@@ -33,3 +40,4 @@ pub use terminator::{WindowCache, WindowInfo, WindowManager, WindowPlacement};
 
 // Re-export the extract_content_json function for testing
 pub use server::extract_content_json;
+pub use server::{read_only_tool_names, tool_catalog};
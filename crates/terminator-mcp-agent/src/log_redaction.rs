@@ -0,0 +1,173 @@
+use regex::Regex;
+use std::borrow::Cow;
+use std::sync::OnceLock;
+
+/// Environment variable holding extra redaction regexes, as a JSON array of strings
+pub const REDACT_PATTERNS_ENV: &str = "MCP_LOG_REDACT_PATTERNS";
+
+/// Environment variable that turns off the built-in patterns when set to `0` or `false`
+pub const REDACT_DEFAULTS_ENV: &str = "MCP_LOG_REDACT_DEFAULTS";
+
+/// What every match is replaced with
+pub const REDACTED: &str = "[REDACTED]";
+
+/// Built-in patterns: payment card numbers, US social security numbers and bearer tokens
+pub const DEFAULT_PATTERNS: &[&str] = &[
+    // 16-digit cards, optionally grouped by spaces or dashes
+    r"\b(?:\d{4}[ -]?){3}\d{4}\b",
+    // 15-digit American Express cards
+    r"\b3[47]\d{2}[ -]?\d{6}[ -]?\d{5}\b",
+    r"\b\d{3}-\d{2}-\d{4}\b",
+    r"(?i)\bbearer\s+[A-Za-z0-9\-._~+/]+=*",
+];
+
+/// Replaces sensitive-looking text in captured logs with `[REDACTED]`
+#[derive(Debug, Clone)]
+pub struct LogRedactor {
+    patterns: Vec<Regex>,
+}
+
+impl Default for LogRedactor {
+    fn default() -> Self {
+        Self::new(DEFAULT_PATTERNS).expect("built-in redaction patterns are valid")
+    }
+}
+
+impl LogRedactor {
+    /// Build a redactor from regex sources; fails on the first invalid one
+    pub fn new<S: AsRef<str>>(patterns: &[S]) -> Result<Self, regex::Error> {
+        let patterns = patterns
+            .iter()
+            .map(|p| Regex::new(p.as_ref()))
+            .collect::<Result<_, _>>()?;
+        Ok(Self { patterns })
+    }
+
+    /// The built-in patterns plus any from `MCP_LOG_REDACT_PATTERNS`, unless
+    /// `MCP_LOG_REDACT_DEFAULTS` turns the built-ins off. Invalid patterns are logged
+    /// and skipped.
+    pub fn from_env() -> Self {
+        let use_defaults = std::env::var(REDACT_DEFAULTS_ENV)
+            .map(|v| !matches!(v.trim().to_lowercase().as_str(), "0" | "false"))
+            .unwrap_or(true);
+        let extra: Vec<String> = match std::env::var(REDACT_PATTERNS_ENV) {
+            Ok(raw) => serde_json::from_str(&raw).unwrap_or_else(|e| {
+                tracing::warn!(
+                    "{} is not a JSON array of strings: {}",
+                    REDACT_PATTERNS_ENV,
+                    e
+                );
+                Vec::new()
+            }),
+            Err(_) => Vec::new(),
+        };
+
+        let defaults = if use_defaults { DEFAULT_PATTERNS } else { &[] };
+        let patterns = defaults
+            .iter()
+            .copied()
+            .chain(extra.iter().map(String::as_str))
+            .filter_map(|p| match Regex::new(p) {
+                Ok(regex) => Some(regex),
+                Err(e) => {
+                    tracing::warn!("Skipping invalid log redaction pattern {:?}: {}", p, e);
+                    None
+                }
+            })
+            .collect();
+        Self { patterns }
+    }
+
+    /// The redactor configured from the environment, built on first use
+    pub fn global() -> &'static LogRedactor {
+        static REDACTOR: OnceLock<LogRedactor> = OnceLock::new();
+        REDACTOR.get_or_init(Self::from_env)
+    }
+
+    /// `text` with every match replaced; borrowed when nothing matched
+    pub fn redact<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        let mut text = Cow::Borrowed(text);
+        for pattern in &self.patterns {
+            if let Cow::Owned(replaced) = pattern.replace_all(&text, REDACTED) {
+                text = Cow::Owned(replaced);
+            }
+        }
+        text
+    }
+
+    /// Redact every string inside a JSON value, in place
+    pub fn redact_value(&self, value: &mut serde_json::Value) {
+        match value {
+            serde_json::Value::String(s) => {
+                if let Cow::Owned(redacted) = self.redact(s) {
+                    *s = redacted;
+                }
+            }
+            serde_json::Value::Array(items) => items.iter_mut().for_each(|v| self.redact_value(v)),
+            serde_json::Value::Object(map) => map.values_mut().for_each(|v| self.redact_value(v)),
+            _ => {}
+        }
+    }
+
+    /// Redact the `logs` and `stderr` arrays of a tool result before it reaches the client,
+    /// both in JSON text content and in error data
+    pub fn redact_tool_result(
+        &self,
+        result: &mut Result<rmcp::model::CallToolResult, rmcp::ErrorData>,
+    ) {
+        match result {
+            Ok(call_result) => {
+                for content in &mut call_result.content {
+                    if let rmcp::model::RawContent::Text(text_content) = &mut content.raw {
+                        let Ok(mut value) =
+                            serde_json::from_str::<serde_json::Value>(&text_content.text)
+                        else {
+                            continue;
+                        };
+                        if self.redact_log_fields(&mut value) {
+                            if let Ok(text) = serde_json::to_string(&value) {
+                                text_content.text = text;
+                            }
+                        }
+                    }
+                }
+            }
+            Err(e) => {
+                if let Some(data) = e.data.as_mut() {
+                    self.redact_log_fields(data);
+                }
+            }
+        }
+    }
+
+    /// Redact every `logs`/`stderr` field at any depth; true when one was found
+    fn redact_log_fields(&self, value: &mut serde_json::Value) -> bool {
+        match value {
+            serde_json::Value::Array(items) => items
+                .iter_mut()
+                .fold(false, |found, v| self.redact_log_fields(v) | found),
+            serde_json::Value::Object(map) => {
+                let mut found = false;
+                for (key, v) in map.iter_mut() {
+                    if key == "logs" || key == "stderr" {
+                        self.redact_value(v);
+                        found = true;
+                    } else {
+                        found |= self.redact_log_fields(v);
+                    }
+                }
+                found
+            }
+            _ => false,
+        }
+    }
+
+    /// Redact the message of every captured log entry, in place
+    pub fn redact_logs(&self, logs: &mut [crate::execution_logger::CapturedLogEntry]) {
+        for log in logs {
+            if let Cow::Owned(redacted) = self.redact(&log.message) {
+                log.message = redacted;
+            }
+        }
+    }
+}
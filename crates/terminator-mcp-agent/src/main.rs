@@ -4,7 +4,7 @@ use axum::{
     body::Body,
     extract::State,
     http::{Method, Request, StatusCode},
-    response::IntoResponse,
+    response::{IntoResponse, Response},
     routing::{get, post},
     Json, Router,
 };
@@ -20,17 +20,20 @@ use rmcp::{
 };
 use std::{
     net::SocketAddr,
-    sync::{
-        atomic::{AtomicUsize, Ordering},
-        Arc, Mutex,
-    },
-    time::SystemTime,
+    path::Path,
+    sync::Arc,
+    time::{Duration, SystemTime},
 };
 use sysinfo::{ProcessesToUpdate, System};
-use terminator_mcp_agent::cancellation::RequestManager;
+use terminator_mcp_agent::cancellation::{
+    cancelled_notification, guard_body, jsonrpc_request_id, RequestManager, ResponseGuard,
+};
 use terminator_mcp_agent::child_process;
+use terminator_mcp_agent::mcp_types::is_read_only_call;
+use terminator_mcp_agent::request_gate::RequestGate;
 use terminator_mcp_agent::server::{self, check_terminator_source};
 use terminator_mcp_agent::utils::init_logging;
+use terminator_mcp_agent::ws_transport;
 use tower_http::cors::CorsLayer;
 use tracing::{debug, error, info};
 use uuid::Uuid;
@@ -73,6 +76,11 @@ struct Args {
     /// Default: false (allows multiple instances via smart parent checking)
     #[arg(long)]
     enforce_single_instance: bool,
+
+    /// Don't kill other MCP agent instances on startup
+    /// For setups that manage the agent's lifecycle themselves
+    #[arg(long, conflicts_with = "enforce_single_instance")]
+    no_kill_existing: bool,
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
@@ -83,8 +91,13 @@ enum TransportMode {
     Sse,
     /// Streamable HTTP transport for HTTP-based clients
     Http,
+    /// WebSocket transport at /ws, served alongside the HTTP endpoints
+    Ws,
 }
 
+/// Gets or lazily creates the DesktopWrapper shared by all HTTP and WebSocket sessions
+type WrapperFactory = Arc<dyn Fn() -> Result<server::DesktopWrapper, std::io::Error> + Send + Sync>;
+
 #[cfg(target_os = "windows")]
 use windows::Win32::Foundation::{CloseHandle, HANDLE, STILL_ACTIVE};
 #[cfg(target_os = "windows")]
@@ -148,8 +161,51 @@ async fn watch_pid(pid: u32) {
     }
 }
 
-fn kill_previous_mcp_instances(enforce_single: bool) {
+/// Another copy of this agent, or the bridge service installed next to it.
+///
+/// Matching on the executable path rather than the process name keeps us from
+/// killing unrelated programs whose names happen to contain ours.
+fn own_instance_kind(process: &sysinfo::Process, own_exe: &Path) -> Option<&'static str> {
+    // Cheap exact check on the name first; a substring match would also hit
+    // e.g. an editor with "terminator-mcp-agent" in an open file's path
+    let name = process.name().to_string_lossy();
+    let stem = name.strip_suffix(".exe").unwrap_or(&name);
+    let kind = if stem.eq_ignore_ascii_case("terminator-mcp-agent") {
+        "MCP agent"
+    } else if stem.eq_ignore_ascii_case("terminator-bridge-service") {
+        "bridge service"
+    } else {
+        return None;
+    };
+
+    // Then confirm the binary on disk is ours, or the bridge installed next to it
+    let exe = std::fs::canonicalize(process.exe()?).ok()?;
+    let is_ours = if kind == "MCP agent" {
+        exe == own_exe
+    } else {
+        exe.file_stem()
+            .is_some_and(|s| s.eq_ignore_ascii_case("terminator-bridge-service"))
+            && exe.parent() == own_exe.parent()
+    };
+    is_ours.then_some(kind)
+}
+
+/// Addresses this instance is about to bind, which killed instances may still hold
+fn ports_to_release(args: &Args) -> Vec<String> {
+    let mut addrs = vec![terminator::extension_bridge::DEFAULT_WS_ADDR.to_string()];
+    if args.transport != TransportMode::Stdio {
+        addrs.push(format!("{}:{}", args.host, args.port));
+    }
+    addrs
+}
+
+fn kill_previous_mcp_instances(args: &Args) {
+    let enforce_single = args.enforce_single_instance;
     let current_pid = std::process::id();
+    let Some(own_exe) = std::env::current_exe().and_then(std::fs::canonicalize).ok() else {
+        eprintln!("⚠️ Could not resolve our own executable path, skipping instance cleanup");
+        return;
+    };
     let mut system = System::new();
     system.refresh_processes(ProcessesToUpdate::All, true);
 
@@ -161,20 +217,16 @@ fn kill_previous_mcp_instances(enforce_single: bool) {
 
     let mut killed_count = 0;
     for (pid, process) in system.processes() {
-        let process_name = process.name().to_string_lossy().to_lowercase();
-
-        // Skip if not an MCP agent or bridge service
-        if !process_name.contains("terminator-mcp-agent")
-            && !process_name.contains("terminator-bridge-service")
-        {
-            continue;
-        }
-
         // Don't kill ourselves
         if pid.as_u32() == current_pid {
             continue;
         }
 
+        // Skip anything that isn't our own executable or its bridge service
+        let Some(kind) = own_instance_kind(process, &own_exe) else {
+            continue;
+        };
+
         // Default (enforce_single=false): check if parent is alive (allow multiple instances)
         // Production (enforce_single=true): kill all other instances
         if !enforce_single {
@@ -189,11 +241,7 @@ fn kill_previous_mcp_instances(enforce_single: bool) {
                 if parent_alive {
                     eprintln!(
                         "✅ Skipping {} PID {} (belongs to parent PID {}, still alive)",
-                        if process_name.contains("mcp-agent") {
-                            "MCP agent"
-                        } else {
-                            "bridge service"
-                        },
+                        kind,
                         pid.as_u32(),
                         parent_pid.as_u32()
                     );
@@ -206,11 +254,7 @@ fn kill_previous_mcp_instances(enforce_single: bool) {
         // Multi-instance mode: kill orphaned processes only (we reach here if no parent or parent is dead)
         eprintln!(
             "🔴 Killing {} PID {}{}",
-            if process_name.contains("mcp-agent") {
-                "MCP agent"
-            } else {
-                "bridge service"
-            },
+            kind,
             pid.as_u32(),
             if enforce_single {
                 " (enforcing single instance)"
@@ -231,40 +275,83 @@ fn kill_previous_mcp_instances(enforce_single: bool) {
 
     if killed_count > 0 {
         eprintln!("🧹 Cleaned up {killed_count} process(es), waiting for ports to be released...");
-        // Increase wait time to 2 seconds for Windows to properly release ports
-        std::thread::sleep(std::time::Duration::from_millis(2000));
-
-        // Verify port 17373 is available
-        let mut retries = 0;
-        while retries < 5 {
-            match std::net::TcpListener::bind("127.0.0.1:17373") {
-                Ok(listener) => {
-                    drop(listener); // Immediately release the port
-                    eprintln!("Port 17373 is now available");
-                    break;
-                }
-                Err(_) => {
-                    retries += 1;
-                    if retries < 5 {
-                        eprintln!("Port 17373 still unavailable, waiting... (attempt {retries}/5)");
-                        std::thread::sleep(std::time::Duration::from_millis(1000));
-                    } else {
-                        eprintln!("WARNING: Port 17373 is still not available after 5 attempts");
-                    }
-                }
-            }
-        }
+        wait_for_ports(&ports_to_release(args));
     } else {
         eprintln!("✨ No orphaned or conflicting processes found");
     }
 }
 
+/// Largest request body the HTTP gate buffers to find out which tool is called
+const MAX_REQUEST_BODY_BYTES: usize = 16 * 1024 * 1024;
+
+/// `MCP_IDLE_SHUTDOWN_SECS`: how long the HTTP server may go without requests before it
+/// shuts itself down. Unset or 0 keeps it running.
+fn idle_shutdown_after() -> Option<Duration> {
+    std::env::var("MCP_IDLE_SHUTDOWN_SECS")
+        .ok()
+        .and_then(|v| v.parse::<u64>().ok())
+        .filter(|&secs| secs > 0)
+        .map(Duration::from_secs)
+}
+
+/// Poll until every address can be bound, backing off between attempts.
+///
+/// `MCP_PORT_WAIT_MS` caps the total wait (default 5000) and `MCP_PORT_RETRIES`
+/// the number of checks (default 10). Delays start at 100ms and double.
+fn wait_for_ports(addrs: &[String]) {
+    let max_wait = Duration::from_millis(
+        std::env::var("MCP_PORT_WAIT_MS")
+            .ok()
+            .and_then(|v| v.parse::<u64>().ok())
+            .unwrap_or(5000),
+    );
+    let retries = std::env::var("MCP_PORT_RETRIES")
+        .ok()
+        .and_then(|v| v.parse::<u32>().ok())
+        .filter(|&n| n > 0)
+        .unwrap_or(10);
+
+    let start = std::time::Instant::now();
+    let mut delay = Duration::from_millis(100);
+    for attempt in 1..=retries {
+        let busy: Vec<&str> = addrs
+            .iter()
+            .map(String::as_str)
+            .filter(|addr| std::net::TcpListener::bind(addr).is_err())
+            .collect();
+        if busy.is_empty() {
+            eprintln!("Ports {} are now available", addrs.join(", "));
+            return;
+        }
+
+        let remaining = max_wait.saturating_sub(start.elapsed());
+        if attempt == retries || remaining.is_zero() {
+            eprintln!(
+                "WARNING: {} still not available after {attempt} attempt(s) in {:?}",
+                busy.join(", "),
+                start.elapsed()
+            );
+            return;
+        }
+        eprintln!(
+            "{} still unavailable, waiting... (attempt {attempt}/{retries})",
+            busy.join(", ")
+        );
+        std::thread::sleep(delay.min(remaining));
+        delay *= 2;
+    }
+}
+
 #[tokio::main]
 async fn main() -> Result<()> {
     let args = Args::parse();
 
     // Kill any previous MCP instances before starting
-    kill_previous_mcp_instances(args.enforce_single_instance);
+    if args.no_kill_existing {
+        eprintln!("⏭️ --no-kill-existing set, leaving other MCP instances alone");
+    } else {
+        kill_previous_mcp_instances(&args);
+    }
 
     // Install panic hook to prevent stdout corruption (used by other MCP servers)
     std::panic::set_hook(Box::new(|panic_info| {
@@ -453,7 +540,7 @@ async fn main() -> Result<()> {
             child_process::kill_all();
             tracing::info!("Shutting down SSE server");
         }
-        TransportMode::Http => {
+        TransportMode::Http | TransportMode::Ws => {
             let addr: SocketAddr = format!("{}:{}", args.host, args.port).parse()?;
             tracing::info!("Starting streamable HTTP server on http://{}", addr);
 
@@ -463,40 +550,42 @@ async fn main() -> Result<()> {
             let desktop_wrapper_for_service = desktop_wrapper.clone();
 
             // Lazy-initialize DesktopWrapper on first /mcp use so that /health can succeed on CI
-            let service = StreamableHttpService::new(
-                {
+            let wrapper_factory: WrapperFactory = Arc::new({
+                let log_capture = log_capture.clone();
+                move || {
+                    // Use async block to handle RwLock
+                    let desktop_wrapper = desktop_wrapper_for_service.clone();
                     let log_capture = log_capture.clone();
-                    move || {
-                        // Use async block to handle RwLock
-                        let desktop_wrapper = desktop_wrapper_for_service.clone();
-                        let log_capture = log_capture.clone();
 
-                        // Block on async to get or create the singleton DesktopWrapper
-                        // Use block_in_place to safely block within tokio runtime (fixes crashes with multiple MCP clients)
-                        tracing::debug!(
-                            "MCP service factory called - using block_in_place for tokio safety"
-                        );
-                        tokio::task::block_in_place(|| {
-                            tokio::runtime::Handle::current().block_on(async move {
-                                let mut wrapper_guard = desktop_wrapper.write().await;
-                                if wrapper_guard.is_none() {
-                                    tracing::info!(
-                                        "Creating singleton DesktopWrapper for HTTP mode"
-                                    );
-                                    match server::DesktopWrapper::new_with_log_capture(log_capture)
-                                    {
-                                        Ok(wrapper) => {
-                                            *wrapper_guard = Some(wrapper.clone());
-                                            Ok(wrapper)
-                                        }
-                                        Err(e) => Err(std::io::Error::other(e.to_string())),
+                    // Block on async to get or create the singleton DesktopWrapper
+                    // Use block_in_place to safely block within tokio runtime (fixes crashes with multiple MCP clients)
+                    tracing::debug!(
+                        "MCP service factory called - using block_in_place for tokio safety"
+                    );
+                    tokio::task::block_in_place(|| {
+                        tokio::runtime::Handle::current().block_on(async move {
+                            let mut wrapper_guard = desktop_wrapper.write().await;
+                            if wrapper_guard.is_none() {
+                                tracing::info!("Creating singleton DesktopWrapper for HTTP mode");
+                                match server::DesktopWrapper::new_with_log_capture(log_capture) {
+                                    Ok(wrapper) => {
+                                        *wrapper_guard = Some(wrapper.clone());
+                                        Ok(wrapper)
                                     }
-                                } else {
-                                    Ok(wrapper_guard.as_ref().unwrap().clone())
+                                    Err(e) => Err(std::io::Error::other(e.to_string())),
                                 }
-                            })
+                            } else {
+                                Ok(wrapper_guard.as_ref().unwrap().clone())
+                            }
                         })
-                    }
+                    })
+                }
+            });
+
+            let service = StreamableHttpService::new(
+                {
+                    let wrapper_factory = wrapper_factory.clone();
+                    move || wrapper_factory()
                 },
                 LocalSessionManager::default().into(),
                 Default::default(),
@@ -505,12 +594,53 @@ async fn main() -> Result<()> {
             // Busy-aware concurrency state with request tracking
             #[derive(Clone)]
             struct AppState {
-                active_requests: Arc<AtomicUsize>,
-                last_activity: Arc<Mutex<String>>, // ISO-8601
-                max_concurrent: usize,
+                gate: RequestGate,
                 request_manager: RequestManager,
                 auth_token: Option<String>,
                 desktop_wrapper: Arc<tokio::sync::RwLock<Option<server::DesktopWrapper>>>,
+                wrapper_factory: WrapperFactory,
+                mcp_service: StreamableHttpService<server::DesktopWrapper, LocalSessionManager>,
+            }
+
+            impl AppState {
+                fn busy_body(&self) -> serde_json::Value {
+                    serde_json::json!({
+                        "busy": self.gate.is_busy(),
+                        "activeRequests": self.gate.active_requests(),
+                        "maxConcurrent": self.gate.max_concurrent(),
+                        "activeReadOnlyRequests": self.gate.active_read_only_requests(),
+                        "maxConcurrentReadOnly": self.gate.max_concurrent_read_only(),
+                        "queuedRequests": self.gate.queued_requests(),
+                        "lastActivity": self.gate.last_activity().to_rfc3339(),
+                    })
+                }
+
+                /// Time since a request last started or finished; zero while any is
+                /// running or queued
+                fn idle_for(&self) -> Duration {
+                    if self.gate.active_requests() > 0
+                        || self.gate.active_read_only_requests() > 0
+                        || self.gate.queued_requests() > 0
+                    {
+                        return Duration::ZERO;
+                    }
+                    (chrono::Utc::now() - self.gate.last_activity())
+                        .to_std()
+                        .unwrap_or_default()
+                }
+
+                /// Resolve once the server has been idle for `limit`, never if None
+                async fn wait_until_idle(&self, limit: Option<Duration>) {
+                    let Some(limit) = limit else {
+                        return std::future::pending().await;
+                    };
+                    loop {
+                        tokio::time::sleep(Duration::from_secs(1)).await;
+                        if self.idle_for() >= limit {
+                            return;
+                        }
+                    }
+                }
             }
 
             let max_concurrent = std::env::var("MCP_MAX_CONCURRENT")
@@ -518,15 +648,40 @@ async fn main() -> Result<()> {
                 .and_then(|s| s.parse::<usize>().ok())
                 .unwrap_or(1);
 
+            // Read-only tools (tree captures, screenshots, file reads) get their
+            // own limit so they don't queue behind a long-running input sequence
+            let max_concurrent_read_only = std::env::var("MCP_MAX_CONCURRENT_READ_ONLY")
+                .ok()
+                .and_then(|s| s.parse::<usize>().ok())
+                .unwrap_or(4);
+
+            // When set, requests wait up to this long for a free slot instead of
+            // getting an immediate 503
+            let queue_timeout = std::env::var("MCP_QUEUE_TIMEOUT_MS")
+                .ok()
+                .and_then(|s| s.parse::<u64>().ok())
+                .filter(|ms| *ms > 0)
+                .map(Duration::from_millis);
+
             let app_state = AppState {
-                active_requests: Arc::new(AtomicUsize::new(0)),
-                last_activity: Arc::new(Mutex::new(chrono::Utc::now().to_rfc3339())),
-                max_concurrent,
+                gate: RequestGate::new(
+                    max_concurrent,
+                    max_concurrent_read_only,
+                    queue_timeout,
+                    terminator_mcp_agent::read_only_tool_names(),
+                ),
                 request_manager: RequestManager::new(),
                 auth_token: args.auth_token.clone(),
                 desktop_wrapper: desktop_wrapper.clone(),
+                wrapper_factory,
+                mcp_service: service.clone(),
             };
 
+            let idle_shutdown = idle_shutdown_after();
+            if let Some(limit) = idle_shutdown {
+                info!("Idle shutdown enabled: exiting after {limit:?} without requests");
+            }
+
             // Log authentication status
             if app_state.auth_token.is_some() {
                 tracing::info!("🔒 Authentication enabled - Bearer token required");
@@ -535,24 +690,12 @@ async fn main() -> Result<()> {
             }
 
             async fn status_handler(State(state): State<AppState>) -> impl IntoResponse {
-                let active = state.active_requests.load(Ordering::SeqCst);
-                let busy = active >= state.max_concurrent;
-                let last_activity = state
-                    .last_activity
-                    .lock()
-                    .map(|s| s.clone())
-                    .unwrap_or_else(|_| chrono::Utc::now().to_rfc3339());
-                let code = if busy {
+                let body = state.busy_body();
+                let code = if body["busy"] == true {
                     StatusCode::SERVICE_UNAVAILABLE
                 } else {
                     StatusCode::OK
                 };
-                let body = serde_json::json!({
-                    "busy": busy,
-                    "activeRequests": active,
-                    "maxConcurrent": state.max_concurrent,
-                    "lastActivity": last_activity,
-                });
                 (code, Json(body))
             }
 
@@ -667,29 +810,49 @@ async fn main() -> Result<()> {
                 next: Next,
             ) -> impl IntoResponse {
                 if req.method() == Method::POST {
-                    let active = state.active_requests.load(Ordering::SeqCst);
-                    if active >= state.max_concurrent {
-                        let last_activity = state
-                            .last_activity
-                            .lock()
-                            .map(|s| s.clone())
-                            .unwrap_or_else(|_| chrono::Utc::now().to_rfc3339());
-                        let body = serde_json::json!({
-                            "busy": true,
-                            "activeRequests": active,
-                            "maxConcurrent": state.max_concurrent,
-                            "lastActivity": last_activity,
-                        });
+                    // Buffer the body to find out which tool is being called
+                    let (parts, body) = req.into_parts();
+                    let bytes = match axum::body::to_bytes(body, MAX_REQUEST_BODY_BYTES).await {
+                        Ok(bytes) => bytes,
+                        Err(e) => {
+                            let body = serde_json::json!({
+                                "error": {
+                                    "code": -32700,
+                                    "message": format!("Failed to read request body: {e}")
+                                }
+                            });
+                            return (StatusCode::BAD_REQUEST, Json(body)).into_response();
+                        }
+                    };
+                    let read_only = is_read_only_call(&bytes, state.gate.read_only_tools());
+                    let rpc_id = jsonrpc_request_id(&bytes);
+                    let rpc_headers = parts.headers.clone();
+                    let mut req = Request::from_parts(parts, Body::from(bytes));
+
+                    let permit = state.gate.acquire(read_only).await;
+                    let Some(permit) = permit else {
+                        let mut body = state.busy_body();
+                        body["busy"] = serde_json::json!(true);
                         return (StatusCode::SERVICE_UNAVAILABLE, Json(body)).into_response();
-                    }
+                    };
 
-                    // Extract request ID from headers or generate one
-                    let headers = req.headers();
-                    let request_id = headers
+                    // Extract request ID from headers or generate one. A generated id is
+                    // written back so the tool call's log span carries the same id
+                    let request_id = match req
+                        .headers()
                         .get("x-request-id")
                         .and_then(|v| v.to_str().ok())
-                        .map(|s| s.to_string())
-                        .unwrap_or_else(|| Uuid::new_v4().to_string());
+                    {
+                        Some(id) => id.to_string(),
+                        None => {
+                            let id = Uuid::new_v4().to_string();
+                            if let Ok(value) = axum::http::HeaderValue::from_str(&id) {
+                                req.headers_mut().insert("x-request-id", value);
+                            }
+                            id
+                        }
+                    };
+                    let headers = req.headers();
 
                     // Extract timeout from headers
                     let timeout_ms = headers
@@ -708,15 +871,31 @@ async fn main() -> Result<()> {
                         .register(request_id.clone(), timeout_ms)
                         .await;
 
-                    state.active_requests.fetch_add(1, Ordering::SeqCst);
-                    if let Ok(mut ts) = state.last_activity.lock() {
-                        *ts = chrono::Utc::now().to_rfc3339();
-                    }
-
-                    // Clone for cleanup
-                    let request_id_cleanup = request_id.clone();
-                    let manager_cleanup = state.request_manager.clone();
-                    let state_cleanup = state.clone();
+                    state.gate.touch();
+
+                    // Cleanup runs when the response is done with. If the client
+                    // disconnected first, hyper drops this future or the streamed
+                    // body, and the tool call is cancelled so it stops holding the slot.
+                    let guard = ResponseGuard::new({
+                        let state = state.clone();
+                        let request_id = request_id.clone();
+                        move |delivered| {
+                            state.gate.touch();
+                            tokio::spawn(async move {
+                                if !delivered {
+                                    info!(
+                                        "Client disconnected before request {} finished, cancelling it",
+                                        request_id
+                                    );
+                                    state.request_manager.cancel_request(&request_id).await;
+                                    if let Some(rpc_id) = rpc_id {
+                                        cancel_tool_call(&state, rpc_headers, &rpc_id).await;
+                                    }
+                                }
+                                state.request_manager.unregister(&request_id).await;
+                            });
+                        }
+                    });
 
                     // Execute the request with cancellation support
                     let response = tokio::select! {
@@ -733,19 +912,60 @@ async fn main() -> Result<()> {
                         }
                     };
 
-                    // Cleanup
-                    manager_cleanup.unregister(&request_id_cleanup).await;
-                    state_cleanup.active_requests.fetch_sub(1, Ordering::SeqCst);
-                    if let Ok(mut ts) = state_cleanup.last_activity.lock() {
-                        *ts = chrono::Utc::now().to_rfc3339();
-                    }
-
-                    return response;
+                    // Keep the slot until the (possibly streamed) body is finished
+                    let (parts, body) = response.into_parts();
+                    return Response::from_parts(parts, guard_body(body, guard, permit));
                 }
 
                 next.run(req).await
             }
 
+            // Tell the MCP session to stop a tool call whose client went away.
+            // Reuses the original request's headers, which carry the session id.
+            async fn cancel_tool_call(
+                state: &AppState,
+                mut headers: axum::http::HeaderMap,
+                rpc_id: &serde_json::Value,
+            ) {
+                headers.remove(axum::http::header::CONTENT_LENGTH);
+                let notification = cancelled_notification(rpc_id, "client disconnected");
+                let mut request = Request::post("/").body(Body::from(notification.to_string()));
+                if let Ok(request) = request.as_mut() {
+                    *request.headers_mut() = headers;
+                }
+                match request {
+                    Ok(request) => {
+                        let response = state.mcp_service.handle(request).await;
+                        debug!(
+                            "Forwarded cancellation for JSON-RPC id {}: {}",
+                            rpc_id,
+                            response.status()
+                        );
+                    }
+                    Err(e) => error!("Failed to build cancellation request: {e}"),
+                }
+            }
+
+            // Handler for GET /ws - upgrades to a WebSocket carrying MCP messages
+            async fn ws_handler(
+                State(state): State<AppState>,
+                req: Request<Body>,
+            ) -> impl IntoResponse {
+                match (state.wrapper_factory)() {
+                    Ok(desktop) => ws_transport::upgrade(req, desktop, state.gate.clone()),
+                    Err(e) => {
+                        error!("Failed to create DesktopWrapper for WebSocket session: {e}");
+                        let body = serde_json::json!({
+                            "error": {
+                                "code": -32603,
+                                "message": format!("Failed to initialize MCP server: {e}")
+                            }
+                        });
+                        (StatusCode::INTERNAL_SERVER_ERROR, Json(body)).into_response()
+                    }
+                }
+            }
+
             // Middleware to extract X-Workflow-Dir header and set current_workflow_dir
             async fn workflow_dir_middleware(
                 State(state): State<AppState>,
@@ -787,14 +1007,26 @@ async fn main() -> Result<()> {
                     auth_middleware,
                 ));
 
-            let mut router: Router = Router::new()
+            let mut router = Router::new()
                 .route("/", get(root_handler))
                 .route("/health", get(health_check))
                 .route("/ready", get(readiness_check))
                 .route("/status", get(status_handler))
+                .route("/schemas", get(schemas_handler))
                 .route("/mode", post(set_mode_handler))
-                .nest("/mcp", mcp_router)
-                .with_state(app_state.clone());
+                .nest("/mcp", mcp_router);
+
+            if args.transport == TransportMode::Ws {
+                router = router.route(
+                    "/ws",
+                    get(ws_handler).layer(axum::middleware::from_fn_with_state(
+                        app_state.clone(),
+                        auth_middleware,
+                    )),
+                );
+            }
+
+            let mut router: Router = router.with_state(app_state.clone());
 
             if args.cors {
                 router = router.layer(CorsLayer::permissive());
@@ -809,15 +1041,25 @@ async fn main() -> Result<()> {
             info!("Available endpoints:");
             info!("  Root (endpoint list): http://{addr}/");
             info!("  MCP client endpoint: http://{addr}/mcp");
+            if args.transport == TransportMode::Ws {
+                info!("  MCP WebSocket endpoint: ws://{addr}/ws");
+            }
             info!("  Status endpoint: http://{addr}/status");
+            info!("  Tool argument schemas: http://{addr}/schemas");
             info!("  Health check: http://{addr}/health");
             info!("  Mode control: POST http://{addr}/mode");
             info!("Press Ctrl+C to stop");
 
             axum::serve(tcp_listener, router)
                 .with_graceful_shutdown(async move {
-                    tokio::signal::ctrl_c().await.ok();
-                    info!("Received shutdown signal, cancelling active requests...");
+                    tokio::select! {
+                        _ = tokio::signal::ctrl_c() => {
+                            info!("Received shutdown signal, cancelling active requests...");
+                        }
+                        _ = app_state.wait_until_idle(idle_shutdown) => {
+                            info!("No requests for {idle_shutdown:?}, shutting down idle server...");
+                        }
+                    }
                     app_state.request_manager.cancel_all().await;
                     // Kill any running child processes (bun/node workflow executors)
                     child_process::kill_all();
@@ -850,8 +1092,10 @@ async fn root_handler() -> impl axum::response::IntoResponse {
             "endpoints": {
                 "/": "This endpoint - lists available endpoints",
                 "/mcp": "MCP protocol endpoint - connect your MCP client here",
+                "/ws": "MCP over WebSocket (when started with -t ws)",
                 "/health": "Health check endpoint - returns server status",
-                "/status": "Status endpoint - shows active requests and concurrency info"
+                "/status": "Status endpoint - shows active requests and concurrency info",
+                "/schemas": "JSON Schema for every tool's arguments, keyed by tool name"
             },
             "usage": {
                 "mcp_client": "Connect your MCP client to: /mcp",
@@ -863,6 +1107,13 @@ async fn root_handler() -> impl axum::response::IntoResponse {
     )
 }
 
+async fn schemas_handler() -> impl axum::response::IntoResponse {
+    (
+        axum::http::StatusCode::OK,
+        axum::Json(terminator_mcp_agent::mcp_types::tool_schemas()),
+    )
+}
+
 async fn health_check() -> impl axum::response::IntoResponse {
     // Lightweight liveness check - confirms process is alive and HTTP server is responding
     // Does NOT perform expensive UIAutomation API checks that can block during workflows
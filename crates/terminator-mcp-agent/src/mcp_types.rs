@@ -1,5 +1,6 @@
 use rmcp::{schemars, schemars::JsonSchema};
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 
 fn default_font_size() -> u32 {
     12
@@ -60,6 +61,74 @@ pub enum TreeOutputFormat {
     ClusteredYaml,
 }
 
+/// When `execute_sequence` captures the UI tree around each step
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum CaptureMode {
+    #[schemars(description = "Don't capture trees (default)")]
+    #[default]
+    None,
+    #[schemars(description = "Capture the tree before each step")]
+    BeforeEach,
+    #[schemars(description = "Capture the tree after each step")]
+    AfterEach,
+    #[schemars(description = "Capture before and after each step, plus the diff between them")]
+    Both,
+}
+
+impl CaptureMode {
+    pub fn captures_before(self) -> bool {
+        matches!(self, Self::BeforeEach | Self::Both)
+    }
+
+    pub fn captures_after(self) -> bool {
+        matches!(self, Self::AfterEach | Self::Both)
+    }
+}
+
+/// Image encoding for screenshots returned by MCP tools
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ImageFormat {
+    #[schemars(description = "Lossless PNG")]
+    Png,
+    #[schemars(description = "Lossy JPEG, smallest payload (default)")]
+    #[default]
+    Jpeg,
+    #[schemars(description = "Lossless WebP, usually smaller than PNG")]
+    Webp,
+}
+
+impl ImageFormat {
+    /// MIME type used when returning the image as MCP content
+    pub fn mime_type(self) -> &'static str {
+        match self {
+            ImageFormat::Png => "image/png",
+            ImageFormat::Jpeg => "image/jpeg",
+            ImageFormat::Webp => "image/webp",
+        }
+    }
+
+    /// Encode a screenshot in this format, downscaling to `max_dimension`.
+    /// `quality` (1-100) only applies to JPEG; PNG and WebP are lossless.
+    pub fn encode(
+        self,
+        screenshot: &terminator::ScreenshotResult,
+        max_dimension: Option<u32>,
+        quality: Option<u8>,
+    ) -> Result<Vec<u8>, terminator::ScreenshotError> {
+        match self {
+            ImageFormat::Png => {
+                screenshot.to_png_resized_with(max_dimension, terminator::CompressionLevel::Fast)
+            }
+            ImageFormat::Jpeg => {
+                screenshot.to_jpeg_resized(max_dimension, quality.map(|q| q.clamp(1, 100)))
+            }
+            ImageFormat::Webp => screenshot.to_webp_resized(max_dimension),
+        }
+    }
+}
+
 /// Font styling options for text overlay
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct FontStyle {
@@ -84,3 +153,137 @@ impl From<FontStyle> for terminator::platforms::windows::FontStyle {
         }
     }
 }
+
+/// A tool's public metadata, as advertised by `tools/list`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToolInfo {
+    pub name: String,
+    pub description: Option<String>,
+    /// JSON Schema for the tool's arguments
+    pub input_schema: serde_json::Value,
+    /// Whether the tool is annotated as read-only (doesn't send input or change state)
+    #[serde(default)]
+    pub read_only: bool,
+}
+
+impl From<rmcp::model::Tool> for ToolInfo {
+    fn from(tool: rmcp::model::Tool) -> Self {
+        Self {
+            name: tool.name.to_string(),
+            description: tool.description.map(|d| d.to_string()),
+            input_schema: serde_json::Value::Object((*tool.input_schema).clone()),
+            read_only: tool
+                .annotations
+                .and_then(|a| a.read_only_hint)
+                .unwrap_or(false),
+        }
+    }
+}
+
+/// Tools allowed in the read-only concurrency lane.
+///
+/// Read-only tools that accept window management options minimize, activate
+/// and maximize windows by default, which steals focus from a concurrent input
+/// tool. Those only count as read-only for calls that pass
+/// `enable_window_management: false`.
+#[derive(Debug, Clone, Default)]
+pub struct ReadOnlyTools {
+    tools: HashSet<String>,
+    window_managed: HashSet<String>,
+}
+
+impl ReadOnlyTools {
+    /// `window_managed` names the read-only tools that run window management
+    /// unless a call turns it off.
+    pub fn new(tools: HashSet<String>, window_managed: HashSet<String>) -> Self {
+        Self {
+            tools,
+            window_managed,
+        }
+    }
+
+    /// Build from the tool catalog, using each tool's `read_only_hint` and
+    /// whether its schema has an `enable_window_management` argument.
+    pub fn from_catalog(catalog: &[ToolInfo]) -> Self {
+        let read_only = catalog.iter().filter(|tool| tool.read_only);
+        let window_managed = read_only
+            .clone()
+            .filter(|tool| {
+                tool.input_schema
+                    .pointer("/properties/enable_window_management")
+                    .is_some()
+            })
+            .map(|tool| tool.name.clone())
+            .collect();
+        Self::new(
+            read_only.map(|tool| tool.name.clone()).collect(),
+            window_managed,
+        )
+    }
+
+    /// Whether a call to `name` with these arguments can run in the read-only lane
+    pub fn is_read_only(
+        &self,
+        name: &str,
+        arguments: Option<&serde_json::Map<String, serde_json::Value>>,
+    ) -> bool {
+        if !self.tools.contains(name) {
+            return false;
+        }
+        if !self.window_managed.contains(name) {
+            return true;
+        }
+        arguments
+            .and_then(|args| args.get("enable_window_management"))
+            .and_then(|enabled| enabled.as_bool())
+            == Some(false)
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.tools.contains(name)
+    }
+}
+
+/// Whether a JSON-RPC request body only calls read-only tools.
+///
+/// Anything else, including batches that mix in other calls, non-`tools/call`
+/// methods, window-managing calls and unparseable bodies, counts as
+/// input-mutating so it keeps the stricter concurrency limit.
+pub fn is_read_only_call(body: &[u8], read_only_tools: &ReadOnlyTools) -> bool {
+    let Ok(value) = serde_json::from_slice::<serde_json::Value>(body) else {
+        return false;
+    };
+
+    let is_read_only = |message: &serde_json::Value| {
+        message.get("method").and_then(|m| m.as_str()) == Some("tools/call")
+            && message
+                .pointer("/params/name")
+                .and_then(|n| n.as_str())
+                .is_some_and(|name| {
+                    read_only_tools.is_read_only(
+                        name,
+                        message
+                            .pointer("/params/arguments")
+                            .and_then(|args| args.as_object()),
+                    )
+                })
+    };
+
+    match &value {
+        serde_json::Value::Array(batch) => !batch.is_empty() && batch.iter().all(is_read_only),
+        message => is_read_only(message),
+    }
+}
+
+/// JSON Schema for every MCP tool's arguments, keyed by tool name.
+///
+/// Taken from [`crate::server::tool_catalog`], so the tools and schemas are
+/// exactly what `tools/list` advertises.
+pub fn tool_schemas() -> serde_json::Value {
+    serde_json::Value::Object(
+        crate::server::tool_catalog()
+            .into_iter()
+            .map(|tool| (tool.name, tool.input_schema))
+            .collect(),
+    )
+}
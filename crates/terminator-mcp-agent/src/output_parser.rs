@@ -1,4 +1,4 @@
-use crate::scripting_engine::execute_javascript_with_nodejs;
+use crate::scripting_engine::{execute_javascript_with_nodejs, ScriptExecutionOptions};
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
@@ -117,9 +117,16 @@ pub async fn run_output_parser(
     };
 
     // Execute JavaScript code asynchronously
-    let result = execute_javascript_with_nodejs(full_script, None, None, None, None, None)
-        .await
-        .map_err(|e| anyhow::anyhow!("JavaScript execution failed: {}", e))?;
+    let result = execute_javascript_with_nodejs(
+        full_script,
+        None,
+        ScriptExecutionOptions::default(),
+        None,
+        None,
+        None,
+    )
+    .await
+    .map_err(|e| anyhow::anyhow!("JavaScript execution failed: {}", e))?;
 
     Ok(Some(result))
 }
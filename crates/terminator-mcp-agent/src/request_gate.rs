@@ -0,0 +1,132 @@
+use crate::mcp_types::ReadOnlyTools;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// Counts one queued request for as long as it lives, so a waiter whose future is
+/// dropped mid-wait (client cancel, timeout, `select!`) is still uncounted
+struct QueuedGuard<'a>(&'a AtomicUsize);
+
+impl<'a> QueuedGuard<'a> {
+    fn new(queued: &'a AtomicUsize) -> Self {
+        queued.fetch_add(1, Ordering::SeqCst);
+        Self(queued)
+    }
+}
+
+impl Drop for QueuedGuard<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Concurrency limits shared by the network transports.
+///
+/// Input tools and read-only tools draw from separate pools of slots, so a tree
+/// capture isn't turned away while a long click sequence holds the input slot.
+#[derive(Clone)]
+pub struct RequestGate {
+    slots: Arc<Semaphore>,
+    read_only_slots: Arc<Semaphore>,
+    queued: Arc<AtomicUsize>,
+    max_concurrent: usize,
+    max_concurrent_read_only: usize,
+    queue_timeout: Option<Duration>,
+    read_only_tools: Arc<ReadOnlyTools>,
+    last_activity: Arc<Mutex<chrono::DateTime<chrono::Utc>>>,
+}
+
+impl RequestGate {
+    /// `queue_timeout` of None rejects immediately when no slot is free.
+    pub fn new(
+        max_concurrent: usize,
+        max_concurrent_read_only: usize,
+        queue_timeout: Option<Duration>,
+        read_only_tools: ReadOnlyTools,
+    ) -> Self {
+        let max_concurrent = max_concurrent.min(Semaphore::MAX_PERMITS);
+        let max_concurrent_read_only = max_concurrent_read_only.min(Semaphore::MAX_PERMITS);
+        Self {
+            slots: Arc::new(Semaphore::new(max_concurrent)),
+            read_only_slots: Arc::new(Semaphore::new(max_concurrent_read_only)),
+            queued: Arc::new(AtomicUsize::new(0)),
+            max_concurrent,
+            max_concurrent_read_only,
+            queue_timeout,
+            read_only_tools: Arc::new(read_only_tools),
+            last_activity: Arc::new(Mutex::new(chrono::Utc::now())),
+        }
+    }
+
+    /// Take a slot from the matching pool, queueing for it first if a queue
+    /// timeout is configured. None means the server is busy.
+    pub async fn acquire(&self, read_only: bool) -> Option<OwnedSemaphorePermit> {
+        let slots = if read_only {
+            self.read_only_slots.clone()
+        } else {
+            self.slots.clone()
+        };
+
+        match self.queue_timeout {
+            None => slots.try_acquire_owned().ok(),
+            Some(wait) => {
+                let _queued = QueuedGuard::new(&self.queued);
+                let acquired = tokio::time::timeout(wait, slots.acquire_owned()).await;
+                acquired.ok().and_then(Result::ok)
+            }
+        }
+    }
+
+    /// Whether a call to `name` with these arguments goes to the read-only pool
+    pub fn is_read_only_call(
+        &self,
+        name: &str,
+        arguments: Option<&serde_json::Map<String, serde_json::Value>>,
+    ) -> bool {
+        self.read_only_tools.is_read_only(name, arguments)
+    }
+
+    pub fn read_only_tools(&self) -> &ReadOnlyTools {
+        &self.read_only_tools
+    }
+
+    pub fn active_requests(&self) -> usize {
+        self.max_concurrent - self.slots.available_permits()
+    }
+
+    pub fn active_read_only_requests(&self) -> usize {
+        self.max_concurrent_read_only - self.read_only_slots.available_permits()
+    }
+
+    pub fn queued_requests(&self) -> usize {
+        self.queued.load(Ordering::SeqCst)
+    }
+
+    pub fn max_concurrent(&self) -> usize {
+        self.max_concurrent
+    }
+
+    pub fn max_concurrent_read_only(&self) -> usize {
+        self.max_concurrent_read_only
+    }
+
+    /// Whether the input-tool pool is full
+    pub fn is_busy(&self) -> bool {
+        self.active_requests() >= self.max_concurrent
+    }
+    /// Record that a request started or finished, on any transport
+    pub fn touch(&self) {
+        if let Ok(mut last) = self.last_activity.lock() {
+            *last = chrono::Utc::now();
+        }
+    }
+
+    /// When a request last started or finished
+    pub fn last_activity(&self) -> chrono::DateTime<chrono::Utc> {
+        self.last_activity
+            .lock()
+            .map(|last| *last)
+            .unwrap_or_else(|_| chrono::Utc::now())
+    }
+}
@@ -0,0 +1,102 @@
+use rmcp::model::{CallToolResult, Content, RawContent};
+use serde_json::json;
+
+/// Environment variable holding the largest tool result, in bytes, the server will send
+pub const MAX_RESPONSE_BYTES_ENV: &str = "MCP_MAX_RESPONSE_BYTES";
+
+/// How many top-level keys of an oversized JSON result are listed in its summary
+const MAX_SUMMARY_KEYS: usize = 20;
+
+/// The configured response size limit; None when `MCP_MAX_RESPONSE_BYTES` is unset,
+/// zero or not a number, in which case results are sent whatever their size.
+pub fn max_response_bytes() -> Option<usize> {
+    std::env::var(MAX_RESPONSE_BYTES_ENV)
+        .ok()
+        .and_then(|s| s.trim().parse::<usize>().ok())
+        .filter(|bytes| *bytes > 0)
+}
+
+/// Serialized size of a result, as it goes over the wire
+pub fn response_size(result: &CallToolResult) -> usize {
+    serde_json::to_vec(result)
+        .map(|bytes| bytes.len())
+        .unwrap_or(0)
+}
+
+/// Replace a result larger than `max_bytes` with a small structured notice.
+///
+/// Clients truncate oversized messages mid-JSON, which leaves them unparseable; a
+/// notice saying what was too large and how to ask for less is far more useful.
+/// Results within the limit are returned unchanged.
+pub fn enforce(tool_name: &str, result: CallToolResult, max_bytes: usize) -> CallToolResult {
+    let size = response_size(&result);
+    if size <= max_bytes {
+        return result;
+    }
+    tracing::warn!(
+        "[response_limit] {} result is {} bytes, over the {} byte limit; returning a summary",
+        tool_name,
+        size,
+        max_bytes
+    );
+
+    let notice = json!({
+        "action": tool_name,
+        "status": "result_too_large",
+        "size_bytes": size,
+        "max_bytes": max_bytes,
+        "summary": result.content.iter().map(summarize).collect::<Vec<_>>(),
+        "guidance": guidance(tool_name),
+    });
+    CallToolResult {
+        content: vec![Content::text(notice.to_string())],
+        structured_content: None,
+        is_error: result.is_error,
+        meta: result.meta,
+    }
+}
+
+/// Type, size and (for JSON text) top-level shape of one content item
+fn summarize(content: &Content) -> serde_json::Value {
+    match &content.raw {
+        RawContent::Text(text) => {
+            let mut summary = json!({"type": "text", "size_bytes": text.text.len()});
+            if let Ok(serde_json::Value::Object(map)) =
+                serde_json::from_str::<serde_json::Value>(&text.text)
+            {
+                let keys: Vec<&String> = map.keys().take(MAX_SUMMARY_KEYS).collect();
+                summary["keys"] = json!(keys);
+                if let Some(status) = map.get("status") {
+                    summary["status"] = status.clone();
+                }
+            }
+            summary
+        }
+        RawContent::Image(image) => json!({
+            "type": "image",
+            "mime_type": image.mime_type,
+            "size_bytes": image.data.len(),
+        }),
+        other => json!({
+            "type": "other",
+            "size_bytes": serde_json::to_vec(other).map(|b| b.len()).unwrap_or(0),
+        }),
+    }
+}
+
+/// Ways to ask the tool for a smaller result
+fn guidance(tool_name: &str) -> Vec<&'static str> {
+    let mut tips = vec![
+        "Set tree_max_depth to limit how deep the UI tree goes",
+        "Set tree_from_selector to return only the subtree you need",
+        "Use tree_output_format 'compact_yaml' instead of 'verbose_json'",
+    ];
+    if tool_name == "get_window_tree" {
+        tips.push("Leave include_ocr, include_omniparser, include_gemini_vision and include_browser_dom off unless needed");
+    }
+    if tool_name == "capture_screenshot" || tool_name == "capture_element_screenshot" {
+        tips.push("Lower max_dimension or use format 'jpeg' with a lower quality");
+    }
+    tips.push("Raise MCP_MAX_RESPONSE_BYTES if your client accepts larger messages");
+    tips
+}
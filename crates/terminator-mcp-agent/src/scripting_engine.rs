@@ -1,8 +1,10 @@
 use crate::event_pipe::{create_event_channel, EventPipeServer, EventSender, WorkflowEvent};
 use rmcp::ErrorData as McpError;
 use serde_json::json;
+use std::collections::HashMap;
 use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 use tracing::{debug, error, info, trace, warn};
 
 /// Windows constant to prevent console window creation during process spawn
@@ -64,6 +66,188 @@ impl ScriptLogBuffer {
     }
 }
 
+/// Per-execution settings for the spawned script runtime process.
+#[derive(Debug, Clone, Default)]
+pub struct ScriptExecutionOptions {
+    /// Working directory for the runtime process. Defaults to the persistent script directory.
+    pub working_dir: Option<PathBuf>,
+    /// Extra environment variables for the runtime process, layered over the inherited environment.
+    pub env: HashMap<String, String>,
+    /// Wall-clock limit for the script. The runtime process is killed when it expires.
+    pub timeout: Option<Duration>,
+    /// Heap cap in megabytes, passed to Node as `--max-old-space-size`.
+    /// Ignored (with a warning) by runtimes without an equivalent flag.
+    pub max_memory_mb: Option<u64>,
+    /// Restrict network access from the user script. Neither Node nor bun has a
+    /// permission flag for this, so the wrapper replaces network-capable globals,
+    /// SDK methods and the script's `require` with stubs that throw. Best-effort;
+    /// `NO_NETWORK_GUARD` lists what is not covered.
+    /// Not supported for Python.
+    pub no_network: bool,
+}
+
+impl ScriptExecutionOptions {
+    /// Set the per-execution env vars on a runtime command.
+    fn apply_env(&self, cmd: &mut tokio::process::Command) {
+        if !self.env.is_empty() {
+            debug!(
+                "Setting {} per-execution env var(s) on runtime process",
+                self.env.len()
+            );
+            cmd.envs(&self.env);
+        }
+    }
+
+    /// Cap the V8 heap via `NODE_OPTIONS`, keeping any options already set.
+    /// Bun runs on JavaScriptCore and has no heap cap flag, so it is skipped.
+    fn apply_memory_limit(&self, cmd: &mut tokio::process::Command, runtime: &str) {
+        let Some(max_memory_mb) = self.max_memory_mb else {
+            return;
+        };
+        if runtime == "bun" {
+            warn!("max_memory_mb={max_memory_mb} ignored: bun does not support a heap size cap");
+            return;
+        }
+        let existing = self
+            .env
+            .get("NODE_OPTIONS")
+            .cloned()
+            .or_else(|| std::env::var("NODE_OPTIONS").ok())
+            .unwrap_or_default();
+        let node_options = format!("{existing} --max-old-space-size={max_memory_mb}");
+        debug!("Setting NODE_OPTIONS={}", node_options.trim());
+        cmd.env("NODE_OPTIONS", node_options.trim());
+    }
+
+    fn deadline(&self) -> Option<tokio::time::Instant> {
+        self.timeout.map(|t| tokio::time::Instant::now() + t)
+    }
+}
+
+/// Wrapper snippet installed before the user script when `no_network` is set.
+/// The SDK is already loaded by then and keeps its own `require`; the user script
+/// gets a filtered one through `NO_NETWORK_USER_SCOPE`.
+///
+/// This is best-effort, not a sandbox: neither Node nor bun can deny network
+/// access at the process level. Covered: network globals, the user script's
+/// `require` of network, process and thread modules, dynamic `import()` of them,
+/// `process.binding`, and the SDK's `desktop.run`, `desktop.runCommand` and
+/// `desktop.openUrl`. Not covered: modules that the user script's own
+/// dependencies require, native addons, handles opened before the wrapper ran,
+/// and `desktop.openApplication`/`desktop.openFile`, which can still start a
+/// program that goes online. The `import()` hook is registered for the whole
+/// process, so the SDK can't dynamically import those modules either.
+const NO_NETWORK_GUARD: &str = r#"
+// Network restrictions for the user script: network attempts throw
+const __noNetworkRequire = (() => {
+    const blocked = (what) => () => {
+        throw new Error(`Network access is disabled for this script (${what})`);
+    };
+    for (const name of ['fetch', 'WebSocket', 'EventSource', 'XMLHttpRequest']) {
+        if (name in globalThis) globalThis[name] = blocked(name);
+    }
+    if (typeof Bun !== 'undefined') {
+        for (const name of ['connect', 'listen', 'serve', 'udpSocket', 'spawn', 'spawnSync']) {
+            try { Bun[name] = blocked(`Bun.${name}`); } catch (_) {}
+        }
+    }
+    for (const name of ['binding', '_linkedBinding', 'dlopen']) {
+        try { process[name] = blocked(`process.${name}`); } catch (_) {}
+    }
+    // Shell commands and URLs opened through the SDK can reach the network too
+    if (typeof desktop !== 'undefined') {
+        for (const name of ['run', 'runCommand', 'openUrl']) {
+            try { desktop[name] = blocked(`desktop.${name}`); } catch (_) {}
+        }
+    }
+    // Network modules, plus modules that can start an unguarded process or thread
+    const blockedModules = ['http', 'https', 'http2', 'net', 'tls', 'dgram', 'dns', 'undici', 'ws', 'child_process', 'worker_threads', 'cluster'];
+    const networkModules = new Set(blockedModules);
+    // Dynamic import() can't be scoped to the user script, so reject the modules at resolution
+    const Module = require('module');
+    if (typeof Module.register === 'function') {
+        const hooks = `
+            const blocked = new Set(${JSON.stringify(blockedModules)});
+            export async function resolve(specifier, context, next) {
+                if (blocked.has(specifier.replace(/^node:/, '').split('/')[0])) {
+                    throw new Error("Network access is disabled for this script (import('" + specifier + "'))");
+                }
+                return next(specifier, context);
+            }`;
+        Module.register('data:text/javascript,' + encodeURIComponent(hooks));
+    }
+    const moduleRequire = require;
+    return function (id) {
+        const name = String(id).replace(/^node:/, '').split('/')[0];
+        if (networkModules.has(name)) {
+            throw new Error(`Network access is disabled for this script (require('${id}'))`);
+        }
+        return moduleRequire(id);
+    };
+})();
+"#;
+
+/// Shadows `require` inside the user script's function with the filtered one
+/// from `NO_NETWORK_GUARD`.
+const NO_NETWORK_USER_SCOPE: &str = "const require = __noNetworkRequire;";
+
+/// Limit for Python scripts run without a configured timeout
+const PYTHON_DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Resolves once the deadline passes; never resolves without one.
+async fn wait_for_deadline(deadline: Option<tokio::time::Instant>) {
+    match deadline {
+        Some(deadline) => tokio::time::sleep_until(deadline).await,
+        None => std::future::pending::<()>().await,
+    }
+}
+
+/// Whether V8 aborted the process because it hit its heap limit.
+fn is_out_of_memory(stderr: &[String]) -> bool {
+    stderr.iter().any(|line| {
+        line.contains("JavaScript heap out of memory") || line.contains("Reached heap limit")
+    })
+}
+
+/// `captured` holds the logs and stderr read before the deadline; they are only
+/// attached when the caller asked for logs (passed a log buffer)
+fn script_timeout_error(
+    engine: &str,
+    timeout: Duration,
+    captured: Option<(&[String], &[String])>,
+) -> McpError {
+    let timeout_ms = timeout.as_millis();
+    let mut data = json!({
+        "reason": "timeout",
+        "engine": engine.to_ascii_lowercase(),
+        "timeout_ms": timeout_ms,
+    });
+    if let Some((logs, stderr)) = captured {
+        data["logs"] = json!(logs);
+        data["stderr"] = json!(stderr);
+    }
+    McpError::internal_error(
+        format!("{engine} script timed out after {timeout_ms}ms"),
+        Some(data),
+    )
+}
+
+fn script_out_of_memory_error(
+    engine: &str,
+    max_memory_mb: Option<u64>,
+    stderr: &[String],
+) -> McpError {
+    McpError::internal_error(
+        format!("{engine} script ran out of memory"),
+        Some(json!({
+            "reason": "out_of_memory",
+            "engine": engine.to_ascii_lowercase(),
+            "max_memory_mb": max_memory_mb,
+            "stderr": stderr.join("\n")
+        })),
+    )
+}
+
 /// Find bundled bun executable next to the current binary (for mediar-app distribution)
 fn find_bundled_bun() -> Option<String> {
     let exe_path = std::env::current_exe().ok()?;
@@ -935,14 +1119,14 @@ async fn ensure_terminator_js_installed(runtime: &str) -> Result<std::path::Path
 /// # Arguments
 /// * `script` - The JavaScript code to execute
 /// * `cancellation_token` - Optional token to cancel execution
-/// * `working_dir` - Optional working directory for script execution
+/// * `options` - Working directory and env vars for the runtime process
 /// * `log_buffer` - Optional shared buffer for real-time log capture (useful for timeout scenarios)
 /// * `event_sender` - Optional channel to send workflow events (for real-time streaming)
 /// * `execution_id` - Optional execution ID for named pipe identification
 pub async fn execute_javascript_with_nodejs(
     script: String,
     cancellation_token: Option<tokio_util::sync::CancellationToken>,
-    working_dir: Option<PathBuf>,
+    options: ScriptExecutionOptions,
     log_buffer: Option<ScriptLogBuffer>,
     event_sender: Option<EventSender>,
     execution_id: Option<&str>,
@@ -1032,7 +1216,7 @@ pub async fn execute_javascript_with_nodejs(
 
     // When we have a custom working directory, we need to handle module resolution differently
     // because require() resolves relative to the script file, not the working directory
-    let module_resolution_setup = if working_dir.is_some() {
+    let module_resolution_setup = if options.working_dir.is_some() {
         // Override require to resolve modules relative to the working directory
         r#"
 // Save original require
@@ -1056,6 +1240,13 @@ Module.prototype.require = function(id) {
         ""
     };
 
+    let (network_guard, network_user_scope) = if options.no_network {
+        info!("[Node.js] Network access disabled for this script");
+        (NO_NETWORK_GUARD, NO_NETWORK_USER_SCOPE)
+    } else {
+        ("", "")
+    };
+
     let wrapper_script = format!(
         r#"
 const {{ Desktop }} = require('@mediar-ai/terminator');
@@ -1106,13 +1297,14 @@ try {{
     // KV package not available
     global.createKVClient = () => {{ throw new Error('@mediar-ai/kv package not installed'); }};
 }}
-
+{network_guard}
 // Execute user script
 (async () => {{
     try {{
         const result = await (async () => {{
             // Expose globals as local variables for user script
             const {{ desktop, emit, createStepEmitter, log, sleep, createKVClient }} = global;
+            {network_user_scope}
             {script}
         }})();
 
@@ -1175,7 +1367,7 @@ try {{
         cfg!(windows) && (runtime_exe.ends_with(".cmd") || runtime_exe.ends_with(".bat"));
 
     // Determine the working directory for the process
-    let process_working_dir = if let Some(ref wd) = working_dir {
+    let process_working_dir = if let Some(ref wd) = options.working_dir {
         info!("[Node.js] Using custom working directory: {}", wd.display());
         wd.clone()
     } else {
@@ -1187,7 +1379,7 @@ try {{
     };
 
     // For custom working dir, we need to use absolute path to the script
-    let script_arg = if working_dir.is_some() {
+    let script_arg = if options.working_dir.is_some() {
         script_path.to_string_lossy().to_string()
     } else {
         unique_filename.clone()
@@ -1327,6 +1519,8 @@ try {{
 
     // Inherit parent environment
     cmd.envs(std::env::vars());
+    options.apply_env(&mut cmd);
+    options.apply_memory_limit(&mut cmd, runtime);
 
     // Set the pipe path environment variable if we have a pipe server
     #[cfg(windows)]
@@ -1357,10 +1551,24 @@ try {{
     let mut env_updates: serde_json::Map<String, serde_json::Value> = serde_json::Map::new();
     let mut stderr_output = Vec::new();
     let mut captured_logs = Vec::new(); // Capture all console output
+    let deadline = options.deadline();
 
     // Handle communication with Node.js process
     loop {
         tokio::select! {
+            _ = wait_for_deadline(deadline) => {
+                warn!("[Node.js] Script exceeded its timeout, terminating child process");
+                if let Err(e) = child.kill().await {
+                    error!("[Node.js] Failed to kill child process: {}", e);
+                }
+                return Err(script_timeout_error(
+                    "JavaScript",
+                    options.timeout.unwrap_or_default(),
+                    log_buffer
+                        .is_some()
+                        .then_some((captured_logs.as_slice(), stderr_output.as_slice())),
+                ));
+            }
             // Check for cancellation
             _ = async {
                 if let Some(ref ct) = cancellation_token {
@@ -1548,6 +1756,16 @@ try {{
 
     if !status.success() {
         let exit_code = status.code();
+
+        if is_out_of_memory(&stderr_output) {
+            warn!("[Node.js] Process ran out of memory");
+            return Err(script_out_of_memory_error(
+                "JavaScript",
+                options.max_memory_mb,
+                &stderr_output,
+            ));
+        }
+
         let stderr_combined = stderr_output.join("\n");
 
         // Use warn! since script failures are expected (user script errors, not system errors)
@@ -1631,14 +1849,14 @@ try {{
 /// # Arguments
 /// * `script` - The TypeScript code to execute
 /// * `cancellation_token` - Optional token to cancel execution
-/// * `working_dir` - Optional working directory for script execution
+/// * `options` - Working directory and env vars for the runtime process
 /// * `log_buffer` - Optional shared buffer for real-time log capture (useful for timeout scenarios)
 /// * `event_sender` - Optional channel to send workflow events (for real-time streaming)
 /// * `execution_id` - Optional execution ID for named pipe identification
 pub async fn execute_typescript_with_nodejs(
     script: String,
     cancellation_token: Option<tokio_util::sync::CancellationToken>,
-    working_dir: Option<PathBuf>,
+    options: ScriptExecutionOptions,
     log_buffer: Option<ScriptLogBuffer>,
     event_sender: Option<EventSender>,
     execution_id: Option<&str>,
@@ -1690,7 +1908,7 @@ pub async fn execute_typescript_with_nodejs(
     let script_path = script_dir.join(&script_filename);
 
     // When we have a custom working directory, we need to handle module resolution differently
-    let module_resolution_setup = if working_dir.is_some() {
+    let module_resolution_setup = if options.working_dir.is_some() {
         // Override require to resolve modules relative to the working directory
         r#"
 // Fix module resolution for custom working directories
@@ -1711,6 +1929,13 @@ Module.prototype.require = function(id: string) {
         ""
     };
 
+    let (network_guard, network_user_scope) = if options.no_network {
+        info!("[TypeScript] Network access disabled for this script");
+        (NO_NETWORK_GUARD, NO_NETWORK_USER_SCOPE)
+    } else {
+        ("", "")
+    };
+
     // Wrap the script with terminator.js imports and helpers (TypeScript version)
     let wrapped_script = format!(
         r#"
@@ -1746,7 +1971,7 @@ try {{
     // KV package not available
     createKVClient = () => {{ throw new Error('@mediar-ai/kv package not installed'); }};
 }}
-
+{network_guard}
 console.log('[TypeScript] Current working directory:', process.cwd());
 
 (async () => {{
@@ -1754,6 +1979,7 @@ console.log('[TypeScript] Current working directory:', process.cwd());
         // Execute user script and capture result
         const result = await (async () => {{
             // User script starts here
+            {network_user_scope}
             {script}
             // User script ends here
         }})();
@@ -1788,7 +2014,7 @@ console.log('[TypeScript] Current working directory:', process.cwd());
 
     // Build the command based on runtime
     // Determine the working directory for the process
-    let process_working_dir = if let Some(ref wd) = working_dir {
+    let process_working_dir = if let Some(ref wd) = options.working_dir {
         info!(
             "[TypeScript] Using custom working directory: {}",
             wd.display()
@@ -1863,6 +2089,8 @@ console.log('[TypeScript] Current working directory:', process.cwd());
     cmd.stderr(Stdio::piped());
     cmd.current_dir(&process_working_dir);
     cmd.env("TERMINATOR_PARENT_BRIDGE_PORT", "17373"); // Enable subprocess proxy mode
+    options.apply_env(&mut cmd);
+    options.apply_memory_limit(&mut cmd, runtime);
 
     // Set the pipe path environment variable if we have a pipe server
     #[cfg(windows)]
@@ -2006,21 +2234,34 @@ console.log('[TypeScript] Current working directory:', process.cwd());
         Ok(())
     };
 
-    // Handle cancellation if token provided
-    let process_result = if let Some(ct) = cancellation_token {
-        tokio::select! {
-            res = process_fut => res,
-            _ = ct.cancelled() => {
-                info!("[TypeScript] Cancellation requested, killing process");
-                let _ = child.kill().await;
-                return Err(McpError::internal_error(
-                    "TypeScript execution cancelled",
-                    None,
-                ));
+    // Handle cancellation and timeout
+    let deadline = options.deadline();
+    let process_result = tokio::select! {
+        res = process_fut => res,
+        _ = async {
+            match cancellation_token {
+                Some(ref ct) => ct.cancelled().await,
+                None => std::future::pending::<()>().await,
             }
+        } => {
+            info!("[TypeScript] Cancellation requested, killing process");
+            let _ = child.kill().await;
+            return Err(McpError::internal_error(
+                "TypeScript execution cancelled",
+                None,
+            ));
+        }
+        _ = wait_for_deadline(deadline) => {
+            warn!("[TypeScript] Script exceeded its timeout, killing process");
+            let _ = child.kill().await;
+            return Err(script_timeout_error(
+                "TypeScript",
+                options.timeout.unwrap_or_default(),
+                log_buffer
+                    .is_some()
+                    .then_some((captured_logs.as_slice(), stderr_output.as_slice())),
+            ));
         }
-    } else {
-        process_fut.await
     };
 
     // Shutdown the pipe server (Windows only)
@@ -2059,6 +2300,11 @@ console.log('[TypeScript] Current working directory:', process.cwd());
                 "stderr": stderr_output
             }))
         }
+        None if is_out_of_memory(&stderr_output) => Err(script_out_of_memory_error(
+            "TypeScript",
+            options.max_memory_mb,
+            &stderr_output,
+        )),
         None => {
             let stderr_combined = stderr_output.join("\n");
 
@@ -2076,11 +2322,18 @@ console.log('[TypeScript] Current working directory:', process.cwd());
 /// Execute Python using system interpreter with terminator.py bindings available
 pub async fn execute_python_with_bindings(
     script: String,
-    working_dir: Option<PathBuf>,
+    options: ScriptExecutionOptions,
 ) -> Result<serde_json::Value, McpError> {
     use std::process::Stdio;
     use tokio::process::Command;
 
+    if options.no_network {
+        return Err(McpError::invalid_params(
+            "no_network is not supported for Python scripts",
+            Some(json!({"engine": "python"})),
+        ));
+    }
+
     info!("[Python] Starting Python execution with terminator.py bindings");
     debug!("[Python] Script to execute ({} bytes)", script.len());
 
@@ -2203,7 +2456,7 @@ asyncio.run(__runner__())
     // This avoids potential path issues
 
     // Determine the working directory for the process
-    let process_working_dir = if let Some(wd) = working_dir {
+    let process_working_dir = if let Some(wd) = options.working_dir.clone() {
         info!("[Python] Using custom working directory: {}", wd.display());
         wd
     } else {
@@ -2220,8 +2473,8 @@ asyncio.run(__runner__())
     info!("[Python] Spawning process: {} {}", python_exe, script_arg);
     info!("[Python] Working dir: {}", process_working_dir.display());
 
-    let child = Command::new(&python_exe)
-        .current_dir(&process_working_dir)
+    let mut cmd = Command::new(&python_exe);
+    cmd.current_dir(&process_working_dir)
         .arg("-u") // Unbuffered output for Windows
         .arg(&script_arg)
         .env("PYTHONUNBUFFERED", "1") // Also set environment variable
@@ -2230,22 +2483,30 @@ asyncio.run(__runner__())
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
         .kill_on_drop(true) // Ensure child process is killed if parent dies
-        .no_window()
-        .spawn()
-        .map_err(|e| {
-            McpError::internal_error(
-                "Failed to spawn python process",
-                Some(json!({"error": e.to_string(), "python": python_exe, "script": script_arg})),
-            )
-        })?;
+        .no_window();
+    options.apply_env(&mut cmd);
+
+    let child = cmd.spawn().map_err(|e| {
+        McpError::internal_error(
+            "Failed to spawn python process",
+            Some(json!({"error": e.to_string(), "python": python_exe, "script": script_arg})),
+        )
+    })?;
 
     debug!(
         "[Python] Process spawned successfully, PID: {:?}",
         child.id()
     );
 
-    // Try waiting for the process to complete with timeout
-    match tokio::time::timeout(std::time::Duration::from_secs(10), child.wait_with_output()).await {
+    if let Some(max_memory_mb) = options.max_memory_mb {
+        warn!("[Python] max_memory_mb={max_memory_mb} ignored: no heap cap for the Python runtime");
+    }
+
+    // Wait for the process to complete, bounded by the configured timeout or, as
+    // before timeouts were configurable, PYTHON_DEFAULT_TIMEOUT
+    let timeout = options.timeout.unwrap_or(PYTHON_DEFAULT_TIMEOUT);
+    let output = tokio::time::timeout(timeout, child.wait_with_output()).await;
+    match output {
         Ok(Ok(output)) => {
             let stdout_str = String::from_utf8_lossy(&output.stdout);
             let stderr_str = String::from_utf8_lossy(&output.stderr);
@@ -2327,8 +2588,9 @@ asyncio.run(__runner__())
         )),
         Err(_) => {
             // Timeout - process will be killed automatically due to kill_on_drop
-            Err(McpError::internal_error(
-                "Python script execution timed out after 10 seconds",
+            Err(script_timeout_error(
+                "Python",
+                options.timeout.unwrap_or(PYTHON_DEFAULT_TIMEOUT),
                 None,
             ))
         }
@@ -3155,4 +3417,133 @@ mod tests {
             "global.emit syntax should still be valid"
         );
     }
+
+    /// Test that per-execution env vars are set on the runtime command
+    #[test]
+    fn test_execution_options_apply_env() {
+        let mut options = super::ScriptExecutionOptions::default();
+        options
+            .env
+            .insert("TERMINATOR_TEST_VAR".to_string(), "42".to_string());
+
+        let mut cmd = tokio::process::Command::new("node");
+        options.apply_env(&mut cmd);
+
+        let envs: Vec<_> = cmd.as_std().get_envs().collect();
+        assert_eq!(
+            envs,
+            vec![(
+                std::ffi::OsStr::new("TERMINATOR_TEST_VAR"),
+                Some(std::ffi::OsStr::new("42"))
+            )]
+        );
+    }
+
+    /// Test that the heap cap is passed to Node but not to bun
+    #[test]
+    fn test_execution_options_memory_limit() {
+        let mut options = super::ScriptExecutionOptions {
+            max_memory_mb: Some(256),
+            ..Default::default()
+        };
+        options.env.insert(
+            "NODE_OPTIONS".to_string(),
+            "--enable-source-maps".to_string(),
+        );
+
+        let mut node_cmd = tokio::process::Command::new("node");
+        options.apply_memory_limit(&mut node_cmd, "node");
+        let node_options = node_cmd
+            .as_std()
+            .get_envs()
+            .find(|(k, _)| *k == "NODE_OPTIONS")
+            .and_then(|(_, v)| v);
+        assert_eq!(
+            node_options,
+            Some(std::ffi::OsStr::new(
+                "--enable-source-maps --max-old-space-size=256"
+            ))
+        );
+
+        let mut bun_cmd = tokio::process::Command::new("bun");
+        options.apply_memory_limit(&mut bun_cmd, "bun");
+        assert_eq!(bun_cmd.as_std().get_envs().count(), 0);
+    }
+
+    /// Test detection of V8 heap exhaustion in stderr
+    #[test]
+    fn test_is_out_of_memory() {
+        let oom = vec![
+            "<--- Last few GCs --->".to_string(),
+            "FATAL ERROR: Reached heap limit Allocation failed - JavaScript heap out of memory"
+                .to_string(),
+        ];
+        assert!(super::is_out_of_memory(&oom));
+        assert!(!super::is_out_of_memory(&[
+            "TypeError: x is undefined".to_string()
+        ]));
+    }
+
+    /// Test that the no-network guard makes network attempts throw in a real runtime
+    #[test]
+    fn test_no_network_guard_blocks_network_apis() {
+        let Some(node) = super::find_executable("node") else {
+            eprintln!("node not found, skipping");
+            return;
+        };
+        let probe = r#"
+// The wrapper's own require stays unfiltered, like the SDK's
+const sdkHttps = typeof require('https').request;
+(async () => {
+    const require = __noNetworkRequire;
+    const attempts = {
+        fetch: () => fetch('http://127.0.0.1:9'),
+        require_https: () => require('https'),
+        require_child_process: () => require('node:child_process'),
+        import_https: () => import('node:https'),
+        import_net: () => import('net'),
+        binding: () => process.binding('tcp_wrap'),
+        desktop_run: () => desktop.run('curl example.com'),
+        desktop_open_url: () => desktop.openUrl('https://example.com'),
+    };
+    const results = {};
+    for (const [name, attempt] of Object.entries(attempts)) {
+        try { await attempt(); results[name] = 'allowed'; }
+        catch (e) { results[name] = /Network access is disabled/.test(e.message) ? 'blocked' : e.message; }
+    }
+    try { await import('node:path'); results.import_path = 'allowed'; } catch (e) { results.import_path = e.message; }
+    try { require('path'); results.require_path = 'allowed'; } catch (e) { results.require_path = e.message; }
+    results.sdk_https = sdkHttps;
+    console.log(JSON.stringify(results));
+})();
+"#;
+        let output = std::process::Command::new(node)
+            .arg("-e")
+            .arg(format!(
+                "global.desktop = {{ run() {{}}, openUrl() {{}} }};{}{probe}",
+                super::NO_NETWORK_GUARD
+            ))
+            .output()
+            .expect("run node");
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let results: serde_json::Value = serde_json::from_str(stdout.trim()).unwrap_or_else(|e| {
+            panic!("{e}: {stdout} {}", String::from_utf8_lossy(&output.stderr))
+        });
+
+        for attempt in [
+            "fetch",
+            "require_https",
+            "require_child_process",
+            "import_https",
+            "import_net",
+            "binding",
+            "desktop_run",
+            "desktop_open_url",
+        ] {
+            assert_eq!(results[attempt], "blocked", "{attempt}: {results}");
+        }
+        assert_eq!(results["import_path"], "allowed", "{results}");
+        assert_eq!(results["require_path"], "allowed", "{results}");
+        assert_eq!(results["sdk_https"], "function", "{results}");
+    }
 }
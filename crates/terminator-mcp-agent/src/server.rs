@@ -2,14 +2,16 @@ use crate::elicitation::try_elicit_raw;
 use crate::event_pipe::{create_event_channel, WorkflowEvent};
 use crate::execution_logger;
 use crate::helpers::*;
+use crate::mcp_types::{ReadOnlyTools, ToolInfo};
 use crate::scripting_engine;
 use crate::telemetry::StepSpan;
 use crate::utils::find_and_execute_with_retry_with_fallback;
 pub use crate::utils::DesktopWrapper;
 use crate::utils::{
-    get_timeout, ActivateElementArgs, AskUserArgs, CaptureScreenshotArgs, ClickElementArgs,
-    CopyContentArgs, DelayArgs, EditFileArgs, ExecuteBrowserScriptArgs, ExecuteSequenceArgs,
-    GeminiComputerUseArgs, GetApplicationsArgs, GetWindowTreeArgs, GlobFilesArgs, GlobalKeyArgs,
+    get_timeout, ActivateElementArgs, AskUserArgs, CaptureElementScreenshotArgs,
+    CaptureScreenshotArgs, ClickElementArgs, CopyContentArgs, DelayArgs, DescribeElementArgs,
+    EditFileArgs, ExecuteBrowserScriptArgs, ExecuteSequenceArgs, GeminiComputerUseArgs,
+    GetApplicationsArgs, GetFocusContextArgs, GetWindowTreeArgs, GlobFilesArgs, GlobalKeyArgs,
     GrepFilesArgs, HighlightElementArgs, InvokeElementArgs, MouseDragArgs, NavigateBrowserArgs,
     OpenApplicationArgs, PressKeyArgs, ReadFileArgs, RunCommandArgs, ScrollElementArgs,
     SelectOptionArgs, SetSelectedArgs, SetValueArgs, StopHighlightingArgs, TypeIntoElementArgs,
@@ -35,14 +37,14 @@ use std::time::Duration;
 use sysinfo::{ProcessesToUpdate, System};
 use terminator::element::UIElementImpl;
 use terminator::{
-    AutomationError, Browser, ComputerUseStep, Desktop, ProgressCallback, Selector, UIElement,
+    AutomationError, Browser, ComputerUseStep, CoordinateConverter, CoordinateMapper, Desktop,
+    ProgressCallback, Selector, UIElement,
 };
 use tokio::sync::Mutex;
 use tracing::{info, warn, Instrument};
 
 // New imports for image encoding
 use base64::{engine::general_purpose, Engine as _};
-use image::codecs::jpeg::JpegEncoder;
 use image::codecs::png::PngEncoder;
 
 use rmcp::service::{NotificationContext, Peer, RequestContext, RoleServer};
@@ -75,6 +77,26 @@ pub fn extract_content_json(content: &Content) -> Result<serde_json::Value, serd
     }
 }
 
+/// The full tool catalog (name, description, argument schema), sorted by name.
+///
+/// Built from the same `#[tool]` annotations the MCP handshake uses, without
+/// needing a running server or an initialized desktop.
+pub fn tool_catalog() -> Vec<ToolInfo> {
+    let mut tools: Vec<ToolInfo> = DesktopWrapper::tool_router()
+        .list_all()
+        .into_iter()
+        .map(ToolInfo::from)
+        .collect();
+    tools.sort_by(|a, b| a.name.cmp(&b.name));
+    tools
+}
+
+/// Tools annotated with `read_only_hint`, which the network gates let run
+/// alongside input tools.
+pub fn read_only_tool_names() -> ReadOnlyTools {
+    ReadOnlyTools::from_catalog(&tool_catalog())
+}
+
 /// Extract raw text from Content (for log extraction from run_command results)
 pub fn extract_content_text(content: &Content) -> Option<String> {
     match &content.raw {
@@ -83,6 +105,16 @@ pub fn extract_content_text(content: &Content) -> Option<String> {
     }
 }
 
+/// Interval of the background window-cache refresh from `MCP_WINDOW_CACHE_REFRESH_MS`;
+/// None (refresh on demand only) when unset or zero.
+fn window_cache_refresh_interval() -> Option<std::time::Duration> {
+    std::env::var("MCP_WINDOW_CACHE_REFRESH_MS")
+        .ok()
+        .and_then(|s| s.trim().parse::<u64>().ok())
+        .filter(|ms| *ms > 0)
+        .map(std::time::Duration::from_millis)
+}
+
 /// Capture screenshots of all monitors, save to disk, and return paths
 async fn capture_monitor_screenshots(desktop: &Desktop) -> Vec<String> {
     let mut paths = Vec::new();
@@ -282,10 +314,21 @@ impl DesktopWrapper {
             return Ok(());
         }
 
-        // Update window cache on-demand before managing windows
-        // Initial state is captured once before sequence starts (in server_sequence.rs)
-        if let Err(e) = self.window_manager.update_window_cache().await {
-            tracing::warn!("Failed to update window cache: {}", e);
+        // Update window cache on-demand before managing windows, unless the background
+        // refresh has kept it warm. Initial state is captured once before sequence starts
+        // (in server_sequence.rs)
+        let cache_is_warm = match &self.window_cache_refresh {
+            Some(refresh) => self
+                .window_manager
+                .cache_age()
+                .await
+                .is_some_and(|age| age <= refresh.interval() * 2),
+            None => false,
+        };
+        if !cache_is_warm {
+            if let Err(e) = self.window_manager.update_window_cache().await {
+                tracing::warn!("Failed to update window cache: {}", e);
+            }
         }
 
         // Handle execution context-aware window management
@@ -792,6 +835,11 @@ impl DesktopWrapper {
             }
         };
 
+        let window_manager = Arc::new(terminator::WindowManager::new());
+        let window_cache_refresh = window_cache_refresh_interval()
+            .filter(|_| tokio::runtime::Handle::try_current().is_ok())
+            .map(|interval| Arc::new(window_manager.start_background_refresh(interval)));
+
         Ok(Self {
             desktop: Arc::new(desktop),
             tool_router: Self::tool_router(),
@@ -801,7 +849,8 @@ impl DesktopWrapper {
             captured_stderr_logs: Arc::new(std::sync::Mutex::new(Vec::new())),
             current_workflow_dir: Arc::new(Mutex::new(None)),
             current_scripts_base_path: Arc::new(Mutex::new(None)),
-            window_manager: Arc::new(terminator::WindowManager::new()),
+            window_manager,
+            window_cache_refresh,
             in_sequence: Arc::new(std::sync::Mutex::new(false)),
             ocr_bounds: Arc::new(std::sync::Mutex::new(std::collections::HashMap::new())),
             omniparser_items: Arc::new(std::sync::Mutex::new(std::collections::HashMap::new())),
@@ -814,6 +863,7 @@ impl DesktopWrapper {
             client_modes: Arc::new(Mutex::new(std::collections::HashMap::new())),
             elicitation_peer: Arc::new(Mutex::new(None)),
             broadcast_peers: Arc::new(Mutex::new(Vec::new())),
+            idempotency: Arc::new(crate::idempotency::IdempotencyStore::default()),
         })
     }
 
@@ -1052,8 +1102,8 @@ impl DesktopWrapper {
         let original_height = screenshot.height;
 
         // DPI DEBUG: Compare logical window bounds vs physical screenshot size
-        let dpi_scale_w = original_width as f64 / win_w;
-        let dpi_scale_h = original_height as f64 / win_h;
+        let mapper = CoordinateMapper::for_capture(bounds, original_width, original_height);
+        let (dpi_scale_w, dpi_scale_h) = mapper.dpi_scale;
         info!(
             "OMNIPARSER DPI DEBUG: window_bounds(logical)=({:.0},{:.0},{:.0},{:.0}), screenshot(physical)={}x{}, dpi_scale=({:.3},{:.3})",
             window_x, window_y, win_w, win_h, original_width, original_height, dpi_scale_w, dpi_scale_h
@@ -1128,23 +1178,13 @@ impl DesktopWrapper {
         .map_err(|e| format!("Omniparser failed: {e}"))?;
 
         // Convert coordinates to absolute screen coordinates
-        // If image was resized, scale coordinates back to original size first
-        // Then apply DPI scaling to convert from physical to logical coordinates
+        // box_2d is [x_min, y_min, x_max, y_max] relative to the (possibly resized) screenshot
+        let mapper = mapper.with_resize_scale(scale_factor);
         let mut absolute_items = Vec::new();
         for item in items {
             let mut new_item = item.clone();
             if let Some(box_2d) = new_item.box_2d {
-                // box_2d is [x_min, y_min, x_max, y_max] relative to the (possibly resized) screenshot
-                // 1. Scale back to original screenshot size if image was resized (inv_scale)
-                // 2. Convert from physical to logical coords (divide by dpi_scale)
-                // 3. Add logical window offset
-                let inv_scale = 1.0 / scale_factor;
-                new_item.box_2d = Some([
-                    window_x + (box_2d[0] * inv_scale / dpi_scale_w),
-                    window_y + (box_2d[1] * inv_scale / dpi_scale_h),
-                    window_x + (box_2d[2] * inv_scale / dpi_scale_w),
-                    window_y + (box_2d[3] * inv_scale / dpi_scale_h),
-                ]);
+                new_item.box_2d = Some(mapper.box_to_screen(box_2d));
             }
             absolute_items.push(new_item);
         }
@@ -1184,8 +1224,8 @@ impl DesktopWrapper {
         let original_height = screenshot.height;
 
         // DPI DEBUG: Compare logical window bounds vs physical screenshot size
-        let dpi_scale_w = original_width as f64 / win_w;
-        let dpi_scale_h = original_height as f64 / win_h;
+        let mapper = CoordinateMapper::for_capture(bounds, original_width, original_height);
+        let (dpi_scale_w, dpi_scale_h) = mapper.dpi_scale;
         info!(
             "GEMINI VISION DPI DEBUG: window_bounds(logical)=({:.0},{:.0},{:.0},{:.0}), screenshot(physical)={}x{}, dpi_scale=({:.3},{:.3})",
             window_x, window_y, win_w, win_h, original_width, original_height, dpi_scale_w, dpi_scale_h
@@ -1256,23 +1296,13 @@ impl DesktopWrapper {
                 .map_err(|e| format!("Gemini Vision failed: {e}"))?;
 
         // Convert coordinates to absolute screen coordinates
-        // If image was resized, scale coordinates back to original size first
-        // Then apply DPI scaling to convert from physical to logical coordinates
+        // box_2d is [x_min, y_min, x_max, y_max] relative to the (possibly resized) screenshot
+        let mapper = mapper.with_resize_scale(scale_factor);
         let mut absolute_items = Vec::new();
         for item in items {
             let mut new_item = item.clone();
             if let Some(box_2d) = new_item.box_2d {
-                // box_2d is [x_min, y_min, x_max, y_max] relative to the (possibly resized) screenshot
-                // 1. Scale back to original screenshot size if image was resized (inv_scale)
-                // 2. Convert from physical to logical coords (divide by dpi_scale)
-                // 3. Add logical window offset
-                let inv_scale = 1.0 / scale_factor;
-                new_item.box_2d = Some([
-                    window_x + (box_2d[0] * inv_scale / dpi_scale_w),
-                    window_y + (box_2d[1] * inv_scale / dpi_scale_h),
-                    window_x + (box_2d[2] * inv_scale / dpi_scale_w),
-                    window_y + (box_2d[3] * inv_scale / dpi_scale_h),
-                ]);
+                new_item.box_2d = Some(mapper.box_to_screen(box_2d));
             }
             absolute_items.push(new_item);
         }
@@ -1283,41 +1313,8 @@ impl DesktopWrapper {
     /// Perform OCR on a window by its process ID and return structured results with bounding boxes
     #[cfg(target_os = "windows")]
     async fn perform_ocr_for_process(&self, pid: u32) -> Result<terminator::OcrElement, String> {
-        // Find the window element for this process
-        let apps = self
-            .desktop
-            .applications()
-            .map_err(|e| format!("Failed to get applications: {e}"))?;
-
-        let window_element = apps
-            .into_iter()
-            .find(|app| app.process_id().unwrap_or(0) == pid)
-            .ok_or_else(|| format!("No window found for PID {pid}"))?;
-
-        // Get window bounds (absolute screen coordinates)
-        let bounds = window_element
-            .bounds()
-            .map_err(|e| format!("Failed to get window bounds: {e}"))?;
-
-        let (window_x, window_y, win_w, win_h) = bounds;
-
-        // Capture screenshot of the window
-        let screenshot = window_element
-            .capture()
-            .map_err(|e| format!("Failed to capture window screenshot: {e}"))?;
-
-        // Calculate DPI scale factors (physical screenshot pixels / logical window size)
-        let dpi_scale_w = screenshot.width as f64 / win_w;
-        let dpi_scale_h = screenshot.height as f64 / win_h;
-        info!(
-            "OCR DPI: window_bounds(logical)=({:.0},{:.0},{:.0},{:.0}), screenshot(physical)={}x{}, dpi_scale=({:.3},{:.3})",
-            window_x, window_y, win_w, win_h, screenshot.width, screenshot.height, dpi_scale_w, dpi_scale_h
-        );
-
-        // Perform OCR with bounding boxes using Desktop's method
-        // Pass DPI scale factors to convert physical OCR coords to logical screen coords
         self.desktop
-            .ocr_screenshot_with_bounds(&screenshot, window_x, window_y, dpi_scale_w, dpi_scale_h)
+            .ocr_window_with_bounds(pid)
             .map_err(|e| format!("OCR failed: {e}"))
     }
 
@@ -1327,7 +1324,8 @@ impl DesktopWrapper {
     }
 
     #[tool(
-        description = "Get UI tree for a process. Use ONLY at task start or for special modes (OCR, DOM, Omniparser, Gemini vision). Do NOT call after action tools - use their ui_diff_before_after/include_tree_after_action params instead. Options: include_browser_dom for DOM, include_ocr for text, include_omniparser for icons, include_gemini_vision for AI detection. tree_max_depth limits depth, tree_from_selector focuses on subtree. Read-only."
+        description = "Get UI tree for a process. Use ONLY at task start or for special modes (OCR, DOM, Omniparser, Gemini vision). Do NOT call after action tools - use their ui_diff_before_after/include_tree_after_action params instead. Options: include_browser_dom for DOM, include_ocr for text, include_omniparser for icons, include_gemini_vision for AI detection. tree_max_depth limits depth, tree_from_selector focuses on subtree. Read-only.",
+        annotations(read_only_hint = true)
     )]
     pub async fn get_window_tree(
         &self,
@@ -1347,6 +1345,19 @@ impl DesktopWrapper {
                 .to_string(),
         );
 
+        // Reject a bad cursor before touching any windows
+        let page_start = match args.page.as_ref().map(|page| page.start()).transpose() {
+            Ok(start) => start,
+            Err(e) => {
+                span.set_status(false, Some("Invalid page cursor"));
+                span.end();
+                return Err(McpError::invalid_params(
+                    e,
+                    Some(json!({"cursor": args.page.as_ref().and_then(|p| p.cursor.clone())})),
+                ));
+            }
+        };
+
         // Check if we need to perform window management (only for direct MCP calls, not sequences)
         let should_restore = {
             let in_sequence = self.in_sequence.lock().unwrap_or_else(|e| e.into_inner());
@@ -1504,6 +1515,8 @@ impl DesktopWrapper {
             &mut result_json,
             None, // No found element for window tree
             include_all_bounds,
+            args.include_relationships,
+            args.stable_ids,
         )
         .await
         {
@@ -1512,6 +1525,26 @@ impl DesktopWrapper {
             }
         }
 
+        // Send only the requested page of the tree; the bounds cache above keeps every
+        // element so indices from any page stay clickable
+        if let (Some(page), Some(start)) = (&args.page, page_start) {
+            if let Some(tree) = result_json.get("ui_tree") {
+                let max_elements = page
+                    .max_elements
+                    .unwrap_or(crate::utils::DEFAULT_TREE_PAGE_SIZE)
+                    .max(1);
+                let paged = crate::tree_formatter::paginate_tree(tree, start, max_elements);
+                span.set_attribute("page.start", start.to_string());
+                result_json["ui_tree"] = paged.tree;
+                result_json["page"] = json!({
+                    "cursor": start.to_string(),
+                    "max_elements": max_elements,
+                    "total_elements": paged.total_elements,
+                    "next_cursor": paged.next_start.map(|next| next.to_string()),
+                });
+            }
+        }
+
         // Perform OCR if requested
         if args.include_ocr {
             match self.perform_ocr_for_process(pid).await {
@@ -2069,7 +2102,8 @@ impl DesktopWrapper {
     }
 
     #[tool(
-        description = "Get all applications and windows currently running with their process names. Returns a list with name, process_name, id, pid, and is_focused status for each application/window. Use this to check which applications are running and which window has focus before performing actions. This is a read-only operation that returns a simple list without UI trees."
+        description = "Get all applications and windows currently running with their process names. Returns a list with name, process_name, id, pid, and is_focused status for each application/window. Use this to check which applications are running and which window has focus before performing actions. This is a read-only operation that returns a simple list without UI trees.",
+        annotations(read_only_hint = true)
     )]
     pub async fn get_applications_and_windows_list(
         &self,
@@ -2146,6 +2180,92 @@ impl DesktopWrapper {
         Ok(CallToolResult::success(vec![Content::json(result_json)?]))
     }
 
+    #[tool(
+        description = "Answers \"where am I\": returns the element that has keyboard focus (role, name, value, and with include_selector a selector that finds it), the window containing it (title and pid), and the siblings around it. Call this before acting when unsure what is focused - it is much smaller than a full get_window_tree. Read-only.",
+        annotations(read_only_hint = true)
+    )]
+    pub async fn get_focus_context(
+        &self,
+        Parameters(args): Parameters<GetFocusContextArgs>,
+    ) -> Result<CallToolResult, McpError> {
+        // Start telemetry span
+        let mut span = StepSpan::new("get_focus_context", None);
+        span.set_attribute("sibling_count", args.sibling_count.to_string());
+        span.set_attribute("include_selector", args.include_selector.to_string());
+
+        let focused = match self.desktop.focused_element() {
+            Ok(element) => element,
+            Err(e) => {
+                span.set_status(false, Some("No focused element"));
+                span.end();
+                return Err(McpError::resource_not_found(
+                    "Failed to get focused element",
+                    Some(json!({"reason": e.to_string()})),
+                ));
+            }
+        };
+
+        let window = terminator::find_parent_window(&focused).map(|window| {
+            json!({
+                "title": window.name().unwrap_or_default(),
+                "role": window.role(),
+                "pid": window.process_id().ok(),
+            })
+        });
+
+        // Siblings in tree order, centred on the focused element
+        let siblings = focused
+            .parent()
+            .ok()
+            .flatten()
+            .and_then(|parent| parent.children().ok())
+            .unwrap_or_default();
+        let position = siblings.iter().position(|sibling| sibling == &focused);
+        let nearby: Vec<_> = match position {
+            Some(position) => {
+                let start = position.saturating_sub(args.sibling_count);
+                let end = position
+                    .saturating_add(args.sibling_count)
+                    .saturating_add(1)
+                    .min(siblings.len());
+                siblings[start..end]
+                    .iter()
+                    .enumerate()
+                    .map(|(offset, sibling)| {
+                        json!({
+                            "offset": (start + offset) as i64 - position as i64,
+                            "role": sibling.role(),
+                            "name": sibling.name().unwrap_or_default(),
+                        })
+                    })
+                    .collect()
+            }
+            None => Vec::new(),
+        };
+
+        let mut result_json = json!({
+            "action": "get_focus_context",
+            "status": "success",
+            "focused_element": {
+                "role": focused.role(),
+                "name": focused.name().unwrap_or_default(),
+                "value": focused.get_value().ok().flatten(),
+                "pid": focused.process_id().ok(),
+            },
+            "window": window,
+            "siblings": nearby,
+            "timestamp": chrono::Utc::now().to_rfc3339()
+        });
+        if args.include_selector {
+            result_json["focused_element"]["selector"] = json!(focused.to_selector().await.ok());
+        }
+
+        span.set_status(true, None);
+        span.end();
+
+        Ok(CallToolResult::success(vec![Content::json(result_json)?]))
+    }
+
     // NOTE: ensure_element_in_view logic moved to terminator backend UIElement::ensure_in_view()
 
     #[tool(
@@ -2321,11 +2441,12 @@ impl DesktopWrapper {
                 alternative_selectors.as_deref(),
                 fallback_selectors.as_deref(),
                 args.timeout_ms,
-                args.retries,
+                &args.retry_policy(),
                 action,
                 args.tree.ui_diff_before_after,
                 args.tree.tree_max_depth,
                 args.tree.include_detailed_attributes,
+                args.tree.capture_visual_diff,
                 tree_output_format,
             )
             .await
@@ -2441,6 +2562,9 @@ impl DesktopWrapper {
 
             result_json["ui_diff"] = json!(diff_result.diff);
             result_json["has_ui_changes"] = json!(diff_result.has_changes);
+            if let Some(visual_diff) = diff_result.visual_diff {
+                result_json["visual_diff"] = visual_diff;
+            }
         }
 
         // Restore windows after typing into element
@@ -2935,11 +3059,12 @@ Click types: 'left' (default), 'double', 'right'. Use ui_diff_before_after:true
                     args.build_alternative_selectors().as_deref(),
                     args.build_fallback_selectors().as_deref(),
                     args.action.timeout_ms,
-                    args.action.retries,
+                    &args.action.retry_policy(),
                     action,
                     args.tree.ui_diff_before_after,
                     args.tree.tree_max_depth,
                     args.tree.include_detailed_attributes,
+                    args.tree.capture_visual_diff,
                     tree_output_format,
                 )
                 .await;
@@ -3039,6 +3164,9 @@ Click types: 'left' (default), 'double', 'right'. Use ui_diff_before_after:true
                     span.set_attribute("ui_diff.has_changes", diff_result.has_changes.to_string());
                     result_json["ui_diff"] = json!(diff_result.diff);
                     result_json["has_ui_changes"] = json!(diff_result.has_changes);
+                    if let Some(visual_diff) = diff_result.visual_diff {
+                        result_json["visual_diff"] = visual_diff;
+                    }
                 }
 
                 self.restore_window_management(should_restore).await;
@@ -3167,11 +3295,12 @@ Click types: 'left' (default), 'double', 'right'. Use ui_diff_before_after:true
                 None, // PressKey doesn't have alternative selectors yet
                 args.selector.build_fallback_selectors().as_deref(),
                 args.action.timeout_ms,
-                args.action.retries,
+                &args.action.retry_policy(),
                 action,
                 args.tree.ui_diff_before_after,
                 args.tree.tree_max_depth,
                 args.tree.include_detailed_attributes,
+                args.tree.capture_visual_diff,
                 tree_output_format,
             )
             .await
@@ -3300,6 +3429,9 @@ Click types: 'left' (default), 'double', 'right'. Use ui_diff_before_after:true
 
             result_json["ui_diff"] = json!(diff_result.diff);
             result_json["has_ui_changes"] = json!(diff_result.has_changes);
+            if let Some(visual_diff) = diff_result.visual_diff {
+                result_json["visual_diff"] = visual_diff;
+            }
         }
 
         // Restore windows after pressing key
@@ -3967,21 +4099,31 @@ DATA PASSING:
             let is_ts = matches!(engine.as_str(), "typescript" | "ts");
             let is_py = matches!(engine.as_str(), "python" | "py");
 
-            if is_js {
-                // Determine the working directory for script execution
-                let script_working_dir = if let Some(ref script_path) = resolved_script_path {
-                    // When using script_file with scripts_base_path, change working dir to script's directory
-                    let scripts_base_guard = self.current_scripts_base_path.lock().await;
-                    if scripts_base_guard.is_some() {
-                        // Use the resolved script path's parent directory
-                        script_path.parent().map(|p| p.to_path_buf())
-                    } else {
-                        None
-                    }
+            // Determine the working directory for script execution
+            let script_working_dir = if let Some(ref wd) = args.working_directory {
+                // An explicit working_directory always wins
+                Some(expand_working_directory_shortcut(wd))
+            } else if let Some(ref script_path) = resolved_script_path {
+                // When using script_file with scripts_base_path, change working dir to script's directory
+                let scripts_base_guard = self.current_scripts_base_path.lock().await;
+                if scripts_base_guard.is_some() {
+                    // Use the resolved script path's parent directory
+                    script_path.parent().map(|p| p.to_path_buf())
                 } else {
                     None
-                };
+                }
+            } else {
+                None
+            };
+            let script_options = scripting_engine::ScriptExecutionOptions {
+                working_dir: script_working_dir,
+                env: args.process_env.clone().unwrap_or_default(),
+                timeout: (timeout_ms > 0).then_some(timeout_duration),
+                max_memory_mb: args.max_memory_mb,
+                no_network: args.no_network.unwrap_or(false),
+            };
 
+            if is_js {
                 // Create shared log buffer for real-time log capture (useful for timeout scenarios)
                 let include_logs = args.include_logs.unwrap_or(true);
                 let log_buffer = if include_logs {
@@ -4120,41 +4262,13 @@ DATA PASSING:
                 let execution_future = scripting_engine::execute_javascript_with_nodejs(
                     final_script,
                     cancellation_token,
-                    script_working_dir,
-                    log_buffer.clone(),
+                    script_options,
+                    log_buffer,
                     Some(event_tx),
                     Some(&execution_id),
                 );
 
-                let execution_result = if timeout_ms == 0 {
-                    execution_future.await?
-                } else {
-                    match tokio::time::timeout(timeout_duration, execution_future).await {
-                        Ok(result) => result?,
-                        Err(_) => {
-                            // On timeout, include any logs captured before the timeout if include_logs is true
-                            let mut error_data = json!({
-                                "reason": format!("Execution exceeded timeout of {}ms", timeout_ms),
-                                "engine": "javascript",
-                                "timeout_ms": timeout_ms
-                            });
-                            if let Some(ref buf) = log_buffer {
-                                let partial_logs = buf.get_logs();
-                                let partial_stderr = buf.get_stderr();
-                                if !partial_logs.is_empty() {
-                                    error_data["logs"] = json!(partial_logs);
-                                }
-                                if !partial_stderr.is_empty() {
-                                    error_data["stderr"] = json!(partial_stderr);
-                                }
-                            }
-                            return Err(McpError::internal_error(
-                                "JavaScript execution timed out",
-                                Some(error_data),
-                            ));
-                        }
-                    }
-                };
+                let execution_result = execution_future.await?;
 
                 // Wait for screenshot collector to finish
                 let _ = screenshot_collector.await;
@@ -4268,20 +4382,6 @@ DATA PASSING:
                     append_monitor_screenshots_if_enabled(&self.desktop, contents, None).await,
                 ));
             } else if is_ts {
-                // Determine the working directory for script execution
-                let script_working_dir = if let Some(ref script_path) = resolved_script_path {
-                    // When using script_file with scripts_base_path, change working dir to script's directory
-                    let scripts_base_guard = self.current_scripts_base_path.lock().await;
-                    if scripts_base_guard.is_some() {
-                        // Use the resolved script path's parent directory
-                        script_path.parent().map(|p| p.to_path_buf())
-                    } else {
-                        None
-                    }
-                } else {
-                    None
-                };
-
                 // Create shared log buffer for real-time log capture (useful for timeout scenarios)
                 let include_logs = args.include_logs.unwrap_or(true);
                 let log_buffer = if include_logs {
@@ -4420,41 +4520,13 @@ DATA PASSING:
                 let execution_future = scripting_engine::execute_typescript_with_nodejs(
                     final_script,
                     cancellation_token,
-                    script_working_dir,
-                    log_buffer.clone(),
+                    script_options,
+                    log_buffer,
                     Some(event_tx),
                     Some(&execution_id),
                 );
 
-                let execution_result = if timeout_ms == 0 {
-                    execution_future.await?
-                } else {
-                    match tokio::time::timeout(timeout_duration, execution_future).await {
-                        Ok(result) => result?,
-                        Err(_) => {
-                            // On timeout, include any logs captured before the timeout if include_logs is true
-                            let mut error_data = json!({
-                                "reason": format!("Execution exceeded timeout of {}ms", timeout_ms),
-                                "engine": "typescript",
-                                "timeout_ms": timeout_ms
-                            });
-                            if let Some(ref buf) = log_buffer {
-                                let partial_logs = buf.get_logs();
-                                let partial_stderr = buf.get_stderr();
-                                if !partial_logs.is_empty() {
-                                    error_data["logs"] = json!(partial_logs);
-                                }
-                                if !partial_stderr.is_empty() {
-                                    error_data["stderr"] = json!(partial_stderr);
-                                }
-                            }
-                            return Err(McpError::internal_error(
-                                "TypeScript execution timed out",
-                                Some(error_data),
-                            ));
-                        }
-                    }
-                };
+                let execution_result = execution_future.await?;
 
                 // Wait for screenshot collector to finish
                 let _ = screenshot_collector.await;
@@ -4557,42 +4629,10 @@ DATA PASSING:
                     append_monitor_screenshots_if_enabled(&self.desktop, contents, None).await,
                 ));
             } else if is_py {
-                // Determine the working directory for script execution
-                let script_working_dir = if let Some(ref script_path) = resolved_script_path {
-                    // When using script_file with scripts_base_path, change working dir to script's directory
-                    let scripts_base_guard = self.current_scripts_base_path.lock().await;
-                    if scripts_base_guard.is_some() {
-                        // Use the resolved script path's parent directory
-                        script_path.parent().map(|p| p.to_path_buf())
-                    } else {
-                        None
-                    }
-                } else {
-                    None
-                };
+                let execution_future =
+                    scripting_engine::execute_python_with_bindings(final_script, script_options);
 
-                let execution_future = scripting_engine::execute_python_with_bindings(
-                    final_script,
-                    script_working_dir,
-                );
-
-                let execution_result = if timeout_ms == 0 {
-                    execution_future.await?
-                } else {
-                    match tokio::time::timeout(timeout_duration, execution_future).await {
-                        Ok(result) => result?,
-                        Err(_) => {
-                            return Err(McpError::internal_error(
-                                "Python execution timed out",
-                                Some(json!({
-                                    "reason": format!("Execution exceeded timeout of {}ms", timeout_ms),
-                                    "engine": "python",
-                                    "timeout_ms": timeout_ms
-                                })),
-                            ));
-                        }
-                    }
-                };
+                let execution_result = execution_future.await?;
 
                 // Extract logs, stderr, and actual result (same structure as JS/TS now)
                 let logs = execution_result.get("logs").cloned();
@@ -4880,9 +4920,13 @@ DATA PASSING:
 
         // Default timeout: 2 minutes (120000ms), 0 means no timeout
         let timeout_ms = args.timeout_ms.unwrap_or(120_000);
-        let command_future = self
-            .desktop
-            .run_command(windows_cmd.as_deref(), unix_cmd.as_deref());
+        let command_future = self.desktop.run_command_with_options(
+            windows_cmd.as_deref(),
+            unix_cmd.as_deref(),
+            terminator::RunCommandOptions {
+                capture_combined: args.capture_combined.unwrap_or(false),
+            },
+        );
 
         let output = if timeout_ms == 0 {
             // No timeout
@@ -4922,19 +4966,24 @@ DATA PASSING:
         span.set_status(true, None);
         span.end();
 
+        let mut result = json!({
+            "exit_status": output.exit_status,
+            "stdout": output.stdout,
+            "stderr": output.stderr,
+            "command": run_str,
+            "shell": args.shell.unwrap_or_else(|| {
+                if cfg!(target_os = "windows") { "powershell" } else { "bash" }.to_string()
+            }),
+            "working_directory": args.working_directory
+        });
+        if let Some(combined) = output.combined {
+            result["combined"] = json!(combined);
+        }
+
         Ok(CallToolResult::success(
             append_monitor_screenshots_if_enabled(
                 &self.desktop,
-                vec![Content::json(json!({
-                    "exit_status": output.exit_status,
-                    "stdout": output.stdout,
-                    "stderr": output.stderr,
-                    "command": run_str,
-                    "shell": args.shell.unwrap_or_else(|| {
-                        if cfg!(target_os = "windows") { "powershell" } else { "bash" }.to_string()
-                    }),
-                    "working_directory": args.working_directory
-                }))?],
+                vec![Content::json(result)?],
                 None,
             )
             .await,
@@ -4994,7 +5043,7 @@ DATA PASSING:
                 None, // ActivateElement doesn't have alternative selectors
                 args.selector.build_fallback_selectors().as_deref(),
                 args.action.timeout_ms,
-                args.action.retries,
+                &args.action.retry_policy(),
                 |element| async move { element.activate_window() },
             )
             .await
@@ -5089,6 +5138,8 @@ DATA PASSING:
             &mut result_json,
             Some(&element),
             false,
+            false,
+            false,
         )
         .await;
 
@@ -5307,7 +5358,7 @@ DATA PASSING:
                 args.selector.build_alternative_selectors().as_deref(),
                 args.selector.build_fallback_selectors().as_deref(),
                 args.action.timeout_ms,
-                args.action.retries,
+                &args.action.retry_policy(),
                 action,
             )
             .await
@@ -5429,7 +5480,8 @@ DATA PASSING:
     }
 
     #[tool(
-        description = "Validates that an element exists and provides detailed information about it. This is a read-only operation that NEVER throws errors. Returns status='success' with exists=true when found, or status='failed' with exists=false when not found. Use {step_id}_status or {step_id}_result.exists for conditional logic. This is the preferred tool for checking optional/conditional UI elements."
+        description = "Validates that an element exists and provides detailed information about it. This is a read-only operation that NEVER throws errors. Returns status='success' with exists=true when found, or status='failed' with exists=false when not found. Use {step_id}_status or {step_id}_result.exists for conditional logic. This is the preferred tool for checking optional/conditional UI elements.",
+        annotations(read_only_hint = true)
     )]
     pub async fn validate_element(
         &self,
@@ -5480,7 +5532,7 @@ DATA PASSING:
             args.selector.build_alternative_selectors().as_deref(),
             args.selector.build_fallback_selectors().as_deref(),
             args.action.timeout_ms,
-            args.action.retries,
+            &args.action.retry_policy(),
             action,
         )
         .await
@@ -5519,6 +5571,8 @@ DATA PASSING:
                     &mut result_json,
                     Some(&element),
                     false,
+                    false,
+                    false,
                 )
                 .await;
 
@@ -5596,6 +5650,121 @@ DATA PASSING:
         }
     }
 
+    #[tool(
+        description = "Focuses an element and reads its accessibility details in one call: role, name, value, description, help_text, states (enabled/focused/selected/toggled) and the interaction patterns it supports (Invoke, Toggle, Value, ExpandCollapse, ...). Use this to learn what actions an element accepts before acting on it. Set focus:false to read without moving focus."
+    )]
+    pub async fn describe_element(
+        &self,
+        Parameters(args): Parameters<DescribeElementArgs>,
+    ) -> Result<CallToolResult, McpError> {
+        // Start telemetry span
+        let mut span = StepSpan::new("describe_element", None);
+        span.set_attribute("selector", args.selector.selector.clone());
+        span.set_attribute("focus", args.focus.to_string());
+        if let Some(retries) = args.action.retries {
+            span.set_attribute("retry.max_attempts", retries.to_string());
+        }
+
+        // Check if we need to perform window management (only for direct MCP calls, not sequences)
+        let should_restore = {
+            let in_sequence = self.in_sequence.lock().unwrap_or_else(|e| e.into_inner());
+            !*in_sequence
+        };
+
+        if should_restore {
+            tracing::info!(
+                "[describe_element] Direct MCP call detected - performing window management"
+            );
+            let _ = self
+                .prepare_window_management(
+                    &args.selector.process,
+                    None,
+                    None,
+                    None,
+                    &args.window_mgmt,
+                )
+                .await;
+        } else {
+            tracing::debug!("[describe_element] In sequence - skipping window management (dispatch_tool handles it)");
+        }
+
+        let focus = args.focus;
+        let action = move |element: UIElement| async move {
+            // A focus failure shouldn't hide the rest of the description
+            let focus_error = if focus {
+                element.focus().err().map(|e| e.to_string())
+            } else {
+                None
+            };
+            Ok(focus_error)
+        };
+
+        let ((focus_error, element), successful_selector) =
+            match find_and_execute_with_retry_with_fallback(
+                &self.desktop,
+                &args.selector.build_full_selector(),
+                args.selector.build_alternative_selectors().as_deref(),
+                args.selector.build_fallback_selectors().as_deref(),
+                args.action.timeout_ms,
+                &args.action.retry_policy(),
+                action,
+            )
+            .await
+            {
+                Ok(result) => result,
+                Err(e) => {
+                    self.restore_window_management(should_restore).await;
+                    span.set_status(false, Some("Element not found"));
+                    span.end();
+                    return Err(build_element_not_found_error(
+                        &args.selector.build_full_selector(),
+                        args.selector.build_alternative_selectors().as_deref(),
+                        args.selector.build_fallback_selectors().as_deref(),
+                        e,
+                    ));
+                }
+            };
+
+        let attributes = element.attributes();
+        let description = json!({
+            "role": element.role(),
+            "name": element.name().unwrap_or_default(),
+            "id": element.id().unwrap_or_default(),
+            "value": element.get_value().ok().flatten(),
+            "description": attributes.description.filter(|d| !d.is_empty()),
+            "help_text": element.help_text(),
+            "shortcut": element.shortcut(),
+            "states": {
+                "enabled": element.is_enabled().unwrap_or(false),
+                "focused": element.is_focused().unwrap_or(false),
+                "selected": element.is_selected().unwrap_or(false),
+                "toggled": element.is_toggled().unwrap_or(false),
+                "visible": element.is_visible().unwrap_or(false),
+                "keyboard_focusable": element.is_keyboard_focusable().unwrap_or(false),
+            },
+            "patterns": element.supported_patterns(),
+        });
+
+        let mut result_json = json!({
+            "action": "describe_element",
+            "status": "success",
+            "element": description,
+            "selector_used": successful_selector,
+            "selectors_tried": get_selectors_tried_all(&args.selector.build_full_selector(), args.selector.build_alternative_selectors().as_deref(), args.selector.build_fallback_selectors().as_deref()),
+            "timestamp": chrono::Utc::now().to_rfc3339()
+        });
+        if let Some(error) = focus_error {
+            result_json["focus_error"] = json!(error);
+        }
+
+        self.restore_window_management(should_restore).await;
+
+        span.set_status(true, None);
+        span.end();
+
+        Ok(CallToolResult::success(vec![Content::json(result_json)?]))
+    }
+
     #[tool(description = "Highlights an element with a colored border for visual confirmation.")]
     async fn highlight_element(
         &self,
@@ -5660,6 +5829,8 @@ DATA PASSING:
         #[cfg(not(target_os = "windows"))]
         let font_style = None;
 
+        let scroll_first = args.scroll_into_view.unwrap_or(false);
+
         let action = {
             move |element: UIElement| {
                 let color = color;
@@ -5667,6 +5838,20 @@ DATA PASSING:
                 let local_text_position = text_position;
                 let local_font_style = font_style.clone();
                 async move {
+                    // Scroll first so the overlay is drawn at the element's bounds after scrolling
+                    let scroll = scroll_first.then(|| {
+                        if let Err(e) = element.scroll_into_view() {
+                            tracing::warn!("[highlight_element] scroll_into_view failed: {}", e);
+                        }
+                        // scroll_into_view can succeed without moving anything on screen,
+                        // so report where the element ended up rather than the call result
+                        json!({
+                            "scrolled": element.is_visible().unwrap_or(false),
+                            "bounds": element.bounds().ok().map(|(x, y, width, height)| {
+                                json!({"x": x, "y": y, "width": width, "height": height})
+                            }),
+                        })
+                    });
                     let handle = element.highlight(
                         color,
                         local_duration,
@@ -5674,7 +5859,7 @@ DATA PASSING:
                         local_text_position,
                         local_font_style,
                     )?;
-                    Ok(handle)
+                    Ok((handle, scroll))
                 }
             }
         };
@@ -5682,14 +5867,14 @@ DATA PASSING:
         // Use a shorter default timeout for highlight to avoid long waits
         let effective_timeout_ms = args.action.timeout_ms.or(Some(1000));
 
-        let ((handle, element), successful_selector) =
+        let (((handle, scroll), element), successful_selector) =
             match find_and_execute_with_retry_with_fallback(
                 &self.desktop,
                 &args.selector.build_full_selector(),
                 args.selector.build_alternative_selectors().as_deref(),
                 args.selector.build_fallback_selectors().as_deref(),
                 effective_timeout_ms,
-                args.action.retries,
+                &args.action.retry_policy(),
                 action,
             )
             .await
@@ -5734,6 +5919,9 @@ DATA PASSING:
             "visibility": { "requested_ms": args.duration_ms.unwrap_or(1000) },
             "timestamp": chrono::Utc::now().to_rfc3339()
         });
+        if let Some(scroll) = scroll {
+            result_json["scroll_into_view"] = scroll;
+        }
 
         if args.include_element_info.unwrap_or(false) {
             let element_info = build_element_info(&element);
@@ -5781,7 +5969,8 @@ DATA PASSING:
     }
 
     #[tool(
-        description = "Waits for an element to meet a specific condition (visible, enabled, focused, exists)."
+        description = "Waits for an element to meet a specific condition (visible, enabled, focused, exists).",
+        annotations(read_only_hint = true)
     )]
     async fn wait_for_element(
         &self,
@@ -5862,6 +6051,8 @@ DATA PASSING:
                         &mut result_json,
                         Some(&element),
                         false,
+                        false,
+                        false,
                     )
                     .await;
 
@@ -6023,6 +6214,8 @@ DATA PASSING:
                             &mut result_json,
                             Some(&element),
                             false,
+                            false,
+                            false,
                         )
                         .await;
 
@@ -6192,6 +6385,8 @@ DATA PASSING:
             &mut result_json,
             Some(&ui_element),
             false,
+            false,
+            false,
         )
         .await;
 
@@ -6298,6 +6493,8 @@ DATA PASSING:
             &mut result_json,
             Some(&ui_element),
             false,
+            false,
+            false,
         )
         .await;
 
@@ -6463,11 +6660,12 @@ DATA PASSING:
                 args.selector.build_alternative_selectors().as_deref(),
                 args.selector.build_fallback_selectors().as_deref(),
                 args.action.timeout_ms,
-                args.action.retries,
+                &args.action.retry_policy(),
                 action,
                 args.tree.ui_diff_before_after,
                 args.tree.tree_max_depth,
                 args.tree.include_detailed_attributes,
+                args.tree.capture_visual_diff,
                 tree_output_format,
             )
             .await
@@ -6588,6 +6786,9 @@ DATA PASSING:
 
             result_json["ui_diff"] = json!(diff_result.diff);
             result_json["has_ui_changes"] = json!(diff_result.has_changes);
+            if let Some(visual_diff) = diff_result.visual_diff {
+                result_json["visual_diff"] = visual_diff;
+            }
         }
 
         self.restore_window_management(should_restore).await;
@@ -6677,11 +6878,12 @@ DATA PASSING:
                 args.selector.build_alternative_selectors().as_deref(),
                 args.selector.build_fallback_selectors().as_deref(),
                 args.action.timeout_ms,
-                args.action.retries,
+                &args.action.retry_policy(),
                 action,
                 args.tree.ui_diff_before_after,
                 args.tree.tree_max_depth,
                 args.tree.include_detailed_attributes,
+                args.tree.capture_visual_diff,
                 tree_output_format,
             )
             .await
@@ -6730,6 +6932,9 @@ DATA PASSING:
 
             result_json["ui_diff"] = json!(diff_result.diff);
             result_json["has_ui_changes"] = json!(diff_result.has_changes);
+            if let Some(visual_diff) = diff_result.visual_diff {
+                result_json["visual_diff"] = visual_diff;
+            }
         }
 
         self.restore_window_management(should_restore).await;
@@ -6817,11 +7022,12 @@ DATA PASSING:
                 None, // SetSelected doesn't have alternative selectors
                 args.selector.build_fallback_selectors().as_deref(),
                 args.action.timeout_ms,
-                args.action.retries,
+                &args.action.retry_policy(),
                 action,
                 args.tree.ui_diff_before_after,
                 args.tree.tree_max_depth,
                 args.tree.include_detailed_attributes,
+                args.tree.capture_visual_diff,
                 tree_output_format,
             )
             .await
@@ -6977,6 +7183,9 @@ DATA PASSING:
 
             result_json["ui_diff"] = json!(diff_result.diff);
             result_json["has_ui_changes"] = json!(diff_result.has_changes);
+            if let Some(visual_diff) = diff_result.visual_diff {
+                result_json["visual_diff"] = visual_diff;
+            }
         }
 
         self.restore_window_management(should_restore).await;
@@ -7002,18 +7211,60 @@ DATA PASSING:
     }
 
     #[tool(
-        description = "Captures a screenshot. Three modes: (1) Element - provide process + selector to capture specific element, (2) Window - provide process only to capture entire window, (3) Monitor - provide process + entire_monitor=true to capture the monitor where the window is located. Automatically resizes to max 1920px (customizable via max_dimension parameter) while maintaining aspect ratio."
+        description = "Captures a screenshot. Three modes: (1) Element - provide process + selector to capture specific element, (2) Window - provide process only to capture entire window, (3) Monitor - provide process + entire_monitor=true to capture the monitor where the window is located. Automatically resizes to max 1920px (customizable via max_dimension parameter) while maintaining aspect ratio. Returns JPEG by default; set format to png or webp for lossless output, or quality to trade JPEG fidelity for size.",
+        annotations(read_only_hint = true)
     )]
     async fn capture_screenshot(
         &self,
         Parameters(args): Parameters<CaptureScreenshotArgs>,
     ) -> Result<CallToolResult, McpError> {
-        // Start telemetry span
-        let mut span = StepSpan::new("capture_screenshot", None);
+        self.capture_screenshot_as("capture_screenshot", args).await
+    }
 
-        // Determine capture mode for telemetry
-        let capture_mode = if args.entire_monitor {
-            "monitor"
+    #[tool(
+        description = "Captures an image of one UI element, e.g. a chart or a form section, to send to a vision model without the rest of the window. Finds the element by selector and captures just its bounds. Returns JPEG by default; set format to png or webp for lossless output. Automatically resizes to max 1920px (customizable via max_dimension).",
+        annotations(read_only_hint = true)
+    )]
+    async fn capture_element_screenshot(
+        &self,
+        Parameters(args): Parameters<CaptureElementScreenshotArgs>,
+    ) -> Result<CallToolResult, McpError> {
+        if args.selector.selector.trim().is_empty() {
+            return Err(McpError::invalid_params(
+                "capture_element_screenshot requires a selector; use capture_screenshot for a whole window or monitor",
+                Some(json!({"process": args.selector.process})),
+            ));
+        }
+        self.capture_screenshot_as(
+            "capture_element_screenshot",
+            CaptureScreenshotArgs {
+                selector: args.selector,
+                action: args.action,
+                tree: Default::default(),
+                monitor: Default::default(),
+                window_screenshot: Default::default(),
+                max_dimension: args.max_dimension,
+                format: args.format,
+                quality: args.quality,
+                entire_monitor: false,
+                window_mgmt: args.window_mgmt,
+            },
+        )
+        .await
+    }
+
+    /// Shared body of the screenshot tools; `tool_name` labels the span and the result
+    async fn capture_screenshot_as(
+        &self,
+        tool_name: &str,
+        args: CaptureScreenshotArgs,
+    ) -> Result<CallToolResult, McpError> {
+        // Start telemetry span
+        let mut span = StepSpan::new(tool_name, None);
+
+        // Determine capture mode for telemetry
+        let capture_mode = if args.entire_monitor {
+            "monitor"
         } else if args.selector.selector.is_empty() {
             "window"
         } else {
@@ -7060,7 +7311,7 @@ DATA PASSING:
                 args.selector.build_alternative_selectors().as_deref(),
                 args.selector.build_fallback_selectors().as_deref(),
                 args.action.timeout_ms,
-                args.action.retries,
+                &args.action.retry_policy(),
                 |element| async move { Ok(element) },
             )
             .await
@@ -7104,7 +7355,7 @@ DATA PASSING:
                 args.selector.build_alternative_selectors().as_deref(),
                 args.selector.build_fallback_selectors().as_deref(),
                 args.action.timeout_ms,
-                args.action.retries,
+                &args.action.retry_policy(),
                 |element| async move { element.capture() },
             )
             .await
@@ -7126,80 +7377,34 @@ DATA PASSING:
         let original_height = screenshot_result.height;
         let original_size_bytes = screenshot_result.image_data.len();
 
-        // Convert BGRA to RGBA (xcap returns BGRA format, we need RGBA)
-        // Swap red and blue channels: BGRA -> RGBA
-        let rgba_data: Vec<u8> = screenshot_result
-            .image_data
-            .chunks_exact(4)
-            .flat_map(|bgra| [bgra[2], bgra[1], bgra[0], bgra[3]]) // B,G,R,A -> R,G,B,A
-            .collect();
-
-        // Apply resize if needed (default max dimension is 1920px)
+        // Apply resize if needed (default max dimension is 1920px) and encode
         let max_dim = args.max_dimension.unwrap_or(1920);
-        let (final_width, final_height, final_rgba_data, was_resized) = if original_width > max_dim
-            || original_height > max_dim
-        {
-            // Calculate new dimensions maintaining aspect ratio
-            let scale = (max_dim as f32 / original_width.max(original_height) as f32).min(1.0);
-            let new_width = (original_width as f32 * scale).round() as u32;
-            let new_height = (original_height as f32 * scale).round() as u32;
-
-            // Create ImageBuffer from RGBA data
-            let img =
-                ImageBuffer::<Rgba<u8>, _>::from_raw(original_width, original_height, rgba_data)
-                    .ok_or_else(|| {
-                        McpError::internal_error(
-                            "Failed to create image buffer from screenshot data",
-                            None,
-                        )
-                    })?;
-
-            // Resize using Lanczos3 filter for high quality
-            let resized =
-                image::imageops::resize(&img, new_width, new_height, FilterType::Lanczos3);
-
-            (new_width, new_height, resized.into_raw(), true)
-        } else {
-            (original_width, original_height, rgba_data, false)
-        };
-
-        // Convert RGBA to RGB for JPEG (JPEG doesn't support alpha channel)
-        let rgb_data: Vec<u8> = final_rgba_data
-            .chunks_exact(4)
-            .flat_map(|rgba| [rgba[0], rgba[1], rgba[2]]) // Drop alpha channel
-            .collect();
-
-        // Encode to JPEG with quality 85 (good balance of quality vs size, ~80% smaller than PNG)
-        let mut jpeg_data = Vec::new();
-        let encoder = JpegEncoder::new_with_quality(Cursor::new(&mut jpeg_data), 85);
-        encoder
-            .write_image(
-                &rgb_data,
-                final_width,
-                final_height,
-                ExtendedColorType::Rgb8,
-            )
+        let format = args.format.unwrap_or_default();
+        let (final_width, final_height) = screenshot_result.resized_dimensions(max_dim);
+        let was_resized = final_width != original_width || final_height != original_height;
+        let image_data = format
+            .encode(&screenshot_result, Some(max_dim), args.quality)
             .map_err(|e| {
                 McpError::internal_error(
-                    "Failed to encode screenshot to JPEG",
-                    Some(json!({ "reason": e.to_string() })),
+                    "Failed to encode screenshot",
+                    Some(json!({ "reason": e.to_string(), "format": format })),
                 )
             })?;
 
-        let base64_image = general_purpose::STANDARD.encode(&jpeg_data);
+        let base64_image = general_purpose::STANDARD.encode(&image_data);
 
         span.set_status(true, None);
         span.end();
 
         // Build metadata with resize information
         let mut metadata = json!({
-            "action": "capture_screenshot",
+            "action": tool_name,
             "status": "executed_without_error",
             "capture_mode": capture_mode,
             "target": element_info,
             "selector_used": successful_selector,
             "selectors_tried": get_selectors_tried_all(&args.selector.build_full_selector(), args.selector.build_alternative_selectors().as_deref(), args.selector.build_fallback_selectors().as_deref()),
-            "image_format": "jpeg",
+            "image_format": format,
             "original_size": {
                 "width": original_width,
                 "height": original_height,
@@ -7209,8 +7414,8 @@ DATA PASSING:
             "final_size": {
                 "width": final_width,
                 "height": final_height,
-                "bytes": jpeg_data.len(),
-                "mb": (jpeg_data.len() as f64 / 1024.0 / 1024.0)
+                "bytes": image_data.len(),
+                "mb": (image_data.len() as f64 / 1024.0 / 1024.0)
             },
             "resized": was_resized,
             "max_dimension_applied": max_dim,
@@ -7227,7 +7432,7 @@ DATA PASSING:
         .await;
         let contents = vec![
             Content::json(metadata)?,
-            Content::image(base64_image, "image/jpeg".to_string()),
+            Content::image(base64_image, format.mime_type().to_string()),
         ];
         let contents = append_monitor_screenshots_if_enabled(
             &self.desktop,
@@ -7298,7 +7503,7 @@ DATA PASSING:
                 args.selector.build_alternative_selectors().as_deref(),
                 args.selector.build_fallback_selectors().as_deref(),
                 args.action.timeout_ms,
-                args.action.retries,
+                &args.action.retry_policy(),
                 |element| async move {
                     // Apply highlighting before action if enabled
                     if highlight_before {
@@ -7309,6 +7514,7 @@ DATA PASSING:
                 args.tree.ui_diff_before_after,
                 args.tree.tree_max_depth,
                 args.tree.include_detailed_attributes,
+                args.tree.capture_visual_diff,
                 tree_output_format,
             )
             .await
@@ -7347,6 +7553,48 @@ DATA PASSING:
             "timestamp": chrono::Utc::now().to_rfc3339()
         });
 
+        // Wait for the invoke's consequence before reporting success
+        if let Some(wait_selector) = args
+            .wait_for_selector
+            .as_deref()
+            .filter(|s| !s.trim().is_empty())
+        {
+            let wait_timeout_ms = args.wait_timeout_ms.unwrap_or(5000);
+            match crate::helpers::verify_post_action(
+                &self.desktop,
+                &element,
+                Some(wait_selector),
+                None,
+                wait_timeout_ms,
+                &successful_selector,
+            )
+            .await
+            {
+                Ok(wait_result) => {
+                    span.set_attribute("wait_for.elapsed_ms", wait_result.elapsed_ms.to_string());
+                    result_json["wait_for"] = json!({
+                        "selector": wait_selector,
+                        "found": true,
+                        "elapsed_ms": wait_result.elapsed_ms,
+                    });
+                }
+                Err(e) => {
+                    tracing::error!("[invoke_element] wait_for_selector failed: {}", e);
+                    self.restore_window_management(should_restore).await;
+                    span.set_status(false, Some("wait_for_selector timed out"));
+                    span.end();
+                    return Err(McpError::internal_error(
+                        format!("Invoked element, but '{wait_selector}' did not appear: {e}"),
+                        Some(json!({
+                            "selector_used": successful_selector,
+                            "wait_for_selector": wait_selector,
+                            "wait_timeout_ms": wait_timeout_ms,
+                        })),
+                    ));
+                }
+            }
+        }
+
         // POST-ACTION VERIFICATION
         if !args.action.verify_element_exists.is_empty()
             || !args.action.verify_element_not_exists.is_empty()
@@ -7427,6 +7675,9 @@ DATA PASSING:
 
             result_json["ui_diff"] = json!(diff_result.diff);
             result_json["has_ui_changes"] = json!(diff_result.has_changes);
+            if let Some(visual_diff) = diff_result.visual_diff {
+                result_json["visual_diff"] = visual_diff;
+            }
         }
 
         // Restore windows after invoking element
@@ -7616,11 +7867,12 @@ DATA PASSING:
                 args.selector.build_alternative_selectors().as_deref(),
                 args.selector.build_fallback_selectors().as_deref(),
                 args.action.timeout_ms,
-                args.action.retries,
+                &args.action.retry_policy(),
                 action,
                 args.tree.ui_diff_before_after,
                 args.tree.tree_max_depth,
                 args.tree.include_detailed_attributes,
+                args.tree.capture_visual_diff,
                 tree_output_format,
             )
             .await
@@ -7786,6 +8038,9 @@ DATA PASSING:
 
             result_json["ui_diff"] = json!(diff_result.diff);
             result_json["has_ui_changes"] = json!(diff_result.has_changes);
+            if let Some(visual_diff) = diff_result.visual_diff {
+                result_json["visual_diff"] = visual_diff;
+            }
         }
 
         self.restore_window_management(should_restore).await;
@@ -8387,10 +8642,10 @@ console.info = function(...args) {
         let script_len = final_script.len();
         let script_preview: String = final_script.chars().take(200).collect();
         tracing::info!(
-            "[execute_browser_script] start selector='{}' timeout_ms={:?} retries={:?} script_bytes={}",
+            "[execute_browser_script] start selector='{}' timeout_ms={:?} retries={} script_bytes={}",
             args.selector.selector,
             args.action.timeout_ms,
-            args.action.retries,
+            args.action.retry_policy().retries,
             script_len
         );
         tracing::debug!(
@@ -8406,7 +8661,7 @@ console.info = function(...args) {
                 args.selector.build_alternative_selectors().as_deref(),
                 args.selector.build_fallback_selectors().as_deref(),
                 args.action.timeout_ms,
-                args.action.retries,
+                &args.action.retry_policy(),
                 |el| {
                     let script = script_clone.clone();
                     async move { el.execute_browser_script(&script).await }
@@ -8777,7 +9032,8 @@ console.info = function(...args) {
     }
 
     #[tool(
-        description = "Read file contents with line numbers. Default 100 lines, max 200. Use grep_files first to find code, then read_file with offset/limit for context."
+        description = "Read file contents with line numbers. Default 100 lines, max 200. Use grep_files first to find code, then read_file with offset/limit for context.",
+        annotations(read_only_hint = true)
     )]
     pub async fn read_file(
         &self,
@@ -9275,7 +9531,8 @@ console.info = function(...args) {
     }
 
     #[tool(
-        description = "Find files matching a glob pattern in the working directory. Returns a list of matching file paths. Automatically respects .gitignore and skips node_modules, .git, dist, etc."
+        description = "Find files matching a glob pattern in the working directory. Returns a list of matching file paths. Automatically respects .gitignore and skips node_modules, .git, dist, etc.",
+        annotations(read_only_hint = true)
     )]
     pub async fn glob_files(
         &self,
@@ -9362,7 +9619,8 @@ console.info = function(...args) {
     }
 
     #[tool(
-        description = "Search for a regex pattern in files within the working directory. Returns matching lines with context. Automatically respects .gitignore and skips node_modules, .git, dist, etc."
+        description = "Search for a regex pattern in files within the working directory. Returns matching lines with context. Automatically respects .gitignore and skips node_modules, .git, dist, etc.",
+        annotations(read_only_hint = true)
     )]
     pub async fn grep_files(
         &self,
@@ -9519,7 +9777,8 @@ console.info = function(...args) {
     }
 
     #[tool(
-        description = "Type-check a TypeScript workflow using tsc --noEmit. Returns structured error information including file, line, column, error code, and message for each type error found."
+        description = "Type-check a TypeScript workflow using tsc --noEmit. Returns structured error information including file, line, column, error code, and message for each type error found.",
+        annotations(read_only_hint = true)
     )]
     pub async fn typecheck_workflow(
         &self,
@@ -9880,10 +10139,7 @@ impl DesktopWrapper {
             "click_element" | "invoke_element" | "hover_element"
         );
         #[cfg(target_os = "windows")]
-        let saved_focus = if window_mgmt_opts
-            .restore_focus
-            .unwrap_or(restore_focus_default)
-        {
+        let saved_focus = if window_mgmt_opts.should_restore_focus(restore_focus_default) {
             tracing::debug!(
                 "[FOCUS_RESTORE] dispatch_tool: saving focus state BEFORE window management (tool={}, default={})",
                 tool_name,
@@ -10054,6 +10310,24 @@ impl DesktopWrapper {
                     )),
                 }
             }
+            "get_focus_context" => {
+                match serde_json::from_value::<GetFocusContextArgs>(arguments.clone()) {
+                    Ok(args) => self.get_focus_context(Parameters(args)).await,
+                    Err(e) => Err(McpError::invalid_params(
+                        "Invalid arguments for get_focus_context",
+                        Some(json!({"error": e.to_string()})),
+                    )),
+                }
+            }
+            "describe_element" => {
+                match serde_json::from_value::<DescribeElementArgs>(arguments.clone()) {
+                    Ok(args) => self.describe_element(Parameters(args)).await,
+                    Err(e) => Err(McpError::invalid_params(
+                        "Invalid arguments for describe_element",
+                        Some(json!({"error": e.to_string()})),
+                    )),
+                }
+            }
             "validate_element" => {
                 match serde_json::from_value::<ValidateElementArgs>(arguments.clone()) {
                     Ok(args) => self.validate_element(Parameters(args)).await,
@@ -10205,7 +10479,7 @@ impl DesktopWrapper {
                     Some(json!({"error": e.to_string()})),
                 )),
             },
-            "capture_screenshot" | "capture_element_screenshot" => {
+            "capture_screenshot" => {
                 match serde_json::from_value::<CaptureScreenshotArgs>(arguments.clone()) {
                     Ok(args) => self.capture_screenshot(Parameters(args)).await,
                     Err(e) => Err(McpError::invalid_params(
@@ -10214,6 +10488,15 @@ impl DesktopWrapper {
                     )),
                 }
             }
+            "capture_element_screenshot" => {
+                match serde_json::from_value::<CaptureElementScreenshotArgs>(arguments.clone()) {
+                    Ok(args) => self.capture_element_screenshot(Parameters(args)).await,
+                    Err(e) => Err(McpError::invalid_params(
+                        "Invalid arguments for capture_element_screenshot",
+                        Some(json!({"error": e.to_string()})),
+                    )),
+                }
+            }
             "invoke_element" => {
                 match serde_json::from_value::<InvokeElementArgs>(arguments.clone()) {
                     Ok(args) => self.invoke_element(Parameters(args)).await,
@@ -10331,6 +10614,10 @@ impl DesktopWrapper {
             )),
         };
 
+        // Logs embedded in the result go back to the client, so redact them there too
+        let mut result = result;
+        crate::log_redaction::LogRedactor::global().redact_tool_result(&mut result);
+
         // Stop log capture and collect all logs (tracing + stderr)
         let mut all_logs: Vec<execution_logger::CapturedLogEntry> = Vec::new();
 
@@ -10440,6 +10727,9 @@ impl DesktopWrapper {
             }
         }
 
+        // Redact every source at once: tracing, stderr, result logs and error data
+        crate::log_redaction::LogRedactor::global().redact_logs(&mut all_logs);
+
         // Log execution response with duration, result, and captured logs
         if let Some(ctx) = log_ctx {
             let duration_ms = start_time.elapsed().as_millis() as u64;
@@ -10527,6 +10817,18 @@ impl DesktopWrapper {
     }
 }
 
+/// Id used to correlate a tool call's logs: the `x-request-id` header set by the HTTP
+/// gate, or the JSON-RPC request id for transports without HTTP headers
+fn request_correlation_id(context: &RequestContext<RoleServer>) -> String {
+    context
+        .extensions
+        .get::<axum::http::request::Parts>()
+        .and_then(|parts| parts.headers.get("x-request-id"))
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string)
+        .unwrap_or_else(|| format!("rpc-{}", context.id))
+}
+
 // Manual implementation instead of #[tool_handler] to add execution logging
 impl ServerHandler for DesktopWrapper {
     fn get_info(&self) -> ServerInfo {
@@ -10543,167 +10845,203 @@ impl ServerHandler for DesktopWrapper {
         request: rmcp::model::CallToolRequestParam,
         context: rmcp::service::RequestContext<rmcp::RoleServer>,
     ) -> Result<CallToolResult, McpError> {
-        use rmcp::handler::server::tool::ToolCallContext;
+        // Every span and log line of this call, including the SDK's #[instrument] spans,
+        // carries the request id so one request can be followed through the logs
+        let request_id = request_correlation_id(&context);
+        let span = tracing::info_span!(
+            "mcp_request",
+            request_id = %request_id,
+            tool = %request.name
+        );
 
-        // Extract tool name and arguments for logging
-        let tool_name = request.name.to_string();
-        let arguments = request
-            .arguments
-            .as_ref()
-            .map(|a| serde_json::Value::Object(a.clone()))
-            .unwrap_or(serde_json::Value::Null);
+        async move {
+            use rmcp::handler::server::tool::ToolCallContext;
 
-        // Check if tool is blocked for this specific client (per-client mode)
-        // Only "claude-code" clients have mode enforced; "mediar-app" (UI) is never blocked
-        {
-            // Get client name from peer info
-            let client_name = context
-                .peer
-                .peer_info()
-                .map(|info| info.client_info.name.to_string())
-                .unwrap_or_default();
+            // Extract tool name and arguments for logging
+            let tool_name = request.name.to_string();
+            let arguments = request
+                .arguments
+                .as_ref()
+                .map(|a| serde_json::Value::Object(a.clone()))
+                .unwrap_or(serde_json::Value::Null);
 
-            let modes_guard = self.client_modes.lock().await;
-            if let Some(client_state) = modes_guard.get(&client_name) {
-                if client_state.mode == "ask" && client_state.blocked_tools.contains(&tool_name) {
-                    tracing::info!(
-                        "[call_tool] Blocked tool '{}' for client '{}' in Ask mode",
-                        tool_name,
-                        client_name
-                    );
-                    return Err(McpError::invalid_request(
-                        format!(
-                            "Tool '{}' is blocked in Ask mode. Ask user to switch to Act mode to execute this action.",
-                            tool_name
-                        ),
-                        Some(serde_json::json!({
-                            "code": -32002,
-                            "tool": tool_name,
-                            "mode": "ask",
-                            "client": client_name,
-                            "action": "switch_to_act_mode"
-                        })),
-                    ));
+            // Check if tool is blocked for this specific client (per-client mode)
+            // Only "claude-code" clients have mode enforced; "mediar-app" (UI) is never blocked
+            {
+                // Get client name from peer info
+                let client_name = context
+                    .peer
+                    .peer_info()
+                    .map(|info| info.client_info.name.to_string())
+                    .unwrap_or_default();
+
+                let modes_guard = self.client_modes.lock().await;
+                if let Some(client_state) = modes_guard.get(&client_name) {
+                    if client_state.mode == "ask" && client_state.blocked_tools.contains(&tool_name) {
+                        tracing::info!(
+                            "[call_tool] Blocked tool '{}' for client '{}' in Ask mode",
+                            tool_name,
+                            client_name
+                        );
+                        return Err(McpError::invalid_request(
+                            format!(
+                                "Tool '{}' is blocked in Ask mode. Ask user to switch to Act mode to execute this action.",
+                                tool_name
+                            ),
+                            Some(serde_json::json!({
+                                "code": -32002,
+                                "tool": tool_name,
+                                "mode": "ask",
+                                "client": client_name,
+                                "action": "switch_to_act_mode"
+                            })),
+                        ));
+                    }
                 }
+                // If no mode is set for this client (e.g., "mediar-app"), allow all tools
             }
-            // If no mode is set for this client (e.g., "mediar-app"), allow all tools
-        }
-
-        // Reset cancellation state before starting a new tool call (except for stop_execution itself)
-        // This clears any previous stop_execution() so new operations can run
-        if tool_name != "stop_execution" {
-            self.desktop.reset_cancellation();
-        }
-
-        // Log request before execution - extract workflow context from execute_sequence args
-        let (wf_id, step_id) = if tool_name == "execute_sequence" {
-            (
-                arguments.get("workflow_id").and_then(|v| v.as_str()),
-                arguments.get("start_from_step").and_then(|v| v.as_str()),
-            )
-        } else {
-            (None, None)
-        };
-        tracing::debug!(
-            "[call_tool] Logging with workflow_id={:?}, step_id={:?}",
-            wf_id,
-            step_id
-        );
-        let log_ctx = execution_logger::log_request(&tool_name, &arguments, wf_id, step_id, None);
-        let start_time = std::time::Instant::now();
 
-        // FOCUS RESTORATION: Extract restore_focus from arguments and save focus state BEFORE tool execution
-        // Each tool's window management (bring_to_front, activate_window) steals focus
-        // Default: false only for click-like tools (user wants focus on clicked element), true for everything else
-        let window_mgmt_opts: crate::utils::WindowManagementOptions =
-            serde_json::from_value(arguments.clone()).unwrap_or_default();
+            // Reset cancellation state before starting a new tool call (except for stop_execution itself)
+            // This clears any previous stop_execution() so new operations can run
+            if tool_name != "stop_execution" {
+                self.desktop.reset_cancellation();
+            }
 
-        #[cfg(target_os = "windows")]
-        let restore_focus_default = !matches!(
-            tool_name.as_str(),
-            "click_element" | "invoke_element" | "hover_element"
-        );
-        #[cfg(target_os = "windows")]
-        let saved_focus = if window_mgmt_opts
-            .restore_focus
-            .unwrap_or(restore_focus_default)
-        {
-            tracing::debug!(
-                "[FOCUS_RESTORE] call_tool: saving focus state BEFORE tool execution (tool={}, default={})",
-                tool_name,
-                restore_focus_default
-            );
-            terminator::platforms::windows::save_focus_state()
-        } else {
+            // Log request before execution - extract workflow context from execute_sequence args
+            let (wf_id, step_id) = if tool_name == "execute_sequence" {
+                (
+                    arguments.get("workflow_id").and_then(|v| v.as_str()),
+                    arguments.get("start_from_step").and_then(|v| v.as_str()),
+                )
+            } else {
+                (None, None)
+            };
             tracing::debug!(
-                "[FOCUS_RESTORE] call_tool: skipping focus save (tool={}, default={})",
-                tool_name,
-                restore_focus_default
+                "[call_tool] Logging with workflow_id={:?}, step_id={:?}",
+                wf_id,
+                step_id
             );
-            None
-        };
-        #[cfg(not(target_os = "windows"))]
-        let saved_focus: Option<()> = None;
+            let log_ctx = execution_logger::log_request(&tool_name, &arguments, wf_id, step_id, None);
+            let start_time = std::time::Instant::now();
 
-        // Execute the tool via router
-        let tcc = ToolCallContext::new(self, request, context);
-        let result = self.tool_router.call(tcc).await;
+            // FOCUS RESTORATION: Extract restore_focus from arguments and save focus state BEFORE tool execution
+            // Each tool's window management (bring_to_front, activate_window) steals focus
+            // Default: false only for click-like tools (user wants focus on clicked element), true for everything else
+            let window_mgmt_opts: crate::utils::WindowManagementOptions =
+                serde_json::from_value(arguments.clone()).unwrap_or_default();
 
-        // FOCUS RESTORATION: Restore focus state after tool execution if we saved it
-        #[cfg(target_os = "windows")]
-        if let Some(state) = saved_focus {
-            tracing::debug!(
-                "[FOCUS_RESTORE] call_tool: restoring focus state after tool execution"
+            #[cfg(target_os = "windows")]
+            let restore_focus_default = !matches!(
+                tool_name.as_str(),
+                "click_element" | "invoke_element" | "hover_element"
             );
-            terminator::platforms::windows::restore_focus_state(state);
-        }
+            #[cfg(target_os = "windows")]
+            let saved_focus = if window_mgmt_opts.should_restore_focus(restore_focus_default) {
+                tracing::debug!(
+                    "[FOCUS_RESTORE] call_tool: saving focus state BEFORE tool execution (tool={}, default={})",
+                    tool_name,
+                    restore_focus_default
+                );
+                terminator::platforms::windows::save_focus_state()
+            } else {
+                tracing::debug!(
+                    "[FOCUS_RESTORE] call_tool: skipping focus save (tool={}, default={})",
+                    tool_name,
+                    restore_focus_default
+                );
+                None
+            };
+            #[cfg(not(target_os = "windows"))]
+            let saved_focus: Option<()> = None;
+
+            // Execute the tool via router. Input tools called with an idempotency_key run at
+            // most once per key; a retry gets the original result back
+            let tcc = ToolCallContext::new(self, request, context);
+            let (result, phases) = crate::tool_timing::collect_phases(async {
+                match crate::idempotency::key_for(&tool_name, &arguments) {
+                    Some(key) => self
+                        .idempotency
+                        .execute_if(
+                            &key,
+                            || self.tool_router.call(tcc),
+                            |result| result.is_error != Some(true),
+                        )
+                        .await
+                        .map(|outcome| {
+                            if let crate::idempotency::Outcome::Replayed(_) = outcome {
+                                tracing::info!(
+                                    "[call_tool] Duplicate {} call, replaying key {}",
+                                    tool_name,
+                                    key
+                                );
+                            }
+                            outcome.into_inner()
+                        }),
+                    None => self.tool_router.call(tcc).await,
+                }
+            })
+            .await;
 
-        // Collect all logs (stderr + result/error data)
-        let mut all_logs: Vec<execution_logger::CapturedLogEntry> = Vec::new();
+            // FOCUS RESTORATION: Restore focus state after tool execution if we saved it
+            #[cfg(target_os = "windows")]
+            if let Some(state) = saved_focus {
+                tracing::debug!(
+                    "[FOCUS_RESTORE] call_tool: restoring focus state after tool execution"
+                );
+                terminator::platforms::windows::restore_focus_state(state);
+            }
 
-        // Get stderr logs from TypeScript workflow execution (if any)
-        if let Ok(mut logs) = self.captured_stderr_logs.lock() {
-            all_logs.extend(logs.drain(..));
-        }
+            // Logs embedded in the result go back to the client, so redact them there too
+            let mut result = result;
+            crate::log_redaction::LogRedactor::global().redact_tool_result(&mut result);
 
-        // Extract logs from run_command/execute_sequence result (logs are embedded in the JSON result)
-        if tool_name == "run_command" || tool_name == "execute_sequence" {
-            if let Ok(ref call_result) = result {
-                for content in &call_result.content {
-                    if let Some(text) = crate::server::extract_content_text(content) {
-                        if let Ok(json_val) = serde_json::from_str::<serde_json::Value>(&text) {
-                            let now = chrono::Utc::now();
-                            // Extract "logs" array (stdout logs)
-                            if let Some(logs_array) =
-                                json_val.get("logs").and_then(|v| v.as_array())
-                            {
-                                for (i, log) in logs_array.iter().enumerate() {
-                                    // Handle both string logs and structured {timestamp, level, message} logs
-                                    if let Some(msg) = log.as_str() {
-                                        all_logs.push(execution_logger::CapturedLogEntry {
-                                            timestamp: now
-                                                + chrono::Duration::microseconds(i as i64),
-                                            level: "INFO".to_string(),
-                                            message: msg.to_string(),
-                                        });
-                                    } else if log.is_object() {
-                                        let level = log
-                                            .get("level")
-                                            .and_then(|v| v.as_str())
-                                            .unwrap_or("INFO")
-                                            .to_string();
-                                        let message = log
-                                            .get("message")
-                                            .and_then(|v| v.as_str())
-                                            .unwrap_or("")
-                                            .to_string();
-                                        if !message.is_empty() {
+            // Collect all logs (stderr + result/error data)
+            let mut all_logs: Vec<execution_logger::CapturedLogEntry> = Vec::new();
+
+            // Get stderr logs from TypeScript workflow execution (if any)
+            if let Ok(mut logs) = self.captured_stderr_logs.lock() {
+                all_logs.extend(logs.drain(..));
+            }
+
+            // Extract logs from run_command/execute_sequence result (logs are embedded in the JSON result)
+            if tool_name == "run_command" || tool_name == "execute_sequence" {
+                if let Ok(ref call_result) = result {
+                    for content in &call_result.content {
+                        if let Some(text) = crate::server::extract_content_text(content) {
+                            if let Ok(json_val) = serde_json::from_str::<serde_json::Value>(&text) {
+                                let now = chrono::Utc::now();
+                                // Extract "logs" array (stdout logs)
+                                if let Some(logs_array) =
+                                    json_val.get("logs").and_then(|v| v.as_array())
+                                {
+                                    for (i, log) in logs_array.iter().enumerate() {
+                                        // Handle both string logs and structured {timestamp, level, message} logs
+                                        if let Some(msg) = log.as_str() {
                                             all_logs.push(execution_logger::CapturedLogEntry {
                                                 timestamp: now
                                                     + chrono::Duration::microseconds(i as i64),
-                                                level,
-                                                message,
+                                                level: "INFO".to_string(),
+                                                message: msg.to_string(),
                                             });
+                                        } else if log.is_object() {
+                                            let level = log
+                                                .get("level")
+                                                .and_then(|v| v.as_str())
+                                                .unwrap_or("INFO")
+                                                .to_string();
+                                            let message = log
+                                                .get("message")
+                                                .and_then(|v| v.as_str())
+                                                .unwrap_or("")
+                                                .to_string();
+                                            if !message.is_empty() {
+                                                all_logs.push(execution_logger::CapturedLogEntry {
+                                                    timestamp: now
+                                                        + chrono::Duration::microseconds(i as i64),
+                                                    level,
+                                                    message,
+                                                });
+                                            }
                                         }
                                     }
                                 }
@@ -10712,99 +11050,119 @@ impl ServerHandler for DesktopWrapper {
                     }
                 }
             }
-        }
 
-        // Extract logs from error data (for TypeScript workflow errors)
-        // The logs are embedded in error_data["logs"] when workflow fails
-        if let Err(ref e) = result {
-            if let Some(ref data) = e.data {
-                let now = chrono::Utc::now();
-                if let Some(logs_array) = data.get("logs").and_then(|v| v.as_array()) {
-                    tracing::debug!(
-                        "[call_tool] Extracting {} logs from error data for tool: {}",
-                        logs_array.len(),
-                        tool_name
-                    );
-                    for (i, log) in logs_array.iter().enumerate() {
-                        // Logs are structured as {timestamp, level, message}
-                        let level = log
-                            .get("level")
-                            .and_then(|v| v.as_str())
-                            .unwrap_or("INFO")
-                            .to_string();
-                        let message = log
-                            .get("message")
-                            .and_then(|v| v.as_str())
-                            .unwrap_or("")
-                            .to_string();
-                        if !message.is_empty() {
-                            all_logs.push(execution_logger::CapturedLogEntry {
-                                timestamp: now + chrono::Duration::microseconds(i as i64),
-                                level,
-                                message,
-                            });
+            // Extract logs from error data (for TypeScript workflow errors)
+            // The logs are embedded in error_data["logs"] when workflow fails
+            if let Err(ref e) = result {
+                if let Some(ref data) = e.data {
+                    let now = chrono::Utc::now();
+                    if let Some(logs_array) = data.get("logs").and_then(|v| v.as_array()) {
+                        tracing::debug!(
+                            "[call_tool] Extracting {} logs from error data for tool: {}",
+                            logs_array.len(),
+                            tool_name
+                        );
+                        for (i, log) in logs_array.iter().enumerate() {
+                            // Logs are structured as {timestamp, level, message}
+                            let level = log
+                                .get("level")
+                                .and_then(|v| v.as_str())
+                                .unwrap_or("INFO")
+                                .to_string();
+                            let message = log
+                                .get("message")
+                                .and_then(|v| v.as_str())
+                                .unwrap_or("")
+                                .to_string();
+                            if !message.is_empty() {
+                                all_logs.push(execution_logger::CapturedLogEntry {
+                                    timestamp: now + chrono::Duration::microseconds(i as i64),
+                                    level,
+                                    message,
+                                });
+                            }
                         }
                     }
                 }
             }
-        }
 
-        // Sort logs by timestamp
-        all_logs.sort_by(|a, b| a.timestamp.cmp(&b.timestamp));
+            // Sort logs by timestamp
+            all_logs.sort_by(|a, b| a.timestamp.cmp(&b.timestamp));
 
-        let logs_option = if all_logs.is_empty() {
-            None
-        } else {
-            Some(all_logs)
-        };
+            // Redact every source at once: stderr, result logs and error data
+            crate::log_redaction::LogRedactor::global().redact_logs(&mut all_logs);
 
-        // Log response after execution
-        let duration_ms = start_time.elapsed().as_millis() as u64;
-        if let Some(ctx) = log_ctx {
+            let logs_option = if all_logs.is_empty() {
+                None
+            } else {
+                Some(all_logs)
+            };
+
+            // Log response after execution
+            let duration_ms = start_time.elapsed().as_millis() as u64;
+            if let Some(ctx) = log_ctx {
+                match &result {
+                    Ok(call_result) => {
+                        // Convert content to JSON Value for logging
+                        // The execution_logger::extract_and_save_screenshots expects an array of content items
+                        let content_value = serde_json::to_value(&call_result.content)
+                            .unwrap_or(serde_json::Value::Null);
+                        execution_logger::log_response_with_logs(
+                            ctx,
+                            Ok(&content_value),
+                            duration_ms,
+                            logs_option,
+                        );
+                    }
+                    Err(e) => {
+                        // Serialize error as JSON instead of Debug format
+                        let error_msg =
+                            serde_json::to_string(&e).unwrap_or_else(|_| format!("{:?}", e));
+                        execution_logger::log_response_with_logs(
+                            ctx,
+                            Err(&error_msg),
+                            duration_ms,
+                            logs_option,
+                        );
+                    }
+                }
+            }
+
+            // Track tool execution (PostHog analytics)
             match &result {
-                Ok(call_result) => {
-                    // Convert content to JSON Value for logging
-                    // The execution_logger::extract_and_save_screenshots expects an array of content items
-                    let content_value = serde_json::to_value(&call_result.content)
-                        .unwrap_or(serde_json::Value::Null);
-                    execution_logger::log_response_with_logs(
-                        ctx,
-                        Ok(&content_value),
-                        duration_ms,
-                        logs_option,
-                    );
+                Ok(_) => {
+                    crate::posthog::track_tool_execution(&tool_name, true, duration_ms, None);
                 }
                 Err(e) => {
-                    // Serialize error as JSON instead of Debug format
-                    let error_msg =
-                        serde_json::to_string(&e).unwrap_or_else(|_| format!("{:?}", e));
-                    execution_logger::log_response_with_logs(
-                        ctx,
-                        Err(&error_msg),
+                    let error_msg = serde_json::to_string(&e).unwrap_or_else(|_| format!("{:?}", e));
+                    crate::posthog::track_tool_execution(
+                        &tool_name,
+                        false,
                         duration_ms,
-                        logs_option,
+                        Some(&error_msg),
                     );
                 }
             }
-        }
 
-        // Track tool execution (PostHog analytics)
-        match &result {
-            Ok(_) => {
-                crate::posthog::track_tool_execution(&tool_name, true, duration_ms, None);
-            }
-            Err(e) => {
-                let error_msg = serde_json::to_string(&e).unwrap_or_else(|_| format!("{:?}", e));
-                crate::posthog::track_tool_execution(
-                    &tool_name,
-                    false,
-                    duration_ms,
-                    Some(&error_msg),
-                );
+            let result = result.map(|mut call_result| {
+                if crate::tool_timing::enabled() {
+                    crate::tool_timing::attach(&mut call_result, duration_ms, &phases);
+                }
+                call_result
+            });
+
+            // Execution logs above keep the full result; the client gets a summary
+            // instead of a message it would truncate mid-JSON
+            match crate::response_limit::max_response_bytes() {
+                Some(max_bytes) => result
+                    .map(|call_result| {
+                        crate::response_limit::enforce(&tool_name, call_result, max_bytes)
+                    }),
+                None => result,
             }
         }
-
-        result
+        .instrument(span)
+        .await
     }
 
     async fn list_tools(
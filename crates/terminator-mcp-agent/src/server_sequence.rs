@@ -2,7 +2,7 @@ use crate::execution_logger;
 use crate::helpers::substitute_variables;
 use crate::output_parser;
 use crate::server::extract_content_json;
-use crate::telemetry::{StepSpan, WorkflowSpan};
+use crate::telemetry::WorkflowSpan;
 use crate::utils::{
     DesktopWrapper, ExecuteSequenceArgs, SequenceItem, ToolCall, ToolGroup, VariableDefinition,
 };
@@ -1090,6 +1090,12 @@ impl DesktopWrapper {
             tracing::debug!("Window management disabled for sequence, skipping capture");
         }
 
+        // Opt-in UI tree capture around every step
+        let capture_mode = args.capture_trees.unwrap_or_default();
+        let capture_depth = args
+            .capture_tree_max_depth
+            .unwrap_or(crate::helpers::DEFAULT_CAPTURE_TREE_MAX_DEPTH);
+
         while current_index < sequence_items.len()
             && (current_index <= end_at_index || (follow_fallback && jumped_to_troubleshooting))
             && iterations < max_iterations
@@ -1219,6 +1225,14 @@ impl DesktopWrapper {
                     .await;
             }
 
+            let tree_before = if capture_mode.captures_before() {
+                Some(
+                    crate::helpers::capture_focused_window_tree(&self.desktop, capture_depth).await,
+                )
+            } else {
+                None
+            };
+
             // 2. Execute with retries
             let mut final_result = json!(null);
             let mut step_error_occurred = false;
@@ -1300,7 +1314,7 @@ impl DesktopWrapper {
 
                         // Start step telemetry span
                         let step_id = original_step.and_then(|s| s.id.as_deref());
-                        let mut step_span = StepSpan::new(&tool_call.tool_name, step_id);
+                        let mut step_span = workflow_span.step(&tool_call.tool_name, step_id);
                         step_span.set_attribute("step.number", (current_index + 1).to_string());
                         step_span.set_attribute("step.total", total_steps.to_string());
                         if attempt > 0 {
@@ -1934,6 +1948,15 @@ impl DesktopWrapper {
                 }
             }
 
+            let tree_after = if capture_mode.captures_after() {
+                Some(
+                    crate::helpers::capture_focused_window_tree(&self.desktop, capture_depth).await,
+                )
+            } else {
+                None
+            };
+            crate::helpers::attach_step_trees(&mut final_result, tree_before, tree_after);
+
             // Mark this step as executed (not skipped) and add to results
             if let Some(obj) = final_result.as_object_mut() {
                 obj.insert("executed".to_string(), json!(true));
@@ -2155,6 +2178,15 @@ impl DesktopWrapper {
             "execute_sequence",
         );
 
+        // Step ids whose `{id}_result`/`{id}_status` entries the snapshot leaves out
+        let step_ids: Vec<&str> = args
+            .steps
+            .iter()
+            .chain(args.troubleshooting.iter())
+            .flatten()
+            .filter_map(|step| step.id.as_deref())
+            .collect();
+
         let mut summary = json!({
             "action": "execute_ts_workflow",
             "status": final_status,
@@ -2166,6 +2198,12 @@ impl DesktopWrapper {
             "used_fallback": used_fallback,
             "results": results,
             "env": execution_context_map.get("env").cloned().unwrap_or_else(|| json!({})),
+            // Redacted, so credentials that scripts or set_env put in scope don't leak
+            "env_snapshot": crate::env_snapshot::snapshot(
+                &execution_context_map,
+                &step_ids,
+                crate::log_redaction::LogRedactor::global(),
+            ),
             "execution_log_path": log_paths.json_path,
             "typescript_snippet_path": log_paths.ts_path,
         });
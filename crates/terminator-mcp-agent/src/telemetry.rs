@@ -19,8 +19,8 @@ mod with_telemetry {
     use opentelemetry::global::BoxedSpan;
     use opentelemetry::{
         global,
-        trace::{Span, SpanKind, Status, Tracer, TracerProvider},
-        KeyValue,
+        trace::{Span, SpanKind, Status, TraceContextExt, Tracer, TracerProvider},
+        Context, KeyValue,
     };
     use opentelemetry_appender_tracing::layer::OpenTelemetryTracingBridge;
     use opentelemetry_otlp::WithExportConfig;
@@ -50,6 +50,12 @@ mod with_telemetry {
             WorkflowSpan { span }
         }
 
+        /// Start a span for one step of this workflow, nested under the workflow span
+        pub fn step(&self, tool_name: &str, step_id: Option<&str>) -> StepSpan {
+            let parent = Context::new().with_remote_span_context(self.span.span_context().clone());
+            StepSpan::start(tool_name, step_id, &parent)
+        }
+
         pub fn add_event(&mut self, name: &str, attributes: Vec<(&str, String)>) {
             let kvs: Vec<KeyValue> = attributes
                 .into_iter()
@@ -89,11 +95,15 @@ mod with_telemetry {
 
     impl StepSpan {
         pub fn new(tool_name: &str, step_id: Option<&str>) -> Self {
+            Self::start(tool_name, step_id, &Context::current())
+        }
+
+        fn start(tool_name: &str, step_id: Option<&str>, parent: &Context) -> Self {
             let tracer = global::tracer("terminator-mcp");
             let mut span = tracer
                 .span_builder(format!("step.{tool_name}"))
                 .with_kind(SpanKind::Internal)
-                .start(&tracer);
+                .start_with_context(&tracer, parent);
 
             span.set_attribute(KeyValue::new("tool.name", tool_name.to_string()));
             span.set_attribute(KeyValue::new(
@@ -581,6 +591,10 @@ mod without_telemetry {
         pub fn set_attribute(&mut self, _key: &str, _value: String) {}
         pub fn set_status(&mut self, _success: bool, _message: &str) {}
         pub fn end(self) {}
+
+        pub fn step(&self, tool_name: &str, step_id: Option<&str>) -> StepSpan {
+            StepSpan::new(tool_name, step_id)
+        }
     }
 
     pub struct StepSpan;
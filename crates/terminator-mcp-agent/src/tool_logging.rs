@@ -1,3 +1,4 @@
+use crate::log_redaction::LogRedactor;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::sync::{Arc, Mutex};
@@ -21,6 +22,7 @@ pub struct LogCapture {
     logs: Arc<Mutex<Vec<LogEntry>>>,
     capture_enabled: Arc<Mutex<bool>>,
     max_entries: usize,
+    redactor: Arc<LogRedactor>,
 }
 
 impl LogCapture {
@@ -30,9 +32,17 @@ impl LogCapture {
             logs: Arc::new(Mutex::new(Vec::new())),
             capture_enabled: Arc::new(Mutex::new(false)),
             max_entries,
+            redactor: Arc::new(LogRedactor::global().clone()),
         }
     }
 
+    /// Redact captured entries with `redactor` instead of the one configured from the
+    /// environment (`MCP_LOG_REDACT_PATTERNS`)
+    pub fn with_redactor(mut self, redactor: LogRedactor) -> Self {
+        self.redactor = Arc::new(redactor);
+        self
+    }
+
     /// Start capturing logs, clearing any existing logs
     pub fn start_capture(&self) {
         let mut enabled = self.capture_enabled.lock().unwrap();
@@ -55,12 +65,20 @@ impl LogCapture {
     }
 
     /// Add a log entry to the buffer (internal use)
-    fn add_log(&self, entry: LogEntry) {
+    fn add_log(&self, mut entry: LogEntry) {
         // Quick check without lock first
         if !self.is_capturing() {
             return;
         }
 
+        // Scrub sensitive-looking text (typed card numbers, tokens...) before storing
+        entry.message = self.redactor.redact(&entry.message).into_owned();
+        if let Some(fields) = entry.fields.as_mut() {
+            fields
+                .values_mut()
+                .for_each(|value| self.redactor.redact_value(value));
+        }
+
         let mut logs = self.logs.lock().unwrap();
 
         // Enforce max entries limit
@@ -0,0 +1,72 @@
+use rmcp::model::{CallToolResult, Meta};
+use serde_json::json;
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::time::{Duration, Instant};
+
+/// Environment variable that leaves timing out of tool results when set to `0` or `false`
+pub const TOOL_TIMING_ENV: &str = "MCP_TOOL_TIMING";
+
+/// Time spent in each named phase of a tool call, e.g. `locate`
+pub type Phases = BTreeMap<&'static str, Duration>;
+
+tokio::task_local! {
+    static PHASES: RefCell<Phases>;
+}
+
+/// Whether tool results carry timing in `_meta`; on unless `MCP_TOOL_TIMING` turns it off
+pub fn enabled() -> bool {
+    std::env::var(TOOL_TIMING_ENV)
+        .map(|v| !matches!(v.trim().to_lowercase().as_str(), "0" | "false"))
+        .unwrap_or(true)
+}
+
+/// Run a tool call, collecting the phases recorded during it
+pub async fn collect_phases<F: Future>(call: F) -> (F::Output, Phases) {
+    PHASES
+        .scope(RefCell::new(Phases::new()), async move {
+            let output = call.await;
+            (output, PHASES.with(RefCell::take))
+        })
+        .await
+}
+
+/// Add `elapsed` to the named phase of the current tool call; a no-op outside one
+pub fn record_phase(name: &'static str, elapsed: Duration) {
+    let _ = PHASES.try_with(|phases| *phases.borrow_mut().entry(name).or_default() += elapsed);
+}
+
+/// Records the time until it is dropped as a phase of the current tool call
+pub struct PhaseTimer {
+    name: &'static str,
+    start: Instant,
+}
+
+impl PhaseTimer {
+    pub fn start(name: &'static str) -> Self {
+        Self {
+            name,
+            start: Instant::now(),
+        }
+    }
+}
+
+impl Drop for PhaseTimer {
+    fn drop(&mut self) {
+        record_phase(self.name, self.start.elapsed());
+    }
+}
+
+/// Put `duration_ms`, and a `phases` breakdown when any were recorded, in the result's `_meta`
+pub fn attach(result: &mut CallToolResult, duration_ms: u64, phases: &Phases) {
+    let meta = result.meta.get_or_insert_with(Meta::new);
+    meta.insert("duration_ms".to_string(), json!(duration_ms));
+    if !phases.is_empty() {
+        let phases: serde_json::Map<String, serde_json::Value> = phases
+            .iter()
+            .map(|(name, elapsed)| (format!("{name}_ms"), json!(elapsed.as_millis() as u64)))
+            .collect();
+        meta.insert("phases".to_string(), phases.into());
+    }
+}
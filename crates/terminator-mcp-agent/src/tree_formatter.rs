@@ -40,6 +40,10 @@ fn ui_node_to_serializable(node: &UINode) -> SerializableUIElement {
         is_selected: node.attributes.is_selected,
         child_count: node.attributes.child_count,
         index_in_parent: node.attributes.index_in_parent,
+        shortcut: node.attributes.shortcut.clone(),
+        labeled_by: node.attributes.labeled_by.clone(),
+        described_by: node.attributes.described_by.clone(),
+        controller_for: node.attributes.controller_for.clone(),
         selector: node.selector.clone(), // Pass through the chained selector from tree building
     }
 }
@@ -161,6 +165,26 @@ fn format_node(
         context_parts.push("toggled".to_string());
     }
 
+    if let Some(ref shortcut) = node.shortcut {
+        if !shortcut.is_empty() {
+            context_parts.push(format!("shortcut: {shortcut}"));
+        }
+    }
+
+    if let Some(ref labeled_by) = node.labeled_by {
+        if !labeled_by.is_empty() {
+            context_parts.push(format!("labeled by: {labeled_by}"));
+        }
+    }
+
+    if !node.described_by.is_empty() {
+        context_parts.push(format!("described by: {}", node.described_by.join(", ")));
+    }
+
+    if !node.controller_for.is_empty() {
+        context_parts.push(format!("controls: {}", node.controller_for.join(", ")));
+    }
+
     // Add value if present
     if let Some(ref value) = node.value {
         if !value.is_empty() {
@@ -550,6 +574,92 @@ pub fn format_browser_dom_as_compact_yaml(elements: &[serde_json::Value]) -> Dom
     }
 }
 
+/// One page of a formatted UI tree, see [`paginate_tree`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct TreePage {
+    pub tree: serde_json::Value,
+    /// Elements in the whole tree
+    pub total_elements: usize,
+    /// Start of the next page, None on the last page
+    pub next_start: Option<usize>,
+}
+
+/// Whether a compact YAML line begins an element rather than continuing a multi-line name
+fn starts_compact_element(line: &str) -> bool {
+    let line = line.trim_start();
+    line.starts_with("- [")
+        || line
+            .strip_prefix('#')
+            .is_some_and(|rest| rest.starts_with(|c: char| c.is_ascii_digit()))
+}
+
+fn flatten_json_tree(node: &serde_json::Value, depth: usize, out: &mut Vec<serde_json::Value>) {
+    let mut flat = node.clone();
+    if let Some(obj) = flat.as_object_mut() {
+        obj.remove("children");
+        obj.insert("depth".to_string(), serde_json::json!(depth));
+    }
+    out.push(flat);
+    if let Some(children) = node.get("children").and_then(|c| c.as_array()) {
+        for child in children {
+            flatten_json_tree(child, depth + 1, out);
+        }
+    }
+}
+
+/// Cut elements `start..start + max_elements`, in document order, out of a formatted `ui_tree`.
+///
+/// Compact YAML keeps its lines, indentation and `#index` prefixes, so `click_element` by
+/// index works with any page. Verbose JSON is flattened into a list of nodes that carry a
+/// `depth` instead of `children`.
+pub fn paginate_tree(tree: &serde_json::Value, start: usize, max_elements: usize) -> TreePage {
+    let end = start.saturating_add(max_elements);
+    match tree {
+        serde_json::Value::String(formatted) => {
+            let mut elements: Vec<String> = Vec::new();
+            for line in formatted.lines() {
+                match elements.last_mut() {
+                    Some(last) if !starts_compact_element(line) => {
+                        last.push('\n');
+                        last.push_str(line);
+                    }
+                    _ => elements.push(line.to_string()),
+                }
+            }
+            let total_elements = elements.len();
+            let page: Vec<String> = elements
+                .into_iter()
+                .skip(start)
+                .take(max_elements)
+                .collect();
+            let mut output = page.join("\n");
+            if !output.is_empty() {
+                output.push('\n');
+            }
+            TreePage {
+                tree: serde_json::Value::String(output),
+                total_elements,
+                next_start: (end < total_elements).then_some(end),
+            }
+        }
+        _ => {
+            let mut elements = Vec::new();
+            flatten_json_tree(tree, 0, &mut elements);
+            let total_elements = elements.len();
+            let page: Vec<serde_json::Value> = elements
+                .into_iter()
+                .skip(start)
+                .take(max_elements)
+                .collect();
+            TreePage {
+                tree: serde_json::Value::Array(page),
+                total_elements,
+                next_start: (end < total_elements).then_some(end),
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -578,6 +688,10 @@ mod tests {
             is_selected: None,
             child_count: None,
             index_in_parent: None,
+            shortcut: None,
+            labeled_by: None,
+            described_by: Vec::new(),
+            controller_for: Vec::new(),
             selector: None,
         };
 
@@ -612,6 +726,10 @@ mod tests {
             is_selected: None,
             child_count: None,
             index_in_parent: None,
+            shortcut: None,
+            labeled_by: None,
+            described_by: Vec::new(),
+            controller_for: Vec::new(),
             selector: None,
         };
 
@@ -637,6 +755,10 @@ mod tests {
             is_selected: None,
             child_count: None,
             index_in_parent: None,
+            shortcut: None,
+            labeled_by: None,
+            described_by: Vec::new(),
+            controller_for: Vec::new(),
             selector: None,
         };
 
@@ -646,4 +768,48 @@ mod tests {
         assert!(!result.formatted.contains("#123")); // IDs should not appear in compact view
         assert!(!result.formatted.contains("#456")); // IDs should not appear in compact view
     }
+
+    #[test]
+    fn test_paginate_compact_tree_keeps_indices() {
+        let tree = serde_json::json!(
+            "- [Window] Main\n  #1 [Button] One (bounds: [0,0,1,1])\n  #2 [Text] Two\nlines\n  #3 [Button] Three\n"
+        );
+
+        let first = paginate_tree(&tree, 0, 2);
+        assert_eq!(first.total_elements, 4);
+        assert_eq!(first.next_start, Some(2));
+        assert_eq!(
+            first.tree,
+            serde_json::json!("- [Window] Main\n  #1 [Button] One (bounds: [0,0,1,1])\n")
+        );
+
+        let last = paginate_tree(&tree, 2, 2);
+        assert_eq!(last.next_start, None);
+        assert_eq!(
+            last.tree,
+            serde_json::json!("  #2 [Text] Two\nlines\n  #3 [Button] Three\n")
+        );
+    }
+
+    #[test]
+    fn test_paginate_json_tree_flattens_with_depth() {
+        let tree = serde_json::json!({
+            "role": "Window",
+            "children": [
+                {"role": "Pane", "children": [{"role": "Button"}]},
+                {"role": "Text"}
+            ]
+        });
+
+        let page = paginate_tree(&tree, 1, 2);
+        assert_eq!(page.total_elements, 4);
+        assert_eq!(page.next_start, Some(3));
+        assert_eq!(
+            page.tree,
+            serde_json::json!([
+                {"role": "Pane", "depth": 1},
+                {"role": "Button", "depth": 2}
+            ])
+        );
+    }
 }
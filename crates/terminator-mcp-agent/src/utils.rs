@@ -1,5 +1,5 @@
 use crate::cancellation::RequestManager;
-use crate::mcp_types::{FontStyle, TextPosition, TreeOutputFormat};
+use crate::mcp_types::{FontStyle, ImageFormat, TextPosition, TreeOutputFormat};
 use crate::tool_logging::{LogCapture, LogCaptureLayer};
 use anyhow::Result;
 use rmcp::service::{Peer, RoleServer};
@@ -82,6 +82,19 @@ pub struct WindowManagementOptions {
         description = "Whether to restore keyboard focus and caret position after tool execution. When true, saves the currently focused element and caret position before window management, then restores them after the tool completes. Defaults to true."
     )]
     pub restore_focus: Option<bool>,
+
+    #[schemars(
+        description = "Whether to hand focus back to the user's window once the tool is done, even for tools that leave focus on the target by default (click, invoke, hover). When true, saves the focused element before window management and restores it after the tool completes. Defaults to false."
+    )]
+    pub preserve_focus: Option<bool>,
+}
+
+impl WindowManagementOptions {
+    /// Whether to save focus before window management and restore it after the tool,
+    /// given the tool's `restore_focus` default
+    pub fn should_restore_focus(&self, restore_focus_default: bool) -> bool {
+        self.preserve_focus.unwrap_or(false) || self.restore_focus.unwrap_or(restore_focus_default)
+    }
 }
 
 /// Tree options for action tools that modify UI - captures diff before/after
@@ -107,6 +120,12 @@ pub struct DiffTreeOptions {
         description = "Output format for UI tree. Options: 'verbose_json' (full JSON with all fields), 'compact_yaml' (minimal YAML: [ROLE] name #id). Defaults to 'compact_yaml'."
     )]
     pub tree_output_format: Option<TreeOutputFormat>,
+
+    #[schemars(
+        description = "Whether to also screenshot the window before and after the action and save a crop of the region whose pixels changed. Only used if ui_diff_before_after is true. Returns a visual_diff field with the screenshot path, the region in screenshot pixels and its screen bounds. Defaults to false."
+    )]
+    #[serde(default)]
+    pub capture_visual_diff: bool,
 }
 
 /// Tree options for navigation/read-only tools - captures tree after action
@@ -222,6 +241,21 @@ pub struct ActionOptions {
     #[schemars(description = "Number of times to retry this step on failure.")]
     pub retries: Option<u32>,
 
+    #[schemars(
+        description = "Delay in milliseconds before the first retry. Defaults to 250ms after a failed action and no delay after a failed element search."
+    )]
+    pub retry_delay_ms: Option<u64>,
+
+    #[schemars(
+        description = "Multiplier applied to the retry delay after each attempt (e.g. 2.0 doubles it). Defaults to 1.0 (constant delay)."
+    )]
+    pub retry_backoff: Option<f64>,
+
+    #[schemars(
+        description = "Only retry when the failure is one of these error kinds. Omit to retry on any error."
+    )]
+    pub retry_on: Option<Vec<RetryableError>>,
+
     #[schemars(
         description = "Selector that should exist after action. EXACT match from UI tree only - never guess. Use \"\" to skip."
     )]
@@ -238,6 +272,18 @@ pub struct ActionOptions {
     pub verify_timeout_ms: Option<u64>,
 }
 
+impl ActionOptions {
+    /// Retry policy for the find-and-execute helpers built from these options
+    pub fn retry_policy(&self) -> RetryPolicy {
+        RetryPolicy {
+            retries: self.retries.unwrap_or(0),
+            retry_delay_ms: self.retry_delay_ms,
+            backoff_multiplier: self.retry_backoff,
+            retry_on: self.retry_on.clone(),
+        }
+    }
+}
+
 /// Common fields for visual highlighting before actions
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct HighlightOptions {
@@ -373,6 +419,10 @@ pub struct DesktopWrapper {
     pub current_scripts_base_path: Arc<TokioMutex<Option<String>>>,
     #[serde(skip)]
     pub window_manager: Arc<WindowManager>,
+    /// Background window-cache refresh, running when `MCP_WINDOW_CACHE_REFRESH_MS` is set.
+    /// Shared by all clones so the refresh stops once the last one is dropped.
+    #[serde(skip)]
+    pub window_cache_refresh: Option<Arc<terminator::WindowCacheRefreshGuard>>,
     /// Tracks whether we're currently executing a workflow sequence
     /// Used to determine if individual tools should handle window management
     #[serde(skip)]
@@ -435,6 +485,9 @@ pub struct DesktopWrapper {
     /// When emit.progress() is called, notifications are sent to ALL connected clients
     #[serde(skip)]
     pub broadcast_peers: Arc<TokioMutex<Vec<Peer<RoleServer>>>>,
+    /// Recently completed input tool calls by idempotency key, so client retries don't repeat them
+    #[serde(skip)]
+    pub idempotency: Arc<crate::idempotency::IdempotencyStore<rmcp::model::CallToolResult>>,
 }
 
 impl Default for DesktopWrapper {
@@ -466,6 +519,31 @@ impl DesktopWrapper {
     }
 }
 
+/// Elements per page when `page.max_elements` is not given
+pub const DEFAULT_TREE_PAGE_SIZE: usize = 500;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct TreePageOptions {
+    #[schemars(
+        description = "Cursor from the previous response's page.next_cursor. Omit to get the first page."
+    )]
+    pub cursor: Option<String>,
+    #[schemars(description = "Maximum number of tree elements per page. Defaults to 500.")]
+    pub max_elements: Option<usize>,
+}
+
+impl TreePageOptions {
+    /// Element offset the cursor points at; the first page when there is no cursor
+    pub fn start(&self) -> Result<usize, String> {
+        match self.cursor.as_deref() {
+            None | Some("") => Ok(0),
+            Some(cursor) => cursor
+                .parse()
+                .map_err(|_| format!("Invalid page cursor '{cursor}'")),
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct GetWindowTreeArgs {
     #[schemars(
@@ -522,6 +600,23 @@ pub struct GetWindowTreeArgs {
         description = "Display mode for overlay labels when show_overlay is set. Valid values: 'rectangles' (no labels), 'index', 'role', 'index_role', 'name', 'index_name', 'full' (index:role:name). Defaults to 'index'."
     )]
     pub overlay_display_mode: Option<String>,
+
+    #[schemars(
+        description = "Whether to add 'labeled by', 'described by' and 'controls' relationships to each element of the tree, e.g. to tell which label belongs to an unnamed input. Costs extra calls per element. Defaults to false."
+    )]
+    #[serde(default)]
+    pub include_relationships: bool,
+
+    #[schemars(
+        description = "Whether to replace element ids with ids derived from each element's position, role, name and automation id, so the same element has the same id in every capture and trees can be diffed or cached across calls. These ids cannot be used in #id selectors. Defaults to false."
+    )]
+    #[serde(default)]
+    pub stable_ids: bool,
+
+    #[schemars(
+        description = "Return the UI tree in pages of at most max_elements elements, for clients that cannot take a large tree in one message. The response's page.next_cursor is passed back as cursor to get the next page; it is null on the last page. The tree is recaptured on each call, so pages of a changing window may overlap or skip elements. verbose_json trees are flattened into a list of nodes with a depth field."
+    )]
+    pub page: Option<TreePageOptions>,
 }
 
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
@@ -531,6 +626,25 @@ pub struct GetApplicationsArgs {
     // Use capture_screen if you need screenshots
 }
 
+fn default_sibling_count() -> usize {
+    3
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct GetFocusContextArgs {
+    #[schemars(
+        description = "How many siblings to list on each side of the focused element. Defaults to 3."
+    )]
+    #[serde(default = "default_sibling_count")]
+    pub sibling_count: usize,
+
+    #[schemars(
+        description = "Also generate a selector that finds the focused element. Searches the desktop, so it is slower; off by default."
+    )]
+    #[serde(default)]
+    pub include_selector: bool,
+}
+
 /// Args for read-only locator tools (is_toggled, is_selected, get_range_value, list_options)
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct LocatorArgs {
@@ -561,6 +675,18 @@ pub struct InvokeElementArgs {
     #[serde(flatten)]
     pub action: ActionOptions,
 
+    #[schemars(
+        description = "Selector for the result of the invoke, e.g. a 'Saved' indicator. The call waits for it to appear and fails if it doesn't, so invoke-and-confirm is one step."
+    )]
+    #[serde(default)]
+    pub wait_for_selector: Option<String>,
+
+    #[schemars(
+        description = "How long to wait for wait_for_selector, in milliseconds. Defaults to 5000ms."
+    )]
+    #[serde(default)]
+    pub wait_timeout_ms: Option<u64>,
+
     #[serde(flatten)]
     pub highlight: HighlightOptions,
 
@@ -572,6 +698,11 @@ pub struct InvokeElementArgs {
     #[serde(flatten)]
     pub window_screenshot: WindowScreenshotOptions,
 
+    #[schemars(
+        description = "Optional key identifying this action. If a call with the same key already completed in the last few minutes (e.g. a retry after a timeout), it is not executed again and the original result is returned."
+    )]
+    pub idempotency_key: Option<String>,
+
     #[serde(flatten)]
     pub window_mgmt: WindowManagementOptions,
 }
@@ -720,6 +851,11 @@ pub struct ClickElementArgs {
     #[serde(flatten)]
     pub window_screenshot: WindowScreenshotOptions,
 
+    #[schemars(
+        description = "Optional key identifying this action. If a call with the same key already completed in the last few minutes (e.g. a retry after a timeout), it is not executed again and the original result is returned."
+    )]
+    pub idempotency_key: Option<String>,
+
     #[serde(flatten)]
     pub window_mgmt: WindowManagementOptions,
 }
@@ -869,6 +1005,21 @@ pub struct TypeIntoElementArgs {
     #[schemars(description = "Number of times to retry this step on failure.")]
     pub retries: Option<u32>,
 
+    #[schemars(
+        description = "Delay in milliseconds before the first retry. Defaults to 250ms after a failed action and no delay after a failed element search."
+    )]
+    pub retry_delay_ms: Option<u64>,
+
+    #[schemars(
+        description = "Multiplier applied to the retry delay after each attempt (e.g. 2.0 doubles it). Defaults to 1.0 (constant delay)."
+    )]
+    pub retry_backoff: Option<f64>,
+
+    #[schemars(
+        description = "Only retry when the failure is one of these error kinds. Omit to retry on any error."
+    )]
+    pub retry_on: Option<Vec<RetryableError>>,
+
     #[serde(flatten)]
     pub highlight: HighlightOptions,
 
@@ -880,10 +1031,27 @@ pub struct TypeIntoElementArgs {
     #[serde(flatten)]
     pub window_screenshot: WindowScreenshotOptions,
 
+    #[schemars(
+        description = "Optional key identifying this action. If a call with the same key already completed in the last few minutes (e.g. a retry after a timeout), it is not executed again and the original result is returned."
+    )]
+    pub idempotency_key: Option<String>,
+
     #[serde(flatten)]
     pub window_mgmt: WindowManagementOptions,
 }
 
+impl TypeIntoElementArgs {
+    /// Retry policy for the find-and-execute helpers built from these arguments
+    pub fn retry_policy(&self) -> RetryPolicy {
+        RetryPolicy {
+            retries: self.retries.unwrap_or(0),
+            retry_delay_ms: self.retry_delay_ms,
+            backoff_multiplier: self.retry_backoff,
+            retry_on: self.retry_on.clone(),
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct PressKeyArgs {
     #[schemars(
@@ -917,6 +1085,11 @@ pub struct PressKeyArgs {
     #[serde(flatten)]
     pub window_screenshot: WindowScreenshotOptions,
 
+    #[schemars(
+        description = "Optional key identifying this action. If a call with the same key already completed in the last few minutes (e.g. a retry after a timeout), it is not executed again and the original result is returned."
+    )]
+    pub idempotency_key: Option<String>,
+
     #[serde(flatten)]
     pub window_mgmt: WindowManagementOptions,
 }
@@ -955,6 +1128,11 @@ pub struct GlobalKeyArgs {
     #[serde(flatten)]
     pub window_screenshot: WindowScreenshotOptions,
 
+    #[schemars(
+        description = "Optional key identifying this action. If a call with the same key already completed in the last few minutes (e.g. a retry after a timeout), it is not executed again and the original result is returned."
+    )]
+    pub idempotency_key: Option<String>,
+
     #[serde(flatten)]
     pub window_mgmt: WindowManagementOptions,
 }
@@ -974,6 +1152,10 @@ pub struct RunCommandArgs {
         schema_with = "json_object_schema"
     )]
     pub env: Option<serde_json::Value>,
+    #[schemars(
+        description = "Optional environment variables set on the spawned script runtime process (only works with 'engine' mode). Unlike 'env', these are not injected as script variables; read them via process.env / os.environ. Layered over the inherited environment."
+    )]
+    pub process_env: Option<HashMap<String, String>>,
     #[schemars(
         description = "Optional high-level engine to execute inline code with SDK bindings. One of: 'node', 'bun', 'javascript', 'js', 'typescript', 'ts', 'python'. When set, 'run' or 'script_file' must contain the code to execute."
     )]
@@ -983,7 +1165,7 @@ pub struct RunCommandArgs {
     )]
     pub shell: Option<String>,
     #[schemars(
-        description = "Working directory where the command should be executed. Defaults to current directory."
+        description = "Working directory where the command should be executed. With 'engine', this is the script runtime's working directory and takes precedence over the script_file's directory. Defaults to current directory."
     )]
     pub working_directory: Option<String>,
     #[schemars(
@@ -995,9 +1177,21 @@ pub struct RunCommandArgs {
     )]
     pub include_logs: Option<bool>,
     #[schemars(
-        description = "Timeout in milliseconds for shell command execution (ignored when 'engine' is used). Defaults to 120000 (2 minutes). Set to 0 for no timeout."
+        description = "Timeout in milliseconds for the command or 'engine' script. On expiry the process is killed and a 'script timed out' error is returned, with partial logs only when 'include_logs' is true. Defaults to 120000 (2 minutes). Set to 0 for no timeout; python scripts then keep their 10 second limit."
     )]
     pub timeout_ms: Option<u64>,
+    #[schemars(
+        description = "Optional heap cap in megabytes for 'engine' scripts, passed to Node as --max-old-space-size. Ignored by bun and python. A script exceeding it fails with a 'script ran out of memory' error."
+    )]
+    pub max_memory_mb: Option<u64>,
+    #[schemars(
+        description = "Best-effort network restriction for 'engine' scripts (JavaScript/TypeScript only). fetch, WebSocket, the script's require/import of network modules such as http/https/net and child_process, process.binding, desktop.run, desktop.runCommand and desktop.openUrl throw 'Network access is disabled for this script'. Not a sandbox: modules required by the script's dependencies, native addons, and programs started through desktop.openApplication or desktop.openFile can still reach the network. Defaults to false."
+    )]
+    pub no_network: Option<bool>,
+    #[schemars(
+        description = "Also return a 'combined' field with stdout and stderr interleaved in the order the lines were written (shell 'run' only). Useful when error context is mixed into normal output. Defaults to false."
+    )]
+    pub capture_combined: Option<bool>,
 }
 
 /// Arguments for the ask_user tool - allows AI to request clarification from the user
@@ -1044,6 +1238,11 @@ pub struct MouseDragArgs {
     #[serde(flatten)]
     pub window_screenshot: WindowScreenshotOptions,
 
+    #[schemars(
+        description = "Optional key identifying this action. If a call with the same key already completed in the last few minutes (e.g. a retry after a timeout), it is not executed again and the original result is returned."
+    )]
+    pub idempotency_key: Option<String>,
+
     #[serde(flatten)]
     pub window_mgmt: WindowManagementOptions,
 }
@@ -1072,6 +1271,24 @@ pub enum VisionType {
     Gemini,
 }
 
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct DescribeElementArgs {
+    #[serde(flatten)]
+    pub selector: SelectorOptions,
+
+    #[serde(flatten)]
+    pub action: ActionOptions,
+
+    #[schemars(
+        description = "If true, focus the element before reading it so screen-reader-style properties are current. Defaults to true."
+    )]
+    #[serde(default = "default_true")]
+    pub focus: bool,
+
+    #[serde(flatten)]
+    pub window_mgmt: WindowManagementOptions,
+}
+
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct ValidateElementArgs {
     #[serde(flatten)]
@@ -1122,6 +1339,20 @@ pub struct CaptureScreenshotArgs {
     )]
     pub max_dimension: Option<u32>,
 
+    /// Image encoding for the returned screenshot. Default: jpeg
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(
+        description = "Image encoding for the returned screenshot: 'jpeg' (default, smallest), 'png' or 'webp' (both lossless)."
+    )]
+    pub format: Option<ImageFormat>,
+
+    /// JPEG quality (1-100). Default: 85
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(
+        description = "JPEG quality from 1 to 100. Lower values shrink the payload. Ignored for png and webp. Default: 85"
+    )]
+    pub quality: Option<u8>,
+
     #[schemars(
         description = "If true, captures the entire monitor where the target window is located instead of the window/element. Defaults to false."
     )]
@@ -1132,6 +1363,34 @@ pub struct CaptureScreenshotArgs {
     pub window_mgmt: WindowManagementOptions,
 }
 
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct CaptureElementScreenshotArgs {
+    #[serde(flatten)]
+    pub selector: SelectorOptions,
+
+    #[serde(flatten)]
+    pub action: ActionOptions,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(
+        description = "Maximum dimension (width or height) of the returned image. Larger captures are resized while maintaining aspect ratio. Default: 1920px"
+    )]
+    pub max_dimension: Option<u32>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(
+        description = "Image encoding: 'jpeg' (default, smallest), 'png' or 'webp' (both lossless)."
+    )]
+    pub format: Option<ImageFormat>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "JPEG quality from 1 to 100. Ignored for png and webp. Default: 85")]
+    pub quality: Option<u8>,
+
+    #[serde(flatten)]
+    pub window_mgmt: WindowManagementOptions,
+}
+
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct HighlightElementArgs {
     #[schemars(description = "BGR color code (optional, default red)")]
@@ -1148,6 +1407,10 @@ pub struct HighlightElementArgs {
     #[schemars(description = "Text color in BGR format (default: 0 = black)")]
     pub font_color: Option<u32>,
     pub include_element_info: Option<bool>,
+    #[schemars(
+        description = "Scroll the element into view before highlighting so the overlay lands on its on-screen bounds, e.g. for items far down a long list (default: false)"
+    )]
+    pub scroll_into_view: Option<bool>,
     #[serde(flatten)]
     pub selector: SelectorOptions,
 
@@ -1308,6 +1571,11 @@ pub struct SelectOptionArgs {
     #[serde(flatten)]
     pub window_screenshot: WindowScreenshotOptions,
 
+    #[schemars(
+        description = "Optional key identifying this action. If a call with the same key already completed in the last few minutes (e.g. a retry after a timeout), it is not executed again and the original result is returned."
+    )]
+    pub idempotency_key: Option<String>,
+
     #[serde(flatten)]
     pub window_mgmt: WindowManagementOptions,
 }
@@ -1333,6 +1601,11 @@ pub struct SetValueArgs {
     #[serde(flatten)]
     pub window_screenshot: WindowScreenshotOptions,
 
+    #[schemars(
+        description = "Optional key identifying this action. If a call with the same key already completed in the last few minutes (e.g. a retry after a timeout), it is not executed again and the original result is returned."
+    )]
+    pub idempotency_key: Option<String>,
+
     #[serde(flatten)]
     pub window_mgmt: WindowManagementOptions,
 }
@@ -1358,6 +1631,11 @@ pub struct SetSelectedArgs {
     #[serde(flatten)]
     pub window_screenshot: WindowScreenshotOptions,
 
+    #[schemars(
+        description = "Optional key identifying this action. If a call with the same key already completed in the last few minutes (e.g. a retry after a timeout), it is not executed again and the original result is returned."
+    )]
+    pub idempotency_key: Option<String>,
+
     #[serde(flatten)]
     pub window_mgmt: WindowManagementOptions,
 }
@@ -1388,6 +1666,11 @@ pub struct ScrollElementArgs {
     #[serde(flatten)]
     pub window_screenshot: WindowScreenshotOptions,
 
+    #[schemars(
+        description = "Optional key identifying this action. If a call with the same key already completed in the last few minutes (e.g. a retry after a timeout), it is not executed again and the original result is returned."
+    )]
+    pub idempotency_key: Option<String>,
+
     #[serde(flatten)]
     pub window_mgmt: WindowManagementOptions,
 }
@@ -1408,6 +1691,11 @@ pub struct ActivateElementArgs {
     #[serde(flatten)]
     pub window_screenshot: WindowScreenshotOptions,
 
+    #[schemars(
+        description = "Optional key identifying this action. If a call with the same key already completed in the last few minutes (e.g. a retry after a timeout), it is not executed again and the original result is returned."
+    )]
+    pub idempotency_key: Option<String>,
+
     #[serde(flatten)]
     pub window_mgmt: WindowManagementOptions,
 }
@@ -1596,6 +1884,14 @@ pub struct ExecuteSequenceArgs {
     )]
     pub execution_id: Option<String>,
 
+    #[schemars(
+        description = "Capture the focused window's UI tree around every step and attach it to the step's result under 'ui_trees': 'before_each', 'after_each', or 'both' (which also adds the diff). Expensive, so off by default ('none')."
+    )]
+    pub capture_trees: Option<crate::mcp_types::CaptureMode>,
+
+    #[schemars(description = "Maximum depth of the trees captured by capture_trees (default: 10)")]
+    pub capture_tree_max_depth: Option<usize>,
+
     #[serde(flatten)]
     pub window_mgmt: WindowManagementOptions,
 }
@@ -2312,6 +2608,7 @@ pub async fn find_element_with_fallbacks(
 ) -> Result<(terminator::UIElement, String), terminator::AutomationError> {
     use tokio::time::Duration;
 
+    let _locate = crate::tool_timing::PhaseTimer::start("locate");
     let find_start = std::time::Instant::now();
     let timeout_duration = get_timeout(timeout_ms).unwrap_or(Duration::from_millis(3000));
 
@@ -2327,6 +2624,7 @@ pub async fn find_element_with_fallbacks(
                 );
                 Ok((element, primary_selector.to_string()))
             }
+            Err(e @ terminator::AutomationError::ElevationRequired(_)) => Err(e),
             Err(e) => {
                 tracing::info!(
                     "[PERF] find_element_with_fallbacks: {}ms (FAILED selector: {})",
@@ -2447,14 +2745,18 @@ pub async fn find_element_with_fallbacks(
                 }
             }
             Ok(Err((selector, error))) => {
-                // Check if this is a UIAutomationAPIError - if so, return immediately
-                if let terminator::AutomationError::UIAutomationAPIError { .. } = error {
+                // UI Automation API failures and UAC prompts affect every selector - return immediately
+                if matches!(
+                    error,
+                    terminator::AutomationError::UIAutomationAPIError { .. }
+                        | terminator::AutomationError::ElevationRequired(_)
+                ) {
                     // This is a system-level failure that affects all selectors
                     // No point trying alternatives - abort remaining tasks
                     for task in remaining_tasks {
                         task.abort();
                     }
-                    // Return the UIAutomationAPIError directly
+                    // Return the error directly
                     return Err(error);
                 }
                 // For other errors, continue collecting them as strings
@@ -2502,6 +2804,91 @@ pub async fn find_element_with_fallbacks(
     Err(terminator::AutomationError::ElementNotFound(combined_error))
 }
 
+/// Error kinds a per-call retry policy can be limited to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum RetryableError {
+    ElementNotFound,
+    Timeout,
+    ElementNotVisible,
+    ElementNotEnabled,
+    ElementNotStable,
+    ElementObscured,
+    ElementDetached,
+    ScrollFailed,
+    /// Platform and UI Automation API errors
+    PlatformError,
+}
+
+impl RetryableError {
+    fn matches(&self, error: &AutomationError) -> bool {
+        matches!(
+            (self, error),
+            (Self::ElementNotFound, AutomationError::ElementNotFound(_))
+                | (Self::Timeout, AutomationError::Timeout(_))
+                | (
+                    Self::ElementNotVisible,
+                    AutomationError::ElementNotVisible(_)
+                )
+                | (
+                    Self::ElementNotEnabled,
+                    AutomationError::ElementNotEnabled(_)
+                )
+                | (Self::ElementNotStable, AutomationError::ElementNotStable(_))
+                | (Self::ElementObscured, AutomationError::ElementObscured(_))
+                | (Self::ElementDetached, AutomationError::ElementDetached(_))
+                | (Self::ScrollFailed, AutomationError::ScrollFailed(_))
+                | (Self::PlatformError, AutomationError::PlatformError(_))
+                | (
+                    Self::PlatformError,
+                    AutomationError::UIAutomationAPIError { .. }
+                )
+        )
+    }
+}
+
+/// Upper bound for a single retry delay, so large backoff multipliers can't stall a workflow
+const MAX_RETRY_DELAY_MS: f64 = 60_000.0;
+
+/// Retry behaviour for the find-and-execute helpers, configurable per tool call
+#[derive(Debug, Clone, Default)]
+pub struct RetryPolicy {
+    /// Number of retries after the first attempt
+    pub retries: u32,
+    /// Delay before the first retry. None keeps the helper's default for the failure kind
+    pub retry_delay_ms: Option<u64>,
+    /// Multiplier applied to the delay after each retry. None means a constant delay
+    pub backoff_multiplier: Option<f64>,
+    /// Only these error kinds are retried. None retries every error
+    pub retry_on: Option<Vec<RetryableError>>,
+}
+
+impl RetryPolicy {
+    pub fn with_retries(retries: Option<u32>) -> Self {
+        Self {
+            retries: retries.unwrap_or(0),
+            ..Default::default()
+        }
+    }
+
+    /// Whether a failure on the zero-based `attempt` should be retried
+    pub fn should_retry(&self, attempt: u32, error: &AutomationError) -> bool {
+        attempt < self.retries
+            && self
+                .retry_on
+                .as_ref()
+                .is_none_or(|kinds| kinds.iter().any(|kind| kind.matches(error)))
+    }
+
+    /// Delay to wait after the zero-based `attempt` failed
+    pub fn delay(&self, attempt: u32, default_ms: u64) -> Duration {
+        let base = self.retry_delay_ms.unwrap_or(default_ms) as f64;
+        let factor = self.backoff_multiplier.unwrap_or(1.0).max(0.0);
+        let ms = (base * factor.powi(attempt as i32)).min(MAX_RETRY_DELAY_MS);
+        Duration::from_millis(ms as u64)
+    }
+}
+
 /// A robust helper that finds a UI element and executes a provided action on it,
 /// with built-in retry logic for both finding the element and performing the action.
 ///
@@ -2512,7 +2899,7 @@ pub async fn find_element_with_fallbacks(
 /// * `primary_selector` - The main selector for the target element.
 /// * `alternatives` - A comma-separated string of fallback selectors.
 /// * `timeout_ms` - The timeout for the initial element search.
-/// * `retries` - The number of times to retry the *entire find-and-act sequence*.
+/// * `retry` - How often and when to retry the *entire find-and-act sequence*.
 /// * `action` - An async closure that takes the found `UIElement` and performs an action,
 ///
 /// # Returns
@@ -2523,14 +2910,14 @@ pub async fn find_and_execute_with_retry<F, Fut, T>(
     primary_selector: &str,
     alternatives: Option<&str>,
     timeout_ms: Option<u64>,
-    retries: Option<u32>,
+    retry: &RetryPolicy,
     action: F,
 ) -> Result<((T, UIElement), String), anyhow::Error>
 where
     F: Fn(UIElement) -> Fut,
     Fut: std::future::Future<Output = Result<T, AutomationError>>,
 {
-    let retry_count = retries.unwrap_or(0);
+    let retry_count = retry.retries;
     let mut last_error: Option<anyhow::Error> = None;
 
     for attempt in 0..=retry_count {
@@ -2540,29 +2927,34 @@ where
             Ok((element, successful_selector)) => match action(element.clone()).await {
                 Ok(result) => return Ok(((result, element), successful_selector)),
                 Err(e) => {
+                    let should_retry = retry.should_retry(attempt, &e);
                     last_error = Some(e.into());
-                    if attempt < retry_count {
-                        warn!(
-                            "Action failed on attempt {}/{}. Retrying... Error: {}",
-                            attempt + 1,
-                            retry_count + 1,
-                            last_error.as_ref().unwrap()
-                        );
-                        tokio::time::sleep(Duration::from_millis(250)).await; // Wait before next retry
+                    if !should_retry {
+                        break;
                     }
-                }
-            },
-            Err(e) => {
-                last_error = Some(e.into());
-                if attempt < retry_count {
                     warn!(
-                        "Find element failed on attempt {}/{}. Retrying... Error: {}",
+                        "Action failed on attempt {}/{}. Retrying... Error: {}",
                         attempt + 1,
                         retry_count + 1,
                         last_error.as_ref().unwrap()
                     );
-                    // No need to sleep here, as find_element_with_fallbacks already has a timeout.
+                    tokio::time::sleep(retry.delay(attempt, 250)).await; // Wait before next retry
                 }
+            },
+            Err(e) => {
+                let should_retry = retry.should_retry(attempt, &e);
+                last_error = Some(e.into());
+                if !should_retry {
+                    break;
+                }
+                warn!(
+                    "Find element failed on attempt {}/{}. Retrying... Error: {}",
+                    attempt + 1,
+                    retry_count + 1,
+                    last_error.as_ref().unwrap()
+                );
+                // No default delay here, as find_element_with_fallbacks already has a timeout.
+                tokio::time::sleep(retry.delay(attempt, 0)).await;
             }
         }
     }
@@ -2583,14 +2975,14 @@ pub async fn find_and_execute_with_retry_with_fallback<F, Fut, T>(
     alternatives: Option<&str>,
     fallback_selectors: Option<&str>,
     timeout_ms: Option<u64>,
-    retries: Option<u32>,
+    retry: &RetryPolicy,
     action: F,
 ) -> Result<((T, UIElement), String), anyhow::Error>
 where
     F: Fn(UIElement) -> Fut,
     Fut: std::future::Future<Output = Result<T, AutomationError>>,
 {
-    let retry_count = retries.unwrap_or(0);
+    let retry_count = retry.retries;
     let mut last_error: Option<anyhow::Error> = None;
 
     for attempt in 0..=retry_count {
@@ -2606,29 +2998,34 @@ where
             Ok((element, successful_selector)) => match action(element.clone()).await {
                 Ok(result) => return Ok(((result, element), successful_selector)),
                 Err(e) => {
+                    let should_retry = retry.should_retry(attempt, &e);
                     last_error = Some(e.into());
-                    if attempt < retry_count {
-                        warn!(
-                            "Action failed on attempt {}/{}. Retrying... Error: {}",
-                            attempt + 1,
-                            retry_count + 1,
-                            last_error.as_ref().unwrap()
-                        );
-                        tokio::time::sleep(Duration::from_millis(250)).await; // Wait before next retry
+                    if !should_retry {
+                        break;
                     }
-                }
-            },
-            Err(e) => {
-                last_error = Some(e.into());
-                if attempt < retry_count {
                     warn!(
-                        "Find element failed on attempt {}/{}. Retrying... Error: {}",
+                        "Action failed on attempt {}/{}. Retrying... Error: {}",
                         attempt + 1,
                         retry_count + 1,
                         last_error.as_ref().unwrap()
                     );
-                    // No need to sleep here, as find_element_with_fallbacks already has a timeout.
+                    tokio::time::sleep(retry.delay(attempt, 250)).await; // Wait before next retry
+                }
+            },
+            Err(e) => {
+                let should_retry = retry.should_retry(attempt, &e);
+                last_error = Some(e.into());
+                if !should_retry {
+                    break;
                 }
+                warn!(
+                    "Find element failed on attempt {}/{}. Retrying... Error: {}",
+                    attempt + 1,
+                    retry_count + 1,
+                    last_error.as_ref().unwrap()
+                );
+                // No default delay here, as find_element_with_fallbacks already has a timeout.
+                tokio::time::sleep(retry.delay(attempt, 0)).await;
             }
         }
     }
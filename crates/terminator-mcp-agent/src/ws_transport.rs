@@ -0,0 +1,246 @@
+//! MCP over a plain WebSocket, one JSON-RPC message per text frame.
+//!
+//! The upgrade is answered directly on top of hyper so `/ws` can live in the
+//! same axum router as `/mcp` and go through the same auth middleware. Tool
+//! calls draw from the same [`RequestGate`] as HTTP requests; a call that can't
+//! get a slot is answered with a [`SERVER_BUSY`] error instead of being run.
+
+use crate::request_gate::RequestGate;
+use crate::server::DesktopWrapper;
+use axum::body::Body;
+use axum::http::{header, Request, Response, StatusCode};
+use axum::response::IntoResponse;
+use futures::channel::mpsc;
+use futures::{SinkExt, StreamExt};
+use hyper_util::rt::TokioIo;
+use rmcp::model::{
+    ClientJsonRpcMessage, ClientNotification, ClientRequest, ErrorCode, ErrorData, JsonRpcMessage,
+    RequestId, ServerJsonRpcMessage,
+};
+use rmcp::ServiceExt;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::sync::OwnedSemaphorePermit;
+use tokio_tungstenite::tungstenite::handshake::derive_accept_key;
+use tokio_tungstenite::tungstenite::protocol::Role;
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::WebSocketStream;
+use tracing::{debug, info, warn};
+
+/// JSON-RPC error code for a tool call turned away because the server is busy
+pub const SERVER_BUSY: ErrorCode = ErrorCode(-32000);
+
+/// Concurrency slots held by in-flight tool calls, released when they're answered
+type Permits = Arc<Mutex<HashMap<RequestId, OwnedSemaphorePermit>>>;
+
+/// Tool calls still waiting for a slot, so a cancellation can stop the wait
+type Waiting = Arc<Mutex<HashMap<RequestId, tokio::task::AbortHandle>>>;
+
+/// Accept a WebSocket upgrade request and serve MCP on the connection.
+///
+/// Returns 400 if the request isn't a WebSocket upgrade.
+pub fn upgrade(
+    mut req: Request<Body>,
+    desktop: DesktopWrapper,
+    gate: RequestGate,
+) -> Response<Body> {
+    let is_websocket = req
+        .headers()
+        .get(header::UPGRADE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.eq_ignore_ascii_case("websocket"));
+    let key = req.headers().get(header::SEC_WEBSOCKET_KEY).cloned();
+    let on_upgrade = req.extensions_mut().remove::<hyper::upgrade::OnUpgrade>();
+
+    let (true, Some(key), Some(on_upgrade)) = (is_websocket, key, on_upgrade) else {
+        return (
+            StatusCode::BAD_REQUEST,
+            "Expected a WebSocket upgrade request",
+        )
+            .into_response();
+    };
+
+    tokio::spawn(async move {
+        match on_upgrade.await {
+            Ok(upgraded) => {
+                let ws =
+                    WebSocketStream::from_raw_socket(TokioIo::new(upgraded), Role::Server, None)
+                        .await;
+                serve(ws, desktop, gate).await;
+            }
+            Err(e) => warn!("WebSocket upgrade failed: {e}"),
+        }
+    });
+
+    (
+        StatusCode::SWITCHING_PROTOCOLS,
+        [
+            (header::CONNECTION, "upgrade".to_string()),
+            (header::UPGRADE, "websocket".to_string()),
+            (
+                header::SEC_WEBSOCKET_ACCEPT,
+                derive_accept_key(key.as_bytes()),
+            ),
+        ],
+    )
+        .into_response()
+}
+
+/// Run an MCP session over an established WebSocket until either side closes it.
+pub async fn serve<S>(ws: WebSocketStream<S>, desktop: DesktopWrapper, gate: RequestGate)
+where
+    S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+{
+    info!("WebSocket MCP session started");
+    let (mut ws_sink, mut ws_source) = ws.split();
+    let (to_service, service_rx) = mpsc::unbounded::<ClientJsonRpcMessage>();
+    let (service_tx, mut from_service) = mpsc::unbounded::<ServerJsonRpcMessage>();
+    let permits: Permits = Arc::default();
+    let waiting: Waiting = Arc::default();
+
+    // Socket -> service, taking a slot for every tool call
+    let reader = {
+        let permits = permits.clone();
+        let waiting = waiting.clone();
+        let busy_tx = service_tx.clone();
+        let gate = gate.clone();
+        tokio::spawn(async move {
+            while let Some(frame) = ws_source.next().await {
+                let text = match frame {
+                    Ok(Message::Text(text)) => text,
+                    Ok(Message::Binary(bytes)) => match String::from_utf8(bytes) {
+                        Ok(text) => text,
+                        Err(_) => continue,
+                    },
+                    Ok(Message::Close(_)) | Err(_) => break,
+                    Ok(_) => continue,
+                };
+                let message: ClientJsonRpcMessage = match serde_json::from_str(&text) {
+                    Ok(message) => message,
+                    Err(e) => {
+                        warn!("Ignoring malformed WebSocket MCP message: {e}");
+                        continue;
+                    }
+                };
+
+                let tool_call = match &message {
+                    JsonRpcMessage::Request(request) => match &request.request {
+                        ClientRequest::CallToolRequest(call) => Some((
+                            request.id.clone(),
+                            gate.is_read_only_call(
+                                &call.params.name,
+                                call.params.arguments.as_ref(),
+                            ),
+                        )),
+                        _ => None,
+                    },
+                    // A cancelled call may never be answered, so free its slot now
+                    JsonRpcMessage::Notification(notification) => {
+                        if let ClientNotification::CancelledNotification(cancelled) =
+                            &notification.notification
+                        {
+                            let id = &cancelled.params.request_id;
+                            if let Some(waiter) = lock(&waiting).remove(id) {
+                                waiter.abort();
+                            }
+                            lock(&permits).remove(id);
+                        }
+                        None
+                    }
+                    _ => None,
+                };
+
+                // Wait for the slot off the read loop, so frames behind a queued call
+                // (cancellations, calls another pool would let through) keep flowing
+                if let Some((id, read_only)) = tool_call {
+                    let mut waiting_now = lock(&waiting);
+                    let waiter = tokio::spawn({
+                        let gate = gate.clone();
+                        let permits = permits.clone();
+                        let waiting = waiting.clone();
+                        let busy_tx = busy_tx.clone();
+                        let to_service = to_service.clone();
+                        let id = id.clone();
+                        async move {
+                            let permit = gate.acquire(read_only).await;
+                            lock(&waiting).remove(&id);
+                            let Some(permit) = permit else {
+                                let busy = ErrorData::new(
+                                    SERVER_BUSY,
+                                    "Server is busy, try again later",
+                                    Some(serde_json::json!({
+                                        "activeRequests": gate.active_requests(),
+                                        "maxConcurrent": gate.max_concurrent(),
+                                    })),
+                                );
+                                let _ = busy_tx.unbounded_send(JsonRpcMessage::error(busy, id));
+                                return;
+                            };
+                            gate.touch();
+                            lock(&permits).insert(id, permit);
+                            let _ = to_service.unbounded_send(message);
+                        }
+                    });
+                    waiting_now.insert(id, waiter.abort_handle());
+                    continue;
+                }
+
+                if to_service.unbounded_send(message).is_err() {
+                    break;
+                }
+            }
+            debug!("WebSocket client closed the connection");
+        })
+    };
+
+    // Service -> socket, releasing slots as calls are answered
+    let writer = {
+        let permits = permits.clone();
+        tokio::spawn(async move {
+            while let Some(message) = from_service.next().await {
+                let id = match &message {
+                    JsonRpcMessage::Response(response) => Some(&response.id),
+                    JsonRpcMessage::Error(error) => Some(&error.id),
+                    _ => None,
+                };
+                if id.is_some_and(|id| lock(&permits).remove(id).is_some()) {
+                    gate.touch();
+                }
+                let text = match serde_json::to_string(&message) {
+                    Ok(text) => text,
+                    Err(e) => {
+                        warn!("Failed to serialize MCP message: {e}");
+                        continue;
+                    }
+                };
+                if ws_sink.send(Message::Text(text)).await.is_err() {
+                    break;
+                }
+            }
+            let _ = ws_sink.close().await;
+        })
+    };
+
+    match desktop.serve((service_tx, service_rx)).await {
+        Ok(service) => {
+            if let Err(e) = service.waiting().await {
+                warn!("WebSocket MCP session ended with an error: {e}");
+            }
+        }
+        Err(e) => warn!("WebSocket MCP session failed to initialize: {e}"),
+    }
+
+    reader.abort();
+    for (_, waiter) in lock(&waiting).drain() {
+        waiter.abort();
+    }
+    let _ = writer.await;
+    // Anything still in flight belongs to a dead session
+    lock(&permits).clear();
+    info!("WebSocket MCP session ended");
+}
+
+fn lock<T>(map: &Mutex<T>) -> std::sync::MutexGuard<'_, T> {
+    map.lock().unwrap_or_else(|e| e.into_inner())
+}
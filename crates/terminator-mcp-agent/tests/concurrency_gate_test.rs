@@ -0,0 +1,158 @@
+use axum::body::Body;
+use futures::StreamExt;
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+use terminator_mcp_agent::cancellation::{guard_body, jsonrpc_request_id, ResponseGuard};
+use terminator_mcp_agent::mcp_types::{is_read_only_call, ReadOnlyTools};
+use terminator_mcp_agent::read_only_tool_names;
+
+fn call(tool: &str) -> serde_json::Value {
+    serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "tools/call",
+        "params": { "name": tool, "arguments": {} }
+    })
+}
+
+#[test]
+fn test_read_only_tools_come_from_annotations() {
+    let read_only = read_only_tool_names();
+    assert!(read_only.contains("get_window_tree"));
+    assert!(read_only.contains("capture_element_screenshot"));
+    assert!(read_only.contains("read_file"));
+    assert!(!read_only.contains("click_element"));
+    assert!(!read_only.contains("type_into_element"));
+    assert!(!read_only.contains("execute_sequence"));
+}
+
+#[test]
+fn test_is_read_only_call_classifies_tool_calls() {
+    let read_only = ReadOnlyTools::new(["get_window_tree".to_string()].into(), HashSet::new());
+
+    let body = serde_json::to_vec(&call("get_window_tree")).unwrap();
+    assert!(is_read_only_call(&body, &read_only));
+
+    let body = serde_json::to_vec(&call("click_element")).unwrap();
+    assert!(!is_read_only_call(&body, &read_only));
+}
+
+#[test]
+fn test_is_read_only_call_is_conservative() {
+    let read_only = ReadOnlyTools::new(["get_window_tree".to_string()].into(), HashSet::new());
+
+    // Only tools/call is classified; everything else keeps the strict limit
+    let list = serde_json::json!({ "jsonrpc": "2.0", "id": 1, "method": "tools/list" });
+    assert!(!is_read_only_call(
+        &serde_json::to_vec(&list).unwrap(),
+        &read_only
+    ));
+    assert!(!is_read_only_call(b"not json", &read_only));
+    assert!(!is_read_only_call(b"[]", &read_only));
+
+    // A batch is read-only only if every call in it is
+    let batch = serde_json::json!([call("get_window_tree"), call("get_window_tree")]);
+    assert!(is_read_only_call(
+        &serde_json::to_vec(&batch).unwrap(),
+        &read_only
+    ));
+    let mixed = serde_json::json!([call("get_window_tree"), call("click_element")]);
+    assert!(!is_read_only_call(
+        &serde_json::to_vec(&mixed).unwrap(),
+        &read_only
+    ));
+}
+
+#[test]
+fn test_window_managing_tools_need_window_management_off() {
+    let read_only = read_only_tool_names();
+    let with_args = |tool: &str, arguments: serde_json::Value| {
+        let mut message = call(tool);
+        message["params"]["arguments"] = arguments;
+        serde_json::to_vec(&message).unwrap()
+    };
+
+    // These minimize, activate and maximize windows by default
+    for tool in [
+        "get_window_tree",
+        "validate_element",
+        "wait_for_element",
+        "capture_element_screenshot",
+    ] {
+        assert!(!is_read_only_call(
+            &with_args(tool, serde_json::json!({})),
+            &read_only
+        ));
+        assert!(!is_read_only_call(
+            &with_args(
+                tool,
+                serde_json::json!({ "enable_window_management": true })
+            ),
+            &read_only
+        ));
+        assert!(is_read_only_call(
+            &with_args(
+                tool,
+                serde_json::json!({ "enable_window_management": false })
+            ),
+            &read_only
+        ));
+    }
+
+    // Tools without window management stay read-only unconditionally
+    assert!(is_read_only_call(
+        &with_args("read_file", serde_json::json!({})),
+        &read_only
+    ));
+}
+
+fn recording_guard() -> (ResponseGuard, Arc<Mutex<Option<bool>>>) {
+    let outcome = Arc::new(Mutex::new(None));
+    let recorded = outcome.clone();
+    let guard = ResponseGuard::new(move |delivered| *recorded.lock().unwrap() = Some(delivered));
+    (guard, outcome)
+}
+
+#[tokio::test]
+async fn test_guarded_body_reports_delivery() {
+    let (guard, outcome) = recording_guard();
+    let held = Arc::new(());
+    let body = guard_body(Body::from("hello"), guard, held.clone());
+    assert_eq!(*outcome.lock().unwrap(), None);
+
+    let bytes = axum::body::to_bytes(body, usize::MAX).await.unwrap();
+    assert_eq!(&bytes[..], b"hello");
+    assert_eq!(*outcome.lock().unwrap(), Some(true));
+    assert_eq!(Arc::strong_count(&held), 1, "held value should be released");
+}
+
+#[tokio::test]
+async fn test_guarded_body_dropped_early_reports_disconnect() {
+    let (guard, outcome) = recording_guard();
+    let held = Arc::new(());
+    let chunks = futures::stream::iter(vec![Ok::<_, std::io::Error>("first"), Ok("second")]);
+    let body = guard_body(Body::from_stream(chunks), guard, held.clone());
+
+    let mut stream = body.into_data_stream();
+    assert!(stream.next().await.is_some());
+    drop(stream);
+
+    assert_eq!(*outcome.lock().unwrap(), Some(false));
+    assert_eq!(Arc::strong_count(&held), 1, "held value should be released");
+}
+
+#[test]
+fn test_jsonrpc_request_id() {
+    let body = serde_json::to_vec(&call("click_element")).unwrap();
+    assert_eq!(jsonrpc_request_id(&body), Some(serde_json::json!(1)));
+
+    let notification = serde_json::json!({
+        "jsonrpc": "2.0",
+        "method": "notifications/initialized"
+    });
+    assert_eq!(
+        jsonrpc_request_id(&serde_json::to_vec(&notification).unwrap()),
+        None
+    );
+    assert_eq!(jsonrpc_request_id(b"not json"), None);
+}
@@ -0,0 +1,81 @@
+use serde_json::json;
+use terminator_mcp_agent::env_snapshot::{is_sensitive_key, snapshot};
+use terminator_mcp_agent::log_redaction::LogRedactor;
+
+#[test]
+fn sensitive_keys_are_recognised_case_insensitively() {
+    for key in [
+        "password",
+        "DB_PASSWORD",
+        "apiKey",
+        "github_token",
+        "ClientSecret",
+    ] {
+        assert!(is_sensitive_key(key), "{key} should be sensitive");
+    }
+    assert!(!is_sensitive_key("username"));
+    assert!(!is_sensitive_key("invoice_id"));
+}
+
+#[test]
+fn snapshot_splits_variables_from_env_and_redacts_both() {
+    let context = json!({
+        "customer": "Acme",
+        "login": { "user": "jane", "password": "hunter2" },
+        "env": {
+            "api_key": "sk-123",
+            "header": "Bearer abc.def",
+            "count": 3
+        }
+    });
+
+    let snapshot = snapshot(context.as_object().unwrap(), &[], &LogRedactor::default());
+
+    assert_eq!(
+        snapshot,
+        json!({
+            "variables": {
+                "customer": "Acme",
+                "login": { "user": "jane", "password": "[REDACTED]" }
+            },
+            "env": {
+                "api_key": "[REDACTED]",
+                "header": "[REDACTED]",
+                "count": 3
+            }
+        })
+    );
+}
+
+#[test]
+fn snapshot_leaves_step_results_out_of_variables() {
+    let context = json!({
+        "customer": "Acme",
+        "login_result": { "status": "success" },
+        "login_status": "success",
+        "test_result": "a variable, not a step",
+        "env": { "login_result": "kept" }
+    });
+
+    let snapshot = snapshot(
+        context.as_object().unwrap(),
+        &["login"],
+        &LogRedactor::default(),
+    );
+
+    assert_eq!(
+        snapshot["variables"],
+        json!({ "customer": "Acme", "test_result": "a variable, not a step" })
+    );
+    assert_eq!(snapshot["env"], json!({ "login_result": "kept" }));
+}
+
+#[test]
+fn snapshot_without_env_reports_an_empty_env() {
+    let context = json!({ "customer": "Acme" });
+
+    let snapshot = snapshot(context.as_object().unwrap(), &[], &LogRedactor::default());
+
+    assert_eq!(snapshot["env"], json!({}));
+    assert_eq!(snapshot["variables"], json!({ "customer": "Acme" }));
+}
@@ -1,6 +1,6 @@
 // Import the functions to be tested
 use serde_json::json;
-use terminator_mcp_agent::helpers::substitute_variables;
+use terminator_mcp_agent::helpers::{attach_step_trees, substitute_variables};
 
 #[test]
 fn test_substitute_no_variables() {
@@ -106,3 +106,46 @@ fn test_substitute_multiple_in_one_string() {
     substitute_variables(&mut args, &variables);
     assert_eq!(args, json!({"key": "Hello Alex, welcome to the machine!"}));
 }
+
+#[test]
+fn test_attach_step_trees_adds_diff_when_both_captured() {
+    let mut result = json!({"status": "success"});
+    let before = "- [Window] Editor\n  - [Button] Save\n".to_string();
+    let after = "- [Window] Editor\n  - [Button] Saved\n".to_string();
+
+    attach_step_trees(
+        &mut result,
+        Some(Ok(before.clone())),
+        Some(Ok(after.clone())),
+    );
+
+    let trees = &result["ui_trees"];
+    assert_eq!(trees["before"], json!(before));
+    assert_eq!(trees["after"], json!(after));
+    assert!(trees["diff"].as_str().unwrap().contains("Saved"));
+}
+
+#[test]
+fn test_attach_step_trees_reports_capture_errors() {
+    let mut result = json!({"status": "success"});
+
+    attach_step_trees(
+        &mut result,
+        None,
+        Some(Err("no focused window".to_string())),
+    );
+
+    assert_eq!(
+        result["ui_trees"],
+        json!({"after": {"error": "no focused window"}})
+    );
+}
+
+#[test]
+fn test_attach_step_trees_leaves_result_alone_when_disabled() {
+    let mut result = json!({"status": "success"});
+
+    attach_step_trees(&mut result, None, None);
+
+    assert_eq!(result, json!({"status": "success"}));
+}
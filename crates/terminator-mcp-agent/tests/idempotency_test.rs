@@ -0,0 +1,129 @@
+use serde_json::json;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use terminator_mcp_agent::idempotency::{
+    key_for, IdempotencyStore, Outcome, IDEMPOTENT_INPUT_TOOLS,
+};
+use terminator_mcp_agent::mcp_types::tool_schemas;
+
+#[test]
+fn test_key_only_for_input_tools_with_a_key() {
+    let args = json!({ "selector": "role:Button", "idempotency_key": "submit-1" });
+    assert_eq!(
+        key_for("click_element", &args).as_deref(),
+        Some("click_element:submit-1")
+    );
+    assert_eq!(key_for("get_window_tree", &args), None);
+    assert_eq!(
+        key_for("click_element", &json!({ "idempotency_key": " " })),
+        None
+    );
+    assert_eq!(key_for("click_element", &json!({})), None);
+}
+
+#[test]
+fn test_only_input_tools_advertise_a_key() {
+    let schemas = tool_schemas();
+    for (tool, schema) in schemas.as_object().unwrap() {
+        let advertised = schema["properties"]["idempotency_key"].is_object();
+        assert_eq!(
+            advertised,
+            IDEMPOTENT_INPUT_TOOLS.contains(&tool.as_str()),
+            "{tool}"
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_completed_key_is_replayed() {
+    let store = IdempotencyStore::new(Duration::from_secs(60));
+    let runs = AtomicUsize::new(0);
+    let action = || async {
+        runs.fetch_add(1, Ordering::SeqCst);
+        Ok::<_, String>("clicked")
+    };
+
+    assert_eq!(
+        store.execute("k", action).await,
+        Ok(Outcome::Executed("clicked"))
+    );
+    assert_eq!(
+        store.execute("k", action).await,
+        Ok(Outcome::Replayed("clicked"))
+    );
+    assert_eq!(runs.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn test_failed_action_can_be_retried() {
+    let store = IdempotencyStore::<&str>::new(Duration::from_secs(60));
+    let failed = store
+        .execute("k", || async { Err::<&str, _>("element not found") })
+        .await;
+    assert_eq!(failed, Err("element not found"));
+    assert!(store.is_empty());
+
+    let retried = store
+        .execute("k", || async { Ok::<_, &str>("clicked") })
+        .await;
+    assert_eq!(retried, Ok(Outcome::Executed("clicked")));
+}
+
+#[tokio::test]
+async fn test_unsuccessful_result_is_not_remembered() {
+    let store = IdempotencyStore::new(Duration::from_secs(60));
+    let is_success = |result: &&str| *result == "clicked";
+
+    let failed = store
+        .execute_if(
+            "k",
+            || async { Ok::<_, ()>("error: not clickable") },
+            is_success,
+        )
+        .await;
+    assert_eq!(failed, Ok(Outcome::Executed("error: not clickable")));
+    assert!(store.is_empty());
+
+    let retried = store
+        .execute_if("k", || async { Ok::<_, ()>("clicked") }, is_success)
+        .await;
+    assert_eq!(retried, Ok(Outcome::Executed("clicked")));
+    let replayed = store
+        .execute_if("k", || async { Ok::<_, ()>("clicked again") }, is_success)
+        .await;
+    assert_eq!(replayed, Ok(Outcome::Replayed("clicked")));
+}
+
+#[tokio::test]
+async fn test_expired_key_runs_again() {
+    let store = IdempotencyStore::new(Duration::from_millis(20));
+    let run = || async { Ok::<_, ()>(1) };
+    assert_eq!(store.execute("k", run).await, Ok(Outcome::Executed(1)));
+    tokio::time::sleep(Duration::from_millis(40)).await;
+    assert_eq!(store.execute("k", run).await, Ok(Outcome::Executed(1)));
+}
+
+#[tokio::test]
+async fn test_retry_during_execution_waits_for_original() {
+    let store = Arc::new(IdempotencyStore::new(Duration::from_secs(60)));
+    let runs = Arc::new(AtomicUsize::new(0));
+
+    let slow_call = |store: Arc<IdempotencyStore<usize>>, runs: Arc<AtomicUsize>| async move {
+        store
+            .execute("k", || async {
+                let run = runs.fetch_add(1, Ordering::SeqCst) + 1;
+                tokio::time::sleep(Duration::from_millis(100)).await;
+                Ok::<_, ()>(run)
+            })
+            .await
+    };
+
+    let original = tokio::spawn(slow_call(store.clone(), runs.clone()));
+    tokio::time::sleep(Duration::from_millis(20)).await;
+    let retry = tokio::spawn(slow_call(store.clone(), runs.clone()));
+
+    assert_eq!(original.await.unwrap(), Ok(Outcome::Executed(1)));
+    assert_eq!(retry.await.unwrap(), Ok(Outcome::Replayed(1)));
+    assert_eq!(runs.load(Ordering::SeqCst), 1);
+}
@@ -0,0 +1,53 @@
+use rmcp::model::{CallToolResult, Content};
+use serde_json::json;
+use terminator_mcp_agent::response_limit::{enforce, response_size};
+
+fn tree_result(nodes: usize) -> CallToolResult {
+    let tree: Vec<_> = (0..nodes)
+        .map(|i| json!({"role": "Button", "name": format!("Button {i}")}))
+        .collect();
+    CallToolResult::success(vec![Content::json(json!({
+        "action": "get_window_tree",
+        "status": "success",
+        "ui_tree": tree,
+    }))
+    .unwrap()])
+}
+
+fn notice(result: &CallToolResult) -> serde_json::Value {
+    let text = &result.content[0].as_text().unwrap().text;
+    serde_json::from_str(text).unwrap()
+}
+
+#[test]
+fn test_result_within_limit_is_unchanged() {
+    let result = tree_result(3);
+    let size = response_size(&result);
+
+    let enforced = enforce("get_window_tree", result.clone(), size);
+    assert_eq!(enforced, result);
+}
+
+#[test]
+fn test_oversized_result_becomes_a_parseable_summary() {
+    let result = tree_result(1000);
+    let size = response_size(&result);
+
+    let enforced = enforce("get_window_tree", result, 1024);
+    let notice = notice(&enforced);
+
+    assert!(response_size(&enforced) < size);
+    assert_eq!(notice["status"], "result_too_large");
+    assert_eq!(notice["size_bytes"], size);
+    assert_eq!(notice["max_bytes"], 1024);
+    assert_eq!(notice["summary"][0]["type"], "text");
+    assert_eq!(
+        notice["summary"][0]["keys"],
+        json!(["action", "status", "ui_tree"])
+    );
+    assert!(notice["guidance"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .any(|tip| tip.as_str().unwrap().contains("tree_max_depth")));
+}
@@ -0,0 +1,92 @@
+use serde_json::json;
+use std::time::Duration;
+use terminator::AutomationError;
+use terminator_mcp_agent::utils::{ActionOptions, RetryPolicy, RetryableError};
+
+fn not_found() -> AutomationError {
+    AutomationError::ElementNotFound("button".to_string())
+}
+
+#[test]
+fn test_default_policy_never_retries() {
+    let policy = RetryPolicy::default();
+    assert!(!policy.should_retry(0, &not_found()));
+}
+
+#[test]
+fn test_retries_limit_attempts() {
+    let policy = RetryPolicy::with_retries(Some(2));
+    assert!(policy.should_retry(0, &not_found()));
+    assert!(policy.should_retry(1, &not_found()));
+    assert!(!policy.should_retry(2, &not_found()));
+}
+
+#[test]
+fn test_retry_on_filters_error_kinds() {
+    let policy = RetryPolicy {
+        retries: 3,
+        retry_on: Some(vec![
+            RetryableError::ElementNotFound,
+            RetryableError::PlatformError,
+        ]),
+        ..Default::default()
+    };
+    assert!(policy.should_retry(0, &not_found()));
+    assert!(policy.should_retry(
+        0,
+        &AutomationError::UIAutomationAPIError {
+            message: "COM failure".to_string(),
+            com_error: None,
+            operation: "click".to_string(),
+            is_retryable: true,
+        }
+    ));
+    assert!(!policy.should_retry(
+        0,
+        &AutomationError::ElementNotEnabled("disabled".to_string())
+    ));
+}
+
+#[test]
+fn test_delay_defaults_and_backoff() {
+    let constant = RetryPolicy::with_retries(Some(3));
+    assert_eq!(constant.delay(0, 250), Duration::from_millis(250));
+    assert_eq!(constant.delay(2, 250), Duration::from_millis(250));
+    assert_eq!(constant.delay(1, 0), Duration::ZERO);
+
+    let backoff = RetryPolicy {
+        retries: 5,
+        retry_delay_ms: Some(100),
+        backoff_multiplier: Some(2.0),
+        retry_on: None,
+    };
+    assert_eq!(backoff.delay(0, 250), Duration::from_millis(100));
+    assert_eq!(backoff.delay(3, 250), Duration::from_millis(800));
+    // Capped so a runaway multiplier can't stall a workflow
+    assert_eq!(backoff.delay(30, 250), Duration::from_secs(60));
+}
+
+#[test]
+fn test_action_options_build_retry_policy() {
+    let options: ActionOptions = serde_json::from_value(json!({
+        "retries": 2,
+        "retry_delay_ms": 500,
+        "retry_backoff": 1.5,
+        "retry_on": ["element_not_found", "timeout"],
+        "verify_element_exists": "",
+        "verify_element_not_exists": ""
+    }))
+    .unwrap();
+
+    let policy = options.retry_policy();
+    assert_eq!(policy.retries, 2);
+    assert_eq!(policy.retry_delay_ms, Some(500));
+    assert_eq!(policy.backoff_multiplier, Some(1.5));
+    assert_eq!(
+        policy.retry_on,
+        Some(vec![
+            RetryableError::ElementNotFound,
+            RetryableError::Timeout
+        ])
+    );
+}
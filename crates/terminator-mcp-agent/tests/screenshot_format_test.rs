@@ -0,0 +1,63 @@
+use terminator::ScreenshotResult;
+use terminator_mcp_agent::mcp_types::ImageFormat;
+
+fn solid_screenshot(width: u32, height: u32) -> ScreenshotResult {
+    ScreenshotResult {
+        image_data: [40u8, 80, 160, 255].repeat((width * height) as usize),
+        width,
+        height,
+        monitor: None,
+    }
+}
+
+#[test]
+fn test_image_format_defaults_to_jpeg() {
+    assert_eq!(ImageFormat::default(), ImageFormat::Jpeg);
+    let parsed: ImageFormat = serde_json::from_str("\"webp\"").unwrap();
+    assert_eq!(parsed, ImageFormat::Webp);
+}
+
+#[test]
+fn test_image_format_encodes_with_matching_signature() {
+    let screenshot = solid_screenshot(32, 16);
+
+    let png = ImageFormat::Png.encode(&screenshot, None, None).unwrap();
+    assert_eq!(&png[..4], b"\x89PNG");
+
+    let jpeg = ImageFormat::Jpeg
+        .encode(&screenshot, None, Some(50))
+        .unwrap();
+    assert_eq!(&jpeg[..2], [0xFF, 0xD8]);
+
+    let webp = ImageFormat::Webp.encode(&screenshot, None, None).unwrap();
+    assert_eq!(&webp[..4], b"RIFF");
+    assert_eq!(&webp[8..12], b"WEBP");
+}
+
+#[test]
+fn test_image_format_resizes_to_max_dimension() {
+    let screenshot = solid_screenshot(64, 32);
+    let png = ImageFormat::Png
+        .encode(&screenshot, Some(16), None)
+        .unwrap();
+    let decoded = image::load_from_memory(&png).unwrap();
+    assert_eq!((decoded.width(), decoded.height()), (16, 8));
+}
+
+#[test]
+fn test_jpeg_quality_changes_size() {
+    // A noisy image so quality actually affects the output size.
+    let mut screenshot = solid_screenshot(64, 64);
+    for (i, byte) in screenshot.image_data.iter_mut().enumerate() {
+        if i % 4 != 3 {
+            *byte = (i.wrapping_mul(2654435761) >> 7) as u8;
+        }
+    }
+    let low = ImageFormat::Jpeg
+        .encode(&screenshot, None, Some(10))
+        .unwrap();
+    let high = ImageFormat::Jpeg
+        .encode(&screenshot, None, Some(95))
+        .unwrap();
+    assert!(low.len() < high.len());
+}
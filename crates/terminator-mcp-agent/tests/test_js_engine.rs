@@ -8,10 +8,16 @@ async fn test_javascript_engine_basic() {
 
     // Test basic JavaScript execution with the new 'run' parameter
     let script = "return {success: true, value: 42};".to_string();
-    let result =
-        scripting_engine::execute_javascript_with_nodejs(script, None, None, None, None, None)
-            .await
-            .expect("JavaScript execution should succeed");
+    let result = scripting_engine::execute_javascript_with_nodejs(
+        script,
+        None,
+        scripting_engine::ScriptExecutionOptions::default(),
+        None,
+        None,
+        None,
+    )
+    .await
+    .expect("JavaScript execution should succeed");
 
     assert_eq!(result["result"]["success"], true);
     assert_eq!(result["result"]["value"], 42);
@@ -30,10 +36,16 @@ async fn test_javascript_engine_with_async() {
     "#
     .to_string();
 
-    let result =
-        scripting_engine::execute_javascript_with_nodejs(script, None, None, None, None, None)
-            .await
-            .expect("Async JavaScript execution should succeed");
+    let result = scripting_engine::execute_javascript_with_nodejs(
+        script,
+        None,
+        scripting_engine::ScriptExecutionOptions::default(),
+        None,
+        None,
+        None,
+    )
+    .await
+    .expect("Async JavaScript execution should succeed");
 
     assert_eq!(result["result"]["delayed"], true);
     assert!(result["result"]["timestamp"].is_number());
@@ -55,10 +67,16 @@ async fn test_javascript_engine_with_desktop_api() {
     "#
     .to_string();
 
-    let result =
-        scripting_engine::execute_javascript_with_nodejs(script, None, None, None, None, None)
-            .await
-            .expect("Desktop API check should succeed");
+    let result = scripting_engine::execute_javascript_with_nodejs(
+        script,
+        None,
+        scripting_engine::ScriptExecutionOptions::default(),
+        None,
+        None,
+        None,
+    )
+    .await
+    .expect("Desktop API check should succeed");
 
     assert_eq!(result["result"]["hasDesktop"], true);
     assert_eq!(result["result"]["hasLocator"], true);
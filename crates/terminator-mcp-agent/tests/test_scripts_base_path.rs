@@ -118,6 +118,8 @@ mod scripts_base_path_tests {
             skip_preflight_check: Some(false),
             trace_id: Some("test-trace-123".to_string()),
             execution_id: Some("test-execution-456".to_string()),
+            capture_trees: None,
+            capture_tree_max_depth: None,
             window_mgmt: Default::default(),
         };
 
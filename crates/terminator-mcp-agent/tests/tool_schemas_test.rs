@@ -0,0 +1,106 @@
+use terminator_mcp_agent::mcp_types::tool_schemas;
+use terminator_mcp_agent::tool_catalog;
+use terminator_mcp_agent::utils::{DescribeElementArgs, GetFocusContextArgs};
+
+#[test]
+fn test_tool_schemas_cover_tool_args() {
+    let schemas = tool_schemas();
+    let schemas = schemas
+        .as_object()
+        .expect("schemas should be keyed by tool");
+
+    for tool in ["click_element", "type_into_element", "run_command"] {
+        let schema = &schemas[tool];
+        assert_eq!(schema["type"], "object", "{tool} args should be an object");
+        assert!(
+            schema["properties"].is_object(),
+            "{tool} schema should list properties"
+        );
+    }
+
+    let run_command = &schemas["run_command"]["properties"];
+    assert!(run_command.get("engine").is_some());
+    assert!(run_command.get("timeout_ms").is_some());
+    assert!(run_command.get("capture_combined").is_some());
+
+    let highlight = &schemas["highlight_element"]["properties"];
+    assert!(highlight.get("scroll_into_view").is_some());
+}
+
+#[test]
+fn test_tool_schemas_are_draft_2020_12() {
+    let schemas = tool_schemas();
+    let schema = &schemas["click_element"];
+    assert_eq!(
+        schema["$schema"],
+        "https://json-schema.org/draft/2020-12/schema"
+    );
+}
+
+#[test]
+fn test_tool_schemas_match_the_catalog() {
+    let schemas = tool_schemas();
+    let catalog = tool_catalog();
+
+    assert_eq!(schemas.as_object().unwrap().len(), catalog.len());
+    for tool in &catalog {
+        assert_eq!(schemas[&tool.name], tool.input_schema, "{}", tool.name);
+    }
+}
+
+#[test]
+fn test_tool_catalog_matches_router() {
+    let catalog = tool_catalog();
+    assert!(!catalog.is_empty());
+
+    let names: Vec<&str> = catalog.iter().map(|t| t.name.as_str()).collect();
+    let mut sorted = names.clone();
+    sorted.sort();
+    assert_eq!(names, sorted, "catalog should be sorted by name");
+
+    let click = catalog
+        .iter()
+        .find(|t| t.name == "click_element")
+        .expect("click_element should be in the catalog");
+    assert!(click.description.as_deref().is_some_and(|d| !d.is_empty()));
+    assert_eq!(click.input_schema["type"], "object");
+    assert!(click.input_schema["properties"].is_object());
+}
+
+#[test]
+fn test_describe_element_focuses_by_default() {
+    let args: DescribeElementArgs = serde_json::from_value(serde_json::json!({
+        "process": "notepad",
+        "selector": "role:Button|Save"
+    }))
+    .unwrap();
+    assert!(args.focus);
+
+    let schemas = tool_schemas();
+    assert!(schemas["describe_element"]["properties"]["focus"].is_object());
+    assert!(tool_catalog().iter().any(|t| t.name == "describe_element"));
+}
+
+#[test]
+fn test_get_focus_context_defaults_to_three_siblings() {
+    let args: GetFocusContextArgs = serde_json::from_value(serde_json::json!({})).unwrap();
+    assert_eq!(args.sibling_count, 3);
+    assert!(!args.include_selector);
+
+    let schemas = tool_schemas();
+    assert!(schemas["get_focus_context"]["properties"]["sibling_count"].is_object());
+    assert!(schemas["get_focus_context"]["properties"]["include_selector"].is_object());
+    assert!(tool_catalog().iter().any(|t| t.name == "get_focus_context"));
+}
+
+#[test]
+fn test_capture_element_screenshot_is_a_tool() {
+    let schemas = tool_schemas();
+    let properties = &schemas["capture_element_screenshot"]["properties"];
+    assert!(properties["selector"].is_object());
+    assert!(properties["format"].is_object());
+    assert!(properties["entire_monitor"].is_null());
+    assert!(tool_catalog()
+        .iter()
+        .any(|t| t.name == "capture_element_screenshot"));
+}
@@ -0,0 +1,49 @@
+use rmcp::model::{CallToolResult, Content};
+use serde_json::json;
+use std::time::Duration;
+use terminator_mcp_agent::tool_timing::{attach, collect_phases, record_phase, PhaseTimer};
+
+#[tokio::test]
+async fn phases_recorded_during_a_call_are_collected() {
+    let ((), phases) = collect_phases(async {
+        record_phase("locate", Duration::from_millis(30));
+        record_phase("locate", Duration::from_millis(12));
+        let _act = PhaseTimer::start("act");
+    })
+    .await;
+
+    assert_eq!(phases["locate"], Duration::from_millis(42));
+    assert!(phases.contains_key("act"));
+
+    // Outside a call there is nothing to record into
+    record_phase("locate", Duration::from_millis(1));
+}
+
+#[tokio::test]
+async fn timing_is_added_to_result_meta() {
+    let (mut result, phases) = collect_phases(async {
+        record_phase("locate", Duration::from_millis(80));
+        CallToolResult::success(vec![Content::text("ok")])
+    })
+    .await;
+
+    attach(&mut result, 120, &phases);
+
+    let meta = serde_json::to_value(&result).unwrap()["_meta"].clone();
+    assert_eq!(
+        meta,
+        json!({"duration_ms": 120, "phases": {"locate_ms": 80}})
+    );
+}
+
+#[test]
+fn phases_are_omitted_when_none_were_recorded() {
+    let mut result = CallToolResult::success(vec![Content::text("ok")]);
+
+    attach(&mut result, 5, &Default::default());
+
+    assert_eq!(
+        result.meta.unwrap().0,
+        json!({"duration_ms": 5}).as_object().unwrap().clone()
+    );
+}
@@ -35,6 +35,8 @@ fn test_execute_sequence_args_serialization() {
         skip_preflight_check: None,
         trace_id: None,
         execution_id: None,
+        capture_trees: None,
+        capture_tree_max_depth: None,
         window_mgmt: Default::default(),
     };
 
@@ -793,7 +795,9 @@ try {
 #[ignore] // wont work in ci
 async fn test_complete_nodejs_terminator_execution() {
     // Test the complete flow: install terminator.js in isolated dir and run script
-    use terminator_mcp_agent::scripting_engine::execute_javascript_with_nodejs;
+    use terminator_mcp_agent::scripting_engine::{
+        execute_javascript_with_nodejs, ScriptExecutionOptions,
+    };
 
     let test_script = r#"
 // Test that terminator.js loads correctly
@@ -825,8 +829,15 @@ try {
 
     println!("🧪 Testing complete Node.js terminator.js execution...");
 
-    let result =
-        execute_javascript_with_nodejs(test_script.to_string(), None, None, None, None, None).await;
+    let result = execute_javascript_with_nodejs(
+        test_script.to_string(),
+        None,
+        ScriptExecutionOptions::default(),
+        None,
+        None,
+        None,
+    )
+    .await;
 
     match result {
         Ok(value) => {
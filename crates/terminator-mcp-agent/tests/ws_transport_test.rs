@@ -0,0 +1,246 @@
+use futures::{SinkExt, StreamExt};
+use std::collections::HashSet;
+use std::time::Duration;
+use terminator_mcp_agent::mcp_types::ReadOnlyTools;
+use terminator_mcp_agent::request_gate::RequestGate;
+use terminator_mcp_agent::server::DesktopWrapper;
+use terminator_mcp_agent::ws_transport;
+use tokio_tungstenite::tungstenite::protocol::Role;
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::WebSocketStream;
+
+fn gate(max: usize, queue_timeout: Option<Duration>) -> RequestGate {
+    let read_only = ReadOnlyTools::new(["get_window_tree".to_string()].into(), HashSet::new());
+    RequestGate::new(max, 1, queue_timeout, read_only)
+}
+
+#[tokio::test]
+async fn test_gate_rejects_when_full_without_queue() {
+    let gate = gate(1, None);
+    let held = gate.acquire(false).await.expect("first slot");
+    assert!(gate.is_busy());
+    assert!(gate.acquire(false).await.is_none());
+
+    // Read-only calls have their own pool
+    assert!(gate.acquire(true).await.is_some());
+
+    drop(held);
+    assert!(!gate.is_busy());
+    assert!(gate.acquire(false).await.is_some());
+}
+
+#[tokio::test]
+async fn test_gate_queues_until_slot_frees() {
+    let gate = gate(1, Some(Duration::from_secs(5)));
+    let held = gate.acquire(false).await.expect("first slot");
+
+    let waiter = {
+        let gate = gate.clone();
+        tokio::spawn(async move { gate.acquire(false).await.is_some() })
+    };
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    assert_eq!(gate.queued_requests(), 1);
+
+    drop(held);
+    assert!(waiter.await.unwrap());
+    assert_eq!(gate.queued_requests(), 0);
+}
+
+#[tokio::test]
+async fn test_gate_queue_times_out() {
+    let gate = gate(1, Some(Duration::from_millis(50)));
+    let _held = gate.acquire(false).await.expect("first slot");
+    assert!(gate.acquire(false).await.is_none());
+    assert_eq!(gate.queued_requests(), 0);
+}
+
+#[tokio::test]
+async fn test_gate_touch_moves_last_activity() {
+    let gate = gate(1, None);
+    let before = gate.last_activity();
+    tokio::time::sleep(Duration::from_millis(10)).await;
+    gate.touch();
+    assert!(gate.last_activity() > before);
+}
+
+#[tokio::test]
+async fn test_cancelled_waiter_is_no_longer_queued() {
+    let gate = gate(1, Some(Duration::from_secs(10)));
+    let _held = gate.acquire(false).await.expect("free slot");
+
+    // The waiter is dropped while it still waits for the slot
+    let waited = tokio::time::timeout(Duration::from_millis(20), gate.acquire(false)).await;
+    assert!(waited.is_err());
+    assert_eq!(gate.queued_requests(), 0);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_ws_session_lists_tools() {
+    let (client_io, server_io) = tokio::io::duplex(1 << 20);
+    let desktop = DesktopWrapper::new().expect("desktop wrapper");
+    let server_ws = WebSocketStream::from_raw_socket(server_io, Role::Server, None).await;
+    tokio::spawn(ws_transport::serve(server_ws, desktop, gate(1, None)));
+
+    let mut client = WebSocketStream::from_raw_socket(client_io, Role::Client, None).await;
+    let send = |value: serde_json::Value| Message::Text(value.to_string());
+
+    client
+        .send(send(serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "initialize",
+            "params": {
+                "protocolVersion": "2025-03-26",
+                "capabilities": {},
+                "clientInfo": { "name": "ws-test", "version": "0.0.0" }
+            }
+        })))
+        .await
+        .unwrap();
+    let init = next_json(&mut client).await;
+    assert_eq!(init["id"], 1);
+    assert!(init["result"]["serverInfo"].is_object());
+
+    client
+        .send(send(serde_json::json!({
+            "jsonrpc": "2.0",
+            "method": "notifications/initialized"
+        })))
+        .await
+        .unwrap();
+    client
+        .send(send(serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 2,
+            "method": "tools/list"
+        })))
+        .await
+        .unwrap();
+    let tools = next_json(&mut client).await;
+    assert_eq!(tools["id"], 2);
+    assert!(tools["result"]["tools"]
+        .as_array()
+        .is_some_and(|tools| tools.iter().any(|t| t["name"] == "get_window_tree")));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_ws_tool_call_counts_as_activity() {
+    let (client_io, server_io) = tokio::io::duplex(1 << 20);
+    let desktop = DesktopWrapper::new().expect("desktop wrapper");
+    let gate = gate(1, None);
+    let server_ws = WebSocketStream::from_raw_socket(server_io, Role::Server, None).await;
+    tokio::spawn(ws_transport::serve(server_ws, desktop, gate.clone()));
+
+    let mut client = WebSocketStream::from_raw_socket(client_io, Role::Client, None).await;
+    let send = |value: serde_json::Value| Message::Text(value.to_string());
+    client
+        .send(send(serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "initialize",
+            "params": {
+                "protocolVersion": "2025-03-26",
+                "capabilities": {},
+                "clientInfo": { "name": "ws-test", "version": "0.0.0" }
+            }
+        })))
+        .await
+        .unwrap();
+    next_json(&mut client).await;
+    client
+        .send(send(serde_json::json!({
+            "jsonrpc": "2.0",
+            "method": "notifications/initialized"
+        })))
+        .await
+        .unwrap();
+
+    let before = gate.last_activity();
+    tokio::time::sleep(Duration::from_millis(10)).await;
+    client
+        .send(send(serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 2,
+            "method": "tools/call",
+            "params": { "name": "delay", "arguments": { "delay_ms": 1 } }
+        })))
+        .await
+        .unwrap();
+    let answer = next_json(&mut client).await;
+    assert_eq!(answer["id"], 2);
+    assert!(gate.last_activity() > before);
+    assert_eq!(gate.active_requests(), 0);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_ws_queued_call_does_not_block_later_frames() {
+    let (client_io, server_io) = tokio::io::duplex(1 << 20);
+    let desktop = DesktopWrapper::new().expect("desktop wrapper");
+    let gate = gate(1, Some(Duration::from_secs(10)));
+    let held = gate.acquire(false).await.expect("free slot");
+    let server_ws = WebSocketStream::from_raw_socket(server_io, Role::Server, None).await;
+    tokio::spawn(ws_transport::serve(server_ws, desktop, gate.clone()));
+
+    let mut client = WebSocketStream::from_raw_socket(client_io, Role::Client, None).await;
+    let send = |value: serde_json::Value| Message::Text(value.to_string());
+    client
+        .send(send(serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "initialize",
+            "params": {
+                "protocolVersion": "2025-03-26",
+                "capabilities": {},
+                "clientInfo": { "name": "ws-test", "version": "0.0.0" }
+            }
+        })))
+        .await
+        .unwrap();
+    next_json(&mut client).await;
+    client
+        .send(send(serde_json::json!({
+            "jsonrpc": "2.0",
+            "method": "notifications/initialized"
+        })))
+        .await
+        .unwrap();
+
+    // The input pool is full, so this call queues
+    client
+        .send(send(serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 2,
+            "method": "tools/call",
+            "params": { "name": "delay", "arguments": { "delay_ms": 1 } }
+        })))
+        .await
+        .unwrap();
+    client
+        .send(send(serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 3,
+            "method": "tools/list"
+        })))
+        .await
+        .unwrap();
+    assert_eq!(next_json(&mut client).await["id"], 3);
+
+    drop(held);
+    assert_eq!(next_json(&mut client).await["id"], 2);
+}
+
+async fn next_json<S>(client: &mut WebSocketStream<S>) -> serde_json::Value
+where
+    S: tokio::io::AsyncRead + tokio::io::AsyncWrite + Unpin,
+{
+    loop {
+        let frame = tokio::time::timeout(Duration::from_secs(10), client.next())
+            .await
+            .expect("timed out waiting for a message")
+            .expect("connection closed")
+            .expect("websocket error");
+        if let Message::Text(text) = frame {
+            return serde_json::from_str(&text).unwrap();
+        }
+    }
+}
@@ -234,6 +234,24 @@ function connect() {
       } catch (err) {
         safeSend({ id, ok: false, error: String(err && (err.message || err)) });
       }
+    } else if (msg.action === "list_tabs") {
+      // List all open tabs across windows
+      const { id } = msg;
+      try {
+        const result = await listTabs();
+        safeSend({ id, ok: true, result });
+      } catch (err) {
+        safeSend({ id, ok: false, error: String(err && (err.message || err)) });
+      }
+    } else if (msg.action === "activate_tab") {
+      // Switch to a tab by list index or URL
+      const { id, index, url } = msg;
+      try {
+        const result = await activateTab(index, url);
+        safeSend({ id, ok: true, result });
+      } catch (err) {
+        safeSend({ id, ok: false, error: String(err && (err.message || err)) });
+      }
     } else if (msg.action === "close_tab") {
       // Close a specific browser tab safely
       const { id, tabId: requestedTabId, url: targetUrl, title: targetTitle } = msg;
@@ -386,6 +404,59 @@ function scheduleReconnect() {
   }, delay);
 }
 
+/**
+ * List all open tabs across windows in a stable order.
+ * `active` is only true for the active tab of the last focused window.
+ */
+async function listTabs() {
+  const tabs = await chrome.tabs.query({});
+  let focusedWindowId = null;
+  try {
+    const focused = await chrome.windows.getLastFocused();
+    focusedWindowId = focused ? focused.id : null;
+  } catch (_) {}
+
+  return tabs.map((t, i) => ({
+    id: t.id,
+    index: i,
+    title: t.title,
+    url: t.url,
+    active: !!t.active && t.windowId === focusedWindowId,
+    windowId: t.windowId,
+  }));
+}
+
+/**
+ * Activate a tab by its index in listTabs() or by URL (exact or substring match),
+ * and bring its window to the foreground.
+ */
+async function activateTab(index, targetUrl) {
+  log(`activateTab called: index=${index}, url=${targetUrl}`);
+  const tabs = await listTabs();
+
+  let target = null;
+  if (index != null && typeof index === "number") {
+    target = tabs[index] || null;
+    if (!target) throw new Error(`No tab at index ${index} (${tabs.length} tabs open)`);
+  } else if (targetUrl) {
+    target =
+      tabs.find(t => t.url === targetUrl) ||
+      tabs.find(t => t.url?.includes(targetUrl)) ||
+      null;
+    if (!target) throw new Error(`No tab matching URL: ${targetUrl}`);
+  } else {
+    throw new Error("activate_tab requires an index or url");
+  }
+
+  await chrome.tabs.update(target.id, { active: true });
+  if (target.windowId != null) {
+    await chrome.windows.update(target.windowId, { focused: true });
+  }
+  log(`Activated tab: id=${target.id}, url=${target.url}`);
+
+  return { ...target, active: true };
+}
+
 async function forceResetDebuggerState() {
   log("Force resetting all debugger state...");
 
@@ -0,0 +1,304 @@
+//! Browser automation over the Chrome DevTools Protocol.
+//!
+//! An alternative to the extension bridge for headless or CI setups: start
+//! Chrome with `--remote-debugging-port=9222`, then attach with
+//! [`Desktop::attach_chrome_cdp`](crate::Desktop::attach_chrome_cdp) using the
+//! `webSocketDebuggerUrl` of a page (from `/json/list`) or of the browser (from
+//! `/json/version`). Browser endpoints are attached to their first page.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures_util::stream::SplitSink;
+use futures_util::{SinkExt, StreamExt};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tokio::net::TcpStream;
+use tokio::sync::{oneshot, Mutex};
+use tokio::task::JoinHandle;
+use tokio_tungstenite::{connect_async, tungstenite::Message, MaybeTlsStream, WebSocketStream};
+use tracing::{debug, warn};
+
+use crate::AutomationError;
+
+/// How long to wait for a single CDP command before giving up
+const CDP_COMMAND_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// How long `navigate` waits for the new page to finish loading
+const NAVIGATION_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Upper bound on elements returned by `query_dom`
+const MAX_QUERY_RESULTS: usize = 1000;
+
+type WsSink = SplitSink<WebSocketStream<MaybeTlsStream<TcpStream>>, Message>;
+type PendingMap = HashMap<u64, oneshot::Sender<Result<Value, String>>>;
+
+/// An element returned by [`BrowserSession::query_dom`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DomElement {
+    /// Lowercase tag name (e.g. "button")
+    pub tag: String,
+    /// Visible text, trimmed and truncated to 500 characters
+    pub text: String,
+    /// All HTML attributes of the element
+    pub attributes: HashMap<String, String>,
+    /// Viewport-relative bounds as (x, y, width, height)
+    pub bounds: (f64, f64, f64, f64),
+}
+
+/// A connection to a Chrome page over the DevTools Protocol.
+///
+/// Dropping the session closes the connection.
+pub struct BrowserSession {
+    sink: Mutex<WsSink>,
+    pending: Arc<Mutex<PendingMap>>,
+    next_id: AtomicU64,
+    /// Set when attached through a browser endpoint (flattened target session)
+    session_id: Option<String>,
+    reader: JoinHandle<()>,
+}
+
+impl BrowserSession {
+    /// Connect to a CDP websocket endpoint (`ws://...`).
+    pub async fn connect(ws_url: &str) -> Result<Self, AutomationError> {
+        if !ws_url.starts_with("ws://") && !ws_url.starts_with("wss://") {
+            return Err(AutomationError::InvalidArgument(format!(
+                "Expected a DevTools websocket URL (ws://...), got '{ws_url}'"
+            )));
+        }
+
+        let (stream, _) = connect_async(ws_url).await.map_err(|e| {
+            AutomationError::PlatformError(format!("Failed to connect to CDP at {ws_url}: {e}"))
+        })?;
+        let (sink, mut source) = stream.split();
+
+        let pending: Arc<Mutex<PendingMap>> = Arc::new(Mutex::new(HashMap::new()));
+        let reader_pending = pending.clone();
+        let reader = tokio::spawn(async move {
+            while let Some(Ok(message)) = source.next().await {
+                let Message::Text(text) = message else {
+                    continue;
+                };
+                let Ok(value) = serde_json::from_str::<Value>(&text) else {
+                    continue;
+                };
+                // Responses carry the command id; events (no id) are ignored
+                let Some(id) = value.get("id").and_then(Value::as_u64) else {
+                    continue;
+                };
+                if let Some(tx) = reader_pending.lock().await.remove(&id) {
+                    let _ = tx.send(parse_response(value));
+                }
+            }
+            // Connection closed: fail anything still waiting
+            reader_pending.lock().await.clear();
+            debug!("CDP connection closed");
+        });
+
+        let mut session = Self {
+            sink: Mutex::new(sink),
+            pending,
+            next_id: AtomicU64::new(1),
+            session_id: None,
+            reader,
+        };
+
+        if ws_url.contains("/devtools/browser/") {
+            session.session_id = Some(session.attach_to_first_page().await?);
+        }
+
+        Ok(session)
+    }
+
+    /// Send a raw CDP command and return its `result` object.
+    pub async fn send(&self, method: &str, params: Value) -> Result<Value, AutomationError> {
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst);
+        let mut command = json!({ "id": id, "method": method, "params": params });
+        if let Some(session_id) = &self.session_id {
+            command["sessionId"] = json!(session_id);
+        }
+
+        let (tx, rx) = oneshot::channel();
+        self.pending.lock().await.insert(id, tx);
+
+        let sent = self
+            .sink
+            .lock()
+            .await
+            .send(Message::Text(command.to_string()))
+            .await;
+        if let Err(e) = sent {
+            self.pending.lock().await.remove(&id);
+            return Err(AutomationError::PlatformError(format!(
+                "Failed to send CDP command {method}: {e}"
+            )));
+        }
+
+        match tokio::time::timeout(CDP_COMMAND_TIMEOUT, rx).await {
+            Ok(Ok(Ok(result))) => Ok(result),
+            Ok(Ok(Err(message))) => Err(AutomationError::PlatformError(format!(
+                "CDP command {method} failed: {message}"
+            ))),
+            Ok(Err(_)) => Err(AutomationError::PlatformError(format!(
+                "CDP connection closed while waiting for {method}"
+            ))),
+            Err(_) => {
+                self.pending.lock().await.remove(&id);
+                Err(AutomationError::Timeout(format!(
+                    "CDP command {method} timed out after {CDP_COMMAND_TIMEOUT:?}"
+                )))
+            }
+        }
+    }
+
+    /// Evaluate JavaScript in the page and return the result.
+    ///
+    /// Promises are awaited. String results are returned as-is; anything else
+    /// is serialized to JSON, matching the extension bridge.
+    pub async fn execute_browser_script(&self, script: &str) -> Result<String, AutomationError> {
+        let value = self.evaluate(script).await?;
+        Ok(match value {
+            Value::String(s) => s,
+            Value::Null => String::new(),
+            other => other.to_string(),
+        })
+    }
+
+    /// Find elements matching a CSS selector.
+    pub async fn query_dom(&self, css_selector: &str) -> Result<Vec<DomElement>, AutomationError> {
+        let selector = serde_json::to_string(css_selector)
+            .map_err(|e| AutomationError::InvalidArgument(e.to_string()))?;
+        let script = format!(
+            r#"Array.from(document.querySelectorAll({selector})).slice(0, {MAX_QUERY_RESULTS}).map(el => {{
+    const r = el.getBoundingClientRect();
+    const attributes = {{}};
+    for (const a of el.attributes) attributes[a.name] = a.value;
+    return {{
+        tag: el.tagName.toLowerCase(),
+        text: (el.innerText ?? el.textContent ?? '').trim().slice(0, 500),
+        attributes,
+        bounds: [r.x, r.y, r.width, r.height],
+    }};
+}})"#
+        );
+
+        let value = self.evaluate(&script).await?;
+        serde_json::from_value(value).map_err(|e| {
+            AutomationError::PlatformError(format!("Unexpected query_dom result: {e}"))
+        })
+    }
+
+    /// Navigate the page to `url` and wait for it to finish loading.
+    pub async fn navigate(&self, url: &str) -> Result<(), AutomationError> {
+        let result = self.send("Page.navigate", json!({ "url": url })).await?;
+        if let Some(error) = result.get("errorText").and_then(Value::as_str) {
+            return Err(AutomationError::PlatformError(format!(
+                "Navigation to {url} failed: {error}"
+            )));
+        }
+
+        let start = std::time::Instant::now();
+        loop {
+            if self.evaluate("document.readyState").await.ok() == Some(json!("complete")) {
+                return Ok(());
+            }
+            if start.elapsed() > NAVIGATION_TIMEOUT {
+                return Err(AutomationError::Timeout(format!(
+                    "Page did not finish loading {url} within {NAVIGATION_TIMEOUT:?}"
+                )));
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+    }
+
+    /// URL of the current page
+    pub async fn current_url(&self) -> Result<String, AutomationError> {
+        self.execute_browser_script("location.href").await
+    }
+
+    async fn evaluate(&self, expression: &str) -> Result<Value, AutomationError> {
+        let result = self
+            .send(
+                "Runtime.evaluate",
+                json!({
+                    "expression": expression,
+                    "returnByValue": true,
+                    "awaitPromise": true,
+                }),
+            )
+            .await?;
+
+        if let Some(details) = result.get("exceptionDetails") {
+            let message = details
+                .pointer("/exception/description")
+                .or_else(|| details.get("text"))
+                .and_then(Value::as_str)
+                .unwrap_or("unknown error");
+            return Err(AutomationError::PlatformError(format!(
+                "JavaScript error: {message}"
+            )));
+        }
+
+        Ok(result
+            .pointer("/result/value")
+            .cloned()
+            .unwrap_or(Value::Null))
+    }
+
+    /// Attach to the first page target of a browser-level connection and
+    /// return the flattened session id to route commands through.
+    async fn attach_to_first_page(&self) -> Result<String, AutomationError> {
+        let targets = self.send("Target.getTargets", json!({})).await?;
+        let target_id = targets
+            .get("targetInfos")
+            .and_then(Value::as_array)
+            .and_then(|infos| {
+                infos
+                    .iter()
+                    .find(|info| info.get("type").and_then(Value::as_str) == Some("page"))
+            })
+            .and_then(|info| info.get("targetId").and_then(Value::as_str))
+            .ok_or_else(|| {
+                AutomationError::ElementNotFound("No page target to attach to".to_string())
+            })?
+            .to_string();
+
+        let attached = self
+            .send(
+                "Target.attachToTarget",
+                json!({ "targetId": target_id, "flatten": true }),
+            )
+            .await?;
+        attached
+            .get("sessionId")
+            .and_then(Value::as_str)
+            .map(str::to_string)
+            .ok_or_else(|| {
+                warn!("Target.attachToTarget returned no sessionId: {attached}");
+                AutomationError::PlatformError("Failed to attach to page target".to_string())
+            })
+    }
+}
+
+impl Drop for BrowserSession {
+    fn drop(&mut self) {
+        self.reader.abort();
+    }
+}
+
+/// Split a CDP response into its `result` or error message
+fn parse_response(mut response: Value) -> Result<Value, String> {
+    if let Some(error) = response.get("error") {
+        return Err(error
+            .get("message")
+            .and_then(Value::as_str)
+            .unwrap_or("unknown CDP error")
+            .to_string());
+    }
+    Ok(response
+        .get_mut("result")
+        .map(Value::take)
+        .unwrap_or(Value::Null))
+}
@@ -0,0 +1,84 @@
+//! Time source for polling loops and settle delays.
+//!
+//! [`Locator::wait_for`](crate::Locator::wait_for),
+//! [`Desktop::execute_with_ui_diff`](crate::Desktop::execute_with_ui_diff) and
+//! [`Desktop::activate_application_and_wait`](crate::Desktop::activate_application_and_wait)
+//! read the time and sleep through a [`Clock`] instead of calling `Instant::now` and
+//! `tokio::time::sleep` directly, so tests can swap in a [`FakeClock`] and check
+//! timeout behavior without waiting in real time.
+
+use async_trait::async_trait;
+use std::fmt::Debug;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+#[async_trait]
+pub trait Clock: Send + Sync + Debug {
+    fn now(&self) -> Instant;
+
+    async fn sleep(&self, duration: Duration);
+}
+
+/// The real clock: `Instant::now` and `tokio::time::sleep`
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SystemClock;
+
+#[async_trait]
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+
+    async fn sleep(&self, duration: Duration) {
+        tokio::time::sleep(duration).await;
+    }
+}
+
+/// A clock that only moves when slept on or advanced.
+///
+/// Sleeping advances the clock by the requested duration and returns
+/// immediately, so a loop that polls every 100ms until a 5s timeout finishes
+/// at once with [`FakeClock::elapsed`] reporting exactly how long it "waited".
+#[derive(Debug)]
+pub struct FakeClock {
+    start: Instant,
+    elapsed: Mutex<Duration>,
+}
+
+impl FakeClock {
+    pub fn new() -> Self {
+        Self {
+            start: Instant::now(),
+            elapsed: Mutex::new(Duration::ZERO),
+        }
+    }
+
+    /// Move the clock forward without sleeping
+    pub fn advance(&self, duration: Duration) {
+        *self.elapsed.lock().unwrap() += duration;
+    }
+
+    /// Total time slept or advanced since the clock was created
+    pub fn elapsed(&self) -> Duration {
+        *self.elapsed.lock().unwrap()
+    }
+}
+
+impl Default for FakeClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Clock for FakeClock {
+    fn now(&self) -> Instant {
+        self.start + self.elapsed()
+    }
+
+    async fn sleep(&self, duration: Duration) {
+        self.advance(duration);
+        // Still yield so other tasks get to run, as they would during a real sleep
+        tokio::task::yield_now().await;
+    }
+}
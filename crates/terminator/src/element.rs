@@ -120,6 +120,14 @@ pub struct SerializableUIElement {
     pub child_count: Option<usize>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub index_in_parent: Option<usize>,
+    #[serde(skip_serializing_if = "is_empty_string", default)]
+    pub shortcut: Option<String>,
+    #[serde(skip_serializing_if = "is_empty_string", default)]
+    pub labeled_by: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub described_by: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub controller_for: Vec<String>,
     /// Chained selector path from root to this element (e.g., "role:Window && name:App >> role:Button && name:Submit")
     #[serde(skip_serializing_if = "Option::is_none")]
     pub selector: Option<String>,
@@ -159,6 +167,10 @@ impl From<&UIElement> for SerializableUIElement {
             is_selected: attrs.is_selected,
             child_count: attrs.child_count,
             index_in_parent: attrs.index_in_parent,
+            shortcut: attrs.shortcut,
+            labeled_by: attrs.labeled_by,
+            described_by: attrs.described_by,
+            controller_for: attrs.controller_for,
             selector: None, // Selector is only available when built from tree context
         }
     }
@@ -191,6 +203,10 @@ impl SerializableUIElement {
             is_selected: None,
             child_count: None,
             index_in_parent: None,
+            shortcut: None,
+            labeled_by: None,
+            described_by: Vec::new(),
+            controller_for: Vec::new(),
             selector: None,
         }
     }
@@ -305,6 +321,74 @@ impl OcrElement {
     }
 }
 
+/// An interaction pattern an element can implement (a UIA control pattern on Windows).
+///
+/// Knowing the patterns up front tells a caller which actions will work, e.g.
+/// `set_toggled` needs `Toggle` and `set_value` needs `Value`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ControlPattern {
+    Invoke,
+    Toggle,
+    Value,
+    RangeValue,
+    ExpandCollapse,
+    Selection,
+    SelectionItem,
+    Grid,
+    GridItem,
+    Table,
+    TableItem,
+    Scroll,
+    ScrollItem,
+    Text,
+    Window,
+    Transform,
+    Dock,
+    MultipleView,
+    ItemContainer,
+    LegacyIAccessible,
+}
+
+impl ControlPattern {
+    pub const ALL: [ControlPattern; 20] = [
+        ControlPattern::Invoke,
+        ControlPattern::Toggle,
+        ControlPattern::Value,
+        ControlPattern::RangeValue,
+        ControlPattern::ExpandCollapse,
+        ControlPattern::Selection,
+        ControlPattern::SelectionItem,
+        ControlPattern::Grid,
+        ControlPattern::GridItem,
+        ControlPattern::Table,
+        ControlPattern::TableItem,
+        ControlPattern::Scroll,
+        ControlPattern::ScrollItem,
+        ControlPattern::Text,
+        ControlPattern::Window,
+        ControlPattern::Transform,
+        ControlPattern::Dock,
+        ControlPattern::MultipleView,
+        ControlPattern::ItemContainer,
+        ControlPattern::LegacyIAccessible,
+    ];
+}
+
+impl fmt::Display for ControlPattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+/// How [`UIElement::multi_select`] extends a list's selection
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MultiSelectMode {
+    /// Add each item to the current selection (Ctrl+click)
+    Add,
+    /// Select everything from the first item to the last (click, then Shift+click)
+    Range,
+}
+
 /// Attributes associated with a UI element
 #[derive(Clone, Serialize, Deserialize, Default)]
 pub struct UIElementAttributes {
@@ -340,6 +424,22 @@ pub struct UIElementAttributes {
     pub child_count: Option<usize>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub index_in_parent: Option<usize>,
+    /// Keyboard shortcut that activates the element (e.g. "Ctrl+S" or "Alt+F").
+    /// Only populated when the tree is built with `include_shortcuts`.
+    #[serde(default, skip_serializing_if = "is_empty_string")]
+    pub shortcut: Option<String>,
+    /// Name of the element that labels this one (UIA `LabeledBy`).
+    /// Only populated when the tree is built with `include_relationships`.
+    #[serde(default, skip_serializing_if = "is_empty_string")]
+    pub labeled_by: Option<String>,
+    /// Names of the elements that describe this one (UIA `DescribedBy`), such as
+    /// hint or validation text. Only populated with `include_relationships`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub described_by: Vec<String>,
+    /// Names of the elements this one controls (UIA `ControllerFor`), e.g. the
+    /// list a combo box opens. Only populated with `include_relationships`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub controller_for: Vec<String>,
 }
 
 impl fmt::Debug for UIElementAttributes {
@@ -426,21 +526,126 @@ impl fmt::Debug for UIElementAttributes {
             debug_struct.field("index_in_parent", &index);
         }
 
+        // Only show non-empty shortcut
+        if let Some(ref shortcut) = self.shortcut {
+            if !shortcut.is_empty() {
+                debug_struct.field("shortcut", shortcut);
+            }
+        }
+
+        // Only show non-empty relationships
+        if let Some(ref labeled_by) = self.labeled_by {
+            if !labeled_by.is_empty() {
+                debug_struct.field("labeled_by", labeled_by);
+            }
+        }
+        if !self.described_by.is_empty() {
+            debug_struct.field("described_by", &self.described_by);
+        }
+        if !self.controller_for.is_empty() {
+            debug_struct.field("controller_for", &self.controller_for);
+        }
+
         debug_struct.finish()
     }
 }
 
+/// One input read by [`UIElement::read_form`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FormField {
+    /// Text of the label associated with the input, if one was found
+    pub label: Option<String>,
+    pub role: String,
+    /// Current value; `"true"`/`"false"` for check boxes and radio buttons
+    pub value: Option<String>,
+    /// Selector that resolves back to the input from the form element, see
+    /// [`UIElement::selector_within`]
+    pub selector: Option<String>,
+}
+
+/// Roles collected as inputs by [`UIElement::read_form`]
+const FORM_FIELD_ROLES: &[&str] = &[
+    "Edit",
+    "ComboBox",
+    "CheckBox",
+    "RadioButton",
+    "Slider",
+    "Spinner",
+];
+
+/// How far (in pixels) a label may sit from its input and still be paired with it
+const MAX_LABEL_DISTANCE: f64 = 200.0;
+
+/// Pick the label closest to `field`, considering only labels that end left of it on
+/// the same row or above it in the same column.
+fn nearest_label(field: &crate::Rect, labels: &[(String, crate::Rect)]) -> Option<String> {
+    let (_, field_y) = field.center();
+    labels
+        .iter()
+        .filter_map(|(text, label)| {
+            let (_, label_y) = label.center();
+            let same_row = label_y >= field.y && label_y <= field.bottom();
+            let same_column = label.x < field.right() && label.right() > field.x;
+            let distance = if label.is_left_of(field) && same_row {
+                field.x - label.right() + (label_y - field_y).abs()
+            } else if label.is_above(field) && same_column {
+                // Slight penalty so a label beside the input wins over the one above
+                field.y - label.bottom() + 1.0
+            } else {
+                return None;
+            };
+            (distance <= MAX_LABEL_DISTANCE).then_some((distance, text))
+        })
+        .min_by(|a, b| a.0.total_cmp(&b.0))
+        .map(|(_, text)| text.clone())
+}
+
 /// Interface for platform-specific element implementations
 pub trait UIElementImpl: Send + Sync + Debug {
     fn object_id(&self) -> usize;
     fn id(&self) -> Option<String>;
+    /// Platform runtime id, unique among live elements (UIA RuntimeId on Windows).
+    /// Returns None if the platform doesn't expose one.
+    fn runtime_id(&self) -> Option<Vec<i32>> {
+        None
+    }
     fn role(&self) -> String;
     fn attributes(&self) -> UIElementAttributes;
+    /// Keyboard shortcut that activates the element, if the platform exposes one.
+    fn shortcut(&self) -> Option<String> {
+        None
+    }
+    /// Help text describing how to use the element, if the platform exposes one.
+    fn help_text(&self) -> Option<String> {
+        None
+    }
+    /// Interaction patterns the element supports.
+    fn supported_patterns(&self) -> Vec<ControlPattern> {
+        Vec::new()
+    }
+    /// Read an arbitrary platform property by numeric id (a UIA property id on Windows).
+    fn get_property(&self, property_id: i32) -> Result<serde_json::Value, AutomationError> {
+        Err(AutomationError::UnsupportedOperation(format!(
+            "get_property({property_id}) not implemented for this platform"
+        )))
+    }
     fn name(&self) -> Option<String> {
         self.attributes().name
     }
     fn children(&self) -> Result<Vec<UIElement>, AutomationError>;
     fn parent(&self) -> Result<Option<UIElement>, AutomationError>;
+    /// The element that labels this one (UIA `LabeledBy` on Windows), if declared
+    fn labeled_by(&self) -> Option<UIElement> {
+        None
+    }
+    /// Elements that describe this one (UIA `DescribedBy` on Windows)
+    fn described_by(&self) -> Vec<UIElement> {
+        Vec::new()
+    }
+    /// Elements this one controls (UIA `ControllerFor` on Windows)
+    fn controller_for(&self) -> Vec<UIElement> {
+        Vec::new()
+    }
     fn bounds(&self) -> Result<(f64, f64, f64, f64), AutomationError>; // x, y, width, height
     fn click(&self) -> Result<ClickResult, AutomationError>;
     fn double_click(&self) -> Result<ClickResult, AutomationError>;
@@ -570,6 +775,28 @@ pub trait UIElementImpl: Send + Sync + Debug {
     fn is_enabled(&self) -> Result<bool, AutomationError>;
     fn is_visible(&self) -> Result<bool, AutomationError>;
     fn is_focused(&self) -> Result<bool, AutomationError>;
+    /// Call a control pattern method by name; see [`UIElement::invoke_pattern_method`]
+    fn invoke_pattern_method(
+        &self,
+        pattern: ControlPattern,
+        method: &str,
+        _args: &[serde_json::Value],
+    ) -> Result<serde_json::Value, AutomationError> {
+        Err(AutomationError::UnsupportedOperation(format!(
+            "{pattern}.{method} is not supported on this platform"
+        )))
+    }
+    /// The element a click at screen point (x, y) would land on, as hit-tested by the platform
+    fn element_at_point(&self, x: f64, y: f64) -> Result<UIElement, AutomationError> {
+        Err(AutomationError::UnsupportedOperation(format!(
+            "element_at_point({x}, {y}) is not supported on this platform"
+        )))
+    }
+    /// Stacking position of the element's own window among its sibling windows, 0 being
+    /// on top. None when the element has no window of its own or the platform can't tell.
+    fn z_order(&self) -> Option<usize> {
+        None
+    }
     fn perform_action(&self, action: &str) -> Result<(), AutomationError>;
     fn as_any(&self) -> &dyn std::any::Any;
     fn create_locator(&self, selector: Selector) -> Result<Locator, AutomationError>;
@@ -693,6 +920,25 @@ pub trait UIElementImpl: Send + Sync + Debug {
     fn set_range_value(&self, value: f64) -> Result<(), AutomationError>;
     fn is_selected(&self) -> Result<bool, AutomationError>;
     fn set_selected(&self, state: bool) -> Result<(), AutomationError>;
+    /// Whether this container lets more than one of its items be selected at once
+    fn can_select_multiple(&self) -> Result<bool, AutomationError> {
+        Ok(false)
+    }
+    /// Add this item to its container's selection without clearing the rest
+    fn add_to_selection(&self) -> Result<(), AutomationError> {
+        Err(AutomationError::UnsupportedOperation(
+            "add_to_selection is not supported on this platform".to_string(),
+        ))
+    }
+    /// Click the element while holding `modifiers`, releasing them afterwards
+    fn click_with_modifiers(
+        &self,
+        _modifiers: &[crate::Modifier],
+    ) -> Result<ClickResult, AutomationError> {
+        Err(AutomationError::UnsupportedOperation(
+            "click_with_modifiers is not supported on this platform".to_string(),
+        ))
+    }
 
     fn set_selected_with_state(&self, state: bool) -> Result<crate::ActionResult, AutomationError> {
         // Default implementation - platforms can override for state tracking
@@ -888,6 +1134,48 @@ impl UIElement {
         self.inner.id()
     }
 
+    /// Get the element's platform runtime id, if available.
+    ///
+    /// Unlike `id()`, which hashes descriptive properties, this identifies the
+    /// live element and differs between otherwise identical siblings.
+    pub fn runtime_id(&self) -> Option<Vec<i32>> {
+        self.inner.runtime_id()
+    }
+
+    /// Get the keyboard shortcut that activates this element, if any.
+    ///
+    /// On Windows this is the UIA `AcceleratorKey` (e.g. "Ctrl+S"), falling back
+    /// to the `AccessKey` mnemonic (e.g. "Alt+F").
+    pub fn shortcut(&self) -> Option<String> {
+        self.inner.shortcut()
+    }
+
+    /// Get the element's help text (tooltip-style usage guidance), if any.
+    ///
+    /// On Windows this is the UIA `HelpText` property.
+    pub fn help_text(&self) -> Option<String> {
+        self.inner.help_text()
+    }
+
+    /// Get the interaction patterns the element supports, such as
+    /// `Invoke`, `Toggle`, `Value` or `ExpandCollapse`.
+    ///
+    /// Useful for deciding which action will work on an element before trying it.
+    pub fn supported_patterns(&self) -> Vec<ControlPattern> {
+        self.inner.supported_patterns()
+    }
+
+    /// Read any accessibility property by id, as JSON.
+    ///
+    /// An escape hatch for properties `UIElementAttributes` doesn't surface, such as
+    /// UIA `ItemStatus` (30026), `HelpText` (30013) or `Culture` (30015), including
+    /// custom properties an app registered. Strings, numbers, booleans and arrays map to
+    /// their JSON counterparts and an empty value to `null`; properties the element
+    /// doesn't support return `UnsupportedOperation`.
+    pub fn get_property(&self, property_id: i32) -> Result<serde_json::Value, AutomationError> {
+        self.inner.get_property(property_id)
+    }
+
     /// Get the element's role (e.g., "button", "textfield")
     pub fn role(&self) -> String {
         self.inner.role()
@@ -908,6 +1196,138 @@ impl UIElement {
         self.inner.parent()
     }
 
+    /// The element that labels this one, if the app declares it (UIA `LabeledBy`)
+    pub fn labeled_by(&self) -> Option<UIElement> {
+        self.inner.labeled_by()
+    }
+
+    /// Elements that describe this one, such as hint or validation text (UIA `DescribedBy`)
+    pub fn described_by(&self) -> Vec<UIElement> {
+        self.inner.described_by()
+    }
+
+    /// Elements this one controls, e.g. the list a combo box opens (UIA `ControllerFor`)
+    pub fn controller_for(&self) -> Vec<UIElement> {
+        self.inner.controller_for()
+    }
+
+    /// Get element bounds as a [`Rect`](crate::Rect)
+    pub fn rect(&self) -> Result<crate::Rect, AutomationError> {
+        self.bounds().map(crate::Rect::from)
+    }
+
+    /// True if this element ends at or above the top edge of `other`.
+    /// Returns false if either element's bounds can't be read.
+    pub fn is_above(&self, other: &UIElement) -> bool {
+        self.compare_rects(other, |a, b| a.is_above(b))
+    }
+
+    /// True if this element ends at or left of the left edge of `other`.
+    /// Returns false if either element's bounds can't be read.
+    pub fn is_left_of(&self, other: &UIElement) -> bool {
+        self.compare_rects(other, |a, b| a.is_left_of(b))
+    }
+
+    /// True if the two elements' bounds share a non-empty area.
+    /// Returns false if either element's bounds can't be read.
+    pub fn overlaps(&self, other: &UIElement) -> bool {
+        self.compare_rects(other, |a, b| a.overlaps(b))
+    }
+
+    fn compare_rects(
+        &self,
+        other: &UIElement,
+        predicate: impl FnOnce(&crate::Rect, &crate::Rect) -> bool,
+    ) -> bool {
+        match (self.rect(), other.rect()) {
+            (Ok(a), Ok(b)) => predicate(&a, &b),
+            (Err(e), _) | (_, Err(e)) => {
+                debug!("Layout comparison skipped, bounds unavailable: {}", e);
+                false
+            }
+        }
+    }
+
+    /// Check whether `ancestor` appears in this element's parent chain.
+    ///
+    /// An element is not considered a descendant of itself. The walk is bounded
+    /// like `find_parent_window` to guard against malformed trees.
+    pub fn is_descendant_of(&self, ancestor: &UIElement) -> bool {
+        let ancestor_id = ancestor.runtime_id();
+        self.ancestors()
+            .iter()
+            .any(|a| a.is_same_element(a.runtime_id().as_deref(), ancestor, ancestor_id.as_deref()))
+    }
+
+    /// Find the nearest element whose subtree contains both `self` and `other`.
+    ///
+    /// If one element contains the other, the containing element is returned.
+    /// Returns None when the two don't share a root (e.g. one was destroyed).
+    pub fn common_ancestor(&self, other: &UIElement) -> Option<UIElement> {
+        let theirs: Vec<(UIElement, Option<Vec<i32>>)> = std::iter::once(other.clone())
+            .chain(other.ancestors())
+            .map(|e| {
+                let id = e.runtime_id();
+                (e, id)
+            })
+            .collect();
+
+        std::iter::once(self.clone())
+            .chain(self.ancestors())
+            .find(|candidate| {
+                let candidate_id = candidate.runtime_id();
+                theirs.iter().any(|(e, id)| {
+                    candidate.is_same_element(candidate_id.as_deref(), e, id.as_deref())
+                })
+            })
+    }
+
+    /// Parent chain of this element, nearest first, excluding the element itself
+    fn ancestors(&self) -> Vec<UIElement> {
+        let mut chain = Vec::new();
+        let mut current = self.clone();
+        let mut current_id = current.runtime_id();
+        // Same iteration limit as find_parent_window
+        for _ in 0..crate::parent_window_max_iterations() {
+            match current.parent() {
+                Ok(Some(parent)) => {
+                    let parent_id = parent.runtime_id();
+                    // The desktop root may report itself as its own parent
+                    if parent.is_same_element(parent_id.as_deref(), &current, current_id.as_deref())
+                    {
+                        break;
+                    }
+                    chain.push(parent.clone());
+                    current = parent;
+                    current_id = parent_id;
+                }
+                Ok(None) => break,
+                Err(e) => {
+                    debug!(
+                        "ancestors: parent() failed after {} hops: {}",
+                        chain.len(),
+                        e
+                    );
+                    break;
+                }
+            }
+        }
+        chain
+    }
+
+    /// Compare by runtime id when both are known, else fall back to `PartialEq`
+    fn is_same_element(
+        &self,
+        id: Option<&[i32]>,
+        other: &UIElement,
+        other_id: Option<&[i32]>,
+    ) -> bool {
+        match (id, other_id) {
+            (Some(a), Some(b)) => a == b,
+            _ => self == other,
+        }
+    }
+
     /// Get element bounds (x, y, width, height)
     pub fn bounds(&self) -> Result<(f64, f64, f64, f64), AutomationError> {
         self.inner.bounds()
@@ -1120,6 +1540,99 @@ impl UIElement {
         })
     }
 
+    /// Type a number formatted for `locale` (e.g. "de-DE" types 1000.5 as "1.000,5").
+    ///
+    /// See [`crate::locale_format::format_number`] for the supported locales.
+    pub fn type_number(&self, value: f64, locale: &str) -> Result<(), AutomationError> {
+        let text = crate::locale_format::format_number(value, locale)?;
+        self.type_text(&text, false)
+    }
+
+    /// Type a date using a pattern ("dd.MM.yyyy"), a locale tag ("en-US") or a strftime string.
+    ///
+    /// See [`crate::locale_format::format_date`] for the accepted formats.
+    pub fn type_date(&self, date: chrono::NaiveDate, format: &str) -> Result<(), AutomationError> {
+        let text = crate::locale_format::format_date(date, format)?;
+        self.type_text(&text, false)
+    }
+
+    /// Replace the element's text and retry until reading it back matches.
+    ///
+    /// Each attempt clears the element, types `text`, and compares the value read back.
+    /// Up to `max_attempts` attempts are made (at least one); the returned verification
+    /// describes the last one. Elements whose value can't be read are typed once and
+    /// reported as passed, like `type_text_with_state`.
+    #[instrument(level = "debug", skip(self))]
+    pub fn type_text_verified(
+        &self,
+        text: &str,
+        max_attempts: u32,
+    ) -> Result<crate::TypeVerification, AutomationError> {
+        let max_attempts = max_attempts.max(1);
+        let mut attempt = 1;
+        loop {
+            self.clear_text();
+            self.type_text(text, false)?;
+
+            let actual = match self.inner.get_value() {
+                Ok(Some(actual)) => actual,
+                Ok(None) => {
+                    return Ok(crate::TypeVerification {
+                        passed: true, // Can't verify, assume success
+                        expected: text.to_string(),
+                        actual: None,
+                        error: None,
+                    });
+                }
+                Err(e) => {
+                    return Ok(crate::TypeVerification {
+                        passed: true, // Can't verify, assume success
+                        expected: text.to_string(),
+                        actual: None,
+                        error: Some(format!("Could not read value: {e}")),
+                    });
+                }
+            };
+
+            if utils::typed_value_matches(text, &actual) {
+                return Ok(crate::TypeVerification {
+                    passed: true,
+                    expected: text.to_string(),
+                    actual: Some(actual),
+                    error: None,
+                });
+            }
+            if attempt >= max_attempts {
+                return Ok(crate::TypeVerification {
+                    passed: false,
+                    expected: text.to_string(),
+                    actual: Some(actual),
+                    error: Some(format!(
+                        "Value still did not match after {attempt} attempt(s)"
+                    )),
+                });
+            }
+
+            debug!(
+                "Typed value {:?} did not match {:?}, retrying ({}/{})",
+                actual, text, attempt, max_attempts
+            );
+            attempt += 1;
+            // Let input handlers and auto-formatters settle before clearing
+            std::thread::sleep(std::time::Duration::from_millis(100));
+        }
+    }
+
+    /// Empty a text element, falling back to select-all and delete when it rejects `set_value`
+    fn clear_text(&self) {
+        let cleared = self.inner.set_value("").is_ok()
+            && matches!(self.inner.get_value(), Ok(Some(ref v)) if v.is_empty());
+        if !cleared {
+            let _ = self.inner.press_key("{Ctrl}a", true, false, false);
+            let _ = self.inner.press_key("{Delete}", false, false, false);
+        }
+    }
+
     /// Press a key while this element is focused
     pub fn press_key(&self, key: &str) -> Result<(), AutomationError> {
         #[cfg(target_os = "windows")]
@@ -1175,6 +1688,56 @@ impl UIElement {
         self.inner.get_text(max_depth)
     }
 
+    /// Get the visible text of this element and all of its descendants, similar to the
+    /// web `innerText` concept.
+    ///
+    /// Descendant name/value text is collected and ordered top-to-bottom, left-to-right by
+    /// bounds. Fragments on the same visual line are joined with spaces and lines are joined
+    /// with newlines. A container's own name is only used when none of its descendants
+    /// contribute text, so labels made of spans aren't repeated.
+    pub fn inner_text(&self) -> String {
+        fn collect(element: &UIElement, depth: usize, out: &mut Vec<utils::TextFragment>) {
+            const MAX_DEPTH: usize = 50;
+
+            let before = out.len();
+            if depth < MAX_DEPTH {
+                for child in element.children().unwrap_or_default() {
+                    collect(&child, depth + 1, out);
+                }
+            }
+            if out.len() > before {
+                return;
+            }
+
+            let attrs = element.attributes();
+            let text = attrs
+                .value
+                .filter(|v| !v.trim().is_empty())
+                .or_else(|| attrs.name.filter(|n| !n.trim().is_empty()));
+            if let Some(text) = text {
+                out.push(utils::TextFragment {
+                    text: text.trim().to_string(),
+                    bounds: element.bounds().ok(),
+                });
+            }
+        }
+
+        let mut fragments = Vec::new();
+        collect(self, 0, &mut fragments);
+        utils::join_in_reading_order(fragments)
+    }
+
+    /// Release this handle to the platform element now instead of at the end of its scope.
+    ///
+    /// On Windows every `UIElement` holds a COM reference to a UI Automation element, which
+    /// the target application keeps alive until all references are gone. Dropping the handle
+    /// does the same thing; calling `release` makes it explicit in code that walks many
+    /// elements, where handles parked in a `Vec` until the walk ends add up over long
+    /// sessions. Clones share one reference, so it is freed when the last clone is released.
+    pub fn release(self) {
+        drop(self);
+    }
+
     /// Get the value attribute of this element (text inputs, combo boxes, etc.)
     /// This fetches the actual value using the platform's Value pattern, not the cached attributes.
     pub fn get_value(&self) -> Result<Option<String>, AutomationError> {
@@ -1197,6 +1760,95 @@ impl UIElement {
         self.inner.is_visible()
     }
 
+    /// Call a control pattern method by name.
+    ///
+    /// This is not an open-ended bridge to UIA: only a fixed whitelist of methods per
+    /// pattern is reachable, and any other name fails with `UnsupportedOperation`. The
+    /// whitelist includes a few methods that typed methods already wrap (`Invoke`,
+    /// `Toggle`, `SetValue`, `Expand`, ...); prefer the typed methods for those.
+    /// Methods that return elements, such as `Grid.GetItem` and
+    /// `Selection.GetSelection`, are not reachable.
+    ///
+    /// **Advanced and unstable:** the whitelist and the JSON shape of results may
+    /// change between releases.
+    ///
+    /// On Windows `method` is a UIA method or property name such as `"SetValue"` or
+    /// `"CurrentToggleState"`, matched case-insensitively with underscores and a
+    /// `get`/`current` prefix ignored. `args` are the method's parameters in order.
+    /// Methods return their result as JSON, or `null` if they return nothing; enum
+    /// results are returned as their variant name, and enum arguments (dock positions,
+    /// scroll amounts, window states) are passed the same way. Table, TableItem, Text
+    /// and ItemContainer have no reachable methods.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # use terminator::{ControlPattern, UIElement};
+    /// # fn example(slider: &UIElement) -> Result<(), terminator::AutomationError> {
+    /// let max = slider.invoke_pattern_method(ControlPattern::RangeValue, "Maximum", &[])?;
+    /// slider.invoke_pattern_method(ControlPattern::RangeValue, "SetValue", &[max])?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[instrument(level = "debug", skip(self, args))]
+    pub fn invoke_pattern_method(
+        &self,
+        pattern: ControlPattern,
+        method: &str,
+        args: &[serde_json::Value],
+    ) -> Result<serde_json::Value, AutomationError> {
+        self.inner.invoke_pattern_method(pattern, method, args)
+    }
+
+    /// Check whether a click at this element's center would actually reach it.
+    ///
+    /// Hit-tests the center point and checks that the element found there is this one
+    /// or one of its descendants. Returns false when the element has no area, is covered
+    /// by another window or popup, or the hit test fails.
+    #[instrument(level = "debug", skip(self))]
+    pub fn is_hittable(&self) -> bool {
+        let Ok((x, y, width, height)) = self.bounds() else {
+            return false;
+        };
+        if width <= 0.0 || height <= 0.0 {
+            return false;
+        }
+        let Ok(hit) = self
+            .inner
+            .element_at_point(x + width / 2.0, y + height / 2.0)
+        else {
+            return false;
+        };
+
+        let mut current = Some(hit);
+        for _ in 0..crate::parent_window_max_iterations() {
+            match current {
+                Some(element) if element == *self => return true,
+                Some(element) => current = element.parent().ok().flatten(),
+                None => return false,
+            }
+        }
+        false
+    }
+
+    /// Position of this element among its parent's children, 0-based. None for a root
+    /// element, or when the parent no longer lists it (the UI changed underneath).
+    pub fn sibling_index(&self) -> Option<usize> {
+        let parent = self.parent().ok().flatten()?;
+        parent
+            .children()
+            .ok()?
+            .iter()
+            .position(|child| child == self)
+    }
+
+    /// Stacking position of this element's window among its sibling windows, 0 being on
+    /// top; for top-level windows this is the desktop z-order. Use it to tell which of two
+    /// overlapping windows is in front. None for elements without a window of their own
+    /// (most controls) and on platforms that don't expose it.
+    pub fn z_order(&self) -> Option<usize> {
+        self.inner.z_order()
+    }
+
     /// Check if element is focused
     pub fn is_focused(&self) -> Result<bool, AutomationError> {
         self.inner.is_focused()
@@ -1218,11 +1870,233 @@ impl UIElement {
         self.inner.create_locator(selector)
     }
 
+    /// Generate a chained selector from the top-level window down to this element.
+    ///
+    /// Each step uses the element's AutomationId (`nativeid:`) when it has one, and
+    /// `role && name` otherwise. The chain is resolved once from the desktop root; if
+    /// it matches several elements, a trailing `nth=` index picks this one. Only
+    /// returned if the chain finds this exact element, so it can be pasted into
+    /// scripts as is.
+    pub async fn to_selector(&self) -> Result<String, AutomationError> {
+        // Ancestors up to (but excluding) the desktop root, top-level window first
+        let mut path = vec![self.clone()];
+        while let Some(parent) = path.last().unwrap().parent()? {
+            path.push(parent);
+        }
+        let root = path.pop().unwrap();
+        if path.is_empty() {
+            return Err(AutomationError::InvalidArgument(
+                "The desktop root has no selector".to_string(),
+            ));
+        }
+        path.reverse();
+
+        let selector = self.resolve_selector_path(&root, &path).await?;
+        debug!("Generated selector for element: {}", selector);
+        Ok(selector)
+    }
+
+    /// Like [`UIElement::to_selector`], but relative to `ancestor`: the chain starts
+    /// below it and resolves with `ancestor.locator(..)`. Searches only the
+    /// ancestor's subtree, so it is much cheaper than a selector from the desktop root.
+    pub async fn selector_within(&self, ancestor: &UIElement) -> Result<String, AutomationError> {
+        let mut path = vec![self.clone()];
+        loop {
+            let parent = path.last().unwrap().parent()?.ok_or_else(|| {
+                AutomationError::InvalidArgument(
+                    "Element is not a descendant of the given ancestor".to_string(),
+                )
+            })?;
+            if &parent == ancestor {
+                break;
+            }
+            path.push(parent);
+        }
+        path.reverse();
+
+        self.resolve_selector_path(ancestor, &path).await
+    }
+
+    /// Chain one segment per element of `path` and resolve it under `scope`,
+    /// appending `nth=` when the chain is ambiguous.
+    ///
+    /// Only the last step is disambiguated: mid-chain `nth=` means "nth child" on
+    /// Windows rather than "nth match", while a trailing one indexes the matches.
+    async fn resolve_selector_path(
+        &self,
+        scope: &UIElement,
+        path: &[UIElement],
+    ) -> Result<String, AutomationError> {
+        let segments: Vec<String> = path
+            .iter()
+            .map(|element| {
+                let attributes = element.attributes();
+                let automation_id = attributes
+                    .properties
+                    .get("AutomationId")
+                    .and_then(|v| v.as_ref()?.as_str().map(str::to_string))
+                    .filter(|id| !id.is_empty());
+                match automation_id {
+                    Some(id) => format!("nativeid:{id}"),
+                    None => crate::selector::build_selector_segment(
+                        &attributes.role,
+                        attributes.name.as_deref(),
+                    ),
+                }
+            })
+            .collect();
+        let mut selector = crate::selector::build_chained_selector(&segments)
+            .ok_or_else(|| AutomationError::InvalidArgument("Empty element path".to_string()))?;
+
+        let timeout = Some(std::time::Duration::from_millis(500));
+        let matches = scope.locator(selector.as_str())?.all(timeout, None).await?;
+        let index = matches.iter().position(|m| m == self).ok_or_else(|| {
+            AutomationError::ElementNotFound(format!(
+                "Generated selector '{selector}' does not resolve to the element"
+            ))
+        })?;
+        if matches.len() > 1 {
+            selector.push_str(&format!(" >> nth={index}"));
+        }
+        Ok(selector)
+    }
+
+    /// Read every input under this element with its label and current value.
+    ///
+    /// Edits, combo boxes, check boxes, radio buttons, sliders and spinners are
+    /// collected in tree order. Each one is labeled by its declared `LabeledBy`
+    /// element when the app provides one; otherwise by the nearest text element
+    /// to its left on the same row or directly above it. Check boxes report
+    /// `"true"`/`"false"` from their toggle state and radio buttons from their
+    /// selection state.
+    pub async fn read_form(&self) -> Vec<FormField> {
+        let mut inputs = Vec::new();
+        let mut labels = Vec::new();
+        let mut stack = self.children().unwrap_or_default();
+        stack.reverse();
+        while let Some(element) = stack.pop() {
+            let role = element.role();
+            if FORM_FIELD_ROLES.contains(&role.as_str()) {
+                inputs.push(element);
+                continue;
+            }
+            if role == "Text" {
+                if let (Some(name), Ok(rect)) = (element.name(), element.rect()) {
+                    if !name.trim().is_empty() {
+                        labels.push((name.trim().to_string(), rect));
+                    }
+                }
+            }
+            let mut children = element.children().unwrap_or_default();
+            children.reverse();
+            stack.extend(children);
+        }
+
+        let mut fields = Vec::with_capacity(inputs.len());
+        for input in inputs {
+            let role = input.role();
+            let label = input
+                .labeled_by()
+                .and_then(|label| label.name())
+                .filter(|name| !name.trim().is_empty())
+                .or_else(|| {
+                    input
+                        .rect()
+                        .ok()
+                        .and_then(|rect| nearest_label(&rect, &labels))
+                });
+            let value = match role.as_str() {
+                "CheckBox" => input.is_toggled().ok().map(|on| on.to_string()),
+                "RadioButton" => input.is_selected().ok().map(|on| on.to_string()),
+                _ => input.get_value().ok().flatten(),
+            };
+            fields.push(FormField {
+                label,
+                role,
+                value,
+                selector: input.selector_within(self).await.ok(),
+            });
+        }
+        fields
+    }
+
     /// Scroll the element in a given direction
     pub fn scroll(&self, direction: &str, amount: f64) -> Result<(), AutomationError> {
         self.inner.scroll(direction, amount)
     }
 
+    /// Scroll this container until an element matching `target_selector` is visible.
+    ///
+    /// Scrolls one page at a time in `direction`, re-querying for the target after
+    /// each scroll, and returns it as soon as it is found and visible. Stops early
+    /// if a scroll leaves the container's content unchanged (the end was reached).
+    ///
+    /// # Errors
+    /// Returns `ElementNotFound` if the target is still not visible after
+    /// `max_scrolls` scrolls or once the end of the content is reached.
+    #[instrument(level = "debug", skip(self))]
+    pub fn scroll_until_visible(
+        &self,
+        target_selector: &str,
+        direction: crate::ScrollDirection,
+        max_scrolls: u32,
+    ) -> Result<UIElement, AutomationError> {
+        const SCROLL_AMOUNT: f64 = 1.0; // one page per step
+        const SETTLE_DELAY: std::time::Duration = std::time::Duration::from_millis(150);
+
+        let locator = self.locator(target_selector)?;
+        let mut scrolls: u32 = 0;
+        let mut reached_end = false;
+
+        loop {
+            if let Ok(target) = locator.find_blocking(std::time::Duration::ZERO) {
+                if target.is_visible().unwrap_or(false) {
+                    debug!("scroll_until_visible: found target after {scrolls} scrolls");
+                    return Ok(target);
+                }
+            }
+
+            if reached_end || scrolls >= max_scrolls {
+                break;
+            }
+
+            let before = self.content_fingerprint();
+            self.scroll(direction.as_str(), SCROLL_AMOUNT)?;
+            scrolls += 1;
+            std::thread::sleep(SETTLE_DELAY);
+
+            // Check once more after this scroll, then give up if nothing moved
+            reached_end = self.content_fingerprint() == before;
+        }
+
+        let reason = if reached_end {
+            "reached the end of the content"
+        } else {
+            "hit the scroll limit"
+        };
+        Err(AutomationError::ElementNotFound(format!(
+            "'{target_selector}' not visible after scrolling {} {scrolls} time(s): {reason}",
+            direction.as_str()
+        )))
+    }
+
+    /// Cheap snapshot of the visible content (child names and positions), used to
+    /// detect when scrolling has stopped moving anything.
+    fn content_fingerprint(&self) -> u64 {
+        use std::hash::{Hash, Hasher};
+
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        for child in self.children().unwrap_or_default() {
+            child.name().hash(&mut hasher);
+            if let Ok((x, y, width, height)) = child.bounds() {
+                [x, y, width, height]
+                    .map(|v| v.round() as i64)
+                    .hash(&mut hasher);
+            }
+        }
+        hasher.finish()
+    }
+
     /// Scroll with state tracking
     #[instrument(level = "debug", skip(self))]
     pub fn scroll_with_state(
@@ -1552,7 +2426,11 @@ impl UIElement {
         self.inner.get_range_value()
     }
 
-    /// Sets the value of a range-based control like a slider.
+    /// Sets the value of a range-based control like a slider or spinner.
+    ///
+    /// The value is snapped to the control's step. Values outside its minimum and
+    /// maximum return `InvalidArgument`; read-only controls such as progress bars and
+    /// elements that aren't range controls return `UnsupportedOperation`.
     pub fn set_range_value(&self, value: f64) -> Result<(), AutomationError> {
         self.inner.set_range_value(value)
     }
@@ -1567,6 +2445,75 @@ impl UIElement {
         self.inner.set_selected(state)
     }
 
+    /// Select several items of this list, grid or tree at once.
+    ///
+    /// When this container reports multi-select support (`SelectionPattern` on Windows)
+    /// the items are selected through the accessibility API: `Add` adds each item to the
+    /// selection, `Range` selects the first item and adds every child between it and the
+    /// last. Otherwise the selection is made with the mouse: `Add` Ctrl+clicks each item,
+    /// `Range` clicks the first item and Shift+clicks the last.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # use terminator::{Desktop, MultiSelectMode};
+    /// # async fn example(desktop: &Desktop) -> Result<(), terminator::AutomationError> {
+    /// let list = desktop.locator("role:List").first(None).await?;
+    /// let rows = list.locator("role:ListItem")?.all(None, None).await?;
+    /// list.multi_select(&[&rows[1], &rows[3]], MultiSelectMode::Add)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[instrument(level = "debug", skip(self, items), fields(items = items.len()))]
+    pub fn multi_select(
+        &self,
+        items: &[&UIElement],
+        mode: MultiSelectMode,
+    ) -> Result<(), AutomationError> {
+        let (Some(first), Some(last)) = (items.first(), items.last()) else {
+            return Err(AutomationError::InvalidArgument(
+                "multi_select needs at least one item".to_string(),
+            ));
+        };
+
+        if self.inner.can_select_multiple().unwrap_or(false) {
+            match mode {
+                MultiSelectMode::Add => {
+                    return items
+                        .iter()
+                        .try_for_each(|item| item.inner.add_to_selection());
+                }
+                MultiSelectMode::Range => {
+                    let children = self.children()?;
+                    let position = |item: &UIElement| children.iter().position(|c| c == item);
+                    if let (Some(start), Some(end)) = (position(first), position(last)) {
+                        let (start, end) = (start.min(end), start.max(end));
+                        first.inner.set_selected(true)?;
+                        return children[start..=end]
+                            .iter()
+                            .filter(|child| *child != *first)
+                            .try_for_each(|child| child.inner.add_to_selection());
+                    }
+                    debug!("Range endpoints are not direct children; falling back to clicks");
+                }
+            }
+        }
+
+        match mode {
+            MultiSelectMode::Add => items.iter().try_for_each(|item| {
+                item.inner
+                    .click_with_modifiers(&[crate::Modifier::Ctrl])
+                    .map(|_| ())
+            }),
+            MultiSelectMode::Range => {
+                first.click()?;
+                if items.len() > 1 {
+                    last.inner.click_with_modifiers(&[crate::Modifier::Shift])?;
+                }
+                Ok(())
+            }
+        }
+    }
+
     /// Set selection state with state tracking
     #[instrument(level = "debug", skip(self))]
     pub fn set_selected_with_state(
@@ -2023,6 +2970,103 @@ pub mod utils {
         }
     }
 
+    /// A piece of text with the on-screen bounds it was read from
+    #[derive(Debug, Clone)]
+    pub struct TextFragment {
+        pub text: String,
+        pub bounds: Option<(f64, f64, f64, f64)>,
+    }
+
+    /// Join text fragments in reading order (top-to-bottom, left-to-right).
+    ///
+    /// Fragments whose vertical centers fall within half a line height of each other are
+    /// treated as one line and joined with spaces; separate lines are joined with newlines.
+    /// Fragments without bounds keep their original order and are appended at the end.
+    pub fn join_in_reading_order(fragments: Vec<TextFragment>) -> String {
+        let (mut placed, unplaced): (Vec<_>, Vec<_>) =
+            fragments.into_iter().partition(|f| f.bounds.is_some());
+
+        placed.sort_by(|a, b| {
+            let (ax, ay, _, _) = a.bounds.unwrap_or_default();
+            let (bx, by, _, _) = b.bounds.unwrap_or_default();
+            ay.total_cmp(&by).then(ax.total_cmp(&bx))
+        });
+
+        let mut lines: Vec<Vec<TextFragment>> = Vec::new();
+        for fragment in placed {
+            let (_, y, _, h) = fragment.bounds.unwrap_or_default();
+            let center = y + h / 2.0;
+            let same_line = lines
+                .last()
+                .and_then(|line| line.first())
+                .is_some_and(|first| {
+                    let (_, fy, _, fh) = first.bounds.unwrap_or_default();
+                    let tolerance = (fh.min(h) / 2.0).max(1.0);
+                    (center - (fy + fh / 2.0)).abs() <= tolerance
+                });
+            match lines.last_mut() {
+                Some(line) if same_line => line.push(fragment),
+                _ => lines.push(vec![fragment]),
+            }
+        }
+
+        let mut output: Vec<String> = lines
+            .into_iter()
+            .map(|mut line| {
+                line.sort_by(|a, b| {
+                    let ax = a.bounds.map(|b| b.0).unwrap_or_default();
+                    let bx = b.bounds.map(|b| b.0).unwrap_or_default();
+                    ax.total_cmp(&bx)
+                });
+                line.into_iter()
+                    .map(|f| f.text)
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            })
+            .collect();
+        output.extend(unplaced.into_iter().map(|f| f.text));
+        output.join("\n")
+    }
+
+    /// Whether the value read back from an element matches the text typed into it.
+    ///
+    /// Trailing line breaks and spaces are ignored, since some editors append them.
+    pub fn typed_value_matches(expected: &str, actual: &str) -> bool {
+        const TRAILING: [char; 3] = ['\r', '\n', ' '];
+        expected.trim_end_matches(TRAILING) == actual.trim_end_matches(TRAILING)
+    }
+
+    /// Check a range-control target against the control's bounds and snap it to its step.
+    ///
+    /// Steps are counted from `min`; a `step` of zero or less leaves the value as given.
+    /// Values outside `[min, max]` are rejected instead of clamped, so callers find out
+    /// when they asked for something the control can't show.
+    pub fn resolve_range_value(
+        value: f64,
+        min: f64,
+        max: f64,
+        step: f64,
+    ) -> Result<f64, AutomationError> {
+        if !value.is_finite() {
+            return Err(AutomationError::InvalidArgument(format!(
+                "Range value must be a finite number, got {value}"
+            )));
+        }
+        if value < min || value > max {
+            return Err(AutomationError::InvalidArgument(format!(
+                "Value {value} is outside the control's range [{min}, {max}]"
+            )));
+        }
+        if !(step.is_finite() && step > 0.0) {
+            return Ok(value);
+        }
+
+        let steps = ((value - min) / step).round();
+        let snapped = (min + steps * step).min(max);
+        // Drop float noise such as 0.30000000000000004
+        Ok((snapped * 1e9).round() / 1e9)
+    }
+
     /// Create a minimal attributes struct with just the essentials
     pub fn essential_attributes(element: &UIElement) -> UIElementAttributes {
         UIElementAttributes {
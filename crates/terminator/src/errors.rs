@@ -1,6 +1,6 @@
 use thiserror::Error;
 
-#[derive(Error, Debug)]
+#[derive(Error, Debug, Clone)]
 pub enum AutomationError {
     #[error("Element not found: {0}")]
     ElementNotFound(String),
@@ -60,4 +60,10 @@ pub enum AutomationError {
 
     #[error("Verification failed: {0}")]
     VerificationFailed(String),
+
+    #[error("Traversal limit exceeded: {0}")]
+    TraversalLimitExceeded(String),
+
+    #[error("Elevation required: {0}")]
+    ElevationRequired(String),
 }
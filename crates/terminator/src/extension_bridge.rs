@@ -29,7 +29,8 @@ pub enum ExtensionBridgeError {
     IoError(#[from] std::io::Error),
 }
 
-const DEFAULT_WS_ADDR: &str = "127.0.0.1:17373";
+/// Address the extension bridge WebSocket server listens on
+pub const DEFAULT_WS_ADDR: &str = "127.0.0.1:17373";
 
 // Reduce type complexity for Clippy
 type BridgeResult = Result<serde_json::Value, String>;
@@ -69,6 +70,57 @@ struct CloseTabRequest {
     title: Option<String>,
 }
 
+#[derive(Debug, Serialize)]
+struct ListTabsRequest {
+    id: String,
+    action: String,
+}
+
+#[derive(Debug, Serialize)]
+struct ActivateTabRequest {
+    id: String,
+    action: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    index: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    url: Option<String>,
+}
+
+/// A browser tab as reported by the extension
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BrowserTab {
+    pub id: i32,
+    /// Position of the tab in the list returned by `list_tabs` (across all windows)
+    pub index: usize,
+    pub title: Option<String>,
+    pub url: Option<String>,
+    /// Whether this is the active tab of the last focused browser window
+    pub active: bool,
+    #[serde(rename = "windowId")]
+    pub window_id: Option<i32>,
+}
+
+/// Identifies which browser tab to activate
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TabTarget {
+    /// Position in the list returned by `list_tabs`
+    Index(usize),
+    /// Exact URL or URL substring
+    Url(String),
+}
+
+impl From<usize> for TabTarget {
+    fn from(index: usize) -> Self {
+        TabTarget::Index(index)
+    }
+}
+
+impl From<&str> for TabTarget {
+    fn from(url: &str) -> Self {
+        TabTarget::Url(url.to_string())
+    }
+}
+
 /// Result of closing a browser tab
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CloseTabResult {
@@ -1336,6 +1388,123 @@ impl ExtensionBridge {
             }
         }
     }
+
+    /// Send a request to the most recent client and wait for its result value.
+    ///
+    /// Returns Ok(None) if no client is connected, the send fails, or the request times out.
+    async fn send_request(
+        &self,
+        id: String,
+        action: &str,
+        payload: String,
+        timeout: Duration,
+    ) -> Result<Option<serde_json::Value>, AutomationError> {
+        if !self.is_client_connected().await {
+            tracing::warn!("ExtensionBridge: no clients connected for {}", action);
+            return Ok(None);
+        }
+
+        let (tx, rx) = oneshot::channel::<BridgeResult>();
+        self.pending.lock().await.insert(id.clone(), tx);
+
+        let mut ok = false;
+        {
+            let mut clients = self.clients.lock().await;
+            clients.retain(|c| !c.sender.is_closed());
+
+            tracing::info!(clients = clients.len(), "Sending {} to extension", action);
+
+            if let Some(c) = clients.last() {
+                ok = c.sender.send(Message::Text(payload)).is_ok();
+            }
+        }
+        if !ok {
+            self.pending.lock().await.remove(&id);
+            tracing::warn!(
+                "ExtensionBridge: failed to send {} - no active clients",
+                action
+            );
+            return Ok(None);
+        }
+
+        match tokio::time::timeout(timeout, rx).await {
+            Ok(Ok(Ok(val))) => Ok(Some(val)),
+            Ok(Ok(Err(err))) => Err(AutomationError::PlatformError(format!(
+                "{action} error: {err}"
+            ))),
+            Ok(Err(_canceled)) => {
+                tracing::warn!("ExtensionBridge: {} oneshot canceled", action);
+                Ok(None)
+            }
+            Err(_elapsed) => {
+                let _ = self.pending.lock().await.remove(&id);
+                tracing::warn!("ExtensionBridge: {} timed out (id={})", action, id);
+                Ok(None)
+            }
+        }
+    }
+
+    /// List all open tabs across browser windows
+    ///
+    /// Returns None if no extension is connected or the request timed out
+    pub async fn list_tabs(
+        &self,
+        timeout: Duration,
+    ) -> Result<Option<Vec<BrowserTab>>, AutomationError> {
+        let id = Uuid::new_v4().to_string();
+        let req = ListTabsRequest {
+            id: id.clone(),
+            action: "list_tabs".into(),
+        };
+        let payload = serde_json::to_string(&req)
+            .map_err(|e| AutomationError::PlatformError(format!("bridge serialize: {e}")))?;
+
+        match self.send_request(id, "list_tabs", payload, timeout).await? {
+            Some(val) => serde_json::from_value::<Vec<BrowserTab>>(val)
+                .map(Some)
+                .map_err(|e| {
+                    AutomationError::PlatformError(format!("Failed to parse list_tabs result: {e}"))
+                }),
+            None => Ok(None),
+        }
+    }
+
+    /// Activate (switch to) a tab by list index or URL and focus its window
+    ///
+    /// Returns info about the activated tab, or None if no extension is connected
+    pub async fn activate_tab(
+        &self,
+        target: &TabTarget,
+        timeout: Duration,
+    ) -> Result<Option<BrowserTab>, AutomationError> {
+        let id = Uuid::new_v4().to_string();
+        let (index, url) = match target {
+            TabTarget::Index(index) => (Some(*index), None),
+            TabTarget::Url(url) => (None, Some(url.clone())),
+        };
+        let req = ActivateTabRequest {
+            id: id.clone(),
+            action: "activate_tab".into(),
+            index,
+            url,
+        };
+        let payload = serde_json::to_string(&req)
+            .map_err(|e| AutomationError::PlatformError(format!("bridge serialize: {e}")))?;
+
+        match self
+            .send_request(id, "activate_tab", payload, timeout)
+            .await?
+        {
+            Some(val) => serde_json::from_value::<BrowserTab>(val)
+                .map(Some)
+                .map_err(|e| {
+                    AutomationError::PlatformError(format!(
+                        "Failed to parse activate_tab result: {e}"
+                    ))
+                }),
+            None => Ok(None),
+        }
+    }
 }
 
 pub async fn try_eval_via_extension(
@@ -1421,3 +1590,24 @@ pub async fn try_close_tab(
     }
     bridge.close_tab(tab_id, url, title, timeout).await
 }
+
+pub async fn try_list_tabs(timeout: Duration) -> Result<Option<Vec<BrowserTab>>, AutomationError> {
+    let bridge = ExtensionBridge::global().await;
+    if bridge._server_task.is_finished() {
+        tracing::error!("Extension bridge server task is not running for list_tabs");
+        return Ok(None);
+    }
+    bridge.list_tabs(timeout).await
+}
+
+pub async fn try_activate_tab(
+    target: &TabTarget,
+    timeout: Duration,
+) -> Result<Option<BrowserTab>, AutomationError> {
+    let bridge = ExtensionBridge::global().await;
+    if bridge._server_task.is_finished() {
+        tracing::error!("Extension bridge server task is not running for activate_tab");
+        return Ok(None);
+    }
+    bridge.activate_tab(target, timeout).await
+}
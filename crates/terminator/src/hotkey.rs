@@ -0,0 +1,154 @@
+//! System-wide hotkeys.
+//!
+//! Register one with [`Desktop::register_hotkey`](crate::Desktop::register_hotkey);
+//! the returned [`HotkeyHandle`] keeps it active until dropped.
+
+use crate::{AutomationError, Modifier};
+use std::fmt;
+use std::str::FromStr;
+
+/// Named (non-character) keys a hotkey can use, in canonical spelling
+const NAMED_KEYS: &[(&str, &[&str])] = &[
+    ("Space", &["space"]),
+    ("Enter", &["enter", "return"]),
+    ("Tab", &["tab"]),
+    ("Escape", &["escape", "esc"]),
+    ("Backspace", &["backspace"]),
+    ("Delete", &["delete", "del"]),
+    ("Insert", &["insert", "ins"]),
+    ("Home", &["home"]),
+    ("End", &["end"]),
+    ("PageUp", &["pageup", "pgup"]),
+    ("PageDown", &["pagedown", "pgdn"]),
+    ("Up", &["up"]),
+    ("Down", &["down"]),
+    ("Left", &["left"]),
+    ("Right", &["right"]),
+    ("PrintScreen", &["printscreen", "prtsc"]),
+    ("Pause", &["pause"]),
+];
+
+/// A parsed key combination such as "ctrl+shift+c".
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hotkey {
+    /// Modifiers held with the key, in the order they were written
+    pub modifiers: Vec<Modifier>,
+    /// Canonical key name: an uppercase letter or digit ("C", "5"),
+    /// a function key ("F5"), or a named key ("Space", "PageUp")
+    pub key: String,
+}
+
+impl Hotkey {
+    /// Parse a combo like "ctrl+shift+c" or "Alt+F4".
+    ///
+    /// Parts are separated by `+` and case-insensitive. Modifiers are
+    /// `ctrl`/`control`, `alt`, `shift` and `win`/`super`/`meta`; exactly one
+    /// other key is required.
+    pub fn parse(combo: &str) -> Result<Self, AutomationError> {
+        let invalid = |reason: &str| {
+            AutomationError::InvalidArgument(format!("Invalid hotkey '{combo}': {reason}"))
+        };
+
+        let mut modifiers = Vec::new();
+        let mut key = None;
+
+        for part in combo.split('+').map(str::trim) {
+            if part.is_empty() {
+                return Err(invalid("empty key in combo"));
+            }
+
+            let modifier = match part.to_lowercase().as_str() {
+                "ctrl" | "control" => Some(Modifier::Ctrl),
+                "alt" => Some(Modifier::Alt),
+                "shift" => Some(Modifier::Shift),
+                "win" | "super" | "meta" => Some(Modifier::Win),
+                _ => None,
+            };
+
+            match modifier {
+                Some(modifier) if !modifiers.contains(&modifier) => modifiers.push(modifier),
+                Some(_) => return Err(invalid(&format!("'{part}' is repeated"))),
+                None if key.is_some() => return Err(invalid("more than one non-modifier key")),
+                None => {
+                    key = Some(
+                        canonical_key(part)
+                            .ok_or_else(|| invalid(&format!("unsupported key '{part}'")))?,
+                    )
+                }
+            }
+        }
+
+        let key = key.ok_or_else(|| invalid("missing a non-modifier key"))?;
+        Ok(Self { modifiers, key })
+    }
+}
+
+impl FromStr for Hotkey {
+    type Err = AutomationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl fmt::Display for Hotkey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for modifier in &self.modifiers {
+            write!(f, "{}+", modifier.key_name())?;
+        }
+        write!(f, "{}", self.key)
+    }
+}
+
+/// Normalize a key name, returning None if it isn't supported.
+fn canonical_key(part: &str) -> Option<String> {
+    let mut chars = part.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        return c
+            .is_ascii_alphanumeric()
+            .then(|| c.to_ascii_uppercase().to_string());
+    }
+
+    let lower = part.to_lowercase();
+    if let Some(n) = lower.strip_prefix('f').and_then(|n| n.parse::<u8>().ok()) {
+        return (1..=24).contains(&n).then(|| format!("F{n}"));
+    }
+
+    NAMED_KEYS
+        .iter()
+        .find(|(_, aliases)| aliases.contains(&lower.as_str()))
+        .map(|(name, _)| name.to_string())
+}
+
+/// Keeps a registered hotkey active. Dropping the handle unregisters it.
+pub struct HotkeyHandle {
+    hotkey: Hotkey,
+    #[cfg(target_os = "windows")]
+    _registration: crate::platforms::windows::hotkey::HotkeyRegistration,
+}
+
+impl HotkeyHandle {
+    #[cfg(target_os = "windows")]
+    pub(crate) fn new(
+        hotkey: Hotkey,
+        registration: crate::platforms::windows::hotkey::HotkeyRegistration,
+    ) -> Self {
+        Self {
+            hotkey,
+            _registration: registration,
+        }
+    }
+
+    /// The combination this handle is listening for
+    pub fn hotkey(&self) -> &Hotkey {
+        &self.hotkey
+    }
+}
+
+impl fmt::Debug for HotkeyHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HotkeyHandle")
+            .field("hotkey", &self.hotkey.to_string())
+            .finish()
+    }
+}
@@ -0,0 +1,88 @@
+//! Capacity-bounded caches behind index-based clicking.
+//!
+//! [`Desktop`](crate::Desktop) keeps one cache per vision source (UI tree, OCR,
+//! Omniparser, Gemini, DOM). Each capture replaces its cache wholesale, so they
+//! normally stay small; the capacity is a safety net for long-running agents
+//! whose captures keep growing.
+
+use std::collections::HashMap;
+
+/// Default number of entries each vision cache holds
+pub const DEFAULT_VISION_CACHE_CAPACITY: usize = 10_000;
+
+/// A map from element index to cached data holding at most `capacity` entries.
+///
+/// Entries are only ever written by [`IndexCache::replace_all`], so there is no
+/// use order to track: when a capture is too large, the highest indices are dropped.
+#[derive(Debug, Clone)]
+pub struct IndexCache<V> {
+    entries: HashMap<u32, V>,
+    capacity: usize,
+}
+
+impl<V> Default for IndexCache<V> {
+    fn default() -> Self {
+        Self::new(DEFAULT_VISION_CACHE_CAPACITY)
+    }
+}
+
+impl<V> IndexCache<V> {
+    /// A capacity of 0 is treated as 1.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            entries: HashMap::new(),
+            capacity: capacity.max(1),
+        }
+    }
+
+    pub fn get(&self, index: u32) -> Option<&V> {
+        self.entries.get(&index)
+    }
+
+    /// Replace the whole cache with the results of a new capture.
+    ///
+    /// If the capture has more entries than the cache can hold, the lowest
+    /// indices are kept.
+    pub fn replace_all(&mut self, items: impl IntoIterator<Item = (u32, V)>) {
+        self.entries = items.into_iter().collect();
+        self.truncate();
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Change the capacity, dropping the highest indices if it shrank.
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity.max(1);
+        self.truncate();
+    }
+
+    fn truncate(&mut self) {
+        if self.entries.len() <= self.capacity {
+            return;
+        }
+        tracing::warn!(
+            "Vision cache capacity {} exceeded by {} elements; dropping the highest indices",
+            self.capacity,
+            self.entries.len() - self.capacity
+        );
+        let mut indices: Vec<u32> = self.entries.keys().copied().collect();
+        indices.sort_unstable();
+        for index in &indices[self.capacity..] {
+            self.entries.remove(index);
+        }
+    }
+}
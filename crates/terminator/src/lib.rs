@@ -6,16 +6,23 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fmt;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex, RwLock};
 use sysinfo::{ProcessesToUpdate, System};
 use tracing::{debug, error, info, instrument};
 
 pub mod browser_script;
+pub mod cdp;
+pub mod clock;
 pub mod element;
 pub mod errors;
 pub mod extension_bridge;
 pub mod health;
+pub mod hotkey;
+pub mod index_cache;
+pub mod locale_format;
 pub mod locator;
+pub mod modal;
 pub mod platforms;
 pub mod screenshot;
 pub mod screenshot_logger;
@@ -30,21 +37,31 @@ pub mod utils;
 #[cfg(target_os = "windows")]
 pub mod computer_use;
 
-pub use element::{OcrElement, SerializableUIElement, UIElement, UIElementAttributes};
+pub use cdp::{BrowserSession, DomElement};
+pub use element::{
+    ControlPattern, FormField, MultiSelectMode, OcrElement, SerializableUIElement, UIElement,
+    UIElementAttributes,
+};
 pub use errors::AutomationError;
-pub use locator::Locator;
+pub use hotkey::{Hotkey, HotkeyHandle};
+use index_cache::IndexCache;
+pub use locator::{Locator, MatchScoring, NameNormalization, StaleHandling, TrackedElement};
+pub use modal::{ModalAction, ModalHandlerId, ModalMatcher};
 pub use screenshot::{
-    get_cursor_position, ScreenshotError, ScreenshotResult, DEFAULT_MAX_DIMENSION,
+    get_cursor_position, CompressionLevel, ScreenshotError, ScreenshotResult, DEFAULT_MAX_DIMENSION,
 };
-pub use selector::Selector;
+pub use selector::{ElementState, Selector};
 pub use tokio_util::sync::CancellationToken;
 pub use tree_formatter::{
     format_clustered_tree_from_caches, format_ocr_tree_as_compact_yaml,
     format_tree_as_compact_yaml, format_ui_node_as_compact_yaml, serializable_to_ui_node,
-    ClusteredFormattingResult, ElementSource, OcrFormattingResult, TreeFormattingResult,
+    ClusteredFormattingResult, ElementSource, FlatNode, OcrFormattingResult, TreeFormattingResult,
     UnifiedElement,
 };
-pub use types::{FontStyle, HighlightHandle, OmniparserItem, TextPosition, VisionElement};
+pub use types::{
+    CoordinateConverter, CoordinateMapper, FontStyle, HighlightHandle, OmniparserItem, Rect,
+    TextPosition, VisionElement,
+};
 pub use utils::find_pid_for_process;
 
 // Re-export types from terminator-computer-use crate
@@ -56,37 +73,90 @@ pub use terminator_computer_use::{
 };
 
 // Re-export cross-platform types from platforms
-pub use platforms::{OverlayDisplayMode, PropertyLoadingMode, TreeBuildConfig};
+pub use platforms::{EngineInitConfig, OverlayDisplayMode, PropertyLoadingMode, TreeBuildConfig};
 
 // Re-export window manager types (Windows only)
 #[cfg(target_os = "windows")]
 pub use platforms::windows::window_manager::{
-    WindowCache, WindowInfo, WindowManager, WindowPlacement,
+    WindowCache, WindowCacheRefreshGuard, WindowInfo, WindowManager, WindowPlacement,
 };
 
+/// Default number of parent hops `find_parent_window` takes before giving up.
+pub const DEFAULT_PARENT_WINDOW_MAX_ITERATIONS: usize = 100;
+
+static PARENT_WINDOW_MAX_ITERATIONS: AtomicUsize =
+    AtomicUsize::new(DEFAULT_PARENT_WINDOW_MAX_ITERATIONS);
+
+/// Set the iteration limit used by `find_parent_window`.
+///
+/// Deeply nested applications (e.g. browsers or Electron apps) can need more than
+/// the default of 100 hops. A value of 0 resets to the default.
+pub fn set_parent_window_max_iterations(max_iters: usize) {
+    let value = if max_iters == 0 {
+        DEFAULT_PARENT_WINDOW_MAX_ITERATIONS
+    } else {
+        max_iters
+    };
+    PARENT_WINDOW_MAX_ITERATIONS.store(value, Ordering::Relaxed);
+}
+
+/// Get the iteration limit currently used by `find_parent_window`.
+pub fn parent_window_max_iterations() -> usize {
+    PARENT_WINDOW_MAX_ITERATIONS.load(Ordering::Relaxed)
+}
+
 /// Walk up the element tree to find the parent Window or Pane element.
 ///
 /// This is useful when you have a focused element (e.g., a button inside a window)
 /// and need to find the containing window to build a UI tree from.
 ///
+/// Uses the limit configured via `set_parent_window_max_iterations`. Use
+/// `find_parent_window_with_limit` to distinguish hitting the limit from
+/// reaching the root.
+///
 /// # Arguments
 /// * `element` - The UIElement to start from
 ///
 /// # Returns
 /// The parent Window/Pane element, or None if not found
 pub fn find_parent_window(element: &UIElement) -> Option<UIElement> {
+    find_parent_window_with_limit(element, parent_window_max_iterations())
+        .ok()
+        .flatten()
+}
+
+/// Walk up the element tree to find the parent Window or Pane element,
+/// taking at most `max_iters` steps.
+///
+/// # Arguments
+/// * `element` - The UIElement to start from
+/// * `max_iters` - Maximum number of elements to inspect before giving up
+///
+/// # Returns
+/// * `Ok(Some(window))` - The parent Window/Pane element
+/// * `Ok(None)` - The root was reached without finding a window
+///
+/// # Errors
+/// * `AutomationError::TraversalLimitExceeded` - If `max_iters` elements were
+///   inspected without finding a window or reaching the root
+/// * Any error returned while fetching an element's parent
+pub fn find_parent_window_with_limit(
+    element: &UIElement,
+    max_iters: usize,
+) -> Result<Option<UIElement>, AutomationError> {
     let mut current = element.clone();
     let start_role = current.role();
     let start_name = current.name().unwrap_or_default();
 
     tracing::debug!(
-        "find_parent_window: starting from element role='{}' name='{}'",
+        "find_parent_window: starting from element role='{}' name='{}' (limit {})",
         start_role,
-        start_name
+        start_name,
+        max_iters
     );
 
     // Limit iterations to prevent infinite loops in malformed trees
-    for depth in 0..100 {
+    for depth in 0..max_iters {
         let role = current.role();
         let name = current.name().unwrap_or_default();
 
@@ -97,7 +167,7 @@ pub fn find_parent_window(element: &UIElement) -> Option<UIElement> {
                 role,
                 name
             );
-            return Some(current);
+            return Ok(Some(current));
         }
 
         match current.parent() {
@@ -116,7 +186,7 @@ pub fn find_parent_window(element: &UIElement) -> Option<UIElement> {
                     role,
                     name
                 );
-                return None;
+                return Ok(None);
             }
             Err(e) => {
                 tracing::warn!(
@@ -126,15 +196,83 @@ pub fn find_parent_window(element: &UIElement) -> Option<UIElement> {
                     name,
                     e
                 );
-                return None;
+                return Err(e);
             }
         }
     }
     tracing::warn!(
-        "find_parent_window: hit iteration limit without finding window (started from role='{}' name='{}')",
+        "find_parent_window: hit iteration limit {} without finding window (started from role='{}' name='{}')",
+        max_iters,
         start_role,
         start_name
     );
+    Err(AutomationError::TraversalLimitExceeded(format!(
+        "no Window or Pane ancestor within {max_iters} levels of element role='{start_role}' name='{start_name}'"
+    )))
+}
+
+/// Native handle of the top-level window containing `element` (a child of the root)
+fn top_level_window_handle(element: &UIElement) -> Option<isize> {
+    let mut current = element.clone();
+    for _ in 0..parent_window_max_iterations() {
+        let parent = current.parent().ok().flatten()?;
+        if parent.parent().ok().flatten().is_none() {
+            return current.get_native_window_handle().ok();
+        }
+        current = parent;
+    }
+    None
+}
+
+/// Patterns that mark an element as the actual target of a click.
+pub const INTERACTABLE_PATTERNS: &[ControlPattern] = &[
+    ControlPattern::Invoke,
+    ControlPattern::SelectionItem,
+    ControlPattern::Toggle,
+];
+
+/// Walk up from `element` (inclusive) to the nearest element exposing one of the
+/// [`INTERACTABLE_PATTERNS`].
+///
+/// Vision and OCR hits often land on a label inside a clickable card or list item;
+/// the label itself does nothing when clicked, its interactable ancestor does. The
+/// walk stops at the containing window and uses the limit configured via
+/// `set_parent_window_max_iterations`.
+///
+/// # Returns
+/// The interactable element, or None if there is none below the window
+pub fn find_interactable_ancestor(element: &UIElement) -> Option<UIElement> {
+    let mut current = element.clone();
+    for depth in 0..parent_window_max_iterations() {
+        let patterns = current.supported_patterns();
+        if patterns
+            .iter()
+            .any(|pattern| INTERACTABLE_PATTERNS.contains(pattern))
+        {
+            tracing::debug!(
+                "find_interactable_ancestor: found role='{}' name='{}' at depth {} (patterns: {:?})",
+                current.role(),
+                current.name().unwrap_or_default(),
+                depth,
+                patterns
+            );
+            return Some(current);
+        }
+
+        if current.role() == "Window" {
+            return None;
+        }
+        match current.parent() {
+            Ok(Some(parent)) => current = parent,
+            Ok(None) => return None,
+            Err(e) => {
+                tracing::debug!(
+                    "find_interactable_ancestor: parent() failed at depth {depth}: {e}"
+                );
+                return None;
+            }
+        }
+    }
     None
 }
 
@@ -162,6 +300,50 @@ pub enum ClickType {
     Right,
 }
 
+/// Direction for incremental scrolling
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ScrollDirection {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl ScrollDirection {
+    /// Direction name accepted by `UIElement::scroll`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ScrollDirection::Up => "up",
+            ScrollDirection::Down => "down",
+            ScrollDirection::Left => "left",
+            ScrollDirection::Right => "right",
+        }
+    }
+}
+
+/// Modifier key held down during a key combo
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Modifier {
+    Ctrl,
+    Alt,
+    Shift,
+    /// Windows logo key
+    Win,
+}
+
+impl Modifier {
+    /// Key name in the `{Key}` syntax accepted by `press_key`
+    pub fn key_name(&self) -> &'static str {
+        match self {
+            Modifier::Ctrl => "Ctrl",
+            Modifier::Alt => "Alt",
+            Modifier::Shift => "Shift",
+            Modifier::Win => "Win",
+        }
+    }
+}
+
 /// Source of indexed elements for click targeting
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
 pub enum VisionType {
@@ -216,11 +398,26 @@ pub struct ActionResult {
     pub verification: Option<TypeVerification>,
 }
 
-/// Holds the output of a terminal command execution
+/// Holds the output of a terminal command execution.
+///
+/// Non-exhaustive so fields like `combined` can be added without breaking callers;
+/// only engines construct it.
+#[non_exhaustive]
 pub struct CommandOutput {
     pub exit_status: Option<i32>,
     pub stdout: String,
     pub stderr: String,
+    /// stdout and stderr interleaved line by line in the order they were written.
+    /// Only set when [`RunCommandOptions::capture_combined`] is requested
+    pub combined: Option<String>,
+}
+
+/// Options for [`Desktop::run_command_with_options`] and [`Desktop::run_with_options`]
+#[derive(Debug, Clone, Default)]
+pub struct RunCommandOptions {
+    /// Read both streams as they arrive and also return them interleaved in
+    /// [`CommandOutput::combined`]
+    pub capture_combined: bool,
 }
 
 /// Result of get_window_tree operation with all computed data
@@ -254,12 +451,39 @@ pub struct WindowTreeResult {
 pub struct UiDiffOptions {
     /// Maximum depth for tree capture
     pub max_depth: Option<usize>,
-    /// Delay in ms after action for UI to settle (default 1500)
+    /// Delay in ms after action for UI to settle (default 1500). Ignored when `settle` is set
     pub settle_delay_ms: Option<u64>,
+    /// How to wait for the UI to settle before capturing the "after" tree.
+    /// Defaults to a fixed `settle_delay_ms` sleep
+    pub settle: Option<SettleStrategy>,
     /// Include detailed element attributes (enabled, focused, etc.)
     pub include_detailed_attributes: Option<bool>,
+    /// Also screenshot the window before and after the action and return a crop of
+    /// the region whose pixels changed
+    pub capture_visual_diff: bool,
+}
+
+/// How UI diff capture waits for the UI to settle after the action
+#[derive(Debug, Clone, PartialEq)]
+pub enum SettleStrategy {
+    /// Sleep for a fixed duration
+    Fixed(std::time::Duration),
+    /// Re-capture the window tree until it has not changed for `quiet_for`,
+    /// giving up and using the latest capture after `timeout`
+    UntilIdle {
+        quiet_for: std::time::Duration,
+        timeout: std::time::Duration,
+    },
+    /// Wait until `selector` matches an element, giving up after `timeout`
+    UntilSelector {
+        selector: String,
+        timeout: std::time::Duration,
+    },
 }
 
+/// How often `SettleStrategy::UntilIdle` re-captures the window tree
+const SETTLE_IDLE_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_millis(100);
+
 /// Result of UI diff capture
 #[derive(Debug, Clone)]
 pub struct UiDiffResult {
@@ -267,6 +491,68 @@ pub struct UiDiffResult {
     pub diff: String,
     /// Whether any UI changes were detected
     pub has_changes: bool,
+    /// Cropped screenshot of the visually changed region, when `capture_visual_diff`
+    /// was set and pixels changed
+    pub visual_diff: Option<VisualDiff>,
+}
+
+/// Screenshot of the region that visually changed during an action
+#[derive(Debug, Clone)]
+pub struct VisualDiff {
+    /// The after-action window screenshot cropped to the changed region
+    pub screenshot: ScreenshotResult,
+    /// Changed region as (x, y, width, height) in window screenshot pixels
+    pub region: (u32, u32, u32, u32),
+    /// Changed region in screen coordinates, for clicking or highlighting
+    pub screen_bounds: Rect,
+}
+
+/// Per-channel difference below which pixels are treated as unchanged
+const VISUAL_DIFF_TOLERANCE: u8 = 16;
+
+/// Capture the window containing `element` ahead of an action, for visual diffing
+fn capture_visual_diff_baseline(element: &UIElement) -> Option<(UIElement, ScreenshotResult)> {
+    let window = find_parent_window(element)?;
+    match window.capture() {
+        Ok(screenshot) => Some((window, screenshot)),
+        Err(e) => {
+            debug!(
+                "[ui_diff] Failed to capture screenshot before action: {}",
+                e
+            );
+            None
+        }
+    }
+}
+
+/// Re-capture the window after an action and crop the region that changed
+fn compute_visual_diff(window: &UIElement, before: &ScreenshotResult) -> Option<VisualDiff> {
+    let after = match window.capture() {
+        Ok(screenshot) => screenshot,
+        Err(e) => {
+            debug!("[ui_diff] Failed to capture screenshot after action: {}", e);
+            return None;
+        }
+    };
+    let region = screenshot::changed_region(before, &after, VISUAL_DIFF_TOLERANCE)?;
+    let (x, y, width, height) = region;
+
+    // Window bounds are logical while captures are physical pixels
+    let screen_bounds = match window.bounds() {
+        Ok(bounds) => CoordinateMapper::for_capture(bounds, after.width, after.height)
+            .physical_to_screen(Rect::new(x as f64, y as f64, width as f64, height as f64)),
+        Err(_) => Rect::new(x as f64, y as f64, width as f64, height as f64),
+    };
+    info!(
+        "[ui_diff] Visual change detected in region {}x{} at ({}, {})",
+        width, height, x, y
+    );
+
+    Some(VisualDiff {
+        screenshot: after.crop(x, y, width, height),
+        region,
+        screen_bounds,
+    })
 }
 
 /// Represents a monitor/display device
@@ -344,6 +630,53 @@ impl fmt::Debug for UINode {
 }
 
 impl UINode {
+    /// Replace every node's `id` with a hash of its selector path from this root, its
+    /// role and its automation id.
+    ///
+    /// The same element gets the same id in every capture as long as it and its
+    /// ancestors keep their role, name and automation id, so ids can correlate nodes
+    /// across captures. Siblings alike in all of these share an id. These are not the
+    /// element ids that `#id` selectors match.
+    pub fn assign_stable_ids(&mut self) {
+        self.assign_stable_ids_under(None);
+    }
+
+    fn assign_stable_ids_under(&mut self, parent_path: Option<&str>) {
+        let segment = crate::selector::build_selector_segment(
+            &self.attributes.role,
+            self.attributes.name.as_deref(),
+        );
+        let path = match parent_path {
+            Some(parent) => format!("{parent} >> {segment}"),
+            None => segment,
+        };
+        self.id = Some(Self::stable_id(&path, &self.attributes));
+        for child in &mut self.children {
+            child.assign_stable_ids_under(Some(&path));
+        }
+    }
+
+    /// Stable id of a node from its selector path from the tree root and its attributes.
+    /// Engines that build trees themselves use this to assign ids as they go.
+    pub(crate) fn stable_id(selector_path: &str, attributes: &UIElementAttributes) -> String {
+        let automation_id = attributes
+            .properties
+            .get("AutomationId")
+            .cloned()
+            .flatten()
+            .and_then(|id| id.as_str().map(str::to_string))
+            .unwrap_or_default();
+        // Unit separators keep the parts from running into each other
+        let hash = blake3::hash(
+            format!(
+                "{selector_path}\u{1f}{}\u{1f}{automation_id}",
+                attributes.role
+            )
+            .as_bytes(),
+        );
+        hash.to_hex()[..16].to_string()
+    }
+
     /// Helper method for debug formatting with depth control
     fn debug_with_depth(
         &self,
@@ -418,11 +751,11 @@ impl fmt::Debug for DebugNodeWithDepth<'_> {
 
 /// Cached element bounds for index-based click targeting
 /// Stored as (role/label, name/text, bounds, optional_selector)
-type UiaBoundsCache = HashMap<u32, (String, String, (f64, f64, f64, f64), Option<String>)>;
+type UiaBoundsCache = IndexCache<(String, String, (f64, f64, f64, f64), Option<String>)>;
 /// OCR bounds cache: (text, bounds)
-type OcrBoundsCache = HashMap<u32, (String, (f64, f64, f64, f64))>;
+type OcrBoundsCache = IndexCache<(String, (f64, f64, f64, f64))>;
 /// DOM bounds cache: (tag, id, bounds)
-type DomBoundsCache = HashMap<u32, (String, String, (f64, f64, f64, f64))>;
+type DomBoundsCache = IndexCache<(String, String, (f64, f64, f64, f64))>;
 
 /// The main entry point for UI automation
 pub struct Desktop {
@@ -434,26 +767,75 @@ pub struct Desktop {
     /// Cache for OCR element bounds
     ocr_cache: Arc<Mutex<OcrBoundsCache>>,
     /// Cache for Omniparser element bounds
-    omniparser_cache: Arc<Mutex<HashMap<u32, OmniparserItem>>>,
+    omniparser_cache: Arc<Mutex<IndexCache<OmniparserItem>>>,
     /// Cache for Gemini Vision element bounds
-    vision_cache: Arc<Mutex<HashMap<u32, VisionElement>>>,
+    vision_cache: Arc<Mutex<IndexCache<VisionElement>>>,
     /// Cache for DOM element bounds
     dom_cache: Arc<Mutex<DomBoundsCache>>,
+    /// Registered modal dialog handlers and their background watcher
+    modal_watcher: Arc<modal::ModalWatcher>,
+    /// Whether locator not-found errors include "did you mean" suggestions
+    verbose_errors: Arc<AtomicBool>,
+    /// Time source for locator polling and UI settle delays
+    clock: Arc<dyn clock::Clock>,
 }
 
 impl Desktop {
     #[instrument(skip(use_background_apps, activate_app))]
     pub fn new(use_background_apps: bool, activate_app: bool) -> Result<Self, AutomationError> {
         let engine = platforms::create_engine(use_background_apps, activate_app)?;
-        Ok(Self {
+        Ok(Self::with_engine(engine))
+    }
+
+    /// Create a desktop, retrying engine initialization with backoff as `config` says.
+    ///
+    /// Useful where the accessibility service may not be ready on the first try, such
+    /// as a cold start in CI.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use std::time::Duration;
+    /// use terminator::{Desktop, EngineInitConfig};
+    ///
+    /// let desktop = Desktop::new_with_config(EngineInitConfig {
+    ///     attempts: 5,
+    ///     initial_backoff: Duration::from_millis(500),
+    ///     ..Default::default()
+    /// })?;
+    /// # Ok::<(), terminator::AutomationError>(())
+    /// ```
+    #[instrument(skip(config))]
+    pub fn new_with_config(config: EngineInitConfig) -> Result<Self, AutomationError> {
+        let engine = platforms::create_engine_with_config(&config)?;
+        Ok(Self::with_engine(engine))
+    }
+
+    /// Create a desktop backed by the given accessibility engine instead of the
+    /// platform one.
+    ///
+    /// Mainly useful for tests: pass a [`platforms::mock::MockEngine`] to drive
+    /// the desktop against a scripted UI tree.
+    pub fn with_engine(engine: Arc<dyn platforms::AccessibilityEngine>) -> Self {
+        Self {
             engine,
             cancellation_token: Arc::new(RwLock::new(CancellationToken::new())),
-            uia_cache: Arc::new(Mutex::new(HashMap::new())),
-            ocr_cache: Arc::new(Mutex::new(HashMap::new())),
-            omniparser_cache: Arc::new(Mutex::new(HashMap::new())),
-            vision_cache: Arc::new(Mutex::new(HashMap::new())),
-            dom_cache: Arc::new(Mutex::new(HashMap::new())),
-        })
+            uia_cache: Arc::new(Mutex::new(IndexCache::default())),
+            ocr_cache: Arc::new(Mutex::new(IndexCache::default())),
+            omniparser_cache: Arc::new(Mutex::new(IndexCache::default())),
+            vision_cache: Arc::new(Mutex::new(IndexCache::default())),
+            dom_cache: Arc::new(Mutex::new(IndexCache::default())),
+            modal_watcher: Arc::new(modal::ModalWatcher::default()),
+            verbose_errors: Arc::new(AtomicBool::new(false)),
+            clock: Arc::new(clock::SystemClock),
+        }
+    }
+
+    /// Use the given clock for locator polling and UI settle delays.
+    ///
+    /// Tests pass a [`clock::FakeClock`] so timeouts elapse instantly.
+    pub fn with_clock(mut self, clock: Arc<dyn clock::Clock>) -> Self {
+        self.clock = clock;
+        self
     }
 
     /// Initializet the desktop without arguments
@@ -494,6 +876,200 @@ impl Desktop {
     pub fn locator(&self, selector: impl Into<Selector>) -> Locator {
         let selector = selector.into();
         Locator::new(self.engine.clone(), selector)
+            .with_verbose_errors(self.verbose_errors.clone())
+            .with_clock(self.clock.clone())
+    }
+
+    /// Include "did you mean" suggestions in locator not-found errors.
+    ///
+    /// When enabled, a failed lookup from [`Desktop::locator`] scans the search
+    /// root for the elements whose names are closest to the requested one and
+    /// lists their roles and names in the error. Off by default because the
+    /// scan adds time to every failure. Applies to all clones of this desktop.
+    pub fn set_verbose_errors(&self, enabled: bool) {
+        self.verbose_errors.store(enabled, Ordering::Relaxed);
+    }
+
+    /// Whether verbose not-found errors are enabled
+    pub fn verbose_errors(&self) -> bool {
+        self.verbose_errors.load(Ordering::Relaxed)
+    }
+
+    /// Resolve many selectors concurrently, returning results in the same order as the input.
+    ///
+    /// Each selector is resolved on its own blocking task, so verification-heavy steps
+    /// don't pay for N serial tree walks. Identical selectors are only resolved once;
+    /// each repeat gets a clone of its first occurrence's result.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::time::Duration;
+    /// use terminator::Desktop;
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let desktop = Desktop::new_default().unwrap();
+    ///     let results = desktop
+    ///         .locate_all(
+    ///             &["process:notepad >> role:Document", "process:notepad >> role:MenuBar"],
+    ///             Duration::from_secs(2),
+    ///         )
+    ///         .await;
+    ///     for result in results {
+    ///         println!("found: {}", result.is_ok());
+    ///     }
+    /// }
+    /// ```
+    #[instrument(skip(self, selectors))]
+    pub async fn locate_all(
+        &self,
+        selectors: &[&str],
+        timeout: std::time::Duration,
+    ) -> Vec<Result<UIElement, AutomationError>> {
+        // Deduplicate parsed selectors so repeated entries share a single lookup
+        let mut unique: Vec<Selector> = Vec::new();
+        let mut slots: Vec<usize> = Vec::with_capacity(selectors.len());
+        for raw in selectors {
+            let selector = Selector::from(*raw);
+            let slot = match unique.iter().position(|s| *s == selector) {
+                Some(pos) => pos,
+                None => {
+                    unique.push(selector);
+                    unique.len() - 1
+                }
+            };
+            slots.push(slot);
+        }
+
+        debug!(
+            "locate_all: resolving {} selectors ({} unique) concurrently",
+            selectors.len(),
+            unique.len()
+        );
+
+        let lookups = unique.into_iter().map(|selector| {
+            let locator = self.locator(selector);
+            async move { locator.first(Some(timeout)).await }
+        });
+        let resolved = futures::future::join_all(lookups).await;
+
+        // Duplicates get a clone of the element or error their first occurrence resolved to
+        slots
+            .into_iter()
+            .map(|slot| resolved[slot].clone())
+            .collect()
+    }
+
+    /// Automatically handle dialogs matching `matcher` by dispatching `action`.
+    ///
+    /// While any handler is registered, a background thread polls the focused
+    /// window; each time focus moves to a window that matches, the first matching
+    /// handler's action runs against it (e.g. clicking "Allow" on a permission prompt).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use terminator::{Desktop, ModalAction, ModalMatcher};
+    /// let desktop = Desktop::new_default()?;
+    /// let id = desktop.register_modal_handler(
+    ///     ModalMatcher::title("Save changes").with_process("notepad"),
+    ///     ModalAction::ClickButton("Don't Save".to_string()),
+    /// );
+    /// // ... run the workflow ...
+    /// desktop.remove_modal_handler(id);
+    /// # Ok::<(), terminator::AutomationError>(())
+    /// ```
+    pub fn register_modal_handler(
+        &self,
+        matcher: ModalMatcher,
+        action: ModalAction,
+    ) -> ModalHandlerId {
+        self.modal_watcher
+            .register(self.engine.clone(), matcher, action)
+    }
+
+    /// Register a system-wide hotkey such as "ctrl+shift+c".
+    ///
+    /// `callback` runs on a dedicated listener thread each time the combination
+    /// is pressed. The hotkey stays registered until the returned handle is dropped.
+    ///
+    /// # Errors
+    /// `InvalidArgument` if the combo can't be parsed, `PlatformError` if the
+    /// combination is already taken by another application.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use terminator::Desktop;
+    /// let desktop = Desktop::new_default()?;
+    /// let _handle = desktop.register_hotkey("ctrl+shift+c", || println!("pressed"))?;
+    /// // The hotkey is active until `_handle` is dropped.
+    /// # Ok::<(), terminator::AutomationError>(())
+    /// ```
+    pub fn register_hotkey(
+        &self,
+        combo: &str,
+        callback: impl Fn() + Send + 'static,
+    ) -> Result<HotkeyHandle, AutomationError> {
+        let hotkey = Hotkey::parse(combo)?;
+
+        #[cfg(target_os = "windows")]
+        {
+            let registration = platforms::windows::hotkey::register(&hotkey, Box::new(callback))?;
+            Ok(HotkeyHandle::new(hotkey, registration))
+        }
+
+        #[cfg(not(target_os = "windows"))]
+        {
+            let _ = callback;
+            Err(AutomationError::UnsupportedPlatform(format!(
+                "Global hotkey '{hotkey}' is only supported on Windows"
+            )))
+        }
+    }
+
+    /// Check whether the desktop session can be automated right now.
+    ///
+    /// Returns false when the workstation is locked, a UAC prompt holds the secure
+    /// desktop, or the (RDP) session is disconnected. UI Automation sees no windows in
+    /// those states, so callers can fail fast with a clear error instead of getting
+    /// empty trees and element-not-found errors.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use terminator::{AutomationError, Desktop};
+    ///
+    /// let desktop = Desktop::new_default()?;
+    /// if !desktop.is_session_interactive()? {
+    ///     return Err(AutomationError::PlatformError("session locked".to_string()));
+    /// }
+    /// # Ok::<(), terminator::AutomationError>(())
+    /// ```
+    pub fn is_session_interactive(&self) -> Result<bool, AutomationError> {
+        #[cfg(target_os = "windows")]
+        {
+            platforms::windows::health::is_session_interactive()
+        }
+
+        #[cfg(not(target_os = "windows"))]
+        {
+            Err(AutomationError::UnsupportedPlatform(
+                "Session state checks are only supported on Windows".to_string(),
+            ))
+        }
+    }
+
+    /// Whether a UAC elevation prompt is currently up. UI Automation can't reach past it,
+    /// so locator waits that give up while it is showing fail with
+    /// [`AutomationError::ElevationRequired`] rather than a timeout.
+    pub fn is_elevation_prompt_active(&self) -> bool {
+        self.engine.is_elevation_prompt_active()
+    }
+
+    /// Remove a handler registered with [`Desktop::register_modal_handler`].
+    /// Returns false if the handler was already removed.
+    pub fn remove_modal_handler(&self, id: ModalHandlerId) -> bool {
+        self.modal_watcher.remove(id)
     }
 
     #[instrument(skip(self))]
@@ -521,6 +1097,48 @@ impl Desktop {
         self.engine.activate_application(app_name)
     }
 
+    /// Activate an application and wait until its window is actually foreground.
+    ///
+    /// [`Desktop::activate_application`] returns once activation is requested, which
+    /// can be before the OS has switched windows, so input sent right after may land in
+    /// the previous window. This polls [`Desktop::foreground_window`] every 50ms until
+    /// it belongs to the application's process or shares its top-level window, replacing
+    /// fixed sleeps after activation. The latter covers UWP apps, whose foreground frame
+    /// is owned by ApplicationFrameHost rather than the app.
+    ///
+    /// Returns [`AutomationError::Timeout`] if the application isn't foreground within
+    /// `timeout`.
+    #[instrument(skip(self, app_name))]
+    pub async fn activate_application_and_wait(
+        &self,
+        app_name: &str,
+        timeout: std::time::Duration,
+    ) -> Result<(), AutomationError> {
+        let app = self.engine.get_application_by_name(app_name)?;
+        let pid = app.process_id()?;
+        let app_window = top_level_window_handle(&app);
+        self.engine.activate_application(app_name)?;
+
+        let poll_interval = std::time::Duration::from_millis(50);
+        let start = self.clock.now();
+        loop {
+            let is_foreground = self.engine.get_foreground_window().is_ok_and(|window| {
+                window.process_id().ok() == Some(pid)
+                    || (app_window.is_some() && top_level_window_handle(&window) == app_window)
+            });
+            if is_foreground {
+                debug!("'{app_name}' (pid {pid}) is foreground");
+                return Ok(());
+            }
+            if self.clock.now().duration_since(start) >= timeout {
+                return Err(AutomationError::Timeout(format!(
+                    "'{app_name}' (pid {pid}) did not become the foreground window within {timeout:?}"
+                )));
+            }
+            self.clock.sleep(poll_interval).await;
+        }
+    }
+
     #[instrument(skip(self, url, browser))]
     pub fn open_url(
         &self,
@@ -541,7 +1159,21 @@ impl Desktop {
         windows_command: Option<&str>,
         unix_command: Option<&str>,
     ) -> Result<CommandOutput, AutomationError> {
-        self.engine.run_command(windows_command, unix_command).await
+        self.run_command_with_options(windows_command, unix_command, RunCommandOptions::default())
+            .await
+    }
+
+    /// Like [`Desktop::run_command`], with [`RunCommandOptions`]
+    #[instrument(skip(self, windows_command, unix_command))]
+    pub async fn run_command_with_options(
+        &self,
+        windows_command: Option<&str>,
+        unix_command: Option<&str>,
+        options: RunCommandOptions,
+    ) -> Result<CommandOutput, AutomationError> {
+        self.engine
+            .run_command_with_options(windows_command, unix_command, &options)
+            .await
     }
 
     /// Execute a shell command using GitHub Actions-style syntax
@@ -571,6 +1203,39 @@ impl Desktop {
         command: &str,
         shell: Option<&str>,
         working_directory: Option<&str>,
+    ) -> Result<CommandOutput, AutomationError> {
+        self.run_with_options(
+            command,
+            shell,
+            working_directory,
+            RunCommandOptions::default(),
+        )
+        .await
+    }
+
+    /// Like [`Desktop::run`], with [`RunCommandOptions`]
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use terminator::{Desktop, RunCommandOptions};
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let desktop = Desktop::new_default().unwrap();
+    ///     let options = RunCommandOptions { capture_combined: true };
+    ///     let output = desktop
+    ///         .run_with_options("cargo build", None, None, options)
+    ///         .await
+    ///         .unwrap();
+    ///     println!("{}", output.combined.unwrap_or_default());
+    /// }
+    /// ```
+    #[instrument(skip(self, command))]
+    pub async fn run_with_options(
+        &self,
+        command: &str,
+        shell: Option<&str>,
+        working_directory: Option<&str>,
+        options: RunCommandOptions,
     ) -> Result<CommandOutput, AutomationError> {
         // Determine which shell to use based on platform and user preference
         let (windows_cmd, unix_cmd) = if cfg!(target_os = "windows") {
@@ -610,7 +1275,7 @@ impl Desktop {
         };
 
         self.engine
-            .run_command(windows_cmd.as_deref(), unix_cmd.as_deref())
+            .run_command_with_options(windows_cmd.as_deref(), unix_cmd.as_deref(), &options)
             .await
     }
 
@@ -879,6 +1544,32 @@ impl Desktop {
         )
     }
 
+    /// Capture the window of process `pid` and OCR it, with line and word bounds in
+    /// absolute screen coordinates
+    #[instrument(skip(self))]
+    pub fn ocr_window_with_bounds(&self, pid: u32) -> Result<OcrElement, AutomationError> {
+        let window = self
+            .applications()?
+            .into_iter()
+            .find(|app| app.process_id().unwrap_or(0) == pid)
+            .ok_or_else(|| {
+                AutomationError::ElementNotFound(format!("No window found for PID {pid}"))
+            })?;
+
+        let bounds = window.bounds()?;
+        let screenshot = window.capture()?;
+
+        // OCR reports physical screenshot pixels; map them back to logical screen coordinates
+        let mapper = CoordinateMapper::for_capture(bounds, screenshot.width, screenshot.height);
+        let (dpi_scale_x, dpi_scale_y) = mapper.dpi_scale;
+        info!(
+            "OCR DPI: window_bounds(logical)=({:.0},{:.0},{:.0},{:.0}), screenshot(physical)={}x{}, dpi_scale=({:.3},{:.3})",
+            bounds.0, bounds.1, bounds.2, bounds.3, screenshot.width, screenshot.height, dpi_scale_x, dpi_scale_y
+        );
+
+        self.ocr_screenshot_with_bounds(&screenshot, bounds.0, bounds.1, dpi_scale_x, dpi_scale_y)
+    }
+
     /// Click at absolute screen coordinates
     /// This is useful for clicking on OCR-detected text elements
     /// If `restore_cursor` is true, the cursor position will be restored after the click
@@ -907,6 +1598,38 @@ impl Desktop {
             .click_at_coordinates_with_type(x, y, click_type, restore_cursor)
     }
 
+    /// Drag the left mouse button along a path of absolute screen coordinates.
+    ///
+    /// Presses at the first point, moves through each following point waiting
+    /// `hold_ms_between` milliseconds after every move, and releases at the last one.
+    /// Useful for drawing on canvases, signature pads and dragging sliders along
+    /// arbitrary paths. Needs at least two points.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use terminator::Desktop;
+    ///
+    /// let desktop = Desktop::new_default()?;
+    /// // A "V" stroke on a whiteboard
+    /// desktop.drag_path(&[(400.0, 300.0), (450.0, 400.0), (500.0, 300.0)], 15)?;
+    /// # Ok::<(), terminator::AutomationError>(())
+    /// ```
+    #[instrument(skip(self, points), fields(points = points.len()))]
+    pub fn drag_path(
+        &self,
+        points: &[(f64, f64)],
+        hold_ms_between: u64,
+    ) -> Result<(), AutomationError> {
+        if points.len() < 2 {
+            return Err(AutomationError::InvalidArgument(format!(
+                "drag_path needs at least 2 points, got {}",
+                points.len()
+            )));
+        }
+        self.engine
+            .drag_path(points, std::time::Duration::from_millis(hold_ms_between))
+    }
+
     /// Click within element bounds at a specified position (percentage-based).
     ///
     /// This is useful for clicking on elements from UI tree, OCR, omniparser, gemini vision, or DOM
@@ -976,7 +1699,7 @@ impl Desktop {
                 let cache = self.uia_cache.lock().map_err(|e| {
                     AutomationError::Internal(format!("Failed to lock UIA cache: {}", e))
                 })?;
-                let entry = cache.get(&index).ok_or_else(|| {
+                let entry = cache.get(index).ok_or_else(|| {
                     AutomationError::ElementNotFound(format!(
                         "UI tree index #{} not found. Call get_window_tree first.",
                         index
@@ -993,7 +1716,7 @@ impl Desktop {
                 let cache = self.ocr_cache.lock().map_err(|e| {
                     AutomationError::Internal(format!("Failed to lock OCR cache: {}", e))
                 })?;
-                let entry = cache.get(&index).ok_or_else(|| {
+                let entry = cache.get(index).ok_or_else(|| {
                     AutomationError::ElementNotFound(format!(
                         "OCR index #{} not found. Call ocr methods first.",
                         index
@@ -1005,7 +1728,7 @@ impl Desktop {
                 let cache = self.omniparser_cache.lock().map_err(|e| {
                     AutomationError::Internal(format!("Failed to lock Omniparser cache: {}", e))
                 })?;
-                let item = cache.get(&index).ok_or_else(|| {
+                let item = cache.get(index).ok_or_else(|| {
                     AutomationError::ElementNotFound(format!(
                         "Omniparser index #{} not found. Call omniparser methods first.",
                         index
@@ -1027,7 +1750,7 @@ impl Desktop {
                 let cache = self.vision_cache.lock().map_err(|e| {
                     AutomationError::Internal(format!("Failed to lock Vision cache: {}", e))
                 })?;
-                let item = cache.get(&index).ok_or_else(|| {
+                let item = cache.get(index).ok_or_else(|| {
                     AutomationError::ElementNotFound(format!(
                         "Gemini index #{} not found. Call gemini vision methods first.",
                         index
@@ -1049,7 +1772,7 @@ impl Desktop {
                 let cache = self.dom_cache.lock().map_err(|e| {
                     AutomationError::Internal(format!("Failed to lock DOM cache: {}", e))
                 })?;
-                let entry = cache.get(&index).ok_or_else(|| {
+                let entry = cache.get(index).ok_or_else(|| {
                     AutomationError::ElementNotFound(format!(
                         "DOM index #{} not found. Call DOM methods first.",
                         index
@@ -1089,8 +1812,7 @@ impl Desktop {
     #[allow(clippy::type_complexity)]
     pub fn populate_ocr_cache(&self, bounds_map: HashMap<u32, (String, (f64, f64, f64, f64))>) {
         if let Ok(mut cache) = self.ocr_cache.lock() {
-            cache.clear();
-            cache.extend(bounds_map);
+            cache.replace_all(bounds_map);
             debug!("Populated OCR cache with {} elements", cache.len());
         }
     }
@@ -1102,8 +1824,7 @@ impl Desktop {
     /// * `items` - Map of index to OmniparserItem from Omniparser result
     pub fn populate_omniparser_cache(&self, items: HashMap<u32, OmniparserItem>) {
         if let Ok(mut cache) = self.omniparser_cache.lock() {
-            cache.clear();
-            cache.extend(items);
+            cache.replace_all(items);
             debug!("Populated Omniparser cache with {} elements", cache.len());
         }
     }
@@ -1115,8 +1836,7 @@ impl Desktop {
     /// * `items` - Map of index to VisionElement from Gemini result
     pub fn populate_vision_cache(&self, items: HashMap<u32, VisionElement>) {
         if let Ok(mut cache) = self.vision_cache.lock() {
-            cache.clear();
-            cache.extend(items);
+            cache.replace_all(items);
             debug!("Populated Vision cache with {} elements", cache.len());
         }
     }
@@ -1132,8 +1852,7 @@ impl Desktop {
         bounds_map: HashMap<u32, (String, String, (f64, f64, f64, f64))>,
     ) {
         if let Ok(mut cache) = self.dom_cache.lock() {
-            cache.clear();
-            cache.extend(bounds_map);
+            cache.replace_all(bounds_map);
             debug!("Populated DOM cache with {} elements", cache.len());
         }
     }
@@ -1159,6 +1878,55 @@ impl Desktop {
         debug!("Cleared all vision caches");
     }
 
+    /// Cap the number of entries each vision cache (UI tree, OCR, Omniparser,
+    /// Gemini, DOM) keeps for index-based clicking.
+    ///
+    /// A capture larger than this keeps its lowest indices. Defaults to
+    /// [`index_cache::DEFAULT_VISION_CACHE_CAPACITY`].
+    pub fn set_vision_cache_capacity(&self, capacity: usize) {
+        if let Ok(mut cache) = self.uia_cache.lock() {
+            cache.set_capacity(capacity);
+        }
+        if let Ok(mut cache) = self.ocr_cache.lock() {
+            cache.set_capacity(capacity);
+        }
+        if let Ok(mut cache) = self.omniparser_cache.lock() {
+            cache.set_capacity(capacity);
+        }
+        if let Ok(mut cache) = self.vision_cache.lock() {
+            cache.set_capacity(capacity);
+        }
+        if let Ok(mut cache) = self.dom_cache.lock() {
+            cache.set_capacity(capacity);
+        }
+    }
+
+    /// Attach to a Chrome instance over the DevTools Protocol.
+    ///
+    /// `ws_url` is a `webSocketDebuggerUrl` from a browser started with
+    /// `--remote-debugging-port`. The returned session supports script execution,
+    /// DOM queries and navigation without the browser extension, which makes it
+    /// suitable for headless Chrome.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # async fn example() -> Result<(), terminator::AutomationError> {
+    /// use terminator::Desktop;
+    /// let desktop = Desktop::new_default()?;
+    /// let session = desktop
+    ///     .attach_chrome_cdp("ws://127.0.0.1:9222/devtools/browser/<id>")
+    ///     .await?;
+    /// session.navigate("https://example.com").await?;
+    /// let links = session.query_dom("a").await?;
+    /// let title = session.execute_browser_script("document.title").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn attach_chrome_cdp(&self, ws_url: &str) -> Result<BrowserSession, AutomationError> {
+        BrowserSession::connect(ws_url).await
+    }
+
     #[instrument(skip(self, title))]
     pub fn activate_browser_window_by_title(&self, title: &str) -> Result<(), AutomationError> {
         self.engine.activate_browser_window_by_title(title)
@@ -1228,11 +1996,182 @@ impl Desktop {
         use std::time::Duration;
         extension_bridge::try_close_tab(tab_id, url, title, Duration::from_secs(10)).await
     }
+
+    /// List all open browser tabs (title, url, active) via the browser extension
+    ///
+    /// Tabs from every browser window are returned in a stable order; each tab's
+    /// `index` can be passed to [`Desktop::activate_browser_tab`].
+    ///
+    /// # Errors
+    /// Returns `PlatformError` if the browser extension is not connected.
+    #[instrument(skip(self))]
+    pub async fn list_browser_tabs(
+        &self,
+    ) -> Result<Vec<extension_bridge::BrowserTab>, AutomationError> {
+        use std::time::Duration;
+        extension_bridge::try_list_tabs(Duration::from_secs(10))
+            .await?
+            .ok_or_else(|| {
+                AutomationError::PlatformError(
+                    "Browser extension not connected; cannot list tabs".to_string(),
+                )
+            })
+    }
+
+    /// Switch to a browser tab by index (from `list_browser_tabs`) or URL via the browser extension
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use terminator::Desktop;
+    ///
+    /// async fn example() {
+    ///     let desktop = Desktop::new_default().unwrap();
+    ///     let tabs = desktop.list_browser_tabs().await.unwrap();
+    ///     if let Some(tab) = tabs.iter().find(|t| !t.active) {
+    ///         desktop.activate_browser_tab(tab.index).await.unwrap();
+    ///     }
+    ///     desktop.activate_browser_tab("github.com").await.unwrap();
+    /// }
+    /// ```
+    #[instrument(skip(self, target))]
+    pub async fn activate_browser_tab(
+        &self,
+        target: impl Into<extension_bridge::TabTarget>,
+    ) -> Result<extension_bridge::BrowserTab, AutomationError> {
+        use std::time::Duration;
+        let target = target.into();
+        extension_bridge::try_activate_tab(&target, Duration::from_secs(10))
+            .await?
+            .ok_or_else(|| {
+                AutomationError::PlatformError(format!(
+                    "Browser extension not connected; cannot activate tab {target:?}"
+                ))
+            })
+    }
+
+    /// List every top-level window owned by a process: its main window plus any
+    /// dialogs, tool windows, tooltips and popups, topmost first. Hidden windows are
+    /// included; check `is_visible` to skip them.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use terminator::Desktop;
+    ///
+    /// let desktop = Desktop::new_default().unwrap();
+    /// for window in desktop.list_windows(1234).unwrap() {
+    ///     println!("{} visible={} foreground={}", window.title, window.is_visible, window.is_foreground);
+    /// }
+    /// ```
+    #[cfg(target_os = "windows")]
+    #[instrument(skip(self))]
+    pub fn list_windows(&self, pid: u32) -> Result<Vec<WindowInfo>, AutomationError> {
+        WindowManager::list_windows_for_pid(pid).map_err(AutomationError::PlatformError)
+    }
+
+    /// Wait until no tooltip, menu, autocomplete list or other transient popup window is
+    /// showing, so a following click reaches its target rather than a lingering popup.
+    ///
+    /// With `dismiss`, Escape is pressed once if popups are up when the wait starts.
+    /// Fails with [`AutomationError::Timeout`], naming the popups still showing, after
+    /// `timeout`.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use std::time::Duration;
+    /// use terminator::Desktop;
+    /// # async fn example(desktop: &Desktop) -> Result<(), terminator::AutomationError> {
+    /// desktop
+    ///     .wait_for_popups_dismissed(Duration::from_secs(2), true)
+    ///     .await?;
+    /// desktop.locator("role:Button|name:Submit").first(None).await?.click()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[instrument(skip(self))]
+    pub async fn wait_for_popups_dismissed(
+        &self,
+        timeout: std::time::Duration,
+        dismiss: bool,
+    ) -> Result<(), AutomationError> {
+        #[cfg(target_os = "windows")]
+        {
+            let start = std::time::Instant::now();
+            let mut escaped = false;
+            loop {
+                let popups = WindowManager::list_transient_popups()
+                    .map_err(AutomationError::PlatformError)?;
+                if popups.is_empty() {
+                    return Ok(());
+                }
+                if dismiss && !escaped {
+                    debug!("Pressing Escape to dismiss {} popup(s)", popups.len());
+                    self.engine.press_key("{Escape}")?;
+                    escaped = true;
+                }
+                if start.elapsed() >= timeout {
+                    let names: Vec<String> = popups
+                        .iter()
+                        .map(|p| format!("{} ({})", p.class_name, p.process_name))
+                        .collect();
+                    return Err(AutomationError::Timeout(format!(
+                        "Popups still showing after {timeout:?}: {}",
+                        names.join(", ")
+                    )));
+                }
+                tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+            }
+        }
+
+        #[cfg(not(target_os = "windows"))]
+        {
+            let _ = (timeout, dismiss);
+            Err(AutomationError::UnsupportedPlatform(
+                "Popup detection is only supported on Windows".to_string(),
+            ))
+        }
+    }
+
     #[instrument(skip(self))]
     pub async fn get_current_window(&self) -> Result<UIElement, AutomationError> {
         self.engine.get_current_window().await
     }
 
+    /// Get the element at a screen coordinate, as reported by the accessibility API.
+    #[instrument(skip(self))]
+    pub fn element_at_point(&self, x: f64, y: f64) -> Result<UIElement, AutomationError> {
+        self.engine.get_element_at_point(x, y)
+    }
+
+    /// Get the element that actually handles a click at a screen coordinate.
+    ///
+    /// Finds the element at the point, then walks up to the nearest ancestor exposing
+    /// an Invoke, SelectionItem or Toggle pattern (see [`find_interactable_ancestor`]).
+    /// Use this for points coming from vision or OCR, which tend to land on labels.
+    ///
+    /// # Errors
+    /// `ElementNotFound` if nothing interactable contains the point.
+    #[instrument(skip(self))]
+    pub fn interactable_at_point(&self, x: f64, y: f64) -> Result<UIElement, AutomationError> {
+        let hit = self.element_at_point(x, y)?;
+        find_interactable_ancestor(&hit).ok_or_else(|| {
+            AutomationError::ElementNotFound(format!(
+                "No interactable element at ({x}, {y}); the element there is role='{}' name='{}'",
+                hit.role(),
+                hit.name().unwrap_or_default()
+            ))
+        })
+    }
+
+    /// Get the window the OS reports as foreground (`GetForegroundWindow` on Windows).
+    ///
+    /// Unlike [`Desktop::get_current_window`], which walks up from the UIA-focused
+    /// element, this is the exact window receiving the user's input, so it stays
+    /// unambiguous when several windows claim focus.
+    #[instrument(skip(self))]
+    pub fn foreground_window(&self) -> Result<UIElement, AutomationError> {
+        self.engine.get_foreground_window()
+    }
+
     #[instrument(skip(self))]
     pub async fn get_current_application(&self) -> Result<UIElement, AutomationError> {
         self.engine.get_current_application().await
@@ -1249,6 +2188,25 @@ impl Desktop {
         self.engine.get_window_tree(pid, title, tree_config)
     }
 
+    /// Get the UI tree of one exact window, identified by its native handle.
+    ///
+    /// Use this when several windows share a PID and title (e.g. two documents in
+    /// the same editor) and `get_window_tree` could pick the wrong one. Handles come
+    /// from [`UIElement::get_native_window_handle`] or the window manager.
+    ///
+    /// # Arguments
+    /// * `hwnd` - Native window handle (HWND on Windows)
+    /// * `config` - Optional tree building configuration
+    #[instrument(skip(self, config))]
+    pub fn get_window_tree_by_hwnd(
+        &self,
+        hwnd: isize,
+        config: Option<crate::platforms::TreeBuildConfig>,
+    ) -> Result<UINode, AutomationError> {
+        self.engine
+            .get_window_tree_by_hwnd(hwnd, config.unwrap_or_default())
+    }
+
     /// Build UI tree directly from a UIElement
     ///
     /// This avoids the PID-based window enumeration which can fail during
@@ -1288,9 +2246,12 @@ impl Desktop {
         element: &UIElement,
         config: Option<crate::platforms::TreeBuildConfig>,
     ) -> Result<UINode, AutomationError> {
-        let window = find_parent_window(element).ok_or_else(|| {
-            AutomationError::ElementNotFound("Could not find parent window for element".to_string())
-        })?;
+        let window = find_parent_window_with_limit(element, parent_window_max_iterations())?
+            .ok_or_else(|| {
+                AutomationError::ElementNotFound(
+                    "Could not find parent window for element".to_string(),
+                )
+            })?;
 
         tracing::info!(
             "Found parent window: '{}' (role: {})",
@@ -1346,8 +2307,7 @@ impl Desktop {
         // Populate the UIA cache for index-based clicking
         if !index_to_bounds.is_empty() {
             if let Ok(mut cache) = self.uia_cache.lock() {
-                cache.clear();
-                cache.extend(index_to_bounds.clone());
+                cache.replace_all(index_to_bounds.clone());
                 debug!("Populated UIA cache with {} elements", cache.len());
             }
         }
@@ -1430,8 +2390,7 @@ impl Desktop {
             // Populate the UIA cache for index-based clicking
             if !index_to_bounds.is_empty() {
                 if let Ok(mut cache) = self.uia_cache.lock() {
-                    cache.clear();
-                    cache.extend(index_to_bounds.clone());
+                    cache.replace_all(index_to_bounds.clone());
                     debug!(
                         "Populated UIA cache with {} elements (from_selector)",
                         cache.len()
@@ -1592,6 +2551,65 @@ impl Desktop {
         self.engine.press_key(key)
     }
 
+    /// Press `key` while holding `modifiers`.
+    ///
+    /// Modifiers go down in order, the key is pressed and released, then the
+    /// modifiers are released in reverse order. The release happens in a drop
+    /// guard, so modifiers are never left stuck down if sending the key fails.
+    ///
+    /// `key` uses the same syntax as `press_key`, e.g. `"s"` or `"{F5}"`.
+    pub async fn press_key_combo(
+        &self,
+        modifiers: &[Modifier],
+        key: &str,
+    ) -> Result<(), AutomationError> {
+        self.engine.press_key_combo(modifiers, key)
+    }
+
+    /// Press and release a key by its hardware scan code.
+    ///
+    /// `press_key` sends virtual-key codes, which some games and legacy apps ignore
+    /// because they read raw keyboard state (e.g. through DirectInput). Scan codes
+    /// reach those apps the way a physical keyboard would. Prefer `press_key` for
+    /// everything else: scan codes depend on the keyboard layout and can't type text.
+    ///
+    /// `scan_code` is the set 1 make code, e.g. `0x1C` for Enter or `0x39` for Space.
+    /// Set `extended` for keys sent with an `E0` prefix, such as the arrow keys
+    /// (`0x48` Up, `0x50` Down), right Ctrl/Alt and the keypad Enter.
+    pub async fn press_scan_code(
+        &self,
+        scan_code: u16,
+        extended: bool,
+    ) -> Result<(), AutomationError> {
+        self.engine.press_scan_code(scan_code, extended)
+    }
+
+    /// Wait for the process with `pid` to exit and return its exit code.
+    ///
+    /// Returns `AutomationError::Timeout` if it is still running after `timeout`.
+    /// Useful between "run installer" and "verify app installed" steps.
+    ///
+    /// This method respects cancellation - if `stop_execution()` is called,
+    /// the wait is interrupted and returns an error.
+    pub async fn wait_for_process_exit(
+        &self,
+        pid: u32,
+        timeout: std::time::Duration,
+    ) -> Result<i32, AutomationError> {
+        let cancel_token = self.cancellation_token();
+        let wait = tokio::task::spawn_blocking(move || {
+            platforms::windows::wait_for_process_exit(pid, timeout)
+        });
+        tokio::select! {
+            result = wait => result.map_err(|e| {
+                AutomationError::Internal(format!("Process wait task failed: {e}"))
+            })?,
+            _ = cancel_token.cancelled() => Err(AutomationError::OperationCancelled(
+                "Process wait cancelled by stop_execution".into(),
+            )),
+        }
+    }
+
     /// Delay execution for a specified number of milliseconds.
     /// Useful for waiting between actions to ensure UI stability.
     ///
@@ -1715,7 +2733,7 @@ impl Desktop {
     /// 1. Finds the element by selector
     /// 2. Captures the UI tree before the action
     /// 3. Executes the action
-    /// 4. Waits for UI to settle (configurable, default 1500ms)
+    /// 4. Waits for UI to settle (see [`SettleStrategy`], default a fixed 1500ms)
     /// 5. Captures the UI tree after the action
     /// 6. Computes and returns the diff
     ///
@@ -1790,6 +2808,10 @@ impl Desktop {
             show_overlay: false,
             overlay_display_mode: None,
             from_selector: None,
+            include_shortcuts: false,
+            include_relationships: false,
+            stable_ids: false,
+            parallel_subtrees: None,
         };
 
         // Capture BEFORE tree
@@ -1806,18 +2828,16 @@ impl Desktop {
             }
         };
         let before_str = format_ui_node_as_compact_yaml(&tree_before, 0).formatted;
+        let visual_baseline = opts
+            .capture_visual_diff
+            .then(|| capture_visual_diff_baseline(&element))
+            .flatten();
 
         // Execute action
         let result = action(&element)?;
 
-        // Wait for UI to settle
-        let settle_ms = opts.settle_delay_ms.unwrap_or(1500);
-        debug!("[ui_diff] Waiting {}ms for UI to settle", settle_ms);
-        tokio::time::sleep(Duration::from_millis(settle_ms)).await;
-
-        // Capture AFTER tree
-        debug!("[ui_diff] Capturing UI tree after action (PID: {})", pid);
-        let tree_after = match self.get_window_tree(pid, None, Some(tree_config)) {
+        // Wait for UI to settle, then capture AFTER tree
+        let tree_after = match self.settle_and_capture_tree(&opts, pid, tree_config).await {
             Ok(tree) => tree,
             Err(e) => {
                 debug!(
@@ -1828,6 +2848,8 @@ impl Desktop {
             }
         };
         let after_str = format_ui_node_as_compact_yaml(&tree_after, 0).formatted;
+        let visual_diff =
+            visual_baseline.and_then(|(window, before)| compute_visual_diff(&window, &before));
 
         // Compute diff
         let diff_result = match ui_tree_diff::simple_ui_tree_diff(&before_str, &after_str) {
@@ -1839,6 +2861,7 @@ impl Desktop {
                 UiDiffResult {
                     diff,
                     has_changes: true,
+                    visual_diff,
                 }
             }
             Ok(None) => {
@@ -1846,6 +2869,7 @@ impl Desktop {
                 UiDiffResult {
                     diff: "No UI changes detected".to_string(),
                     has_changes: false,
+                    visual_diff,
                 }
             }
             Err(e) => {
@@ -1860,6 +2884,70 @@ impl Desktop {
         Ok((result, element, Some(diff_result)))
     }
 
+    /// Wait for the UI to settle per `opts` and capture the window tree of `pid`.
+    async fn settle_and_capture_tree(
+        &self,
+        opts: &UiDiffOptions,
+        pid: u32,
+        tree_config: platforms::TreeBuildConfig,
+    ) -> Result<UINode, AutomationError> {
+        let strategy = opts.settle.clone().unwrap_or_else(|| {
+            SettleStrategy::Fixed(std::time::Duration::from_millis(
+                opts.settle_delay_ms.unwrap_or(1500),
+            ))
+        });
+
+        match strategy {
+            SettleStrategy::Fixed(delay) => {
+                debug!("[ui_diff] Waiting {:?} for UI to settle", delay);
+                self.clock.sleep(delay).await;
+            }
+            SettleStrategy::UntilIdle { quiet_for, timeout } => {
+                debug!(
+                    "[ui_diff] Waiting for UI to stay unchanged for {:?} (timeout {:?})",
+                    quiet_for, timeout
+                );
+                let start = self.clock.now();
+                let mut tree = self.get_window_tree(pid, None, Some(tree_config.clone()))?;
+                let mut snapshot = format_ui_node_as_compact_yaml(&tree, 0).formatted;
+                let mut unchanged_since = start;
+                loop {
+                    let now = self.clock.now();
+                    if now.duration_since(unchanged_since) >= quiet_for {
+                        return Ok(tree);
+                    }
+                    if now.duration_since(start) >= timeout {
+                        debug!("[ui_diff] UI still changing after {:?}", timeout);
+                        return Ok(tree);
+                    }
+                    self.clock.sleep(SETTLE_IDLE_POLL_INTERVAL).await;
+                    tree = self.get_window_tree(pid, None, Some(tree_config.clone()))?;
+                    let next = format_ui_node_as_compact_yaml(&tree, 0).formatted;
+                    if next != snapshot {
+                        snapshot = next;
+                        unchanged_since = self.clock.now();
+                    }
+                }
+            }
+            SettleStrategy::UntilSelector { selector, timeout } => {
+                debug!(
+                    "[ui_diff] Waiting for '{}' to appear (timeout {:?})",
+                    selector, timeout
+                );
+                if let Err(e) = self
+                    .locator(selector.as_str())
+                    .wait_for(locator::WaitCondition::Exists, Some(timeout))
+                    .await
+                {
+                    debug!("[ui_diff] '{}' did not appear: {}", selector, e);
+                }
+            }
+        }
+
+        debug!("[ui_diff] Capturing UI tree after action (PID: {})", pid);
+        self.get_window_tree(pid, None, Some(tree_config))
+    }
+
     /// Execute an action on an already-found element with UI diff capture (async action variant).
     ///
     /// Use this when you have complex element-finding logic (fallback selectors, retries)
@@ -1883,8 +2971,6 @@ impl Desktop {
         F: FnOnce(UIElement) -> Fut,
         Fut: std::future::Future<Output = Result<T, AutomationError>>,
     {
-        use std::time::Duration;
-
         let opts = options.unwrap_or_default();
 
         // Clone element so we can return it after action consumes one copy
@@ -1916,6 +3002,10 @@ impl Desktop {
             show_overlay: false,
             overlay_display_mode: None,
             from_selector: None,
+            include_shortcuts: false,
+            include_relationships: false,
+            stable_ids: false,
+            parallel_subtrees: None,
         };
 
         // Capture BEFORE tree
@@ -1932,18 +3022,16 @@ impl Desktop {
             }
         };
         let before_str = format_ui_node_as_compact_yaml(&tree_before, 0).formatted;
+        let visual_baseline = opts
+            .capture_visual_diff
+            .then(|| capture_visual_diff_baseline(&element))
+            .flatten();
 
         // Execute action (async)
         let result = action(element).await?;
 
-        // Wait for UI to settle
-        let settle_ms = opts.settle_delay_ms.unwrap_or(1500);
-        debug!("[ui_diff] Waiting {}ms for UI to settle", settle_ms);
-        tokio::time::sleep(Duration::from_millis(settle_ms)).await;
-
-        // Capture AFTER tree
-        debug!("[ui_diff] Capturing UI tree after action (PID: {})", pid);
-        let tree_after = match self.get_window_tree(pid, None, Some(tree_config)) {
+        // Wait for UI to settle, then capture AFTER tree
+        let tree_after = match self.settle_and_capture_tree(&opts, pid, tree_config).await {
             Ok(tree) => tree,
             Err(e) => {
                 debug!(
@@ -1954,6 +3042,8 @@ impl Desktop {
             }
         };
         let after_str = format_ui_node_as_compact_yaml(&tree_after, 0).formatted;
+        let visual_diff =
+            visual_baseline.and_then(|(window, before)| compute_visual_diff(&window, &before));
 
         // Compute diff
         let diff_result = match ui_tree_diff::simple_ui_tree_diff(&before_str, &after_str) {
@@ -1965,6 +3055,7 @@ impl Desktop {
                 UiDiffResult {
                     diff,
                     has_changes: true,
+                    visual_diff,
                 }
             }
             Ok(None) => {
@@ -1972,6 +3063,7 @@ impl Desktop {
                 UiDiffResult {
                     diff: "No UI changes detected".to_string(),
                     has_changes: false,
+                    visual_diff,
                 }
             }
             Err(e) => {
@@ -2102,6 +3194,10 @@ impl Clone for Desktop {
             omniparser_cache: self.omniparser_cache.clone(),
             vision_cache: self.vision_cache.clone(),
             dom_cache: self.dom_cache.clone(),
+            // Clone shares modal handlers so they can be removed from any clone
+            modal_watcher: self.modal_watcher.clone(),
+            verbose_errors: self.verbose_errors.clone(),
+            clock: self.clock.clone(),
         }
     }
 }
@@ -0,0 +1,161 @@
+//! Locale-aware formatting for numbers and dates typed into input fields.
+//!
+//! Used by [`UIElement::type_number`](crate::UIElement::type_number) and
+//! [`UIElement::type_date`](crate::UIElement::type_date) so a value can be typed
+//! the way the target application expects it ("1.000,5" rather than "1000.5").
+
+use crate::AutomationError;
+use chrono::NaiveDate;
+use std::fmt::Write;
+
+/// How a locale writes numbers and short dates
+struct LocaleFormat {
+    decimal: char,
+    group: char,
+    date: &'static str,
+}
+
+const fn locale(decimal: char, group: char, date: &'static str) -> LocaleFormat {
+    LocaleFormat {
+        decimal,
+        group,
+        date,
+    }
+}
+
+/// Known locales, most specific first. Regional tags ("de-ch") take precedence
+/// over their language ("de"). Groups use a plain space where the locale uses a
+/// (narrow) no-break space, since that is what input validators accept when typed.
+const LOCALES: &[(&str, LocaleFormat)] = &[
+    ("en-us", locale('.', ',', "MM/dd/yyyy")),
+    ("en-gb", locale('.', ',', "dd/MM/yyyy")),
+    ("en-au", locale('.', ',', "dd/MM/yyyy")),
+    ("en-in", locale('.', ',', "dd/MM/yyyy")),
+    ("en-ca", locale('.', ',', "yyyy-MM-dd")),
+    ("en", locale('.', ',', "MM/dd/yyyy")),
+    ("de-ch", locale('.', '\'', "dd.MM.yyyy")),
+    ("de", locale(',', '.', "dd.MM.yyyy")),
+    ("fr-ch", locale(',', ' ', "dd.MM.yyyy")),
+    ("fr-ca", locale(',', ' ', "yyyy-MM-dd")),
+    ("fr", locale(',', ' ', "dd/MM/yyyy")),
+    ("es", locale(',', '.', "dd/MM/yyyy")),
+    ("it", locale(',', '.', "dd/MM/yyyy")),
+    ("pt-br", locale(',', '.', "dd/MM/yyyy")),
+    ("pt", locale(',', '.', "dd/MM/yyyy")),
+    ("nl", locale(',', '.', "dd-MM-yyyy")),
+    ("da", locale(',', '.', "dd.MM.yyyy")),
+    ("tr", locale(',', '.', "dd.MM.yyyy")),
+    ("id", locale(',', '.', "dd/MM/yyyy")),
+    ("sv", locale(',', ' ', "yyyy-MM-dd")),
+    ("nb", locale(',', ' ', "dd.MM.yyyy")),
+    ("fi", locale(',', ' ', "dd.MM.yyyy")),
+    ("pl", locale(',', ' ', "dd.MM.yyyy")),
+    ("cs", locale(',', ' ', "dd.MM.yyyy")),
+    ("ru", locale(',', ' ', "dd.MM.yyyy")),
+    ("uk", locale(',', ' ', "dd.MM.yyyy")),
+    ("ja", locale('.', ',', "yyyy/MM/dd")),
+    ("zh", locale('.', ',', "yyyy/MM/dd")),
+    ("ko", locale('.', ',', "yyyy.MM.dd")),
+];
+
+/// Look up a locale tag such as "de-DE", "de_DE" or "de", ignoring case.
+fn lookup(tag: &str) -> Option<&'static LocaleFormat> {
+    let tag = tag.trim().replace('_', "-").to_ascii_lowercase();
+    let language = tag.split('-').next().unwrap_or_default();
+    let find = |key: &str| LOCALES.iter().find(|(name, _)| *name == key);
+    find(&tag)
+        .or_else(|| find(language))
+        .map(|(_, format)| format)
+}
+
+/// Format `value` with the decimal and grouping separators of `locale`.
+///
+/// The shortest representation of the value is used, so `1000.5` becomes
+/// "1.000,5" for "de-DE"; round the value first to control decimal places.
+pub fn format_number(value: f64, locale: &str) -> Result<String, AutomationError> {
+    if !value.is_finite() {
+        return Err(AutomationError::InvalidArgument(format!(
+            "Cannot type non-finite number {value}"
+        )));
+    }
+    let format = lookup(locale).ok_or_else(|| unknown_locale(locale))?;
+
+    let plain = value.abs().to_string();
+    let (integer, fraction) = plain.split_once('.').unwrap_or((&plain, ""));
+
+    let mut out = String::new();
+    if value < 0.0 {
+        out.push('-');
+    }
+    for (i, digit) in integer.chars().enumerate() {
+        if i > 0 && (integer.len() - i) % 3 == 0 {
+            out.push(format.group);
+        }
+        out.push(digit);
+    }
+    if !fraction.is_empty() {
+        out.push(format.decimal);
+        out.push_str(fraction);
+    }
+    Ok(out)
+}
+
+/// Format `date` with a pattern or a locale's short date format.
+///
+/// `format` is either a locale tag ("de-DE" gives "dd.MM.yyyy"), a pattern built
+/// from `yyyy`, `yy`, `MM`, `M`, `dd` and `d` (e.g. "MM/dd/yyyy"), or a chrono
+/// strftime string containing `%` (e.g. "%d.%m.%Y").
+pub fn format_date(date: NaiveDate, format: &str) -> Result<String, AutomationError> {
+    let strftime = if format.contains('%') {
+        format.to_string()
+    } else if let Some(locale) = lookup(format) {
+        pattern_to_strftime(locale.date)?
+    } else {
+        pattern_to_strftime(format)?
+    };
+
+    let mut out = String::new();
+    write!(out, "{}", date.format(&strftime))
+        .map_err(|_| AutomationError::InvalidArgument(format!("Invalid date format '{format}'")))?;
+    Ok(out)
+}
+
+/// Translate a "dd.MM.yyyy"-style pattern into a strftime string
+fn pattern_to_strftime(pattern: &str) -> Result<String, AutomationError> {
+    let chars: Vec<char> = pattern.chars().collect();
+    let mut out = String::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let run = chars[i..].iter().take_while(|&&x| x == c).count();
+        let item = match (c, run) {
+            ('y' | 'Y', 4) => "%Y",
+            ('y' | 'Y', 2) => "%y",
+            ('M', 2) => "%m",
+            ('M', 1) => "%-m",
+            ('d' | 'D', 2) => "%d",
+            ('d' | 'D', 1) => "%-d",
+            _ if c.is_ascii_alphabetic() => {
+                return Err(AutomationError::InvalidArgument(format!(
+                    "Unsupported date pattern '{pattern}': expected yyyy, yy, MM, M, dd or d, a locale tag, or a strftime string"
+                )));
+            }
+            _ => {
+                out.extend(std::iter::repeat_n(c, run));
+                i += run;
+                continue;
+            }
+        };
+        out.push_str(item);
+        i += run;
+    }
+    Ok(out)
+}
+
+fn unknown_locale(locale: &str) -> AutomationError {
+    let known: Vec<&str> = LOCALES.iter().map(|(name, _)| *name).collect();
+    AutomationError::InvalidArgument(format!(
+        "Unknown locale '{locale}'. Known locales: {}",
+        known.join(", ")
+    ))
+}
@@ -1,9 +1,12 @@
 use tracing::{debug, instrument};
 
+use crate::clock::{Clock, SystemClock};
 use crate::element::UIElement;
 use crate::errors::AutomationError;
 use crate::platforms::AccessibilityEngine;
-use crate::selector::Selector;
+use crate::selector::{ElementState, Selector};
+use std::collections::{HashSet, VecDeque};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::task;
@@ -21,6 +24,381 @@ pub enum WaitCondition {
     Focused,
 }
 
+/// What [`TrackedElement::resolve`] does when the UI element it tracks is gone
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum StaleHandling {
+    /// Return an `ElementNotFound` error naming the stale element
+    #[default]
+    Fail,
+    /// Re-run the locator's selector and pick up the element that took its place
+    Refresh,
+}
+
+/// An element returned by [`Locator::all_tracked`].
+///
+/// Besides the element itself it remembers the platform runtime id it had when found,
+/// its position among the matches and its role and name, so it can cheaply check
+/// whether it is still alive before each action and be re-acquired if it is not.
+#[derive(Clone)]
+pub struct TrackedElement {
+    element: UIElement,
+    runtime_id: Option<Vec<i32>>,
+    index: usize,
+    role: String,
+    name: Option<String>,
+    locator: Locator,
+    depth: Option<usize>,
+}
+
+impl TrackedElement {
+    fn new(element: UIElement, index: usize, locator: Locator, depth: Option<usize>) -> Self {
+        Self {
+            runtime_id: element.runtime_id(),
+            role: element.role(),
+            name: element.name(),
+            element,
+            index,
+            locator,
+            depth,
+        }
+    }
+
+    /// The element as last resolved, without checking whether it is still alive
+    pub fn element(&self) -> &UIElement {
+        &self.element
+    }
+
+    /// Runtime id the element had when it was found or last refreshed
+    pub fn runtime_id(&self) -> Option<&[i32]> {
+        self.runtime_id.as_deref()
+    }
+
+    /// Selector that re-finds this element: the locator's selector plus its match index
+    pub fn selector(&self) -> Selector {
+        Selector::Chain(vec![
+            self.locator.selector.clone(),
+            Selector::Nth(self.index as i32),
+        ])
+    }
+
+    /// Whether the element no longer reports the runtime id it was found with.
+    ///
+    /// Elements on platforms without runtime ids are never considered stale.
+    pub fn is_stale(&self) -> bool {
+        self.runtime_id
+            .as_ref()
+            .is_some_and(|recorded| self.element.runtime_id().as_ref() != Some(recorded))
+    }
+
+    /// Return the element, re-validating it first.
+    ///
+    /// A live element is returned as is. A stale one is handled according to the
+    /// locator's [`StaleHandling`]: with `Refresh` the selector is run again and the
+    /// match at the same position is taken if it has the same role and name,
+    /// otherwise the first match that does.
+    pub async fn resolve(&mut self) -> Result<UIElement, AutomationError> {
+        if !self.is_stale() {
+            return Ok(self.element.clone());
+        }
+        let description = format!("{} {:?}", self.role, self.name.as_deref().unwrap_or(""));
+        if self.locator.stale_handling == StaleHandling::Fail {
+            return Err(AutomationError::ElementNotFound(format!(
+                "Element {description} found by {} is stale",
+                self.locator.selector_string()
+            )));
+        }
+
+        debug!("Refreshing stale element {}", description);
+        let candidates = self.locator.all(None, self.depth).await?;
+        let same = |element: &UIElement| element.role() == self.role && element.name() == self.name;
+        let refreshed = candidates
+            .get(self.index)
+            .filter(|element| same(element))
+            .or_else(|| candidates.iter().find(|element| same(element)))
+            .cloned()
+            .ok_or_else(|| {
+                AutomationError::ElementNotFound(format!(
+                    "Element {description} found by {} is stale and no longer matches",
+                    self.locator.selector_string()
+                ))
+            })?;
+        self.runtime_id = refreshed.runtime_id();
+        self.element = refreshed.clone();
+        Ok(refreshed)
+    }
+}
+
+/// Weights used by [`Locator::best`] to rank ambiguous matches.
+///
+/// Only one name weight applies per candidate (the strongest match wins);
+/// visibility and enabled weights are added on top. Raise a weight to bias
+/// the ranking toward that property.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MatchScoring {
+    /// Candidate name equals the selector's name (case-insensitive)
+    pub exact_name: f64,
+    /// Candidate name starts with the selector's name
+    pub prefix_name: f64,
+    /// Candidate name contains the selector's name
+    pub substring_name: f64,
+    /// Candidate is visible on screen
+    pub visible: f64,
+    /// Candidate is enabled
+    pub enabled: f64,
+}
+
+impl Default for MatchScoring {
+    fn default() -> Self {
+        Self {
+            exact_name: 100.0,
+            prefix_name: 60.0,
+            substring_name: 30.0,
+            visible: 20.0,
+            enabled: 10.0,
+        }
+    }
+}
+
+impl MatchScoring {
+    /// Score a single candidate. `query` is the name the selector asked for,
+    /// if any; `name` is the candidate's actual name.
+    pub fn score(
+        &self,
+        query: Option<&str>,
+        name: Option<&str>,
+        visible: bool,
+        enabled: bool,
+    ) -> f64 {
+        let mut score = 0.0;
+
+        if let (Some(query), Some(name)) = (query, name) {
+            let query = query.trim().to_lowercase();
+            let name = name.trim().to_lowercase();
+            if !query.is_empty() {
+                if name == query {
+                    score += self.exact_name;
+                } else if name.starts_with(&query) {
+                    score += self.prefix_name;
+                } else if name.contains(&query) {
+                    score += self.substring_name;
+                }
+            }
+        }
+
+        if visible {
+            score += self.visible;
+        }
+        if enabled {
+            score += self.enabled;
+        }
+        score
+    }
+}
+
+/// Extract the name a selector is looking for, used to rank candidates.
+/// For chains the last named step wins, since it describes the target element.
+pub(crate) fn selector_name_query(selector: &Selector) -> Option<String> {
+    match selector {
+        Selector::Role {
+            name: Some(name), ..
+        } => Some(name.clone()),
+        Selector::Name(name) | Selector::Text(name) => Some(name.clone()),
+        Selector::Chain(parts) => parts.iter().rev().find_map(selector_name_query),
+        Selector::And(parts) => parts.iter().find_map(selector_name_query),
+        _ => None,
+    }
+}
+
+/// How element names are cleaned up before a [`Locator`] compares them.
+///
+/// Menu and toolbar labels often carry decorations that differ between the name
+/// a user types and the name UI Automation reports: `&Open...`, `Save As\tCtrl+S`,
+/// `File(&F)`. Enabling a rule drops that decoration on both sides of the comparison.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NameNormalization {
+    /// Drop mnemonic ampersands (`&Open` -> `Open`); `&&` stays a literal `&`
+    pub strip_mnemonics: bool,
+    /// Trim the name and collapse runs of whitespace into a single space
+    pub collapse_whitespace: bool,
+    /// Drop a trailing `...` or `…`
+    pub strip_ellipsis: bool,
+    /// Drop a tab-separated shortcut (`Save\tCtrl+S`) and a trailing access key (`File(&F)`)
+    pub strip_access_key_hints: bool,
+}
+
+impl Default for NameNormalization {
+    fn default() -> Self {
+        Self {
+            strip_mnemonics: true,
+            collapse_whitespace: true,
+            strip_ellipsis: true,
+            strip_access_key_hints: true,
+        }
+    }
+}
+
+impl NameNormalization {
+    /// Apply the enabled rules to `name`.
+    pub fn normalize(&self, name: &str) -> String {
+        let mut name = name;
+        if self.strip_access_key_hints {
+            name = name.split('\t').next().unwrap_or(name);
+        }
+        if self.strip_ellipsis {
+            name = strip_ellipsis(name);
+        }
+        if self.strip_access_key_hints {
+            name = strip_access_key_suffix(name);
+        }
+        let name = if self.strip_mnemonics {
+            strip_mnemonics(name)
+        } else {
+            name.to_string()
+        };
+        if self.collapse_whitespace {
+            name.split_whitespace().collect::<Vec<_>>().join(" ")
+        } else {
+            name
+        }
+    }
+
+    /// Normalize the names a selector asks for, leaving everything else untouched.
+    fn normalize_selector(&self, selector: &Selector) -> Selector {
+        match selector {
+            Selector::Role {
+                role,
+                name: Some(name),
+            } => Selector::Role {
+                role: role.clone(),
+                name: Some(self.normalize(name)),
+            },
+            Selector::Name(name) => Selector::Name(self.normalize(name)),
+            Selector::Text(name) => Selector::Text(self.normalize(name)),
+            Selector::Chain(parts) => {
+                Selector::Chain(parts.iter().map(|s| self.normalize_selector(s)).collect())
+            }
+            Selector::And(parts) => {
+                Selector::And(parts.iter().map(|s| self.normalize_selector(s)).collect())
+            }
+            Selector::Or(parts) => {
+                Selector::Or(parts.iter().map(|s| self.normalize_selector(s)).collect())
+            }
+            other => other.clone(),
+        }
+    }
+
+    /// Whether `candidate` contains `query` once both are normalized (case-insensitive).
+    pub(crate) fn matches(&self, query: &str, candidate: &str) -> bool {
+        self.normalize(candidate)
+            .to_lowercase()
+            .contains(&self.normalize(query).to_lowercase())
+    }
+}
+
+fn strip_ellipsis(name: &str) -> &str {
+    let trimmed = name.trim_end();
+    trimmed
+        .strip_suffix("...")
+        .or_else(|| trimmed.strip_suffix('…'))
+        .map_or(name, str::trim_end)
+}
+
+/// `File(&F)` / `File (F)` -> `File`
+fn strip_access_key_suffix(name: &str) -> &str {
+    let trimmed = name.trim_end();
+    let Some(inner) = trimmed.strip_suffix(')') else {
+        return name;
+    };
+    let Some(open) = inner.rfind('(') else {
+        return name;
+    };
+    let key = &inner[open + 1..];
+    let key = key.strip_prefix('&').unwrap_or(key);
+    let mut chars = key.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) if c.is_alphanumeric() => inner[..open].trim_end(),
+        _ => name,
+    }
+}
+
+fn strip_mnemonics(name: &str) -> String {
+    let mut out = String::with_capacity(name.len());
+    let mut chars = name.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '&' {
+            out.push(c);
+        } else if chars.peek() == Some(&'&') {
+            chars.next();
+            out.push('&');
+        }
+    }
+    out
+}
+
+/// How many suggestions a verbose not-found error lists
+const MAX_SUGGESTIONS: usize = 5;
+
+/// Upper bound on elements inspected when collecting suggestions
+const SUGGESTION_SCAN_LIMIT: usize = 2000;
+
+/// Case-insensitive edit (Levenshtein) distance between two names
+pub(crate) fn name_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.trim().to_lowercase().chars().collect();
+    let b: Vec<char> = b.trim().to_lowercase().chars().collect();
+
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.iter().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+/// Pick up to `limit` (role, name) pairs whose names are closest to `query`,
+/// closest first. Unnamed, duplicate and completely dissimilar candidates are skipped.
+pub(crate) fn closest_matches(
+    query: &str,
+    candidates: impl IntoIterator<Item = (String, String)>,
+    limit: usize,
+) -> Vec<(String, String)> {
+    let query_len = query.trim().chars().count();
+    let mut seen = HashSet::new();
+    let mut scored: Vec<(usize, (String, String))> = candidates
+        .into_iter()
+        .filter(|(_, name)| !name.trim().is_empty())
+        .filter(|candidate| seen.insert(candidate.clone()))
+        .filter_map(|(role, name)| {
+            let distance = name_distance(query, &name);
+            // Replacing every character means the names share nothing
+            (distance < query_len.max(name.trim().chars().count()))
+                .then_some((distance, (role, name)))
+        })
+        .collect();
+    // Stable sort keeps tree order among equally close names
+    scored.sort_by_key(|(distance, _)| *distance);
+    scored
+        .into_iter()
+        .take(limit)
+        .map(|(_, candidate)| candidate)
+        .collect()
+}
+
+/// Render suggestions as a hint to append to a not-found error message
+pub(crate) fn suggestion_hint(suggestions: &[(String, String)]) -> String {
+    if suggestions.is_empty() {
+        return String::new();
+    }
+    let listed: Vec<String> = suggestions
+        .iter()
+        .map(|(role, name)| format!("{role} \"{name}\""))
+        .collect();
+    format!(" Did you mean: {}?", listed.join(", "))
+}
+
 // Default timeout if none is specified on the locator itself
 // Set to 0 for one-time search (no polling) - add explicit timeout where waiting is needed
 const DEFAULT_LOCATOR_TIMEOUT: Duration = Duration::from_secs(0);
@@ -35,6 +413,41 @@ pub struct Locator {
     selector: Selector,
     timeout: Duration, // Default timeout for this locator instance
     root: Option<UIElement>,
+    scoring: MatchScoring,
+    /// Shared with the owning Desktop; see `Desktop::set_verbose_errors`
+    verbose_errors: Arc<AtomicBool>,
+    /// Time source for `wait_for` polling; see `Desktop::with_clock`
+    clock: Arc<dyn Clock>,
+    /// Applies to elements returned by `all_tracked`
+    stale_handling: StaleHandling,
+    /// See [`Locator::with_name_normalization`]
+    name_normalization: Option<NameNormalization>,
+}
+
+/// Breadth-first (role, name) pairs under `root`, up to `SUGGESTION_SCAN_LIMIT` elements
+fn scan_names(root: UIElement) -> Vec<(String, String)> {
+    let mut names = Vec::new();
+    let mut queue = VecDeque::from([root]);
+    let mut visited = 0;
+    while let Some(element) = queue.pop_front() {
+        visited += 1;
+        if visited > SUGGESTION_SCAN_LIMIT {
+            break;
+        }
+        if let Some(name) = element.name() {
+            names.push((element.role(), name));
+        }
+        queue.extend(element.children().unwrap_or_default());
+    }
+    names
+}
+
+/// A search that gave up while a UAC prompt held the screen; UI Automation can't see past
+/// the prompt, so waiting longer would not have helped
+fn elevation_required(detail: String) -> AutomationError {
+    AutomationError::ElevationRequired(format!(
+        "A UAC elevation prompt is blocking UI Automation and must be handled out-of-band; {detail}"
+    ))
 }
 
 impl Locator {
@@ -45,9 +458,26 @@ impl Locator {
             selector,
             timeout: DEFAULT_LOCATOR_TIMEOUT, // Use default
             root: None,
+            scoring: MatchScoring::default(),
+            verbose_errors: Arc::new(AtomicBool::new(false)),
+            clock: Arc::new(SystemClock),
+            stale_handling: StaleHandling::default(),
+            name_normalization: None,
         }
     }
 
+    /// Follow a desktop's verbose-errors setting
+    pub(crate) fn with_verbose_errors(mut self, verbose_errors: Arc<AtomicBool>) -> Self {
+        self.verbose_errors = verbose_errors;
+        self
+    }
+
+    /// Use the given clock for polling in [`Locator::wait_for`]
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
     /// Set a default timeout for waiting operations on this locator instance.
     /// This timeout is used if no specific timeout is passed to action/wait methods.
     pub fn set_default_timeout(mut self, timeout: Duration) -> Self {
@@ -61,6 +491,147 @@ impl Locator {
         self
     }
 
+    /// Set the weights used by [`Locator::best`] to rank matches.
+    pub fn with_scoring(mut self, scoring: MatchScoring) -> Self {
+        self.scoring = scoring;
+        self
+    }
+
+    /// Set what elements from [`Locator::all_tracked`] do when they go stale.
+    pub fn with_stale_handling(mut self, stale_handling: StaleHandling) -> Self {
+        self.stale_handling = stale_handling;
+        self
+    }
+
+    /// Compare element names after normalizing them, so `"Open"` finds a menu item
+    /// named `&Open...` and `"Save As"` finds `Save  As\tCtrl+Shift+S`.
+    ///
+    /// Names in this locator's selector are normalized right away. When the selector
+    /// ends in a `role:...|name` step, every search (`all`, `wait`, `wait_for`, ...)
+    /// looks that step up without its name and keeps candidates whose normalized name
+    /// contains the normalized query.
+    pub fn with_name_normalization(mut self, normalization: NameNormalization) -> Self {
+        self.selector = normalization.normalize_selector(&self.selector);
+        self.name_normalization = Some(normalization);
+        self
+    }
+
+    /// The selector with its last `role:...|name` step's name dropped, plus that name.
+    /// `None` when normalization is off or the selector doesn't end in a named role step.
+    fn relaxed_selector(&self) -> Option<(Selector, String, NameNormalization)> {
+        let normalization = self.name_normalization?;
+        let (relaxed, query) = match &self.selector {
+            Selector::Role {
+                role,
+                name: Some(name),
+            } => (
+                Selector::Role {
+                    role: role.clone(),
+                    name: None,
+                },
+                name.clone(),
+            ),
+            Selector::Chain(parts) => match parts.split_last() {
+                Some((
+                    Selector::Role {
+                        role,
+                        name: Some(name),
+                    },
+                    rest,
+                )) => {
+                    let mut relaxed = rest.to_vec();
+                    relaxed.push(Selector::Role {
+                        role: role.clone(),
+                        name: None,
+                    });
+                    (Selector::Chain(relaxed), name.clone())
+                }
+                _ => return None,
+            },
+            _ => return None,
+        };
+        Some((relaxed, query, normalization))
+    }
+
+    /// One pass of the relaxed search under the locator's root (the desktop root when
+    /// unset): candidates whose normalized name contains the normalized query.
+    fn find_relaxed_once(
+        &self,
+        relaxed: &Selector,
+        query: &str,
+        normalization: NameNormalization,
+        timeout: Duration,
+        depth: Option<usize>,
+    ) -> Result<Vec<UIElement>, AutomationError> {
+        let root = self
+            .root
+            .clone()
+            .unwrap_or_else(|| self.engine.get_root_element());
+        let candidates = match self
+            .engine
+            .find_elements(relaxed, Some(&root), Some(timeout), depth)
+        {
+            Ok(candidates) => candidates,
+            Err(AutomationError::ElementNotFound(_)) | Err(AutomationError::Timeout(_)) => {
+                Vec::new()
+            }
+            Err(e) => return Err(e),
+        };
+        Ok(candidates
+            .into_iter()
+            .filter(|element| {
+                element
+                    .name()
+                    .is_some_and(|name| normalization.matches(query, &name))
+            })
+            .collect())
+    }
+
+    /// Blocking half of [`Locator::find_normalized`]: the strict selector gets the whole
+    /// `timeout`, and only when it finds nothing does a single relaxed pass run.
+    fn find_normalized_blocking(
+        &self,
+        relaxed: &Selector,
+        query: &str,
+        normalization: NameNormalization,
+        timeout: Duration,
+        depth: Option<usize>,
+    ) -> Result<Vec<UIElement>, AutomationError> {
+        match self
+            .engine
+            .find_elements(&self.selector, self.root.as_ref(), Some(timeout), depth)
+        {
+            Ok(found) if !found.is_empty() => Ok(found),
+            Ok(_) | Err(AutomationError::ElementNotFound(_)) | Err(AutomationError::Timeout(_)) => {
+                debug!("Searching {relaxed:?} for normalized name {query:?}");
+                // A zero timeout is a one-time search, as with DEFAULT_LOCATOR_TIMEOUT
+                self.find_relaxed_once(relaxed, query, normalization, Duration::ZERO, depth)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// The search behind [`Locator::with_name_normalization`]. The relaxed selector
+    /// matches as soon as any element of the role exists, so it can't be polled on its
+    /// own; it runs once, after the strict search has used up `timeout`. The result is
+    /// empty when neither finds anything.
+    /// `None` when [`Locator::relaxed_selector`] is.
+    async fn find_normalized(
+        &self,
+        timeout: Duration,
+        depth: Option<usize>,
+    ) -> Option<Result<Vec<UIElement>, AutomationError>> {
+        let (relaxed, query, normalization) = self.relaxed_selector()?;
+        let locator = self.clone();
+        let result = task::spawn_blocking(move || {
+            locator.find_normalized_blocking(&relaxed, &query, normalization, timeout, depth)
+        })
+        .await
+        .map_err(|e| AutomationError::PlatformError(format!("Task join error: {e}")))
+        .and_then(|result| result);
+        Some(result)
+    }
+
     /// Get all elements matching this locator, waiting up to the specified timeout.
     /// If no timeout is provided, uses the locator's default timeout.
     pub async fn all(
@@ -69,6 +640,9 @@ impl Locator {
         depth: Option<usize>,
     ) -> Result<Vec<UIElement>, AutomationError> {
         let effective_timeout = timeout.unwrap_or(self.timeout);
+        if let Some(result) = self.find_normalized(effective_timeout, depth).await {
+            return result;
+        }
         // find_elements itself handles the timeout now
         self.engine.find_elements(
             &self.selector,
@@ -78,11 +652,71 @@ impl Locator {
         )
     }
 
+    /// Like [`Locator::all`], but each element can re-validate itself before use.
+    ///
+    /// Call [`TrackedElement::resolve`] before acting on an element in an iterate-and-act
+    /// loop; it detects elements the UI has replaced in the meantime and handles them
+    /// according to this locator's [`StaleHandling`].
+    pub async fn all_tracked(
+        &self,
+        timeout: Option<Duration>,
+        depth: Option<usize>,
+    ) -> Result<Vec<TrackedElement>, AutomationError> {
+        let elements = self.all(timeout, depth).await?;
+        Ok(elements
+            .into_iter()
+            .enumerate()
+            .map(|(index, element)| TrackedElement::new(element, index, self.clone(), depth))
+            .collect())
+    }
+
     pub async fn first(&self, timeout: Option<Duration>) -> Result<UIElement, AutomationError> {
         let element = self.wait(timeout).await?;
         Ok(element)
     }
 
+    /// Find all matches and return the one that best fits the selector.
+    ///
+    /// Candidates are ranked by how closely their name matches the selector's
+    /// name (exact > prefix > substring), then by visibility and enabled state,
+    /// using this locator's [`MatchScoring`]. Ties keep the first match in tree order.
+    #[instrument(level = "debug", skip(self, timeout))]
+    pub async fn best(&self, timeout: Option<Duration>) -> Result<UIElement, AutomationError> {
+        if let Selector::Invalid(reason) = &self.selector {
+            return Err(AutomationError::InvalidSelector(reason.clone()));
+        }
+
+        let candidates = self.all(timeout, None).await?;
+        let query = selector_name_query(&self.selector);
+
+        let mut best: Option<(f64, UIElement)> = None;
+        for element in candidates {
+            let score = self.scoring.score(
+                query.as_deref(),
+                element.name().as_deref(),
+                element.is_visible().unwrap_or(false),
+                element.is_enabled().unwrap_or(false),
+            );
+            debug!("Candidate {:?} scored {}", element.name(), score);
+            if best
+                .as_ref()
+                .is_none_or(|(best_score, _)| score > *best_score)
+            {
+                best = Some((score, element));
+            }
+        }
+
+        match best {
+            Some((_, element)) => Ok(element),
+            None => Err(self
+                .explain_not_found(AutomationError::ElementNotFound(format!(
+                    "No elements found for selector {}",
+                    self.selector_string()
+                )))
+                .await),
+        }
+    }
+
     /// Wait for an element matching the locator to appear, up to the specified timeout.
     /// If no timeout is provided, uses the locator's default timeout.
     #[instrument(level = "debug", skip(self, timeout))]
@@ -94,6 +728,70 @@ impl Locator {
         }
 
         let effective_timeout = timeout.unwrap_or(self.timeout);
+        let result = self.find_first(effective_timeout).await?;
+
+        match result {
+            Ok(element) => Ok(element),
+            Err(AutomationError::ElementNotFound(inner_msg))
+                if self.engine.is_elevation_prompt_active() =>
+            {
+                Err(elevation_required(format!(
+                    "gave up waiting for element {} after {effective_timeout:?}: {inner_msg}",
+                    self.selector_string()
+                )))
+            }
+            // The engine returns ElementNotFound on timeout. We convert it to a more specific Timeout error here.
+            Err(AutomationError::ElementNotFound(inner_msg)) => Err(self
+                .explain_not_found(AutomationError::Timeout(format!(
+                    "Timed out after {effective_timeout:?} waiting for element {}. Original error: {inner_msg}",
+                    self.selector_string()
+                )))
+                .await),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// With verbose errors enabled, append the closest-named elements under the
+    /// search root to a not-found or timeout error.
+    async fn explain_not_found(&self, error: AutomationError) -> AutomationError {
+        if !self.verbose_errors.load(Ordering::Relaxed) {
+            return error;
+        }
+        let Some(query) = selector_name_query(&self.selector) else {
+            return error;
+        };
+
+        let engine = self.engine.clone();
+        let root = self.root.clone();
+        let suggestions = task::spawn_blocking(move || {
+            let root = root.unwrap_or_else(|| engine.get_root_element());
+            closest_matches(&query, scan_names(root), MAX_SUGGESTIONS)
+        })
+        .await
+        .unwrap_or_default();
+
+        if suggestions.is_empty() {
+            return error;
+        }
+        let hint = suggestion_hint(&suggestions);
+        match error {
+            AutomationError::ElementNotFound(msg) => {
+                AutomationError::ElementNotFound(format!("{msg}.{hint}"))
+            }
+            AutomationError::Timeout(msg) => AutomationError::Timeout(format!("{msg}.{hint}")),
+            other => other,
+        }
+    }
+
+    /// Find the first match within `timeout`. The outer `Result` is a join failure
+    /// of the blocking search; not finding anything is `ElementNotFound` inside it.
+    async fn find_first(
+        &self,
+        timeout: Duration,
+    ) -> Result<Result<UIElement, AutomationError>, AutomationError> {
+        if let Some(result) = self.find_normalized(timeout, None).await {
+            return Ok(result.and_then(|elements| self.first_or_not_found(elements)));
+        }
 
         // Since the underlying engine's find_element is a blocking call that
         // already handles polling and timeouts, we should not wrap it in another async loop.
@@ -102,24 +800,34 @@ impl Locator {
         let selector = self.selector.clone();
         let root = self.root.clone();
 
-        task::spawn_blocking(move || {
-            engine.find_element(&selector, root.as_ref(), Some(effective_timeout))
-        })
-        .await
-        .map_err(|e| AutomationError::PlatformError(format!("Task join error: {e}")))?
-        .map_err(|e| {
-            // The engine returns ElementNotFound on timeout. We convert it to a more specific Timeout error here.
-            if let AutomationError::ElementNotFound(inner_msg) = e {
-                AutomationError::Timeout(format!(
-                    "Timed out after {effective_timeout:?} waiting for element {}. Original error: {inner_msg}",
-                    self.selector_string()
-                ))
-            } else {
-                e
-            }
+        task::spawn_blocking(move || engine.find_element(&selector, root.as_ref(), Some(timeout)))
+            .await
+            .map_err(|e| AutomationError::PlatformError(format!("Task join error: {e}")))
+    }
+
+    fn first_or_not_found(&self, elements: Vec<UIElement>) -> Result<UIElement, AutomationError> {
+        elements.into_iter().next().ok_or_else(|| {
+            AutomationError::ElementNotFound(format!(
+                "No element with a name matching {} after normalization",
+                self.selector_string()
+            ))
         })
     }
 
+    /// Run a single synchronous search, for callers that can't await.
+    pub(crate) fn find_blocking(&self, timeout: Duration) -> Result<UIElement, AutomationError> {
+        if let Selector::Invalid(reason) = &self.selector {
+            return Err(AutomationError::InvalidSelector(reason.clone()));
+        }
+        if let Some((relaxed, query, normalization)) = self.relaxed_selector() {
+            return self
+                .find_normalized_blocking(&relaxed, &query, normalization, timeout, None)
+                .and_then(|elements| self.first_or_not_found(elements));
+        }
+        self.engine
+            .find_element(&self.selector, self.root.as_ref(), Some(timeout))
+    }
+
     /// Validate element existence without throwing an error.
     /// Returns Ok(Some(element)) if found, Ok(None) if not found.
     /// Only returns Err for invalid selectors or platform errors.
@@ -136,16 +844,7 @@ impl Locator {
 
         let effective_timeout = timeout.unwrap_or(self.timeout);
 
-        let engine = self.engine.clone();
-        let selector = self.selector.clone();
-        let root = self.root.clone();
-
-        task::spawn_blocking(move || {
-            engine.find_element(&selector, root.as_ref(), Some(effective_timeout))
-        })
-        .await
-        .map_err(|e| AutomationError::PlatformError(format!("Task join error: {e}")))?
-        .map_or_else(
+        self.find_first(effective_timeout).await?.map_or_else(
             |e| {
                 // For ElementNotFound or Timeout, return Ok(None) instead of error
                 match e {
@@ -182,18 +881,30 @@ impl Locator {
         }
 
         let effective_timeout = timeout.unwrap_or(self.timeout);
-        let start_time = std::time::Instant::now();
+        let start_time = self.clock.now();
         let poll_interval = Duration::from_millis(100);
+        // Whether the last poll found the element without it meeting the condition
+        let mut found = false;
 
         loop {
             // Check if we've exceeded the timeout
-            if start_time.elapsed() > effective_timeout {
-                return Err(AutomationError::Timeout(format!(
-                    "Timed out after {:?} waiting for element {} to be {:?}",
+            if self.clock.now() - start_time > effective_timeout {
+                let outcome = if found {
+                    format!("it was found but never {condition:?}")
+                } else {
+                    "it was never found".to_string()
+                };
+                let message = format!(
+                    "Timed out after {:?} waiting for element {} to be {:?}: {}",
                     effective_timeout,
                     self.selector_string(),
-                    condition
-                )));
+                    condition,
+                    outcome
+                );
+                if self.engine.is_elevation_prompt_active() {
+                    return Err(elevation_required(message));
+                }
+                return Err(AutomationError::Timeout(message));
             }
 
             // Try to find the element with a short timeout
@@ -212,14 +923,16 @@ impl Locator {
                             "Condition {:?} met for selector {} after {:?}",
                             condition,
                             self.selector_string(),
-                            start_time.elapsed()
+                            self.clock.now() - start_time
                         );
                         return Ok(element);
                     }
                     // Condition not met yet, continue polling
+                    found = true;
                 }
                 Ok(None) => {
                     // Element doesn't exist yet, continue polling
+                    found = false;
                 }
                 Err(e) => {
                     // Platform error or invalid selector
@@ -228,10 +941,22 @@ impl Locator {
             }
 
             // Wait before the next poll
-            tokio::time::sleep(poll_interval).await;
+            self.clock.sleep(poll_interval).await;
         }
     }
 
+    /// Wait for an element to exist and be enabled, e.g. a Submit button that is
+    /// disabled until a form is valid.
+    ///
+    /// Shorthand for `wait_for(WaitCondition::Enabled, timeout)`. The `Timeout` error
+    /// says whether the element was found but stayed disabled, or was never found.
+    pub async fn wait_enabled(
+        &self,
+        timeout: Option<Duration>,
+    ) -> Result<UIElement, AutomationError> {
+        self.wait_for(WaitCondition::Enabled, timeout).await
+    }
+
     fn append_selector(&self, selector_to_append: Selector) -> Locator {
         let mut new_chain = match self.selector.clone() {
             Selector::Chain(existing_chain) => existing_chain,
@@ -239,6 +964,11 @@ impl Locator {
             _ => vec![],
         };
 
+        let selector_to_append = match &self.name_normalization {
+            Some(normalization) => normalization.normalize_selector(&selector_to_append),
+            None => selector_to_append,
+        };
+
         // Append the new selector, flattening if it's also a chain
         match selector_to_append {
             Selector::Chain(mut next_chain_parts) => {
@@ -252,6 +982,11 @@ impl Locator {
             selector: Selector::Chain(new_chain),
             timeout: self.timeout,
             root: self.root.clone(),
+            scoring: self.scoring,
+            verbose_errors: self.verbose_errors.clone(),
+            clock: self.clock.clone(),
+            stale_handling: self.stale_handling,
+            name_normalization: self.name_normalization,
         }
     }
 
@@ -260,6 +995,22 @@ impl Locator {
         self.append_selector(Selector::Visible(is_visible))
     }
 
+    /// Adds a filter to find elements based on whether they are enabled.
+    pub fn enabled(&self, is_enabled: bool) -> Locator {
+        self.append_selector(Selector::State {
+            state: ElementState::Enabled,
+            expected: is_enabled,
+        })
+    }
+
+    /// Adds a filter to find elements based on whether they have keyboard focus.
+    pub fn focused(&self, is_focused: bool) -> Locator {
+        self.append_selector(Selector::State {
+            state: ElementState::Focused,
+            expected: is_focused,
+        })
+    }
+
     /// Get a nested locator
     pub fn locator(&self, selector: impl Into<Selector>) -> Locator {
         self.append_selector(selector.into())
@@ -0,0 +1,239 @@
+//! Automatic handling of unexpected modal dialogs.
+//!
+//! Handlers are registered through [`Desktop::register_modal_handler`](crate::Desktop::register_modal_handler).
+//! While at least one handler is registered, a background thread polls the focused
+//! window; when a newly focused window matches a handler's [`ModalMatcher`], the
+//! handler's [`ModalAction`] is dispatched against it.
+
+use crate::platforms::AccessibilityEngine;
+use crate::selector::Selector;
+use crate::{parent_window_max_iterations, AutomationError, UIElement};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tracing::{debug, info, warn};
+
+/// How often the watcher checks the focused window
+const MODAL_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// How long to look for the target button of a [`ModalAction::ClickButton`]
+const MODAL_BUTTON_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Describes which dialogs a handler applies to.
+///
+/// All criteria that are set must match. A matcher with no criteria never
+/// matches, so a handler can't accidentally act on every window.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ModalMatcher {
+    /// Case-insensitive substring the dialog title must contain
+    pub title_contains: Option<String>,
+    /// Name of the process owning the dialog (case-insensitive, ".exe" optional)
+    pub process: Option<String>,
+}
+
+impl ModalMatcher {
+    /// Match dialogs whose title contains `title` (case-insensitive).
+    pub fn title(title: impl Into<String>) -> Self {
+        Self {
+            title_contains: Some(title.into()),
+            process: None,
+        }
+    }
+
+    /// Restrict the matcher to dialogs owned by `process`.
+    pub fn with_process(mut self, process: impl Into<String>) -> Self {
+        self.process = Some(process.into());
+        self
+    }
+
+    /// Check a window's title and owning process against this matcher.
+    pub fn matches(&self, title: &str, process: Option<&str>) -> bool {
+        if self.title_contains.is_none() && self.process.is_none() {
+            return false;
+        }
+
+        if let Some(expected) = &self.title_contains {
+            if !title.to_lowercase().contains(&expected.to_lowercase()) {
+                return false;
+            }
+        }
+
+        if let Some(expected) = &self.process {
+            let normalize = |name: &str| {
+                let lower = name.to_lowercase();
+                lower.strip_suffix(".exe").unwrap_or(&lower).to_string()
+            };
+            match process {
+                Some(actual) if normalize(actual) == normalize(expected) => {}
+                _ => return false,
+            }
+        }
+
+        true
+    }
+}
+
+/// What to do when a matching dialog appears.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ModalAction {
+    /// Click the dialog's button with this name (e.g. "Allow", "Don't Save")
+    ClickButton(String),
+    /// Send a key to the dialog (e.g. "{Enter}", "{Escape}")
+    PressKey(String),
+    /// Close the dialog window
+    Close,
+}
+
+/// Identifies a registered modal handler so it can be removed later.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ModalHandlerId(u64);
+
+struct ModalHandler {
+    id: ModalHandlerId,
+    matcher: ModalMatcher,
+    action: ModalAction,
+}
+
+/// Registered handlers plus the state of the background polling thread.
+#[derive(Default)]
+pub(crate) struct ModalWatcher {
+    handlers: Mutex<Vec<ModalHandler>>,
+    next_id: AtomicU64,
+    running: AtomicBool,
+}
+
+impl ModalWatcher {
+    /// Add a handler, starting the polling thread if it isn't running yet.
+    pub(crate) fn register(
+        self: &Arc<Self>,
+        engine: Arc<dyn AccessibilityEngine>,
+        matcher: ModalMatcher,
+        action: ModalAction,
+    ) -> ModalHandlerId {
+        let id = ModalHandlerId(self.next_id.fetch_add(1, Ordering::SeqCst));
+        self.handlers
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(ModalHandler {
+                id,
+                matcher,
+                action,
+            });
+
+        if !self.running.swap(true, Ordering::SeqCst) {
+            let watcher = Arc::clone(self);
+            let spawned = std::thread::Builder::new()
+                .name("terminator-modal-watcher".to_string())
+                .spawn(move || watcher.run(engine));
+            if let Err(e) = spawned {
+                warn!("Failed to start modal watcher thread: {e}");
+                self.running.store(false, Ordering::SeqCst);
+            }
+        }
+
+        id
+    }
+
+    /// Remove a handler. Returns false if no handler had this id.
+    /// The polling thread exits on its own once no handlers remain.
+    pub(crate) fn remove(&self, id: ModalHandlerId) -> bool {
+        let mut handlers = self.handlers.lock().unwrap_or_else(|e| e.into_inner());
+        let before = handlers.len();
+        handlers.retain(|handler| handler.id != id);
+        handlers.len() != before
+    }
+
+    fn has_handlers(&self) -> bool {
+        !self
+            .handlers
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .is_empty()
+    }
+
+    fn run(&self, engine: Arc<dyn AccessibilityEngine>) {
+        debug!("Modal watcher started");
+        // Only act when focus moves to a different window, so a dialog that
+        // survives its action isn't handled over and over.
+        let mut last_window: Option<String> = None;
+
+        loop {
+            std::thread::sleep(MODAL_POLL_INTERVAL);
+
+            if !self.has_handlers() {
+                self.running.store(false, Ordering::SeqCst);
+                // A handler registered between the check and the store saw
+                // `running == true` and didn't spawn a thread, so keep going.
+                if self.has_handlers() && !self.running.swap(true, Ordering::SeqCst) {
+                    continue;
+                }
+                debug!("Modal watcher stopped");
+                return;
+            }
+
+            let Some(window) = engine
+                .get_focused_element()
+                .ok()
+                .and_then(|focused| containing_window(&focused))
+            else {
+                continue;
+            };
+
+            let window_id = window.id();
+            if window_id.is_some() && window_id == last_window {
+                continue;
+            }
+            last_window = window_id;
+
+            let title = window.name().unwrap_or_default();
+            let process = window.process_name().ok();
+            let action = {
+                let handlers = self.handlers.lock().unwrap_or_else(|e| e.into_inner());
+                handlers
+                    .iter()
+                    .find(|handler| handler.matcher.matches(&title, process.as_deref()))
+                    .map(|handler| handler.action.clone())
+            };
+
+            if let Some(action) = action {
+                info!("Modal dialog '{title}' matched a handler, dispatching {action:?}");
+                if let Err(e) = dispatch(engine.as_ref(), &window, &action) {
+                    warn!("Modal handler {action:?} failed on '{title}': {e}");
+                }
+            }
+        }
+    }
+}
+
+/// The nearest `Window` at or above `element`. Unlike [`crate::find_parent_window`]
+/// this skips panes, whose name is a region of the dialog rather than its title.
+pub(crate) fn containing_window(element: &UIElement) -> Option<UIElement> {
+    let mut current = element.clone();
+    for _ in 0..parent_window_max_iterations() {
+        if current.role() == "Window" {
+            return Some(current);
+        }
+        current = current.parent().ok()??;
+    }
+    None
+}
+
+fn dispatch(
+    engine: &dyn AccessibilityEngine,
+    window: &UIElement,
+    action: &ModalAction,
+) -> Result<(), AutomationError> {
+    match action {
+        ModalAction::ClickButton(name) => {
+            let selector = Selector::Role {
+                role: "Button".to_string(),
+                name: Some(name.clone()),
+            };
+            let button =
+                engine.find_element(&selector, Some(window), Some(MODAL_BUTTON_TIMEOUT))?;
+            button.click().map(|_| ())
+        }
+        ModalAction::PressKey(key) => window.press_key(key),
+        ModalAction::Close => window.close(),
+    }
+}
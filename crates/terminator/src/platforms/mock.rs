@@ -0,0 +1,1187 @@
+//! In-memory accessibility engine for testing code that drives a [`Desktop`](crate::Desktop)
+//! without a live UI.
+//!
+//! [`MockEngine`] serves a scripted [`UINode`] tree and records every action performed on
+//! it, so tests can assert which element a selector resolved to and what was done to it:
+//!
+//! ```no_run
+//! use std::sync::Arc;
+//! use terminator::platforms::mock::MockEngine;
+//! use terminator::{Desktop, UIElementAttributes, UINode};
+//!
+//! fn node(role: &str, name: &str, children: Vec<UINode>) -> UINode {
+//!     UINode {
+//!         id: None,
+//!         attributes: UIElementAttributes {
+//!             role: role.to_string(),
+//!             name: Some(name.to_string()),
+//!             ..Default::default()
+//!         },
+//!         children,
+//!         selector: None,
+//!     }
+//! }
+//!
+//! # async fn example() -> Result<(), terminator::AutomationError> {
+//! let engine = Arc::new(MockEngine::new(node(
+//!     "Pane",
+//!     "Desktop",
+//!     vec![node("Window", "Editor", vec![node("Button", "Save", vec![])])],
+//! )));
+//! let desktop = Desktop::with_engine(engine.clone());
+//!
+//! desktop.locator("role:Button|name:Save").first(None).await?.click()?;
+//! assert_eq!(engine.actions()[0].action, "click");
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Only the simple selectors (`role`, `name`, `text`, `id`, `nativeid`, `classname`, `app`,
+//! `has` and their boolean/chain combinations) are supported; anything else returns
+//! [`AutomationError::UnsupportedOperation`]. Operations that need a real screen (monitors,
+//! screenshots, OCR, commands) do the same.
+
+use crate::element::UIElementImpl;
+use crate::platforms::{AccessibilityEngine, TreeBuildConfig};
+use crate::{
+    AutomationError, Browser, ClickResult, ClickType, ControlPattern, FontStyle, HighlightHandle,
+    Locator, Modifier, ScreenshotResult, Selector, TextPosition, UIElement, UIElementAttributes,
+    UINode,
+};
+use std::fmt;
+use std::sync::atomic::AtomicBool;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// An action performed against a [`MockEngine`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct MockAction {
+    /// `UINode::id` of the target element; None for desktop-level actions such as `press_key`
+    pub element_id: Option<String>,
+    /// Name of the target element
+    pub element_name: Option<String>,
+    /// Method that was called, e.g. `click`, `type_text` or `open_application`
+    pub action: String,
+    /// Main argument of the call (typed text, key, value...), if any
+    pub argument: Option<String>,
+}
+
+struct MockNode {
+    id: Option<String>,
+    attributes: UIElementAttributes,
+    parent: Option<usize>,
+    children: Vec<usize>,
+    /// Removed from the tree by [`MockEngine::replace_element`]
+    detached: bool,
+}
+
+#[derive(Default)]
+struct MockState {
+    nodes: Mutex<Vec<MockNode>>,
+    actions: Mutex<Vec<MockAction>>,
+    focused: Mutex<Option<usize>>,
+    elevation_prompt: AtomicBool,
+}
+
+/// Append `tree` to the arena under `parent`, returning the index of its root
+fn insert_tree(nodes: &mut Vec<MockNode>, tree: UINode, parent: Option<usize>) -> usize {
+    let root = nodes.len();
+    let mut stack = vec![(tree, parent)];
+    while let Some((node, parent)) = stack.pop() {
+        let index = nodes.len();
+        nodes.push(MockNode {
+            id: node.id,
+            attributes: node.attributes,
+            parent,
+            children: Vec::new(),
+            detached: false,
+        });
+        if let Some(parent) = parent {
+            nodes[parent].children.push(index);
+        }
+        // Reversed so children are numbered, and listed, in document order
+        stack.extend(node.children.into_iter().rev().map(|c| (c, Some(index))));
+    }
+    root
+}
+
+impl MockState {
+    fn record(&self, index: Option<usize>, action: &str, argument: Option<String>) {
+        let (element_id, element_name) = match index {
+            Some(index) => {
+                let nodes = self.nodes.lock().unwrap();
+                (
+                    nodes[index].id.clone(),
+                    nodes[index].attributes.name.clone(),
+                )
+            }
+            None => (None, None),
+        };
+        self.actions.lock().unwrap().push(MockAction {
+            element_id,
+            element_name,
+            action: action.to_string(),
+            argument,
+        });
+    }
+}
+
+/// Accessibility engine backed by a scripted UI tree.
+///
+/// The root of the tree stands in for the desktop and its children for the applications.
+/// Actions update the tree where it makes sense (typing changes the value, toggling
+/// changes `is_toggled`, ...) so later reads observe them.
+#[derive(Clone)]
+pub struct MockEngine {
+    state: Arc<MockState>,
+}
+
+impl MockEngine {
+    pub fn new(tree: UINode) -> Self {
+        let state = MockState::default();
+        insert_tree(&mut state.nodes.lock().unwrap(), tree, None);
+        Self {
+            state: Arc::new(state),
+        }
+    }
+
+    /// Swap the element with the given `UINode::id` for a freshly built subtree, the way
+    /// an app re-renders a list item. Elements obtained from the old subtree go stale:
+    /// their `runtime_id` becomes None and selectors no longer find them.
+    pub fn replace_element(&self, element_id: &str, tree: UINode) -> Result<(), AutomationError> {
+        let old = self.index_of(element_id)?;
+        let mut nodes = self.state.nodes.lock().unwrap();
+        let parent = nodes[old].parent.ok_or_else(|| {
+            AutomationError::InvalidArgument("Cannot replace the mock root".to_string())
+        })?;
+        let new = insert_tree(&mut nodes, tree, None);
+        nodes[new].parent = Some(parent);
+        for child in nodes[parent].children.iter_mut() {
+            if *child == old {
+                *child = new;
+            }
+        }
+        let mut stack = vec![old];
+        while let Some(index) = stack.pop() {
+            nodes[index].detached = true;
+            stack.extend(nodes[index].children.iter().copied());
+        }
+        Ok(())
+    }
+
+    /// Actions performed so far, oldest first
+    pub fn actions(&self) -> Vec<MockAction> {
+        self.state.actions.lock().unwrap().clone()
+    }
+
+    pub fn clear_actions(&self) {
+        self.state.actions.lock().unwrap().clear();
+    }
+
+    /// Simulate a UAC prompt holding the screen, see
+    /// [`AccessibilityEngine::is_elevation_prompt_active`]
+    pub fn set_elevation_prompt_active(&self, active: bool) {
+        self.state
+            .elevation_prompt
+            .store(active, std::sync::atomic::Ordering::SeqCst);
+    }
+
+    /// Make the element with the given `UINode::id` the focused element
+    pub fn set_focused(&self, element_id: &str) -> Result<(), AutomationError> {
+        let index = self.index_of(element_id)?;
+        *self.state.focused.lock().unwrap() = Some(index);
+        Ok(())
+    }
+
+    /// Look up an element by its `UINode::id`
+    pub fn element(&self, element_id: &str) -> Result<UIElement, AutomationError> {
+        Ok(self.element_at(self.index_of(element_id)?))
+    }
+
+    fn index_of(&self, element_id: &str) -> Result<usize, AutomationError> {
+        self.state
+            .nodes
+            .lock()
+            .unwrap()
+            .iter()
+            .position(|node| !node.detached && node.id.as_deref() == Some(element_id))
+            .ok_or_else(|| {
+                AutomationError::ElementNotFound(format!("No mock element with id '{element_id}'"))
+            })
+    }
+
+    fn element_at(&self, index: usize) -> UIElement {
+        UIElement::new(Box::new(MockElement {
+            state: self.state.clone(),
+            index,
+        }))
+    }
+
+    fn index_of_element(&self, element: &UIElement) -> Result<usize, AutomationError> {
+        match element.as_any().downcast_ref::<MockElement>() {
+            Some(mock) if Arc::ptr_eq(&mock.state, &self.state) => Ok(mock.index),
+            _ => Err(AutomationError::InvalidArgument(
+                "Element does not belong to this MockEngine".to_string(),
+            )),
+        }
+    }
+
+    fn descendants(&self, root: usize, max_depth: Option<usize>) -> Vec<usize> {
+        let nodes = self.state.nodes.lock().unwrap();
+        let mut found = Vec::new();
+        let mut stack: Vec<(usize, usize)> =
+            nodes[root].children.iter().rev().map(|&c| (c, 1)).collect();
+        while let Some((index, depth)) = stack.pop() {
+            found.push(index);
+            if max_depth.is_none_or(|max| depth < max) {
+                stack.extend(nodes[index].children.iter().rev().map(|&c| (c, depth + 1)));
+            }
+        }
+        found
+    }
+
+    fn matches(&self, index: usize, selector: &Selector) -> Result<bool, AutomationError> {
+        let contains = |haystack: &Option<String>, needle: &str| {
+            haystack
+                .as_deref()
+                .is_some_and(|h| h.to_lowercase().contains(&needle.to_lowercase()))
+        };
+        let attributes = self.state.nodes.lock().unwrap()[index].attributes.clone();
+        let property = |key: &str| {
+            attributes
+                .properties
+                .get(key)
+                .cloned()
+                .flatten()
+                .and_then(|v| v.as_str().map(str::to_string))
+        };
+        match selector {
+            Selector::Role { role, name } => Ok(attributes.role.eq_ignore_ascii_case(role)
+                && name.as_ref().is_none_or(|n| contains(&attributes.name, n))),
+            Selector::Name(name) => Ok(contains(&attributes.name, name)),
+            Selector::Text(text) => Ok([&attributes.name, &attributes.text]
+                .iter()
+                .any(|value| value.as_deref().is_some_and(|v| v.contains(text.as_str())))),
+            Selector::Id(id) => {
+                let id = id.strip_prefix('#').unwrap_or(id);
+                Ok(self.state.nodes.lock().unwrap()[index].id.as_deref() == Some(id))
+            }
+            Selector::NativeId(id) => Ok(property("AutomationId").as_deref() == Some(id.as_str())),
+            Selector::ClassName(class) => {
+                Ok(property("ClassName").as_deref() == Some(class.as_str()))
+            }
+            Selector::And(selectors) => {
+                for sel in selectors {
+                    if !self.matches(index, sel)? {
+                        return Ok(false);
+                    }
+                }
+                Ok(true)
+            }
+            Selector::Or(selectors) => {
+                for sel in selectors {
+                    if self.matches(index, sel)? {
+                        return Ok(true);
+                    }
+                }
+                Ok(false)
+            }
+            Selector::Not(inner) => Ok(!self.matches(index, inner)?),
+            Selector::Has(inner) => {
+                for descendant in self.descendants(index, None) {
+                    if self.matches(descendant, inner)? {
+                        return Ok(true);
+                    }
+                }
+                Ok(false)
+            }
+            Selector::App(target) => {
+                // Applications are the children of the root
+                if self.state.nodes.lock().unwrap()[index].parent != Some(0) {
+                    return Ok(false);
+                }
+                Ok(match target.parse::<u32>() {
+                    Ok(pid) => self.process_id_of(index) == Some(pid),
+                    Err(_) => contains(&attributes.name, target),
+                })
+            }
+            Selector::Invalid(reason) => Err(AutomationError::InvalidSelector(reason.clone())),
+            other => Err(AutomationError::UnsupportedOperation(format!(
+                "Selector {other:?} is not supported by MockEngine"
+            ))),
+        }
+    }
+
+    fn find_indices(
+        &self,
+        selector: &Selector,
+        root: usize,
+        depth: Option<usize>,
+    ) -> Result<Vec<usize>, AutomationError> {
+        match selector {
+            Selector::Chain(selectors) => {
+                let Some((first, rest)) = selectors.split_first() else {
+                    return Err(AutomationError::InvalidArgument(
+                        "Selector chain cannot be empty".to_string(),
+                    ));
+                };
+                let mut current = self.find_indices(first, root, depth)?;
+                for selector in rest {
+                    current = match selector {
+                        Selector::Nth(n) => {
+                            let n = if *n < 0 {
+                                *n + current.len() as i32
+                            } else {
+                                *n
+                            };
+                            usize::try_from(n)
+                                .ok()
+                                .and_then(|n| current.get(n).copied())
+                                .into_iter()
+                                .collect()
+                        }
+                        Selector::Range { start, end } => {
+                            crate::selector::apply_range(current, *start, *end)
+                        }
+                        _ => {
+                            let mut next = Vec::new();
+                            for &index in &current {
+                                next.extend(self.find_indices(selector, index, None)?);
+                            }
+                            next
+                        }
+                    };
+                }
+                Ok(current)
+            }
+            Selector::Nth(_) => Err(AutomationError::InvalidSelector(
+                "Nth selector must be used as part of a chain (e.g. 'list >> nth=0')".to_string(),
+            )),
+            Selector::Range { .. } => Err(AutomationError::InvalidSelector(
+                "Range selector must be used as part of a chain (e.g. 'list >> range:1..5')"
+                    .to_string(),
+            )),
+            _ => {
+                let mut found = Vec::new();
+                for index in self.descendants(root, depth) {
+                    if self.matches(index, selector)? {
+                        found.push(index);
+                    }
+                }
+                Ok(found)
+            }
+        }
+    }
+
+    fn ancestor_or_self(&self, index: usize, role: &str) -> Option<usize> {
+        let nodes = self.state.nodes.lock().unwrap();
+        let mut current = Some(index);
+        while let Some(i) = current {
+            if nodes[i].attributes.role.eq_ignore_ascii_case(role) {
+                return Some(i);
+            }
+            current = nodes[i].parent;
+        }
+        None
+    }
+
+    fn application_of(&self, index: usize) -> Option<usize> {
+        let nodes = self.state.nodes.lock().unwrap();
+        let mut current = index;
+        while let Some(parent) = nodes[current].parent {
+            if parent == 0 {
+                return Some(current);
+            }
+            current = parent;
+        }
+        None
+    }
+
+    fn process_id_of(&self, index: usize) -> Option<u32> {
+        let nodes = self.state.nodes.lock().unwrap();
+        let mut current = Some(index);
+        while let Some(i) = current {
+            if let Some(Some(pid)) = nodes[i].attributes.properties.get("ProcessId") {
+                return pid.as_u64().and_then(|pid| u32::try_from(pid).ok());
+            }
+            current = nodes[i].parent;
+        }
+        None
+    }
+
+    fn build_tree(&self, index: usize, max_depth: Option<usize>, depth: usize) -> UINode {
+        let (id, attributes, children) = {
+            let nodes = self.state.nodes.lock().unwrap();
+            let node = &nodes[index];
+            (
+                node.id.clone(),
+                node.attributes.clone(),
+                node.children.clone(),
+            )
+        };
+        let children = if max_depth.is_none_or(|max| depth < max) {
+            children
+                .into_iter()
+                .map(|child| self.build_tree(child, max_depth, depth + 1))
+                .collect()
+        } else {
+            Vec::new()
+        };
+        UINode {
+            id,
+            attributes,
+            children,
+            selector: None,
+        }
+    }
+
+    /// Tree under `index` as `TreeBuildConfig` asks for it
+    fn tree_with_config(&self, index: usize, config: &TreeBuildConfig) -> UINode {
+        let mut tree = self.build_tree(index, config.max_depth, 0);
+        if config.stable_ids {
+            tree.assign_stable_ids();
+        }
+        tree
+    }
+
+    fn focused_index(&self) -> Result<usize, AutomationError> {
+        self.state
+            .focused
+            .lock()
+            .unwrap()
+            .ok_or_else(|| AutomationError::ElementNotFound("No mock element is focused".into()))
+    }
+
+    fn unsupported<T>(operation: &str) -> Result<T, AutomationError> {
+        Err(AutomationError::UnsupportedOperation(format!(
+            "{operation} is not supported by MockEngine"
+        )))
+    }
+}
+
+#[async_trait::async_trait]
+impl AccessibilityEngine for MockEngine {
+    fn get_root_element(&self) -> UIElement {
+        self.element_at(0)
+    }
+
+    fn get_element_by_id(&self, id: i32) -> Result<UIElement, AutomationError> {
+        self.element(&id.to_string())
+    }
+
+    fn get_focused_element(&self) -> Result<UIElement, AutomationError> {
+        Ok(self.element_at(self.focused_index()?))
+    }
+
+    fn get_applications(&self) -> Result<Vec<UIElement>, AutomationError> {
+        let children = self.state.nodes.lock().unwrap()[0].children.clone();
+        Ok(children.into_iter().map(|i| self.element_at(i)).collect())
+    }
+
+    fn get_application_by_name(&self, name: &str) -> Result<UIElement, AutomationError> {
+        self.get_applications()?
+            .into_iter()
+            .find(|app| {
+                app.name()
+                    .is_some_and(|n| n.to_lowercase().contains(&name.to_lowercase()))
+            })
+            .ok_or_else(|| AutomationError::ElementNotFound(format!("Application '{name}'")))
+    }
+
+    fn get_application_by_pid(
+        &self,
+        pid: i32,
+        _timeout: Option<Duration>,
+    ) -> Result<UIElement, AutomationError> {
+        let children = self.state.nodes.lock().unwrap()[0].children.clone();
+        children
+            .into_iter()
+            .find(|&i| self.process_id_of(i).is_some_and(|p| p as i32 == pid))
+            .map(|i| self.element_at(i))
+            .ok_or_else(|| AutomationError::ElementNotFound(format!("Application with PID {pid}")))
+    }
+
+    fn find_element(
+        &self,
+        selector: &Selector,
+        root: Option<&UIElement>,
+        timeout: Option<Duration>,
+    ) -> Result<UIElement, AutomationError> {
+        self.find_elements(selector, root, timeout, None)?
+            .into_iter()
+            .next()
+            .ok_or_else(|| {
+                AutomationError::ElementNotFound(format!("No mock element matches {selector:?}"))
+            })
+    }
+
+    fn find_elements(
+        &self,
+        selector: &Selector,
+        root: Option<&UIElement>,
+        _timeout: Option<Duration>,
+        depth: Option<usize>,
+    ) -> Result<Vec<UIElement>, AutomationError> {
+        let root = root.map(|r| self.index_of_element(r)).transpose()?;
+        Ok(self
+            .find_indices(selector, root.unwrap_or(0), depth)?
+            .into_iter()
+            .map(|i| self.element_at(i))
+            .collect())
+    }
+
+    fn open_application(&self, app_name: &str) -> Result<UIElement, AutomationError> {
+        self.state
+            .record(None, "open_application", Some(app_name.to_string()));
+        self.get_application_by_name(app_name)
+    }
+
+    fn activate_application(&self, app_name: &str) -> Result<(), AutomationError> {
+        self.state
+            .record(None, "activate_application", Some(app_name.to_string()));
+        Ok(())
+    }
+
+    fn open_url(&self, url: &str, _browser: Option<Browser>) -> Result<UIElement, AutomationError> {
+        self.state.record(None, "open_url", Some(url.to_string()));
+        Self::unsupported("open_url")
+    }
+
+    fn open_file(&self, file_path: &str) -> Result<(), AutomationError> {
+        self.state
+            .record(None, "open_file", Some(file_path.to_string()));
+        Ok(())
+    }
+
+    async fn run_command(
+        &self,
+        _windows_command: Option<&str>,
+        _unix_command: Option<&str>,
+    ) -> Result<crate::CommandOutput, AutomationError> {
+        Self::unsupported("run_command")
+    }
+
+    async fn list_monitors(&self) -> Result<Vec<crate::Monitor>, AutomationError> {
+        Self::unsupported("list_monitors")
+    }
+
+    async fn get_primary_monitor(&self) -> Result<crate::Monitor, AutomationError> {
+        Self::unsupported("get_primary_monitor")
+    }
+
+    async fn get_active_monitor(&self) -> Result<crate::Monitor, AutomationError> {
+        Self::unsupported("get_active_monitor")
+    }
+
+    async fn get_monitor_by_id(&self, _id: &str) -> Result<crate::Monitor, AutomationError> {
+        Self::unsupported("get_monitor_by_id")
+    }
+
+    async fn get_monitor_by_name(&self, _name: &str) -> Result<crate::Monitor, AutomationError> {
+        Self::unsupported("get_monitor_by_name")
+    }
+
+    async fn capture_monitor_by_id(&self, _id: &str) -> Result<ScreenshotResult, AutomationError> {
+        Self::unsupported("capture_monitor_by_id")
+    }
+
+    async fn ocr_image_path(&self, _image_path: &str) -> Result<String, AutomationError> {
+        Self::unsupported("ocr_image_path")
+    }
+
+    async fn ocr_screenshot(
+        &self,
+        _screenshot: &ScreenshotResult,
+    ) -> Result<String, AutomationError> {
+        Self::unsupported("ocr_screenshot")
+    }
+
+    fn activate_browser_window_by_title(&self, title: &str) -> Result<(), AutomationError> {
+        self.state.record(
+            None,
+            "activate_browser_window_by_title",
+            Some(title.to_string()),
+        );
+        Ok(())
+    }
+
+    async fn get_current_browser_window(&self) -> Result<UIElement, AutomationError> {
+        Self::unsupported("get_current_browser_window")
+    }
+
+    async fn get_current_window(&self) -> Result<UIElement, AutomationError> {
+        self.get_foreground_window()
+    }
+
+    fn get_element_at_point(&self, x: f64, y: f64) -> Result<UIElement, AutomationError> {
+        // The deepest element whose bounds contain the point, like a real hit test
+        let nodes = self.state.nodes.lock().unwrap();
+        let mut hit = None;
+        let mut candidates = vec![0];
+        while let Some(index) = candidates.pop() {
+            if let Some((bx, by, bw, bh)) = nodes[index].attributes.bounds {
+                if x >= bx && x < bx + bw && y >= by && y < by + bh {
+                    hit = Some(index);
+                    candidates = nodes[index].children.iter().rev().copied().collect();
+                }
+            } else if index == 0 {
+                candidates.extend(nodes[0].children.iter().rev());
+            }
+        }
+        drop(nodes);
+        hit.map(|i| self.element_at(i))
+            .ok_or_else(|| AutomationError::ElementNotFound(format!("No element at ({x}, {y})")))
+    }
+
+    fn get_foreground_window(&self) -> Result<UIElement, AutomationError> {
+        let focused = self.focused_index()?;
+        self.ancestor_or_self(focused, "Window")
+            .map(|i| self.element_at(i))
+            .ok_or_else(|| AutomationError::ElementNotFound("No window is focused".into()))
+    }
+
+    async fn get_current_application(&self) -> Result<UIElement, AutomationError> {
+        let focused = self.focused_index()?;
+        self.application_of(focused)
+            .map(|i| self.element_at(i))
+            .ok_or_else(|| AutomationError::ElementNotFound("No application is focused".into()))
+    }
+
+    fn press_key(&self, key: &str) -> Result<(), AutomationError> {
+        self.state.record(None, "press_key", Some(key.to_string()));
+        Ok(())
+    }
+
+    fn is_elevation_prompt_active(&self) -> bool {
+        self.state
+            .elevation_prompt
+            .load(std::sync::atomic::Ordering::SeqCst)
+    }
+
+    fn drag_path(
+        &self,
+        points: &[(f64, f64)],
+        _hold_between: Duration,
+    ) -> Result<(), AutomationError> {
+        let path: Vec<String> = points.iter().map(|(x, y)| format!("{x},{y}")).collect();
+        self.state.record(None, "drag_path", Some(path.join(" ")));
+        Ok(())
+    }
+
+    fn press_key_combo(&self, modifiers: &[Modifier], key: &str) -> Result<(), AutomationError> {
+        let combo = modifiers
+            .iter()
+            .map(|m| format!("{m:?}"))
+            .chain(std::iter::once(key.to_string()))
+            .collect::<Vec<_>>()
+            .join("+");
+        self.state.record(None, "press_key_combo", Some(combo));
+        Ok(())
+    }
+
+    fn press_scan_code(&self, scan_code: u16, extended: bool) -> Result<(), AutomationError> {
+        let code = if extended {
+            format!("E0 {scan_code:02X}")
+        } else {
+            format!("{scan_code:02X}")
+        };
+        self.state.record(None, "press_scan_code", Some(code));
+        Ok(())
+    }
+
+    fn set_zoom(&self, percentage: u32) -> Result<(), AutomationError> {
+        self.state
+            .record(None, "set_zoom", Some(percentage.to_string()));
+        Ok(())
+    }
+
+    fn get_window_tree(
+        &self,
+        pid: u32,
+        title: Option<&str>,
+        config: TreeBuildConfig,
+    ) -> Result<UINode, AutomationError> {
+        let windows = self.find_indices(
+            &Selector::Role {
+                role: "Window".to_string(),
+                name: title.map(str::to_string),
+            },
+            0,
+            None,
+        )?;
+        let window = windows
+            .into_iter()
+            .find(|&w| self.process_id_of(w).is_none_or(|p| p == pid))
+            .ok_or_else(|| {
+                AutomationError::ElementNotFound(format!("No window for PID {pid} ({title:?})"))
+            })?;
+        Ok(self.tree_with_config(window, &config))
+    }
+
+    fn get_tree_from_element(
+        &self,
+        element: &UIElement,
+        config: TreeBuildConfig,
+    ) -> Result<UINode, AutomationError> {
+        let index = self.index_of_element(element)?;
+        Ok(self.tree_with_config(index, &config))
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+/// Element of a [`MockEngine`] tree
+#[derive(Clone)]
+pub struct MockElement {
+    state: Arc<MockState>,
+    index: usize,
+}
+
+impl fmt::Debug for MockElement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let nodes = self.state.nodes.lock().unwrap();
+        f.debug_struct("MockElement")
+            .field("id", &nodes[self.index].id)
+            .field("role", &nodes[self.index].attributes.role)
+            .field("name", &nodes[self.index].attributes.name)
+            .finish()
+    }
+}
+
+impl MockElement {
+    fn engine(&self) -> MockEngine {
+        MockEngine {
+            state: self.state.clone(),
+        }
+    }
+
+    fn record(&self, action: &str, argument: Option<String>) {
+        self.state.record(Some(self.index), action, argument);
+    }
+
+    fn update(&self, f: impl FnOnce(&mut UIElementAttributes)) {
+        f(&mut self.state.nodes.lock().unwrap()[self.index].attributes);
+    }
+
+    fn element_at(&self, index: Option<usize>) -> Option<UIElement> {
+        index.map(|index| self.engine().element_at(index))
+    }
+
+    fn click_result(&self, method: &str) -> ClickResult {
+        let bounds = self.attributes().bounds;
+        ClickResult {
+            method: method.to_string(),
+            coordinates: bounds.map(|(x, y, w, h)| (x + w / 2.0, y + h / 2.0)),
+            details: "Recorded by MockEngine".to_string(),
+        }
+    }
+}
+
+impl UIElementImpl for MockElement {
+    fn object_id(&self) -> usize {
+        (Arc::as_ptr(&self.state) as usize).wrapping_add(self.index)
+    }
+
+    fn id(&self) -> Option<String> {
+        self.state.nodes.lock().unwrap()[self.index].id.clone()
+    }
+
+    /// The arena index, or None once the element was replaced
+    fn runtime_id(&self) -> Option<Vec<i32>> {
+        let nodes = self.state.nodes.lock().unwrap();
+        (!nodes[self.index].detached).then(|| vec![self.index as i32])
+    }
+
+    fn role(&self) -> String {
+        self.attributes().role
+    }
+
+    fn attributes(&self) -> UIElementAttributes {
+        let nodes = self.state.nodes.lock().unwrap();
+        let mut attributes = nodes[self.index].attributes.clone();
+        attributes.child_count = Some(nodes[self.index].children.len());
+        attributes.is_focused = Some(*self.state.focused.lock().unwrap() == Some(self.index));
+        attributes
+    }
+
+    fn children(&self) -> Result<Vec<UIElement>, AutomationError> {
+        let children = self.state.nodes.lock().unwrap()[self.index]
+            .children
+            .clone();
+        Ok(children
+            .into_iter()
+            .filter_map(|i| self.element_at(Some(i)))
+            .collect())
+    }
+
+    fn parent(&self) -> Result<Option<UIElement>, AutomationError> {
+        let parent = self.state.nodes.lock().unwrap()[self.index].parent;
+        Ok(self.element_at(parent))
+    }
+
+    /// Follows a `LabeledBy` property holding the label's `UINode::id`
+    fn labeled_by(&self) -> Option<UIElement> {
+        let label_id = self.attributes().properties.get("LabeledBy")?.clone()?;
+        let index = self.engine().index_of(label_id.as_str()?).ok()?;
+        self.element_at(Some(index))
+    }
+
+    fn bounds(&self) -> Result<(f64, f64, f64, f64), AutomationError> {
+        self.attributes()
+            .bounds
+            .ok_or_else(|| AutomationError::PlatformError("Mock element has no bounds".to_string()))
+    }
+
+    fn click(&self) -> Result<ClickResult, AutomationError> {
+        self.record("click", None);
+        Ok(self.click_result("Mock"))
+    }
+
+    fn double_click(&self) -> Result<ClickResult, AutomationError> {
+        self.record("double_click", None);
+        Ok(self.click_result("Mock"))
+    }
+
+    fn right_click(&self) -> Result<(), AutomationError> {
+        self.record("right_click", None);
+        Ok(())
+    }
+
+    fn click_at_position(
+        &self,
+        x_pct: u8,
+        y_pct: u8,
+        click_type: ClickType,
+    ) -> Result<ClickResult, AutomationError> {
+        self.record(
+            "click_at_position",
+            Some(format!("{x_pct},{y_pct},{click_type:?}")),
+        );
+        Ok(self.click_result("Mock"))
+    }
+
+    fn hover(&self) -> Result<(), AutomationError> {
+        self.record("hover", None);
+        Ok(())
+    }
+
+    fn focus(&self) -> Result<(), AutomationError> {
+        self.record("focus", None);
+        *self.state.focused.lock().unwrap() = Some(self.index);
+        Ok(())
+    }
+
+    fn invoke(&self) -> Result<(), AutomationError> {
+        self.record("invoke", None);
+        Ok(())
+    }
+
+    fn type_text(
+        &self,
+        text: &str,
+        _use_clipboard: bool,
+        _try_focus_before: bool,
+        _try_click_before: bool,
+        _restore_focus: bool,
+    ) -> Result<(), AutomationError> {
+        self.record("type_text", Some(text.to_string()));
+        self.update(|a| a.value = Some(a.value.take().unwrap_or_default() + text));
+        Ok(())
+    }
+
+    fn press_key(
+        &self,
+        key: &str,
+        _try_focus_before: bool,
+        _try_click_before: bool,
+        _restore_focus: bool,
+    ) -> Result<(), AutomationError> {
+        self.record("press_key", Some(key.to_string()));
+        Ok(())
+    }
+
+    fn get_text(&self, max_depth: usize) -> Result<String, AutomationError> {
+        let engine = self.engine();
+        let mut indices = vec![self.index];
+        indices.extend(engine.descendants(self.index, Some(max_depth)));
+        let nodes = self.state.nodes.lock().unwrap();
+        Ok(indices
+            .into_iter()
+            .filter_map(|i| {
+                let a = &nodes[i].attributes;
+                a.text.clone().or_else(|| a.name.clone())
+            })
+            .filter(|text| !text.is_empty())
+            .collect::<Vec<_>>()
+            .join(" "))
+    }
+
+    fn set_value(&self, value: &str) -> Result<(), AutomationError> {
+        self.record("set_value", Some(value.to_string()));
+        self.update(|a| a.value = Some(value.to_string()));
+        Ok(())
+    }
+
+    fn get_value(&self) -> Result<Option<String>, AutomationError> {
+        Ok(self.attributes().value)
+    }
+
+    fn is_enabled(&self) -> Result<bool, AutomationError> {
+        Ok(self.attributes().enabled.unwrap_or(true))
+    }
+
+    fn is_visible(&self) -> Result<bool, AutomationError> {
+        Ok(true)
+    }
+
+    fn is_focused(&self) -> Result<bool, AutomationError> {
+        Ok(*self.state.focused.lock().unwrap() == Some(self.index))
+    }
+
+    fn element_at_point(&self, x: f64, y: f64) -> Result<UIElement, AutomationError> {
+        self.engine().get_element_at_point(x, y)
+    }
+
+    fn invoke_pattern_method(
+        &self,
+        pattern: ControlPattern,
+        method: &str,
+        args: &[serde_json::Value],
+    ) -> Result<serde_json::Value, AutomationError> {
+        self.record(
+            "invoke_pattern_method",
+            Some(format!("{pattern}.{method}{}", serde_json::json!(args))),
+        );
+        Ok(serde_json::Value::Null)
+    }
+
+    fn perform_action(&self, action: &str) -> Result<(), AutomationError> {
+        self.record("perform_action", Some(action.to_string()));
+        Ok(())
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn create_locator(&self, selector: Selector) -> Result<Locator, AutomationError> {
+        Ok(Locator::new(Arc::new(self.engine()), selector)
+            .within(UIElement::new(Box::new(self.clone()))))
+    }
+
+    fn scroll(&self, direction: &str, amount: f64) -> Result<(), AutomationError> {
+        self.record("scroll", Some(format!("{direction} {amount}")));
+        Ok(())
+    }
+
+    fn activate_window(&self) -> Result<(), AutomationError> {
+        self.record("activate_window", None);
+        Ok(())
+    }
+
+    fn minimize_window(&self) -> Result<(), AutomationError> {
+        self.record("minimize_window", None);
+        Ok(())
+    }
+
+    fn maximize_window(&self) -> Result<(), AutomationError> {
+        self.record("maximize_window", None);
+        Ok(())
+    }
+
+    fn maximize_window_keyboard(&self) -> Result<(), AutomationError> {
+        self.maximize_window()
+    }
+
+    fn minimize_window_keyboard(&self) -> Result<(), AutomationError> {
+        self.minimize_window()
+    }
+
+    /// Reads a `NativeWindowHandle` property, so tests can script window handles
+    fn get_native_window_handle(&self) -> Result<isize, AutomationError> {
+        self.attributes()
+            .properties
+            .get("NativeWindowHandle")
+            .cloned()
+            .flatten()
+            .and_then(|handle| handle.as_i64())
+            .and_then(|handle| isize::try_from(handle).ok())
+            .ok_or_else(|| {
+                AutomationError::PlatformError(
+                    "Mock element has no NativeWindowHandle property".to_string(),
+                )
+            })
+    }
+
+    fn clone_box(&self) -> Box<dyn UIElementImpl> {
+        Box::new(self.clone())
+    }
+
+    fn is_keyboard_focusable(&self) -> Result<bool, AutomationError> {
+        Ok(self.attributes().is_keyboard_focusable.unwrap_or(false))
+    }
+
+    fn mouse_drag(
+        &self,
+        start_x: f64,
+        start_y: f64,
+        end_x: f64,
+        end_y: f64,
+    ) -> Result<(), AutomationError> {
+        self.record(
+            "mouse_drag",
+            Some(format!("{start_x},{start_y} -> {end_x},{end_y}")),
+        );
+        Ok(())
+    }
+
+    fn mouse_click_and_hold(&self, x: f64, y: f64) -> Result<(), AutomationError> {
+        self.record("mouse_click_and_hold", Some(format!("{x},{y}")));
+        Ok(())
+    }
+
+    fn mouse_move(&self, x: f64, y: f64) -> Result<(), AutomationError> {
+        self.record("mouse_move", Some(format!("{x},{y}")));
+        Ok(())
+    }
+
+    fn mouse_release(&self) -> Result<(), AutomationError> {
+        self.record("mouse_release", None);
+        Ok(())
+    }
+
+    fn application(&self) -> Result<Option<UIElement>, AutomationError> {
+        Ok(self.element_at(self.engine().application_of(self.index)))
+    }
+
+    fn window(&self) -> Result<Option<UIElement>, AutomationError> {
+        Ok(self.element_at(self.engine().ancestor_or_self(self.index, "Window")))
+    }
+
+    fn highlight(
+        &self,
+        _color: Option<u32>,
+        _duration: Option<Duration>,
+        text: Option<&str>,
+        _text_position: Option<TextPosition>,
+        _font_style: Option<FontStyle>,
+    ) -> Result<HighlightHandle, AutomationError> {
+        self.record("highlight", text.map(str::to_string));
+        Ok(HighlightHandle {
+            should_close: Arc::new(AtomicBool::new(true)),
+            handle: None,
+        })
+    }
+
+    fn set_transparency(&self, percentage: u8) -> Result<(), AutomationError> {
+        self.record("set_transparency", Some(percentage.to_string()));
+        Ok(())
+    }
+
+    fn process_id(&self) -> Result<u32, AutomationError> {
+        self.engine().process_id_of(self.index).ok_or_else(|| {
+            AutomationError::PlatformError("Mock element has no ProcessId property".to_string())
+        })
+    }
+
+    fn capture(&self) -> Result<ScreenshotResult, AutomationError> {
+        MockEngine::unsupported("capture")
+    }
+
+    fn close(&self) -> Result<(), AutomationError> {
+        self.record("close", None);
+        Ok(())
+    }
+
+    fn url(&self) -> Option<String> {
+        None
+    }
+
+    fn select_option(&self, option_name: &str) -> Result<(), AutomationError> {
+        let engine = self.engine();
+        let option = engine
+            .find_indices(&Selector::Name(option_name.to_string()), self.index, None)?
+            .into_iter()
+            .next()
+            .ok_or_else(|| {
+                AutomationError::ElementNotFound(format!("Option '{option_name}' not found"))
+            })?;
+        self.record("select_option", Some(option_name.to_string()));
+        for index in engine.descendants(self.index, None) {
+            self.state.nodes.lock().unwrap()[index]
+                .attributes
+                .is_selected = Some(index == option);
+        }
+        Ok(())
+    }
+
+    fn list_options(&self) -> Result<Vec<String>, AutomationError> {
+        let nodes = self.state.nodes.lock().unwrap();
+        Ok(nodes[self.index]
+            .children
+            .iter()
+            .filter_map(|&i| nodes[i].attributes.name.clone())
+            .collect())
+    }
+
+    fn is_toggled(&self) -> Result<bool, AutomationError> {
+        Ok(self.attributes().is_toggled.unwrap_or(false))
+    }
+
+    fn set_toggled(&self, state: bool) -> Result<(), AutomationError> {
+        self.record("set_toggled", Some(state.to_string()));
+        self.update(|a| a.is_toggled = Some(state));
+        Ok(())
+    }
+
+    fn get_range_value(&self) -> Result<f64, AutomationError> {
+        self.attributes()
+            .value
+            .and_then(|v| v.parse().ok())
+            .ok_or_else(|| {
+                AutomationError::PlatformError("Mock element has no numeric value".to_string())
+            })
+    }
+
+    fn set_range_value(&self, value: f64) -> Result<(), AutomationError> {
+        self.record("set_range_value", Some(value.to_string()));
+        self.update(|a| a.value = Some(value.to_string()));
+        Ok(())
+    }
+
+    fn is_selected(&self) -> Result<bool, AutomationError> {
+        Ok(self.attributes().is_selected.unwrap_or(false))
+    }
+
+    fn set_selected(&self, state: bool) -> Result<(), AutomationError> {
+        self.record("set_selected", Some(state.to_string()));
+        self.update(|a| a.is_selected = Some(state));
+        Ok(())
+    }
+
+    /// Driven by a `CanSelectMultiple` boolean in the node's properties
+    fn can_select_multiple(&self) -> Result<bool, AutomationError> {
+        Ok(matches!(
+            self.attributes().properties.get("CanSelectMultiple"),
+            Some(Some(serde_json::Value::Bool(true)))
+        ))
+    }
+
+    fn add_to_selection(&self) -> Result<(), AutomationError> {
+        self.record("add_to_selection", None);
+        self.update(|a| a.is_selected = Some(true));
+        Ok(())
+    }
+
+    fn click_with_modifiers(&self, modifiers: &[Modifier]) -> Result<ClickResult, AutomationError> {
+        let held = modifiers
+            .iter()
+            .map(|m| format!("{m:?}"))
+            .collect::<Vec<_>>()
+            .join("+");
+        self.record("click_with_modifiers", Some(held));
+        Ok(self.click_result("Mock"))
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{AutomationError, Browser, OcrElement, Selector, UIElement, UINode};
+use crate::{AutomationError, Browser, Modifier, OcrElement, Selector, UIElement, UINode};
 use std::sync::Arc;
 use std::time::Duration;
 
@@ -30,6 +30,20 @@ pub struct TreeBuildConfig {
     /// When specified, the tree will be built from the element matching this selector
     /// rather than the full window. Useful for getting focused subtrees.
     pub from_selector: Option<String>,
+    /// Populate `UIElementAttributes::shortcut` from the element's accelerator/access key
+    /// so agents can press the shortcut instead of clicking. Default: false
+    pub include_shortcuts: bool,
+    /// Populate `UIElementAttributes::labeled_by`, `described_by` and `controller_for`
+    /// from the UIA relationship properties. Costs extra calls per element. Default: false
+    pub include_relationships: bool,
+    /// Replace node ids with content-addressed ones that stay the same across captures,
+    /// for diffing and caching; see [`UINode::assign_stable_ids`](crate::UINode::assign_stable_ids).
+    /// Such ids can't be used in `#id` selectors. Default: false
+    pub stable_ids: bool,
+    /// Maximum number of threads used to build sibling subtrees in parallel when the
+    /// tree is walked element by element (`from_selector` and the uncached fallback).
+    /// Only elements with many children are split. None or 1 = serial (default)
+    pub parallel_subtrees: Option<usize>,
 }
 
 /// Display mode for inspect overlay labels (cross-platform definition)
@@ -77,6 +91,10 @@ impl Default for TreeBuildConfig {
             show_overlay: false,
             overlay_display_mode: None,
             from_selector: None,
+            include_shortcuts: false,
+            include_relationships: false,
+            stable_ids: false,
+            parallel_subtrees: None,
         }
     }
 }
@@ -143,6 +161,18 @@ pub trait AccessibilityEngine: Send + Sync {
         unix_command: Option<&str>,
     ) -> Result<crate::CommandOutput, AutomationError>;
 
+    /// Run a command with [`crate::RunCommandOptions`]. The default ignores the options
+    /// and calls [`AccessibilityEngine::run_command`], so `combined` stays unset on
+    /// engines that don't override it.
+    async fn run_command_with_options(
+        &self,
+        windows_command: Option<&str>,
+        unix_command: Option<&str>,
+        _options: &crate::RunCommandOptions,
+    ) -> Result<crate::CommandOutput, AutomationError> {
+        self.run_command(windows_command, unix_command).await
+    }
+
     // ============== NEW MONITOR ABSTRACTIONS ==============
 
     /// List all available monitors/displays
@@ -260,6 +290,25 @@ pub trait AccessibilityEngine: Send + Sync {
         ))
     }
 
+    /// Whether a UAC elevation prompt currently blocks UI Automation.
+    /// Default implementation returns false - override in platform-specific engines
+    fn is_elevation_prompt_active(&self) -> bool {
+        false
+    }
+
+    /// Drag the left mouse button along `points` in absolute screen coordinates: press at
+    /// the first, move through the rest waiting `hold_between` after each, release at the last.
+    /// Default implementation returns UnsupportedOperation - override in platform-specific engines
+    fn drag_path(
+        &self,
+        _points: &[(f64, f64)],
+        _hold_between: Duration,
+    ) -> Result<(), AutomationError> {
+        Err(AutomationError::UnsupportedOperation(
+            "Drag along a path not supported on this platform".to_string(),
+        ))
+    }
+
     /// Activate browser window
     fn activate_browser_window_by_title(&self, title: &str) -> Result<(), AutomationError>;
 
@@ -269,10 +318,21 @@ pub trait AccessibilityEngine: Send + Sync {
     /// Get current window
     async fn get_current_window(&self) -> Result<UIElement, AutomationError>;
 
+    /// Get the element at a screen coordinate
+    fn get_element_at_point(&self, x: f64, y: f64) -> Result<UIElement, AutomationError>;
+
+    /// Get the OS foreground window, which can differ from the window of the
+    /// UIA-focused element
+    fn get_foreground_window(&self) -> Result<UIElement, AutomationError>;
+
     /// Get current application
     async fn get_current_application(&self) -> Result<UIElement, AutomationError>;
 
     fn press_key(&self, key: &str) -> Result<(), AutomationError>;
+    /// Press a key while holding modifiers, releasing them even if the press fails
+    fn press_key_combo(&self, modifiers: &[Modifier], key: &str) -> Result<(), AutomationError>;
+    /// Press and release a key by hardware scan code, bypassing virtual-key translation
+    fn press_scan_code(&self, scan_code: u16, extended: bool) -> Result<(), AutomationError>;
     /// Sets the zoom level to a specific percentage (e.g., 100 for 100%, 150 for 150%)
     fn set_zoom(&self, percentage: u32) -> Result<(), AutomationError>;
 
@@ -293,6 +353,20 @@ pub trait AccessibilityEngine: Send + Sync {
         config: TreeBuildConfig,
     ) -> Result<UINode, AutomationError>;
 
+    /// Get the UI tree of the window with the given native handle (HWND on Windows).
+    ///
+    /// Unlike `get_window_tree`, this is unambiguous when several windows share a
+    /// process and title.
+    fn get_window_tree_by_hwnd(
+        &self,
+        _hwnd: isize,
+        _config: TreeBuildConfig,
+    ) -> Result<UINode, AutomationError> {
+        Err(AutomationError::UnsupportedOperation(
+            "Window tree by handle not supported on this platform".to_string(),
+        ))
+    }
+
     /// Build UI tree directly from a UIElement (no PID-based window search needed)
     ///
     /// This is more efficient when you already have a reference to the target element,
@@ -319,6 +393,7 @@ pub trait AccessibilityEngine: Send + Sync {
 #[cfg(not(target_os = "windows"))]
 compile_error!("Terminator only supports Windows. Linux and macOS are not supported.");
 
+pub mod mock;
 #[cfg(target_os = "windows")]
 pub mod windows;
 #[cfg(all(target_os = "windows", test))]
@@ -328,10 +403,88 @@ pub mod windows_tests;
 #[cfg(test)]
 pub mod windows_benchmarks;
 
+/// How the platform engine is initialized, including retries for when the
+/// accessibility service isn't ready yet (e.g. a cold start in CI)
+#[derive(Debug, Clone)]
+pub struct EngineInitConfig {
+    pub use_background_apps: bool,
+    pub activate_app: bool,
+    /// Total initialization attempts, including the first. 0 and 1 both mean no retry
+    pub attempts: u32,
+    /// Delay before the first retry; doubled after each further failure
+    pub initial_backoff: Duration,
+    /// Upper bound on the delay between attempts
+    pub max_backoff: Duration,
+}
+
+impl Default for EngineInitConfig {
+    fn default() -> Self {
+        Self {
+            use_background_apps: false,
+            activate_app: false,
+            attempts: 1,
+            initial_backoff: Duration::from_millis(250),
+            max_backoff: Duration::from_secs(4),
+        }
+    }
+}
+
 /// Create the appropriate engine for the current platform
 pub fn create_engine(
     use_background_apps: bool,
     activate_app: bool,
+) -> Result<Arc<dyn AccessibilityEngine>, AutomationError> {
+    create_engine_with_config(&EngineInitConfig {
+        use_background_apps,
+        activate_app,
+        ..Default::default()
+    })
+}
+
+/// Create the engine for the current platform, retrying with exponential backoff
+/// as configured if initialization fails
+pub fn create_engine_with_config(
+    config: &EngineInitConfig,
+) -> Result<Arc<dyn AccessibilityEngine>, AutomationError> {
+    retry_init(config, std::thread::sleep, || {
+        create_platform_engine(config.use_background_apps, config.activate_app)
+    })
+}
+
+/// Run `init` up to `config.attempts` times, calling `sleep` with the backoff between
+/// attempts. An `UnsupportedPlatform` error is returned at once since retrying can't help.
+pub(crate) fn retry_init<T>(
+    config: &EngineInitConfig,
+    sleep: impl Fn(Duration),
+    mut init: impl FnMut() -> Result<T, AutomationError>,
+) -> Result<T, AutomationError> {
+    let attempts = config.attempts.max(1);
+    let mut backoff = config.initial_backoff;
+    let mut attempt = 1;
+    loop {
+        match init() {
+            Ok(value) => return Ok(value),
+            Err(e @ AutomationError::UnsupportedPlatform(_)) => return Err(e),
+            Err(e) if attempt >= attempts => return Err(e),
+            Err(e) => {
+                tracing::warn!(
+                    "Engine initialization attempt {}/{} failed: {}; retrying in {:?}",
+                    attempt,
+                    attempts,
+                    e,
+                    backoff
+                );
+                sleep(backoff);
+                backoff = backoff.saturating_mul(2).min(config.max_backoff);
+                attempt += 1;
+            }
+        }
+    }
+}
+
+fn create_platform_engine(
+    use_background_apps: bool,
+    activate_app: bool,
 ) -> Result<Arc<dyn AccessibilityEngine>, AutomationError> {
     #[cfg(target_os = "windows")]
     {
@@ -22,7 +22,9 @@ use uiautomation::variants::Variant;
 // Windows API imports
 use windows::core::{HRESULT, HSTRING, PCWSTR};
 use windows::Win32::Foundation::LPARAM;
-use windows::Win32::Foundation::{CloseHandle, HANDLE, HINSTANCE, HWND};
+use windows::Win32::Foundation::{
+    CloseHandle, HANDLE, HINSTANCE, HWND, WAIT_OBJECT_0, WAIT_TIMEOUT,
+};
 use windows::Win32::System::Com::{
     CoCreateInstance, CoInitializeEx, CLSCTX_ALL, COINIT_MULTITHREADED,
 };
@@ -30,14 +32,16 @@ use windows::Win32::System::Diagnostics::ToolHelp::{
     CreateToolhelp32Snapshot, Process32FirstW, Process32NextW, PROCESSENTRY32W, TH32CS_SNAPPROCESS,
 };
 use windows::Win32::System::Threading::{
-    CreateProcessW, GetProcessId, CREATE_NEW_CONSOLE, PROCESS_INFORMATION, STARTUPINFOW,
+    CreateProcessW, GetExitCodeProcess, GetProcessId, OpenProcess, WaitForSingleObject,
+    CREATE_NEW_CONSOLE, INFINITE, PROCESS_INFORMATION, PROCESS_QUERY_LIMITED_INFORMATION,
+    PROCESS_SYNCHRONIZE, STARTUPINFOW,
 };
 use windows::Win32::UI::Shell::{
     ApplicationActivationManager, IApplicationActivationManager, ShellExecuteExW, ShellExecuteW,
     ACTIVATEOPTIONS, SEE_MASK_NOASYNC, SEE_MASK_NOCLOSEPROCESS, SHELLEXECUTEINFOW,
 };
 use windows::Win32::UI::WindowsAndMessaging::{
-    EnumWindows, GetWindowThreadProcessId, IsWindowVisible, SW_SHOWNORMAL,
+    EnumWindows, GetForegroundWindow, GetWindowThreadProcessId, IsWindowVisible, SW_SHOWNORMAL,
 };
 
 use super::utils::WindowsUIElement;
@@ -193,6 +197,50 @@ pub fn get_process_name_by_pid(pid: i32) -> Result<String, AutomationError> {
     }
 }
 
+/// Block until the process exits and return its exit code.
+///
+/// Waits on the process handle with `WaitForSingleObject` instead of polling.
+/// Returns `Timeout` if the process is still running after `timeout`, and
+/// `ElementNotFound` if no process with `pid` exists (or it can't be opened).
+pub fn wait_for_process_exit(pid: u32, timeout: Duration) -> Result<i32, AutomationError> {
+    unsafe {
+        let process = OpenProcess(
+            PROCESS_SYNCHRONIZE | PROCESS_QUERY_LIMITED_INFORMATION,
+            false,
+            pid,
+        )
+        .map_err(|e| {
+            AutomationError::ElementNotFound(format!("Cannot open process with PID {pid}: {e}"))
+        })?;
+        let _guard = HandleGuard(process);
+
+        // Clamp below INFINITE so a huge timeout doesn't turn into "wait forever"
+        let timeout_ms = timeout.as_millis().min((INFINITE - 1) as u128) as u32;
+        let wait = WaitForSingleObject(process, timeout_ms);
+        if wait == WAIT_TIMEOUT {
+            return Err(AutomationError::Timeout(format!(
+                "Process {pid} still running after {}ms",
+                timeout.as_millis()
+            )));
+        }
+        if wait != WAIT_OBJECT_0 {
+            return Err(AutomationError::PlatformError(format!(
+                "Waiting for process {pid} failed (wait result {:#x})",
+                wait.0
+            )));
+        }
+
+        let mut exit_code = 0u32;
+        GetExitCodeProcess(process, &mut exit_code).map_err(|e| {
+            AutomationError::PlatformError(format!(
+                "Failed to get exit code for process {pid}: {e}"
+            ))
+        })?;
+        debug!("Process {} exited with code {}", pid, exit_code);
+        Ok(exit_code as i32)
+    }
+}
+
 /// Check if a process (by PID) is a known browser
 /// Returns true if the process name matches any known browser, false otherwise
 pub fn is_browser_process(pid: u32) -> bool {
@@ -359,6 +407,18 @@ pub fn get_application_by_name(
     })))
 }
 
+/// The window the OS considers foreground, i.e. the one receiving keyboard input
+pub fn get_foreground_window(engine: &WindowsEngine) -> Result<UIElement, AutomationError> {
+    let hwnd = unsafe { GetForegroundWindow() };
+    if hwnd.0.is_null() {
+        // Happens while focus is switching or on the secure desktop
+        return Err(AutomationError::ElementNotFound(
+            "No foreground window (focus may be changing)".to_string(),
+        ));
+    }
+    hwnd_to_uielement(engine, hwnd.0 as isize)
+}
+
 pub fn get_application_by_pid(
     engine: &WindowsEngine,
     pid: i32,
@@ -3,12 +3,15 @@
 use super::action_overlay::ActionOverlayGuard;
 use super::input::{restore_focus_state, save_focus_state};
 use super::types::{FontStyle, HighlightHandle, TextPosition, ThreadSafeWinUIElement};
-use super::utils::{create_ui_automation_with_com_init, generate_element_id};
+use super::utils::{
+    create_ui_automation_with_com_init, generate_element_id, pattern_available_property,
+    variant_value_to_json,
+};
 use crate::element::UIElementImpl;
 use crate::platforms::windows::applications::get_application_by_pid;
 use crate::platforms::windows::{highlighting, WindowsEngine};
 use crate::{
-    AutomationError, ClickResult, Locator, ScreenshotResult, Selector, UIElement,
+    AutomationError, ClickResult, ControlPattern, Locator, ScreenshotResult, Selector, UIElement,
     UIElementAttributes,
 };
 use std::collections::HashMap;
@@ -22,6 +25,7 @@ use uiautomation::patterns;
 use uiautomation::types::{TreeScope, UIProperty};
 use uiautomation::variants::Variant;
 use uiautomation::UIAutomation;
+use windows::Win32::UI::Accessibility::{IUIAutomationElement, UIA_PROPERTY_ID};
 
 trait ScrollFallback {
     fn scroll_with_fallback(&self, direction: &str, amount: f64) -> Result<(), AutomationError>;
@@ -471,6 +475,15 @@ impl WindowsUIElement {
     }
 }
 
+/// Wrap an element reached through a UIA relationship property (LabeledBy, DescribedBy...)
+fn wrap_related_element(element: uiautomation::UIElement) -> UIElement {
+    #[allow(clippy::arc_with_non_send_sync)]
+    UIElement::new(Box::new(WindowsUIElement {
+        element: ThreadSafeWinUIElement(Arc::new(element)),
+        engine: None,
+    }))
+}
+
 impl Debug for WindowsUIElement {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("WindowsUIElement").finish()
@@ -487,6 +500,82 @@ impl UIElementImpl for WindowsUIElement {
         Some(self.object_id().to_string().chars().take(6).collect())
     }
 
+    fn runtime_id(&self) -> Option<Vec<i32>> {
+        self.element.0.get_runtime_id().ok()
+    }
+
+    fn shortcut(&self) -> Option<String> {
+        let non_empty = |key: uiautomation::Result<String>| key.ok().filter(|k| !k.is_empty());
+        non_empty(self.element.0.get_accelerator_key())
+            .or_else(|| non_empty(self.element.0.get_access_key()))
+    }
+
+    fn help_text(&self) -> Option<String> {
+        self.element
+            .0
+            .get_help_text()
+            .ok()
+            .filter(|text| !text.is_empty())
+    }
+
+    fn supported_patterns(&self) -> Vec<ControlPattern> {
+        // The Is*PatternAvailable properties are plain booleans, cheaper than
+        // fetching each pattern object just to see whether it exists
+        ControlPattern::ALL
+            .into_iter()
+            .filter(|pattern| {
+                self.element
+                    .0
+                    .get_property_value(pattern_available_property(*pattern))
+                    .and_then(|value| value.try_into())
+                    .unwrap_or(false)
+            })
+            .collect()
+    }
+
+    fn labeled_by(&self) -> Option<UIElement> {
+        let label = self.element.0.get_labeled_by().ok()?;
+        Some(wrap_related_element(label))
+    }
+
+    fn described_by(&self) -> Vec<UIElement> {
+        self.element
+            .0
+            .get_described_by()
+            .map(|elements| elements.into_iter().map(wrap_related_element).collect())
+            .unwrap_or_default()
+    }
+
+    fn controller_for(&self) -> Vec<UIElement> {
+        self.element
+            .0
+            .get_controller_for()
+            .map(|elements| elements.into_iter().map(wrap_related_element).collect())
+            .unwrap_or_default()
+    }
+
+    fn get_property(&self, property_id: i32) -> Result<serde_json::Value, AutomationError> {
+        // Go through the raw COM interface so ids missing from `UIProperty`
+        // (including app-registered custom properties) work too
+        let element: &IUIAutomationElement = (*self.element.0).as_ref();
+        let variant = unsafe { element.GetCurrentPropertyValue(UIA_PROPERTY_ID(property_id)) }
+            .map_err(|e| {
+                AutomationError::PlatformError(format!(
+                    "Failed to read UIA property {property_id}: {e}"
+                ))
+            })?;
+        let value = Variant::from(variant).get_value().map_err(|e| {
+            AutomationError::PlatformError(format!(
+                "Unrecognized value type for UIA property {property_id}: {e}"
+            ))
+        })?;
+        variant_value_to_json(value).ok_or_else(|| {
+            AutomationError::UnsupportedOperation(format!(
+                "UIA property {property_id} is not supported by this element or has no JSON representation"
+            ))
+        })
+    }
+
     fn role(&self) -> String {
         self.element
             .0
@@ -575,6 +664,10 @@ impl UIElementImpl for WindowsUIElement {
             is_selected: None,
             child_count: None,
             index_in_parent: None,
+            shortcut: None,
+            labeled_by: None,
+            described_by: Vec::new(),
+            controller_for: Vec::new(),
         }
     }
 
@@ -1487,6 +1580,60 @@ impl UIElementImpl for WindowsUIElement {
         })
     }
 
+    fn invoke_pattern_method(
+        &self,
+        pattern: ControlPattern,
+        method: &str,
+        args: &[serde_json::Value],
+    ) -> Result<serde_json::Value, AutomationError> {
+        super::pattern_methods::invoke(&self.element.0, pattern, method, args)
+    }
+
+    fn element_at_point(&self, x: f64, y: f64) -> Result<UIElement, AutomationError> {
+        // Reuse the engine's UIAutomation; only elements created without an engine
+        // reference pay for a new one
+        if let Some(engine) = &self.engine {
+            use crate::platforms::AccessibilityEngine;
+            return engine.get_element_at_point(x, y);
+        }
+        let automation = create_ui_automation_with_com_init()?;
+        let point = uiautomation::types::Point::new(x.round() as i32, y.round() as i32);
+        let element = automation.element_from_point(point).map_err(|e| {
+            AutomationError::ElementNotFound(format!("No element at ({x}, {y}): {e}"))
+        })?;
+
+        #[allow(clippy::arc_with_non_send_sync)]
+        let element = ThreadSafeWinUIElement(Arc::new(element));
+        Ok(UIElement::new(Box::new(WindowsUIElement {
+            element,
+            engine: self.engine.clone(),
+        })))
+    }
+
+    fn z_order(&self) -> Option<usize> {
+        use windows::Win32::UI::WindowsAndMessaging::{GetWindow, GW_HWNDPREV};
+
+        let handle = self.element.0.get_native_window_handle().ok()?;
+        let mut hwnd: windows::Win32::Foundation::HWND = handle.into();
+        if hwnd.0.is_null() {
+            return None;
+        }
+
+        // Count the sibling windows stacked above this one. Bounded in case windows are
+        // re-stacked mid-walk and the chain loops
+        let mut above = 0;
+        while above < 100_000 {
+            match unsafe { GetWindow(hwnd, GW_HWNDPREV) } {
+                Ok(previous) if !previous.0.is_null() => {
+                    above += 1;
+                    hwnd = previous;
+                }
+                _ => break,
+            }
+        }
+        Some(above)
+    }
+
     fn perform_action(&self, action: &str) -> Result<(), AutomationError> {
         // actions those don't take args
         match action {
@@ -2603,8 +2750,6 @@ impl UIElementImpl for WindowsUIElement {
     }
 
     fn set_range_value(&self, value: f64) -> Result<(), AutomationError> {
-        self.focus()?; // Always focus first for keyboard interaction
-
         let range_pattern = self
             .element
             .0
@@ -2620,19 +2765,44 @@ impl UIElementImpl for WindowsUIElement {
                 }
             })?;
 
+        if range_pattern.is_readonly().unwrap_or(false) {
+            return Err(AutomationError::UnsupportedOperation(
+                "Range control is read-only (e.g. a progress bar); its value can't be set"
+                    .to_string(),
+            ));
+        }
+
+        let min_value = range_pattern
+            .get_minimum()
+            .map_err(|e| AutomationError::PlatformError(format!("Failed to get min value: {e}")))?;
+        let max_value = range_pattern
+            .get_maximum()
+            .map_err(|e| AutomationError::PlatformError(format!("Failed to get max value: {e}")))?;
+        let reported_step = range_pattern.get_small_change().unwrap_or(0.0);
+
+        let target_value =
+            crate::element::utils::resolve_range_value(value, min_value, max_value, reported_step)?;
+
+        self.focus()?; // Always focus first for keyboard interaction
+
         // Try setting value directly first, as it's the most efficient method.
-        if range_pattern.set_value(value).is_ok() {
+        if range_pattern.set_value(target_value).is_ok() {
             // Optional: Short sleep to allow UI to update.
             std::thread::sleep(std::time::Duration::from_millis(100));
             if let Ok(new_value) = range_pattern.get_value() {
                 // Use a tolerance for floating-point comparison.
-                if (new_value - value).abs() < 1.0 {
+                let tolerance = if reported_step > 0.0 {
+                    reported_step / 2.0
+                } else {
+                    1.0
+                };
+                if (new_value - target_value).abs() < tolerance {
                     debug!("Direct set_value for RangeValuePattern succeeded.");
                     return Ok(());
                 }
                 debug!(
                     "Direct set_value was inaccurate, new value: {}. Expected: {}",
-                    new_value, value
+                    new_value, target_value
                 );
             }
         }
@@ -2640,14 +2810,7 @@ impl UIElementImpl for WindowsUIElement {
         // Fallback to keyboard simulation.
         debug!("Direct set_value for RangeValuePattern failed or was inaccurate, falling back to keyboard simulation.");
 
-        let min_value = range_pattern
-            .get_minimum()
-            .map_err(|e| AutomationError::PlatformError(format!("Failed to get min value: {e}")))?;
-        let max_value = range_pattern
-            .get_maximum()
-            .map_err(|e| AutomationError::PlatformError(format!("Failed to get max value: {e}")))?;
-
-        let mut small_change = range_pattern.get_small_change().unwrap_or(0.0);
+        let mut small_change = reported_step;
 
         if small_change <= 0.0 {
             debug!("Slider small_change is not positive, calculating fallback step.");
@@ -2663,9 +2826,6 @@ impl UIElementImpl for WindowsUIElement {
             }
         }
 
-        // Clamp the target value to be within the allowed range.
-        let target_value = value.clamp(min_value, max_value);
-
         debug!(
             "Slider properties: min={}, max={}, small_change={}, target={}",
             min_value, max_value, small_change, target_value
@@ -2820,6 +2980,37 @@ impl UIElementImpl for WindowsUIElement {
         ))
     }
 
+    fn can_select_multiple(&self) -> Result<bool, AutomationError> {
+        match self.element.0.get_pattern::<patterns::UISelectionPattern>() {
+            Ok(selection) => selection.can_select_multiple().map_err(|e| {
+                AutomationError::PlatformError(format!("Failed to read CanSelectMultiple: {e}"))
+            }),
+            Err(_) => Ok(false),
+        }
+    }
+
+    fn add_to_selection(&self) -> Result<(), AutomationError> {
+        let selection_item = self
+            .element
+            .0
+            .get_pattern::<patterns::UISelectionItemPattern>()
+            .map_err(|e| {
+                AutomationError::UnsupportedOperation(format!(
+                    "Element does not support SelectionItemPattern: {e}"
+                ))
+            })?;
+        selection_item.add_to_selection().map_err(|e| {
+            AutomationError::PlatformError(format!("Failed to add item to selection: {e}"))
+        })
+    }
+
+    fn click_with_modifiers(
+        &self,
+        modifiers: &[crate::Modifier],
+    ) -> Result<ClickResult, AutomationError> {
+        super::input::with_modifiers_held(modifiers, || self.click())
+    }
+
     // State tracking implementations
     fn invoke_with_state(&self) -> Result<crate::ActionResult, AutomationError> {
         self.execute_with_state_tracking("invoke", |elem| elem.invoke(), None)
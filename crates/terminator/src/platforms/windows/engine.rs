@@ -14,7 +14,7 @@ use crate::platforms::windows::virtual_display::{
 use crate::platforms::windows::{applications, generate_element_id, WindowsUIElement};
 use crate::platforms::AccessibilityEngine;
 use crate::ScreenshotResult;
-use crate::{AutomationError, Selector, UIElement};
+use crate::{AutomationError, ElementState, Modifier, Selector, UIElement};
 use image::DynamicImage;
 use image::{ImageBuffer, Rgba};
 use std::panic;
@@ -180,10 +180,19 @@ pub fn get_process_name_by_pid(pid: i32) -> Result<String, AutomationError> {
     }
 }
 
+// Helper function to read a `state:` predicate from a raw UIA element
+fn element_has_state(element: &uiautomation::UIElement, state: ElementState) -> bool {
+    match state {
+        ElementState::Enabled => element.is_enabled().unwrap_or(false),
+        ElementState::Focused => element.has_keyboard_focus().unwrap_or(false),
+        ElementState::Offscreen => element.is_offscreen().unwrap_or(true),
+    }
+}
+
 // Helper function to check if a selector has process scoping
 fn selector_has_process_scope(selector: &Selector) -> bool {
     match selector {
-        Selector::Process(_) => true,
+        Selector::Process(_) | Selector::App(_) => true,
         Selector::Chain(selectors) => selectors.iter().any(selector_has_process_scope),
         Selector::And(selectors) => selectors.iter().any(selector_has_process_scope),
         Selector::Or(selectors) => selectors.iter().any(selector_has_process_scope),
@@ -500,6 +509,100 @@ impl WindowsEngine {
         }
     }
 
+    /// Build the UI tree of an already selected window, trying the cached
+    /// single-call walk first and falling back to the element-by-element builder
+    fn build_window_tree(
+        &self,
+        selected_window: uiautomation::UIElement,
+        pid: u32,
+        config: &crate::platforms::TreeBuildConfig,
+    ) -> Result<crate::UINode, AutomationError> {
+        // Get application name from process using sysinfo (efficient single lookup)
+        let application_name = {
+            use sysinfo::{ProcessesToUpdate, System};
+            let mut system = System::new();
+            system.refresh_processes(ProcessesToUpdate::All, true);
+            system
+                .process(sysinfo::Pid::from_u32(pid))
+                .map(|p| p.name().to_string_lossy().to_string())
+        };
+
+        // Try the new CACHED tree building approach first (30-50x faster)
+        // Falls back to the old recursive approach if caching fails
+        let max_depth = config.max_depth.or(Some(500));
+
+        info!("[TREE_BUILD] Attempting cached tree build for PID: {}", pid);
+        match build_tree_with_cache(
+            &self.automation.0,
+            &selected_window,
+            max_depth,
+            application_name.clone(),
+            config.include_all_bounds,
+            config.include_shortcuts,
+            config.include_relationships,
+            config.stable_ids,
+        ) {
+            Ok(result) => {
+                info!("[TREE_BUILD] Cached approach succeeded for PID: {}", pid);
+                return Ok(result);
+            }
+            Err(e) => {
+                warn!(
+                    "[TREE_BUILD] Cached approach failed for PID: {}, falling back to recursive: {}",
+                    pid, e
+                );
+            }
+        }
+
+        // Fallback to old recursive approach if caching fails
+        info!(
+            "[TREE_BUILD] Using fallback recursive approach for PID: {}",
+            pid
+        );
+
+        // Wrap the raw OS element into our UIElement
+        let window_element_wrapper = UIElement::new(Box::new(WindowsUIElement {
+            element: ThreadSafeWinUIElement(Arc::new(selected_window)),
+            engine: None,
+        }));
+
+        let mut context = TreeBuildingContext {
+            config: TreeBuildingConfig {
+                timeout_per_operation_ms: config.timeout_per_operation_ms.unwrap_or(50),
+                yield_every_n_elements: config.yield_every_n_elements.unwrap_or(50),
+                batch_size: config.batch_size.unwrap_or(50),
+                max_depth,
+                parallel_subtrees: config.parallel_subtrees.unwrap_or(1).max(1),
+            },
+            property_mode: config.property_mode.clone(),
+            elements_processed: 0,
+            max_depth_reached: 0,
+            cache_hits: 0,
+            fallback_calls: 0,
+            errors_encountered: 0,
+            application_name,
+            include_all_bounds: config.include_all_bounds,
+            include_shortcuts: config.include_shortcuts,
+            include_relationships: config.include_relationships,
+            stable_ids: config.stable_ids,
+        };
+
+        let result =
+            build_ui_node_tree_configurable(&window_element_wrapper, 0, &mut context, vec![])?;
+
+        info!(
+            "[TREE_BUILD] Fallback completed for PID: {}. Stats: elements={}, depth={}, cache_hits={}, fallbacks={}, errors={}",
+            pid,
+            context.elements_processed,
+            context.max_depth_reached,
+            context.cache_hits,
+            context.fallback_calls,
+            context.errors_encountered
+        );
+
+        Ok(result)
+    }
+
     /// Enhanced title matching that handles browser windows and fuzzy matching
     fn find_best_title_match(
         &self,
@@ -660,6 +763,12 @@ impl WindowsEngine {
                     Err(_) => Ok(false),
                 }
             }
+            Selector::App(target) => match target.parse::<u32>() {
+                Ok(expected_pid) => Ok(element.process_id().is_ok_and(|pid| pid == expected_pid)),
+                Err(_) => {
+                    self.element_matches_selector(element, &Selector::Process(target.clone()))
+                }
+            },
             Selector::And(selectors) => {
                 // Recursively check all AND conditions
                 for sel in selectors {
@@ -682,6 +791,9 @@ impl WindowsEngine {
                 // Negate the inner selector
                 Ok(!self.element_matches_selector(element, inner_selector)?)
             }
+            Selector::State { state, expected } => {
+                Ok(element_has_state(win_element, *state) == *expected)
+            }
             // Complex selectors that would need more context
             Selector::Chain(_)
             | Selector::Has(_)
@@ -697,6 +809,7 @@ impl WindowsEngine {
             | Selector::Filter(_)
             | Selector::LocalizedRole(_)
             | Selector::Nth(_)
+            | Selector::Range { .. }
             | Selector::Invalid(_) => {
                 // These selectors require searching relative to other elements or are complex queries
                 // For now, we'll return false as they can't be evaluated on a single element
@@ -782,6 +895,9 @@ impl WindowsEngine {
             .Lines()
             .map_err(|e| AutomationError::PlatformError(format!("Failed to get OCR lines: {e}")))?;
 
+        let mapper =
+            crate::CoordinateMapper::new((window_x, window_y), (dpi_scale_x, dpi_scale_y), 1.0);
+
         let mut ocr_lines = Vec::new();
         for line in lines {
             let line_text = line.Text().map(|s| s.to_string()).unwrap_or_default();
@@ -804,12 +920,13 @@ impl WindowsEngine {
                     AutomationError::PlatformError(format!("Failed to get word bounds: {e}"))
                 })?;
 
-                let word_bounds = (
-                    window_x + (rect.X as f64 / dpi_scale_x),
-                    window_y + (rect.Y as f64 / dpi_scale_y),
-                    rect.Width as f64 / dpi_scale_x,
-                    rect.Height as f64 / dpi_scale_y,
-                );
+                let screen = mapper.physical_to_screen(crate::Rect::new(
+                    rect.X as f64,
+                    rect.Y as f64,
+                    rect.Width as f64,
+                    rect.Height as f64,
+                ));
+                let word_bounds = (screen.x, screen.y, screen.width, screen.height);
 
                 // Update line bounds to encompass all words
                 line_bounds = Some(match line_bounds {
@@ -1484,6 +1601,13 @@ impl AccessibilityEngine for WindowsEngine {
 
                 Ok(filtered_elements)
             }
+            Selector::App(target) => match target.parse::<u32>() {
+                // A pid names exactly one app, so skip the process name lookups
+                Ok(pid) => Ok(vec![self.get_application_by_pid(pid as i32, timeout)?]),
+                Err(_) => {
+                    self.find_elements(&Selector::Process(target.clone()), root, timeout, depth)
+                }
+            },
             Selector::Path(_) => Err(AutomationError::UnsupportedOperation(
                 "`Path` selector not supported".to_string(),
             )),
@@ -1635,6 +1759,13 @@ impl AccessibilityEngine for WindowsEngine {
                             // Index out of bounds, no elements match.
                             current_results.clear();
                         }
+                    } else if let Selector::Range { start, end } = selector {
+                        // Keep a slice of the collection; out-of-range bounds just keep less
+                        current_results = crate::selector::apply_range(
+                            std::mem::take(&mut current_results),
+                            *start,
+                            *end,
+                        );
                     } else {
                         // For other selectors, find all children that match from the current set of results.
                         let mut next_results = Vec::new();
@@ -1717,6 +1848,33 @@ impl AccessibilityEngine for WindowsEngine {
                     })
                     .collect())
             }
+            Selector::State { state, expected } => {
+                let (state, expected) = (*state, *expected);
+                let matcher = self
+                    .automation
+                    .0
+                    .create_matcher()
+                    .from_ref(root_ele)
+                    .depth(depth.unwrap_or(50) as u32)
+                    .filter_fn(Box::new(move |e: &uiautomation::UIElement| {
+                        Ok(element_has_state(e, state) == expected)
+                    }))
+                    .timeout(timeout_ms as u64);
+                let elements = matcher.find_all().map_err(|e| {
+                    AutomationError::ElementNotFound(format!(
+                        "State: {state:?}={expected}, Err: {e}"
+                    ))
+                })?;
+                Ok(elements
+                    .into_iter()
+                    .map(|ele| {
+                        UIElement::new(Box::new(WindowsUIElement {
+                            element: ThreadSafeWinUIElement(Arc::new(ele)),
+                            engine: None,
+                        }))
+                    })
+                    .collect())
+            }
             Selector::LocalizedRole(localized_role) => {
                 debug!("searching elements by localized role: {}", localized_role);
                 let lr = localized_role.clone();
@@ -1866,6 +2024,10 @@ impl AccessibilityEngine for WindowsEngine {
             Selector::Nth(_) => Err(AutomationError::InvalidSelector(
                 "Nth selector must be used as part of a chain (e.g. 'list >> nth=0')".to_string(),
             )),
+            Selector::Range { .. } => Err(AutomationError::InvalidSelector(
+                "Range selector must be used as part of a chain (e.g. 'list >> range:1..5')"
+                    .to_string(),
+            )),
             Selector::Parent => {
                 // Get parent element using the existing parent() method
                 if let Some(root_element) = root {
@@ -1972,8 +2134,8 @@ impl AccessibilityEngine for WindowsEngine {
                     .next()
                     .ok_or_else(|| AutomationError::ElementNotFound("No element found".to_string()))
             }
-            // Process selector - delegate to find_elements and take first result
-            Selector::Process(_) => {
+            // Process/app selector - delegate to find_elements and take first result
+            Selector::Process(_) | Selector::App(_) => {
                 let elements = self.find_elements(selector, root, timeout, None)?;
                 elements.into_iter().next().ok_or_else(|| {
                     AutomationError::ElementNotFound("No element found for process".to_string())
@@ -2293,6 +2455,23 @@ impl AccessibilityEngine for WindowsEngine {
                     selectors
                 );
 
+                // A range slices a whole collection, so materialize the chain and take its first
+                if selectors
+                    .iter()
+                    .any(|s| matches!(s, Selector::Range { .. }))
+                {
+                    let chain = Selector::Chain(selectors.clone());
+                    return self
+                        .find_elements(&chain, root, timeout, None)?
+                        .into_iter()
+                        .next()
+                        .ok_or_else(|| {
+                            AutomationError::ElementNotFound(format!(
+                                "Range selector chain {chain:?} matched no elements"
+                            ))
+                        });
+                }
+
                 // Check if the chain ends with Nth selector
                 // When a chain ends with Nth, it should select from the collection, not get children
                 if let Some(Selector::Nth(index)) = selectors.last() {
@@ -2517,6 +2696,28 @@ impl AccessibilityEngine for WindowsEngine {
                     engine: None,
                 })))
             }
+            Selector::State { state, expected } => {
+                let (state, expected) = (*state, *expected);
+                let matcher = self
+                    .automation
+                    .0
+                    .create_matcher()
+                    .from_ref(root_ele)
+                    .depth(50)
+                    .filter_fn(Box::new(move |e: &uiautomation::UIElement| {
+                        Ok(element_has_state(e, state) == expected)
+                    }))
+                    .timeout(timeout_ms as u64);
+                let element = matcher.find_first().map_err(|e| {
+                    AutomationError::ElementNotFound(format!(
+                        "State: {state:?}={expected}, Err: {e}"
+                    ))
+                })?;
+                Ok(UIElement::new(Box::new(WindowsUIElement {
+                    element: ThreadSafeWinUIElement(Arc::new(element)),
+                    engine: None,
+                })))
+            }
             Selector::LocalizedRole(localized_role) => {
                 debug!("searching element by localized role: {}", localized_role);
                 let lr = localized_role.clone();
@@ -2547,6 +2748,10 @@ impl AccessibilityEngine for WindowsEngine {
             Selector::Nth(_) => Err(AutomationError::InvalidSelector(
                 "Nth selector must be used as part of a chain (e.g. 'list >> nth=0')".to_string(),
             )),
+            Selector::Range { .. } => Err(AutomationError::InvalidSelector(
+                "Range selector must be used as part of a chain (e.g. 'list >> range:1..5')"
+                    .to_string(),
+            )),
             Selector::Has(_) => Err(AutomationError::InvalidSelector(
                 "Has selector must be used as part of a chain (e.g. 'list >> has:button')"
                     .to_string(),
@@ -3153,9 +3358,23 @@ impl AccessibilityEngine for WindowsEngine {
     }
 
     async fn run_command(
+        &self,
+        windows_command: Option<&str>,
+        unix_command: Option<&str>,
+    ) -> Result<crate::CommandOutput, AutomationError> {
+        self.run_command_with_options(
+            windows_command,
+            unix_command,
+            &crate::RunCommandOptions::default(),
+        )
+        .await
+    }
+
+    async fn run_command_with_options(
         &self,
         windows_command: Option<&str>,
         _unix_command: Option<&str>,
+        options: &crate::RunCommandOptions,
     ) -> Result<crate::CommandOutput, AutomationError> {
         let command_str = windows_command.ok_or_else(|| {
             AutomationError::InvalidArgument("Windows command must be provided".to_string())
@@ -3163,7 +3382,8 @@ impl AccessibilityEngine for WindowsEngine {
 
         // Use tokio::process::Command for async execution
         // CREATE_NO_WINDOW prevents console allocation which can steal focus/minimize parent windows
-        let output = tokio::process::Command::new("powershell")
+        let mut command = tokio::process::Command::new("powershell");
+        command
             .args([
                 "-NoProfile",
                 "-WindowStyle",
@@ -3171,15 +3391,49 @@ impl AccessibilityEngine for WindowsEngine {
                 "-Command",
                 command_str,
             ])
-            .creation_flags(CREATE_NO_WINDOW)
-            .output()
-            .await // Await the async output
+            .creation_flags(CREATE_NO_WINDOW);
+
+        if !options.capture_combined {
+            let output = command
+                .output()
+                .await // Await the async output
+                .map_err(|e| AutomationError::PlatformError(e.to_string()))?;
+
+            return Ok(crate::CommandOutput {
+                exit_status: output.status.code(),
+                stdout: String::from_utf8_lossy(&output.stdout).to_string(),
+                stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+                combined: None,
+            });
+        }
+
+        // Read both pipes while the command runs so their relative order is kept
+        let mut child = command
+            .stdin(std::process::Stdio::null())
+            .stdout(std::process::Stdio::piped())
+            .stderr(std::process::Stdio::piped())
+            .spawn()
+            .map_err(|e| AutomationError::PlatformError(e.to_string()))?;
+        let (child_stdout, child_stderr) = (
+            child.stdout.take().expect("stdout is piped"),
+            child.stderr.take().expect("stderr is piped"),
+        );
+
+        let (stdout, stderr, combined) = crate::utils::read_interleaved(child_stdout, child_stderr)
+            .await
+            .map_err(|e| {
+                AutomationError::PlatformError(format!("Failed to read command output: {e}"))
+            })?;
+        let status = child
+            .wait()
+            .await
             .map_err(|e| AutomationError::PlatformError(e.to_string()))?;
 
         Ok(crate::CommandOutput {
-            exit_status: output.status.code(),
-            stdout: String::from_utf8_lossy(&output.stdout).to_string(),
-            stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+            exit_status: status.code(),
+            stdout,
+            stderr,
+            combined: Some(combined),
         })
     }
 
@@ -3597,6 +3851,18 @@ impl AccessibilityEngine for WindowsEngine {
         super::input::send_mouse_click(x, y, click_type, restore_cursor)
     }
 
+    fn is_elevation_prompt_active(&self) -> bool {
+        super::health::is_elevation_prompt_active()
+    }
+
+    fn drag_path(
+        &self,
+        points: &[(f64, f64)],
+        hold_between: Duration,
+    ) -> Result<(), AutomationError> {
+        super::input::send_mouse_drag_path(points, hold_between)
+    }
+
     fn activate_browser_window_by_title(&self, title: &str) -> Result<(), AutomationError> {
         info!(
             "Attempting to activate browser window containing title: {}",
@@ -3810,6 +4076,22 @@ impl AccessibilityEngine for WindowsEngine {
         ))
     }
 
+    fn get_element_at_point(&self, x: f64, y: f64) -> Result<UIElement, AutomationError> {
+        let point = uiautomation::types::Point::new(x.round() as i32, y.round() as i32);
+        let element = self.automation.0.element_from_point(point).map_err(|e| {
+            AutomationError::ElementNotFound(format!("No element at ({x}, {y}): {e}"))
+        })?;
+
+        Ok(UIElement::new(Box::new(WindowsUIElement {
+            element: ThreadSafeWinUIElement(Arc::new(element)),
+            engine: None,
+        })))
+    }
+
+    fn get_foreground_window(&self) -> Result<UIElement, AutomationError> {
+        applications::get_foreground_window(self)
+    }
+
     async fn get_current_application(&self) -> Result<UIElement, AutomationError> {
         info!("Attempting to get the current focused application.");
         let focused_element_raw = self.automation.0.get_focused_element().map_err(|e| {
@@ -3948,83 +4230,31 @@ impl AccessibilityEngine for WindowsEngine {
             selected_window_name, pid, title
         );
 
-        // Get application name from process using sysinfo (efficient single lookup)
-        let application_name = {
-            use sysinfo::{ProcessesToUpdate, System};
-            let mut system = System::new();
-            system.refresh_processes(ProcessesToUpdate::All, true);
-            system
-                .process(sysinfo::Pid::from_u32(pid))
-                .map(|p| p.name().to_string_lossy().to_string())
-        };
-
-        // Try the new CACHED tree building approach first (30-50x faster)
-        // Falls back to the old recursive approach if caching fails
-        let max_depth = config.max_depth.or(Some(500));
-
-        info!("[TREE_BUILD] Attempting cached tree build for PID: {}", pid);
-        match build_tree_with_cache(
-            &self.automation.0,
-            &selected_window,
-            max_depth,
-            application_name.clone(),
-            config.include_all_bounds,
-        ) {
-            Ok(result) => {
-                info!("[TREE_BUILD] Cached approach succeeded for PID: {}", pid);
-                return Ok(result);
-            }
-            Err(e) => {
-                warn!(
-                    "[TREE_BUILD] Cached approach failed for PID: {}, falling back to recursive: {}",
-                    pid, e
-                );
-            }
-        }
-
-        // Fallback to old recursive approach if caching fails
-        info!(
-            "[TREE_BUILD] Using fallback recursive approach for PID: {}",
-            pid
-        );
-
-        // Wrap the raw OS element into our UIElement
-        let window_element_wrapper = UIElement::new(Box::new(WindowsUIElement {
-            element: ThreadSafeWinUIElement(Arc::new(selected_window)),
-            engine: None,
-        }));
-
-        let mut context = TreeBuildingContext {
-            config: TreeBuildingConfig {
-                timeout_per_operation_ms: config.timeout_per_operation_ms.unwrap_or(50),
-                yield_every_n_elements: config.yield_every_n_elements.unwrap_or(50),
-                batch_size: config.batch_size.unwrap_or(50),
-                max_depth,
-            },
-            property_mode: config.property_mode.clone(),
-            elements_processed: 0,
-            max_depth_reached: 0,
-            cache_hits: 0,
-            fallback_calls: 0,
-            errors_encountered: 0,
-            application_name,
-            include_all_bounds: config.include_all_bounds,
-        };
-
-        let result =
-            build_ui_node_tree_configurable(&window_element_wrapper, 0, &mut context, vec![])?;
+        self.build_window_tree(selected_window, pid, &config)
+    }
 
+    fn get_window_tree_by_hwnd(
+        &self,
+        hwnd: isize,
+        config: crate::platforms::TreeBuildConfig,
+    ) -> Result<crate::UINode, AutomationError> {
         info!(
-            "[TREE_BUILD] Fallback completed for PID: {}. Stats: elements={}, depth={}, cache_hits={}, fallbacks={}, errors={}",
-            pid,
-            context.elements_processed,
-            context.max_depth_reached,
-            context.cache_hits,
-            context.fallback_calls,
-            context.errors_encountered
+            "Getting window tree for HWND: 0x{:X} with config: {:?}",
+            hwnd, config
         );
-
-        Ok(result)
+        let window = self
+            .automation
+            .0
+            .element_from_handle(hwnd.into())
+            .map_err(|e| {
+                AutomationError::ElementNotFound(format!("No window for HWND 0x{hwnd:X}: {e}"))
+            })?;
+        let pid = window.get_process_id().map_err(|e| {
+            AutomationError::PlatformError(format!(
+                "Failed to get process ID for HWND 0x{hwnd:X}: {e}"
+            ))
+        })?;
+        self.build_window_tree(window, pid, &config)
     }
 
     fn get_tree_from_element(
@@ -4057,6 +4287,7 @@ impl AccessibilityEngine for WindowsEngine {
                 yield_every_n_elements: config.yield_every_n_elements.unwrap_or(50),
                 batch_size: config.batch_size.unwrap_or(50),
                 max_depth: config.max_depth.or(Some(500)),
+                parallel_subtrees: config.parallel_subtrees.unwrap_or(1).max(1),
             },
             property_mode: config.property_mode.clone(),
             elements_processed: 0,
@@ -4066,6 +4297,9 @@ impl AccessibilityEngine for WindowsEngine {
             errors_encountered: 0,
             application_name,
             include_all_bounds: config.include_all_bounds,
+            include_shortcuts: config.include_shortcuts,
+            include_relationships: config.include_relationships,
+            stable_ids: config.stable_ids,
         };
 
         let result = build_ui_node_tree_configurable(element, 0, &mut context, vec![])?;
@@ -4091,6 +4325,14 @@ impl AccessibilityEngine for WindowsEngine {
             .map_err(|e| AutomationError::PlatformError(format!("Failed to press key: {e:?}")))
     }
 
+    fn press_key_combo(&self, modifiers: &[Modifier], key: &str) -> Result<(), AutomationError> {
+        super::input::send_key_combo(modifiers, key)
+    }
+
+    fn press_scan_code(&self, scan_code: u16, extended: bool) -> Result<(), AutomationError> {
+        super::input::send_scan_code(scan_code, extended)
+    }
+
     fn set_zoom(&self, percentage: u32) -> Result<(), AutomationError> {
         // Fallback approach using keyboard shortcuts. This works for most browsers and many applications.
         // NOTE: This method is imprecise because browser zoom levels are not always linear (e.g., 90%, 100%, 110%, 125%).
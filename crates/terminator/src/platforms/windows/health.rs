@@ -203,3 +203,112 @@ fn perform_sync_health_check() -> HealthCheckResult {
     result.update_status();
     result
 }
+
+/// Whether the current session can be automated: it is connected (not a disconnected
+/// RDP session) and its input desktop is the normal user desktop rather than the lock
+/// screen or a UAC prompt, both of which UI Automation can't see into.
+pub fn is_session_interactive() -> Result<bool, crate::AutomationError> {
+    use windows::core::PWSTR;
+    use windows::Win32::System::RemoteDesktop::{
+        WTSActive, WTSConnectState, WTSFreeMemory, WTSQuerySessionInformationW,
+        WTS_CONNECTSTATE_CLASS, WTS_CURRENT_SERVER_HANDLE, WTS_CURRENT_SESSION,
+    };
+    use windows::Win32::System::StationsAndDesktops::{
+        CloseDesktop, GetUserObjectInformationW, OpenInputDesktop, DESKTOP_CONTROL_FLAGS,
+        DESKTOP_READOBJECTS, UOI_NAME,
+    };
+
+    unsafe {
+        let mut buffer = PWSTR::null();
+        let mut bytes = 0u32;
+        WTSQuerySessionInformationW(
+            Some(WTS_CURRENT_SERVER_HANDLE),
+            WTS_CURRENT_SESSION,
+            WTSConnectState,
+            &mut buffer,
+            &mut bytes,
+        )
+        .map_err(|e| {
+            crate::AutomationError::PlatformError(format!(
+                "Failed to query session connect state: {e}"
+            ))
+        })?;
+        let state = *(buffer.0 as *const WTS_CONNECTSTATE_CLASS);
+        WTSFreeMemory(buffer.0 as *mut _);
+        if state != WTSActive {
+            debug!("Session is not active (connect state {})", state.0);
+            return Ok(false);
+        }
+
+        // A locked workstation switches input to the secure "Winlogon" desktop, which
+        // ordinary processes can't open
+        let desktop = match OpenInputDesktop(DESKTOP_CONTROL_FLAGS(0), false, DESKTOP_READOBJECTS) {
+            Ok(desktop) => desktop,
+            Err(e) => {
+                debug!("Input desktop is not accessible (likely locked): {}", e);
+                return Ok(false);
+            }
+        };
+        let mut name = [0u16; 64];
+        let named = GetUserObjectInformationW(
+            windows::Win32::Foundation::HANDLE(desktop.0),
+            UOI_NAME,
+            Some(name.as_mut_ptr() as *mut _),
+            (name.len() * 2) as u32,
+            None,
+        );
+        let _ = CloseDesktop(desktop);
+        if named.is_err() {
+            return Ok(true);
+        }
+        let len = name.iter().position(|c| *c == 0).unwrap_or(name.len());
+        let name = String::from_utf16_lossy(&name[..len]);
+        debug!("Input desktop is '{}'", name);
+        Ok(name.eq_ignore_ascii_case("Default"))
+    }
+}
+
+/// Whether a UAC elevation prompt is showing. The prompt is drawn by `consent.exe`, which
+/// only runs while it is up, either on the secure desktop or (with dimming turned off) as
+/// an elevated window that a non-elevated process can't drive through UI Automation.
+pub fn is_elevation_prompt_active() -> bool {
+    use windows::Win32::Foundation::CloseHandle;
+    use windows::Win32::System::Diagnostics::ToolHelp::{
+        CreateToolhelp32Snapshot, Process32FirstW, Process32NextW, PROCESSENTRY32W,
+        TH32CS_SNAPPROCESS,
+    };
+
+    unsafe {
+        let Ok(snapshot) = CreateToolhelp32Snapshot(TH32CS_SNAPPROCESS, 0) else {
+            return false;
+        };
+        let mut entry = PROCESSENTRY32W {
+            dwSize: std::mem::size_of::<PROCESSENTRY32W>() as u32,
+            ..Default::default()
+        };
+        let mut found = false;
+        if Process32FirstW(snapshot, &mut entry).is_ok() {
+            loop {
+                let len = entry
+                    .szExeFile
+                    .iter()
+                    .position(|c| *c == 0)
+                    .unwrap_or(entry.szExeFile.len());
+                if String::from_utf16_lossy(&entry.szExeFile[..len])
+                    .eq_ignore_ascii_case("consent.exe")
+                {
+                    found = true;
+                    break;
+                }
+                if Process32NextW(snapshot, &mut entry).is_err() {
+                    break;
+                }
+            }
+        }
+        let _ = CloseHandle(snapshot);
+        if found {
+            debug!("UAC elevation prompt (consent.exe) is active");
+        }
+        found
+    }
+}
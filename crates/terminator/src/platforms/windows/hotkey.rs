@@ -0,0 +1,156 @@
+//! `RegisterHotKey`-based implementation of system-wide hotkeys.
+//!
+//! Each hotkey gets its own thread: `RegisterHotKey` without a window delivers
+//! `WM_HOTKEY` to the registering thread's message queue, so that thread runs a
+//! message loop until it is told to quit.
+
+use crate::hotkey::Hotkey;
+use crate::{AutomationError, Modifier};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::atomic::{AtomicI32, Ordering};
+use std::sync::mpsc;
+use std::thread::JoinHandle;
+use tracing::{debug, error};
+use windows::Win32::Foundation::{LPARAM, WPARAM};
+use windows::Win32::System::Threading::GetCurrentThreadId;
+use windows::Win32::UI::Input::KeyboardAndMouse::{
+    RegisterHotKey, UnregisterHotKey, HOT_KEY_MODIFIERS, MOD_ALT, MOD_CONTROL, MOD_NOREPEAT,
+    MOD_SHIFT, MOD_WIN, VIRTUAL_KEY, VK_BACK, VK_DELETE, VK_DOWN, VK_END, VK_ESCAPE, VK_F1,
+    VK_HOME, VK_INSERT, VK_LEFT, VK_NEXT, VK_PAUSE, VK_PRIOR, VK_RETURN, VK_RIGHT, VK_SNAPSHOT,
+    VK_SPACE, VK_TAB, VK_UP,
+};
+use windows::Win32::UI::WindowsAndMessaging::{
+    GetMessageW, PeekMessageW, PostThreadMessageW, MSG, PM_NOREMOVE, WM_HOTKEY, WM_QUIT, WM_USER,
+};
+
+/// Hotkey ids must be unique within the process and below 0xC000
+static NEXT_HOTKEY_ID: AtomicI32 = AtomicI32::new(1);
+
+/// A live hotkey registration; dropping it stops the listener thread,
+/// which unregisters the hotkey on its way out.
+pub(crate) struct HotkeyRegistration {
+    thread_id: u32,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl Drop for HotkeyRegistration {
+    fn drop(&mut self) {
+        unsafe {
+            let _ = PostThreadMessageW(self.thread_id, WM_QUIT, WPARAM(0), LPARAM(0));
+        }
+        if let Some(thread) = self.thread.take() {
+            // Dropping the handle from inside its own callback can't wait for itself
+            if thread.thread().id() != std::thread::current().id() {
+                let _ = thread.join();
+            }
+        }
+    }
+}
+
+/// Register `hotkey` system-wide and run `callback` on its thread each time it is pressed.
+pub(crate) fn register(
+    hotkey: &Hotkey,
+    callback: Box<dyn Fn() + Send + 'static>,
+) -> Result<HotkeyRegistration, AutomationError> {
+    let modifiers = hotkey
+        .modifiers
+        .iter()
+        .fold(MOD_NOREPEAT, |acc, modifier| acc | modifier_flag(*modifier));
+    let vk = virtual_key(&hotkey.key).ok_or_else(|| {
+        AutomationError::InvalidArgument(format!("Unsupported hotkey key '{}'", hotkey.key))
+    })?;
+    let id = NEXT_HOTKEY_ID.fetch_add(1, Ordering::SeqCst);
+    let name = hotkey.to_string();
+
+    let (ready_tx, ready_rx) = mpsc::channel::<Result<u32, AutomationError>>();
+    let thread = std::thread::Builder::new()
+        .name(format!("terminator-hotkey-{name}"))
+        .spawn(move || unsafe {
+            // Make sure this thread has a message queue before anyone posts to it
+            let mut msg = MSG::default();
+            let _ = PeekMessageW(&mut msg, None, WM_USER, WM_USER, PM_NOREMOVE);
+
+            if let Err(e) = RegisterHotKey(None, id, modifiers, vk) {
+                let _ = ready_tx.send(Err(AutomationError::PlatformError(format!(
+                    "RegisterHotKey failed for '{name}' (already in use?): {e}"
+                ))));
+                return;
+            }
+            let _ = ready_tx.send(Ok(GetCurrentThreadId()));
+            debug!("Registered hotkey '{name}'");
+
+            // GetMessageW returns 0 on WM_QUIT and -1 on error
+            while GetMessageW(&mut msg, None, 0, 0).0 > 0 {
+                if msg.message == WM_HOTKEY
+                    && msg.wParam.0 == id as usize
+                    && catch_unwind(AssertUnwindSafe(&callback)).is_err()
+                {
+                    error!("Hotkey '{name}' callback panicked");
+                }
+            }
+
+            let _ = UnregisterHotKey(None, id);
+            debug!("Unregistered hotkey '{name}'");
+        })
+        .map_err(|e| {
+            AutomationError::PlatformError(format!("Failed to spawn hotkey thread: {e}"))
+        })?;
+
+    match ready_rx.recv() {
+        Ok(Ok(thread_id)) => Ok(HotkeyRegistration {
+            thread_id,
+            thread: Some(thread),
+        }),
+        Ok(Err(e)) => {
+            let _ = thread.join();
+            Err(e)
+        }
+        Err(_) => Err(AutomationError::Internal(
+            "Hotkey thread exited before registering".to_string(),
+        )),
+    }
+}
+
+fn modifier_flag(modifier: Modifier) -> HOT_KEY_MODIFIERS {
+    match modifier {
+        Modifier::Ctrl => MOD_CONTROL,
+        Modifier::Alt => MOD_ALT,
+        Modifier::Shift => MOD_SHIFT,
+        Modifier::Win => MOD_WIN,
+    }
+}
+
+/// Virtual-key code for a canonical [`Hotkey::key`] name
+fn virtual_key(key: &str) -> Option<u32> {
+    let mut chars = key.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        // 'A'-'Z' and '0'-'9' share their ASCII codes with VK codes
+        return c.is_ascii_alphanumeric().then_some(c as u32);
+    }
+
+    if let Some(n) = key.strip_prefix('F').and_then(|n| n.parse::<u16>().ok()) {
+        return (1..=24).contains(&n).then_some((VK_F1.0 + n - 1) as u32);
+    }
+
+    let vk: VIRTUAL_KEY = match key {
+        "Space" => VK_SPACE,
+        "Enter" => VK_RETURN,
+        "Tab" => VK_TAB,
+        "Escape" => VK_ESCAPE,
+        "Backspace" => VK_BACK,
+        "Delete" => VK_DELETE,
+        "Insert" => VK_INSERT,
+        "Home" => VK_HOME,
+        "End" => VK_END,
+        "PageUp" => VK_PRIOR,
+        "PageDown" => VK_NEXT,
+        "Up" => VK_UP,
+        "Down" => VK_DOWN,
+        "Left" => VK_LEFT,
+        "Right" => VK_RIGHT,
+        "PrintScreen" => VK_SNAPSHOT,
+        "Pause" => VK_PAUSE,
+        _ => return None,
+    };
+    Some(vk.0 as u32)
+}
@@ -3,10 +3,10 @@
 //! This module provides low-level input functions that are shared across
 //! engine.rs and element.rs to avoid code duplication.
 
-use crate::{AutomationError, ClickType};
+use crate::{AutomationError, ClickType, Modifier};
 use std::thread;
 use std::time::Duration;
-use tracing::info;
+use tracing::{info, warn};
 use windows::core::BOOL;
 use windows::Win32::Foundation::POINT;
 use windows::Win32::System::Com::{
@@ -17,8 +17,10 @@ use windows::Win32::UI::Accessibility::{
     IUIAutomationTextRange, UIA_TextPattern2Id,
 };
 use windows::Win32::UI::Input::KeyboardAndMouse::{
-    SendInput, INPUT, INPUT_0, INPUT_MOUSE, MOUSEEVENTF_ABSOLUTE, MOUSEEVENTF_LEFTDOWN,
-    MOUSEEVENTF_LEFTUP, MOUSEEVENTF_MOVE, MOUSEEVENTF_RIGHTDOWN, MOUSEEVENTF_RIGHTUP, MOUSEINPUT,
+    SendInput, INPUT, INPUT_0, INPUT_KEYBOARD, INPUT_MOUSE, KEYBDINPUT, KEYBD_EVENT_FLAGS,
+    KEYEVENTF_EXTENDEDKEY, KEYEVENTF_KEYUP, KEYEVENTF_SCANCODE, MOUSEEVENTF_ABSOLUTE,
+    MOUSEEVENTF_LEFTDOWN, MOUSEEVENTF_LEFTUP, MOUSEEVENTF_MOVE, MOUSEEVENTF_RIGHTDOWN,
+    MOUSEEVENTF_RIGHTUP, MOUSEINPUT,
 };
 use windows::Win32::UI::WindowsAndMessaging::{
     GetCursorPos, GetSystemMetrics, SetCursorPos, SM_CXSCREEN, SM_CYSCREEN,
@@ -134,6 +136,58 @@ pub fn send_mouse_click(
     Ok(())
 }
 
+/// Press the left button at the first point, move through the rest and release at the last.
+///
+/// `hold_between` is waited after each move so apps that sample the pointer (canvases,
+/// signature pads, sliders) see every point.
+pub fn send_mouse_drag_path(
+    points: &[(f64, f64)],
+    hold_between: Duration,
+) -> Result<(), AutomationError> {
+    let (Some(&(first_x, first_y)), Some(&(last_x, last_y))) = (points.first(), points.last())
+    else {
+        return Err(AutomationError::InvalidArgument(
+            "drag path needs at least one point".to_string(),
+        ));
+    };
+
+    let (screen_width, screen_height) = unsafe {
+        (
+            GetSystemMetrics(SM_CXSCREEN) as f64,
+            GetSystemMetrics(SM_CYSCREEN) as f64,
+        )
+    };
+    let mouse_input = |x: f64, y: f64, flags| INPUT {
+        r#type: INPUT_MOUSE,
+        Anonymous: INPUT_0 {
+            mi: MOUSEINPUT {
+                dx: ((x * 65535.0) / screen_width) as i32,
+                dy: ((y * 65535.0) / screen_height) as i32,
+                mouseData: 0,
+                dwFlags: MOUSEEVENTF_ABSOLUTE | flags,
+                time: 0,
+                dwExtraInfo: 0,
+            },
+        },
+    };
+    let send = |input: INPUT| unsafe {
+        SendInput(&[input], std::mem::size_of::<INPUT>() as i32);
+    };
+
+    send(mouse_input(first_x, first_y, MOUSEEVENTF_MOVE));
+    send(mouse_input(first_x, first_y, MOUSEEVENTF_LEFTDOWN));
+    thread::sleep(Duration::from_millis(20));
+    for &(x, y) in &points[1..] {
+        send(mouse_input(x, y, MOUSEEVENTF_MOVE));
+        if !hold_between.is_zero() {
+            thread::sleep(hold_between);
+        }
+    }
+    thread::sleep(Duration::from_millis(20));
+    send(mouse_input(last_x, last_y, MOUSEEVENTF_LEFTUP));
+    Ok(())
+}
+
 /// Send a simple left click at absolute screen coordinates.
 /// Convenience wrapper for send_mouse_click with Left click type.
 #[inline]
@@ -141,6 +195,110 @@ pub fn send_left_click(x: f64, y: f64, restore_cursor: bool) -> Result<(), Autom
     send_mouse_click(x, y, ClickType::Left, restore_cursor)
 }
 
+/// Releases held modifier keys when dropped, in reverse press order.
+struct HeldModifiers(uiautomation::inputs::Keyboard);
+
+impl Drop for HeldModifiers {
+    fn drop(&mut self) {
+        if let Err(e) = self.0.end_hold_keys() {
+            warn!("Failed to release modifier keys: {e:?}");
+        }
+    }
+}
+
+/// Press `key` while holding `modifiers`, releasing the modifiers even if
+/// sending the key fails (or panics).
+///
+/// `key` uses the `uiautomation` key syntax, e.g. `"s"` or `"{F5}"`.
+pub fn send_key_combo(modifiers: &[Modifier], key: &str) -> Result<(), AutomationError> {
+    use uiautomation::inputs::Keyboard;
+
+    if modifiers.is_empty() {
+        return Keyboard::new()
+            .interval(10)
+            .send_keys(key)
+            .map_err(|e| AutomationError::PlatformError(format!("Failed to press key: {e:?}")));
+    }
+
+    let hold: String = modifiers
+        .iter()
+        .map(|m| format!("{{{}}}", m.key_name()))
+        .collect();
+
+    let mut keyboard = Keyboard::new().interval(10);
+    keyboard.begin_hold_keys(&hold).map_err(|e| {
+        AutomationError::PlatformError(format!("Failed to hold modifiers {hold}: {e:?}"))
+    })?;
+    let held = HeldModifiers(keyboard);
+
+    held.0
+        .send_keys(key)
+        .map_err(|e| AutomationError::PlatformError(format!("Failed to press {hold}+{key}: {e:?}")))
+}
+
+/// Run `action` while holding `modifiers`, releasing them even if it fails (or panics).
+pub fn with_modifiers_held<T>(
+    modifiers: &[Modifier],
+    action: impl FnOnce() -> Result<T, AutomationError>,
+) -> Result<T, AutomationError> {
+    use uiautomation::inputs::Keyboard;
+
+    if modifiers.is_empty() {
+        return action();
+    }
+
+    let hold: String = modifiers
+        .iter()
+        .map(|m| format!("{{{}}}", m.key_name()))
+        .collect();
+
+    let mut keyboard = Keyboard::new().interval(10);
+    keyboard.begin_hold_keys(&hold).map_err(|e| {
+        AutomationError::PlatformError(format!("Failed to hold modifiers {hold}: {e:?}"))
+    })?;
+    let _held = HeldModifiers(keyboard);
+
+    action()
+}
+
+/// Press and release a key by hardware scan code using `KEYEVENTF_SCANCODE`.
+///
+/// The virtual-key field is left at zero, so the input skips the virtual-key
+/// layer and reaches apps that read raw keyboard state (DirectInput games,
+/// some legacy and remote-desktop software). `extended` adds
+/// `KEYEVENTF_EXTENDEDKEY` for `E0`-prefixed keys such as the arrows.
+pub fn send_scan_code(scan_code: u16, extended: bool) -> Result<(), AutomationError> {
+    let base_flags = if extended {
+        KEYEVENTF_SCANCODE | KEYEVENTF_EXTENDEDKEY
+    } else {
+        KEYEVENTF_SCANCODE
+    };
+    let key_input = |flags: KEYBD_EVENT_FLAGS| INPUT {
+        r#type: INPUT_KEYBOARD,
+        Anonymous: INPUT_0 {
+            ki: KEYBDINPUT {
+                wScan: scan_code,
+                dwFlags: flags,
+                ..Default::default()
+            },
+        },
+    };
+    let inputs = [
+        key_input(base_flags),
+        key_input(base_flags | KEYEVENTF_KEYUP),
+    ];
+
+    let sent = unsafe { SendInput(&inputs, std::mem::size_of::<INPUT>() as i32) };
+    if sent as usize != inputs.len() {
+        // SendInput is blocked by UIPI when the foreground app runs elevated
+        return Err(AutomationError::PlatformError(format!(
+            "SendInput delivered {sent} of {} events for scan code {scan_code:#04x}",
+            inputs.len()
+        )));
+    }
+    Ok(())
+}
+
 /// Saved focus state for restoration after automation operations.
 ///
 /// Contains the previously focused element, optional caret position (for text fields),
@@ -9,8 +9,10 @@ pub mod element;
 pub mod engine;
 pub mod health;
 pub mod highlighting;
+pub mod hotkey;
 pub mod input;
 pub mod inspect_overlay;
+mod pattern_methods;
 pub mod tree_builder;
 pub mod types;
 pub mod utils;
@@ -26,7 +28,9 @@ pub use types::{FontStyle, HighlightHandle, TextPosition};
 pub use utils::{convert_uiautomation_element_to_terminator, generate_element_id};
 
 // Re-export from applications module
-pub use applications::{get_process_name_by_pid, is_browser_process, KNOWN_BROWSER_PROCESS_NAMES};
+pub use applications::{
+    get_process_name_by_pid, is_browser_process, wait_for_process_exit, KNOWN_BROWSER_PROCESS_NAMES,
+};
 
 // Re-export highlighting control functions
 pub use highlighting::{highlight_bounds, set_recording_mode, stop_all_highlights};
@@ -49,7 +53,9 @@ pub use virtual_display::{
 };
 
 // Re-export window manager
-pub use window_manager::{WindowCache, WindowInfo, WindowManager, WindowPlacement};
+pub use window_manager::{
+    WindowCache, WindowCacheRefreshGuard, WindowInfo, WindowManager, WindowPlacement,
+};
 
 // Re-export input functions
 pub use input::{restore_focus_state, save_focus_state, send_mouse_click, FocusState};
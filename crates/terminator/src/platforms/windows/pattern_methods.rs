@@ -0,0 +1,338 @@
+//! Name-based dispatch of UIA control pattern methods
+//!
+//! Backs [`UIElement::invoke_pattern_method`](crate::UIElement::invoke_pattern_method).
+//! The `match` arms in `invoke` are the complete whitelist of reachable methods;
+//! a method is added by adding an arm. Methods that return elements
+//! (`Grid.GetItem`, `Selection.GetSelection`, the Table headers) have no JSON form and
+//! are left out. Method names follow the UIA documentation
+//! (`SetValue`, `CurrentToggleState`) or the uiautomation crate (`set_value`,
+//! `get_toggle_state`); both are matched case-insensitively with underscores and any
+//! `get`/`current` prefix ignored.
+
+use crate::{AutomationError, ControlPattern};
+use serde_json::{json, Value};
+use uiautomation::patterns::{
+    UIDockPattern, UIExpandCollapsePattern, UIGridItemPattern, UIGridPattern, UIInvokePattern,
+    UILegacyIAccessiblePattern, UIMultipleViewPattern, UIRangeValuePattern, UIScrollItemPattern,
+    UIScrollPattern, UISelectionItemPattern, UISelectionPattern, UITogglePattern,
+    UITransformPattern, UIValuePattern, UIWindowPattern,
+};
+use uiautomation::types::{DockPosition, ScrollAmount, WindowVisualState};
+
+macro_rules! pattern {
+    ($element:expr, $ty:ty, $pattern:expr) => {
+        $element.get_pattern::<$ty>().map_err(|e| {
+            AutomationError::UnsupportedOperation(format!(
+                "Element does not support the {} pattern: {e}",
+                $pattern
+            ))
+        })?
+    };
+}
+
+/// Call `method` on `pattern` of `element` with JSON `args`, returning the method's
+/// result as JSON (`null` for methods that return nothing)
+pub(crate) fn invoke(
+    element: &uiautomation::UIElement,
+    pattern: ControlPattern,
+    method: &str,
+    args: &[Value],
+) -> Result<Value, AutomationError> {
+    let name = normalize(method);
+    let uia = |e: uiautomation::Error| {
+        AutomationError::PlatformError(format!("{pattern}.{method} failed: {e}"))
+    };
+    let unknown = || {
+        AutomationError::UnsupportedOperation(format!(
+            "{pattern}.{method} is not available through invoke_pattern_method"
+        ))
+    };
+
+    let value = match pattern {
+        ControlPattern::Invoke => {
+            let p = pattern!(element, UIInvokePattern, pattern);
+            match name.as_str() {
+                "invoke" => p.invoke().map(|_| Value::Null),
+                _ => return Err(unknown()),
+            }
+        }
+        ControlPattern::Toggle => {
+            let p = pattern!(element, UITogglePattern, pattern);
+            match name.as_str() {
+                "toggle" => p.toggle().map(|_| Value::Null),
+                "togglestate" => p.get_toggle_state().map(|s| json!(format!("{s:?}"))),
+                _ => return Err(unknown()),
+            }
+        }
+        ControlPattern::Value => {
+            let p = pattern!(element, UIValuePattern, pattern);
+            match name.as_str() {
+                "setvalue" => p.set_value(arg_str(args, 0, method)?).map(|_| Value::Null),
+                "value" => p.get_value().map(|v| json!(v)),
+                "isreadonly" => p.is_readonly().map(|v| json!(v)),
+                _ => return Err(unknown()),
+            }
+        }
+        ControlPattern::RangeValue => {
+            let p = pattern!(element, UIRangeValuePattern, pattern);
+            match name.as_str() {
+                "setvalue" => p.set_value(arg_f64(args, 0, method)?).map(|_| Value::Null),
+                "value" => p.get_value().map(|v| json!(v)),
+                "isreadonly" => p.is_readonly().map(|v| json!(v)),
+                "maximum" => p.get_maximum().map(|v| json!(v)),
+                "minimum" => p.get_minimum().map(|v| json!(v)),
+                "largechange" => p.get_large_change().map(|v| json!(v)),
+                "smallchange" => p.get_small_change().map(|v| json!(v)),
+                _ => return Err(unknown()),
+            }
+        }
+        ControlPattern::ExpandCollapse => {
+            let p = pattern!(element, UIExpandCollapsePattern, pattern);
+            match name.as_str() {
+                "expand" => p.expand().map(|_| Value::Null),
+                "collapse" => p.collapse().map(|_| Value::Null),
+                "state" | "expandcollapsestate" => p.get_state().map(|s| json!(format!("{s:?}"))),
+                _ => return Err(unknown()),
+            }
+        }
+        ControlPattern::Selection => {
+            let p = pattern!(element, UISelectionPattern, pattern);
+            match name.as_str() {
+                "canselectmultiple" => p.can_select_multiple().map(|v| json!(v)),
+                "isselectionrequired" => p.is_selection_required().map(|v| json!(v)),
+                "itemcount" => p.get_item_count().map(|v| json!(v)),
+                _ => return Err(unknown()),
+            }
+        }
+        ControlPattern::SelectionItem => {
+            let p = pattern!(element, UISelectionItemPattern, pattern);
+            match name.as_str() {
+                "select" => p.select().map(|_| Value::Null),
+                "addtoselection" => p.add_to_selection().map(|_| Value::Null),
+                "removefromselection" => p.remove_from_selection().map(|_| Value::Null),
+                "isselected" => p.is_selected().map(|v| json!(v)),
+                _ => return Err(unknown()),
+            }
+        }
+        ControlPattern::Grid => {
+            let p = pattern!(element, UIGridPattern, pattern);
+            match name.as_str() {
+                "rowcount" => p.get_row_count().map(|v| json!(v)),
+                "columncount" => p.get_column_count().map(|v| json!(v)),
+                _ => return Err(unknown()),
+            }
+        }
+        ControlPattern::GridItem => {
+            let p = pattern!(element, UIGridItemPattern, pattern);
+            match name.as_str() {
+                "row" => p.get_row().map(|v| json!(v)),
+                "column" => p.get_column().map(|v| json!(v)),
+                "rowspan" => p.get_row_span().map(|v| json!(v)),
+                "columnspan" => p.get_column_span().map(|v| json!(v)),
+                _ => return Err(unknown()),
+            }
+        }
+        ControlPattern::Scroll => {
+            let p = pattern!(element, UIScrollPattern, pattern);
+            match name.as_str() {
+                "scroll" => p
+                    .scroll(
+                        arg_scroll_amount(args, 0, method)?,
+                        arg_scroll_amount(args, 1, method)?,
+                    )
+                    .map(|_| Value::Null),
+                "setscrollpercent" => p
+                    .set_scroll_percent(arg_f64(args, 0, method)?, arg_f64(args, 1, method)?)
+                    .map(|_| Value::Null),
+                "horizontalscrollpercent" => p.get_horizontal_scroll_percent().map(|v| json!(v)),
+                "verticalscrollpercent" => p.get_vertical_scroll_percent().map(|v| json!(v)),
+                "horizontalviewsize" => p.get_horizontal_view_size().map(|v| json!(v)),
+                "verticalviewsize" => p.get_vertical_view_size().map(|v| json!(v)),
+                "horizontallyscrollable" | "ishorizontallyscrollable" => {
+                    p.is_horizontally_scrollable().map(|v| json!(v))
+                }
+                "verticallyscrollable" | "isverticallyscrollable" => {
+                    p.is_vertically_scrollable().map(|v| json!(v))
+                }
+                _ => return Err(unknown()),
+            }
+        }
+        ControlPattern::ScrollItem => {
+            let p = pattern!(element, UIScrollItemPattern, pattern);
+            match name.as_str() {
+                "scrollintoview" => p.scroll_into_view().map(|_| Value::Null),
+                _ => return Err(unknown()),
+            }
+        }
+        ControlPattern::Window => {
+            let p = pattern!(element, UIWindowPattern, pattern);
+            match name.as_str() {
+                "close" => p.close().map(|_| Value::Null),
+                "waitforinputidle" => p
+                    .wait_for_input_idle(arg_i32(args, 0, method)?)
+                    .map(|v| json!(v)),
+                "windowvisualstate" => p.get_window_visual_state().map(|s| json!(format!("{s:?}"))),
+                "setwindowvisualstate" => {
+                    let state = match arg_str(args, 0, method)?.to_lowercase().as_str() {
+                        "normal" => WindowVisualState::Normal,
+                        "maximized" => WindowVisualState::Maximized,
+                        "minimized" => WindowVisualState::Minimized,
+                        other => {
+                            return Err(AutomationError::InvalidArgument(format!(
+                                "{method} expects Normal, Maximized or Minimized, got '{other}'"
+                            )))
+                        }
+                    };
+                    p.set_window_visual_state(state).map(|_| Value::Null)
+                }
+                "canmaximize" => p.can_maximize().map(|v| json!(v)),
+                "canminimize" => p.can_minimize().map(|v| json!(v)),
+                "ismodal" => p.is_modal().map(|v| json!(v)),
+                "istopmost" => p.is_topmost().map(|v| json!(v)),
+                _ => return Err(unknown()),
+            }
+        }
+        ControlPattern::Transform => {
+            let p = pattern!(element, UITransformPattern, pattern);
+            match name.as_str() {
+                "canmove" => p.can_move().map(|v| json!(v)),
+                "canresize" => p.can_resize().map(|v| json!(v)),
+                "canrotate" => p.can_rotate().map(|v| json!(v)),
+                "move" | "moveto" => p
+                    .move_to(arg_f64(args, 0, method)?, arg_f64(args, 1, method)?)
+                    .map(|_| Value::Null),
+                "resize" => p
+                    .resize(arg_f64(args, 0, method)?, arg_f64(args, 1, method)?)
+                    .map(|_| Value::Null),
+                "rotate" => p.rotate(arg_f64(args, 0, method)?).map(|_| Value::Null),
+                _ => return Err(unknown()),
+            }
+        }
+        ControlPattern::Dock => {
+            let p = pattern!(element, UIDockPattern, pattern);
+            match name.as_str() {
+                "dockposition" => p.get_dock_position().map(|s| json!(format!("{s:?}"))),
+                "setdockposition" => {
+                    let position = match arg_str(args, 0, method)?.to_lowercase().as_str() {
+                        "top" => DockPosition::Top,
+                        "left" => DockPosition::Left,
+                        "bottom" => DockPosition::Bottom,
+                        "right" => DockPosition::Right,
+                        "fill" => DockPosition::Fill,
+                        "none" => DockPosition::None,
+                        other => {
+                            return Err(AutomationError::InvalidArgument(format!(
+                                "{method} expects Top, Left, Bottom, Right, Fill or None, got '{other}'"
+                            )))
+                        }
+                    };
+                    p.set_dock_position(position).map(|_| Value::Null)
+                }
+                _ => return Err(unknown()),
+            }
+        }
+        ControlPattern::MultipleView => {
+            let p = pattern!(element, UIMultipleViewPattern, pattern);
+            match name.as_str() {
+                // `CurrentView` normalizes to "view"; UIA's `CurrentCurrentView` and
+                // `get_current_view` lose only their first prefix and land on "currentview"
+                "currentview" | "view" => p.get_current_view().map(|v| json!(v)),
+                "setcurrentview" => p
+                    .set_current_view(arg_i32(args, 0, method)?)
+                    .map(|_| Value::Null),
+                "supportedviews" => p.get_supported_views().map(|v| json!(v)),
+                "viewname" => p.get_view_name(arg_i32(args, 0, method)?).map(|v| json!(v)),
+                _ => return Err(unknown()),
+            }
+        }
+        ControlPattern::LegacyIAccessible => {
+            let p = pattern!(element, UILegacyIAccessiblePattern, pattern);
+            match name.as_str() {
+                "dodefaultaction" => p.do_default_action().map(|_| Value::Null),
+                "select" => p.select(arg_i32(args, 0, method)?).map(|_| Value::Null),
+                "setvalue" => p.set_value(arg_str(args, 0, method)?).map(|_| Value::Null),
+                "childid" => p.get_child_id().map(|v| json!(v)),
+                "name" => p.get_name().map(|v| json!(v)),
+                "value" => p.get_value().map(|v| json!(v)),
+                "description" => p.get_description().map(|v| json!(v)),
+                "role" => p.get_role().map(|v| json!(v)),
+                "state" => p.get_state().map(|v| json!(v)),
+                "help" => p.get_help().map(|v| json!(v)),
+                "keyboardshortcut" => p.get_keyboard_shortcut().map(|v| json!(v)),
+                "defaultaction" => p.get_default_action().map(|v| json!(v)),
+                _ => return Err(unknown()),
+            }
+        }
+        ControlPattern::Table
+        | ControlPattern::TableItem
+        | ControlPattern::Text
+        | ControlPattern::ItemContainer => return Err(unknown()),
+    };
+    value.map_err(uia)
+}
+
+/// Lowercase, drop underscores and a leading `get` or `current`, so the UIA and
+/// uiautomation spellings of a method land on the same name
+fn normalize(method: &str) -> String {
+    let name: String = method
+        .chars()
+        .filter(|c| *c != '_')
+        .flat_map(char::to_lowercase)
+        .collect();
+    ["current", "get"]
+        .iter()
+        .find_map(|prefix| name.strip_prefix(prefix).filter(|rest| !rest.is_empty()))
+        .map(str::to_string)
+        .unwrap_or(name)
+}
+
+fn arg<'a>(args: &'a [Value], index: usize, method: &str) -> Result<&'a Value, AutomationError> {
+    args.get(index).ok_or_else(|| {
+        AutomationError::InvalidArgument(format!(
+            "{method} expects at least {} argument(s), got {}",
+            index + 1,
+            args.len()
+        ))
+    })
+}
+
+fn arg_str<'a>(args: &'a [Value], index: usize, method: &str) -> Result<&'a str, AutomationError> {
+    arg(args, index, method)?.as_str().ok_or_else(|| {
+        AutomationError::InvalidArgument(format!("{method} argument {index} must be a string"))
+    })
+}
+
+fn arg_f64(args: &[Value], index: usize, method: &str) -> Result<f64, AutomationError> {
+    arg(args, index, method)?.as_f64().ok_or_else(|| {
+        AutomationError::InvalidArgument(format!("{method} argument {index} must be a number"))
+    })
+}
+
+fn arg_scroll_amount(
+    args: &[Value],
+    index: usize,
+    method: &str,
+) -> Result<ScrollAmount, AutomationError> {
+    match arg_str(args, index, method)?.to_lowercase().as_str() {
+        "largedecrement" => Ok(ScrollAmount::LargeDecrement),
+        "smalldecrement" => Ok(ScrollAmount::SmallDecrement),
+        "noamount" => Ok(ScrollAmount::NoAmount),
+        "largeincrement" => Ok(ScrollAmount::LargeIncrement),
+        "smallincrement" => Ok(ScrollAmount::SmallIncrement),
+        other => Err(AutomationError::InvalidArgument(format!(
+            "{method} argument {index} must be LargeDecrement, SmallDecrement, NoAmount, \
+             LargeIncrement or SmallIncrement, got '{other}'"
+        ))),
+    }
+}
+
+fn arg_i32(args: &[Value], index: usize, method: &str) -> Result<i32, AutomationError> {
+    arg(args, index, method)?
+        .as_i64()
+        .and_then(|n| i32::try_from(n).ok())
+        .ok_or_else(|| {
+            AutomationError::InvalidArgument(format!(
+                "{method} argument {index} must be an integer"
+            ))
+        })
+}
@@ -1,37 +1,27 @@
 //! UI tree building functionality for Windows
 
+use crate::selector::{build_chained_selector, build_selector_segment};
 use crate::{AutomationError, UIElement, UIElementAttributes};
 use std::sync::mpsc;
 use std::thread;
 use std::time::Duration;
-use tracing::{debug, info};
+use tracing::{debug, info, warn};
 use uiautomation::types::{TreeScope, UIProperty};
 use uiautomation::UIAutomation;
+use windows::Win32::System::Com::{CoInitializeEx, CoUninitialize, COINIT_MULTITHREADED};
 
-/// Build a selector segment for a single element (e.g., "role:Button && name:Submit")
-/// Only includes name if it's non-empty and meaningful
-fn build_selector_segment(role: &str, name: Option<&str>) -> String {
-    match name {
-        Some(n) if !n.is_empty() => format!("role:{} && name:{}", role, n),
-        _ => format!("role:{}", role),
-    }
-}
-
-/// Build a chained selector from a list of segments (e.g., "role:Window && name:App >> role:Button && name:Submit")
-fn build_chained_selector(segments: &[String]) -> Option<String> {
-    if segments.is_empty() {
-        None
-    } else {
-        Some(segments.join(" >> "))
-    }
-}
+/// Minimum number of children an element needs before its subtrees are built in parallel
+const PARALLEL_SUBTREE_MIN_CHILDREN: usize = 16;
 
 /// Configuration for tree building operations
+#[derive(Clone)]
 pub(crate) struct TreeBuildingConfig {
     pub(crate) timeout_per_operation_ms: u64,
     pub(crate) yield_every_n_elements: usize,
     pub(crate) batch_size: usize,
     pub(crate) max_depth: Option<usize>,
+    /// Threads used for sibling subtrees; 1 builds serially
+    pub(crate) parallel_subtrees: usize,
 }
 
 /// Context for tracking tree building progress and stats
@@ -45,6 +35,9 @@ pub(crate) struct TreeBuildingContext {
     pub(crate) errors_encountered: usize,
     pub(crate) application_name: Option<String>, // Cached application name for all nodes in tree
     pub(crate) include_all_bounds: bool, // Include bounds for all elements (not just focusable)
+    pub(crate) include_shortcuts: bool,  // Populate accelerator/access key shortcuts
+    pub(crate) include_relationships: bool, // Populate LabeledBy/DescribedBy/ControllerFor
+    pub(crate) stable_ids: bool,         // Use content-addressed node ids
 }
 
 impl TreeBuildingContext {
@@ -73,6 +66,60 @@ impl TreeBuildingContext {
     pub(crate) fn increment_errors(&mut self) {
         self.errors_encountered += 1;
     }
+
+    /// Fresh context for a worker thread; workers build serially and never fan out again
+    fn for_worker(&self) -> Self {
+        Self {
+            config: TreeBuildingConfig {
+                parallel_subtrees: 1,
+                ..self.config.clone()
+            },
+            property_mode: self.property_mode.clone(),
+            elements_processed: 0,
+            max_depth_reached: 0,
+            cache_hits: 0,
+            fallback_calls: 0,
+            errors_encountered: 0,
+            application_name: self.application_name.clone(),
+            include_all_bounds: self.include_all_bounds,
+            include_shortcuts: self.include_shortcuts,
+            include_relationships: self.include_relationships,
+            stable_ids: self.stable_ids,
+        }
+    }
+
+    fn merge_worker_stats(&mut self, worker: &TreeBuildingContext) {
+        self.elements_processed += worker.elements_processed;
+        self.max_depth_reached = self.max_depth_reached.max(worker.max_depth_reached);
+        self.cache_hits += worker.cache_hits;
+        self.fallback_calls += worker.fallback_calls;
+        self.errors_encountered += worker.errors_encountered;
+    }
+}
+
+/// Keeps the current thread in the multithreaded apartment until dropped
+struct MtaGuard {
+    initialized: bool,
+}
+
+impl MtaGuard {
+    fn enter() -> Self {
+        let hr = unsafe { CoInitializeEx(None, COINIT_MULTITHREADED) };
+        if hr.is_err() {
+            debug!("Tree worker could not join the MTA: {:?}", hr);
+        }
+        Self {
+            initialized: hr.is_ok(),
+        }
+    }
+}
+
+impl Drop for MtaGuard {
+    fn drop(&mut self) {
+        if self.initialized {
+            unsafe { CoUninitialize() };
+        }
+    }
 }
 
 /// Build a UI node tree with configurable properties and performance tuning
@@ -116,6 +163,8 @@ pub(crate) fn build_ui_node_tree_configurable(
             &work_item.element,
             &context.property_mode,
             context.include_all_bounds,
+            context.include_shortcuts,
+            context.include_relationships,
         );
 
         // Populate application_name from context if available
@@ -130,10 +179,14 @@ pub(crate) fn build_ui_node_tree_configurable(
 
         // Build the chained selector for this node
         let selector = build_chained_selector(&current_selector_path);
+        let id = match &selector {
+            Some(path) if context.stable_ids => Some(crate::UINode::stable_id(path, &attributes)),
+            _ => work_item.element.id(),
+        };
 
         // Create node without children initially
         let mut node = crate::UINode {
-            id: work_item.element.id(),
+            id,
             attributes,
             children: Vec::new(),
             selector,
@@ -150,10 +203,25 @@ pub(crate) fn build_ui_node_tree_configurable(
             // Get children with safe strategy
             match get_element_children_safe(&work_item.element, context) {
                 Ok(children_elements) => {
-                    // Process children in batches
-                    let mut child_index = 0;
-                    for batch in children_elements.chunks(context.config.batch_size) {
-                        for child_element in batch {
+                    // Consume the children so each one's COM reference is released as soon as
+                    // its subtree is built, instead of every sibling list along the current
+                    // path staying alive until the walk unwinds
+                    let child_count = children_elements.len();
+                    let batch_size = context.config.batch_size.max(1);
+                    if context.config.parallel_subtrees > 1
+                        && child_count >= PARALLEL_SUBTREE_MIN_CHILDREN
+                        && work_item.depth < 100
+                    {
+                        node.children = build_children_parallel(
+                            children_elements,
+                            work_item.depth + 1,
+                            context,
+                            &current_selector_path,
+                        );
+                    } else {
+                        for (child_index, child_element) in
+                            children_elements.into_iter().enumerate()
+                        {
                             // Create path for this child
                             let mut child_path = work_item.node_path.clone();
                             child_path.push(child_index);
@@ -162,7 +230,7 @@ pub(crate) fn build_ui_node_tree_configurable(
                             if work_item.depth < 100 {
                                 // Limit recursion depth
                                 match build_ui_node_tree_configurable(
-                                    child_element,
+                                    &child_element,
                                     work_item.depth + 1,
                                     context,
                                     current_selector_path.clone(),
@@ -176,23 +244,21 @@ pub(crate) fn build_ui_node_tree_configurable(
                                         context.increment_errors();
                                     }
                                 }
+                                child_element.release();
                             } else {
                                 // If too deep, add to work queue for iterative processing
                                 work_queue.push(WorkItem {
-                                    element: child_element.clone(),
+                                    element: child_element,
                                     depth: work_item.depth + 1,
                                     node_path: child_path,
                                     selector_path: current_selector_path.clone(),
                                 });
                             }
-                            child_index += 1;
-                        }
 
-                        // Small yield between large batches to maintain responsiveness
-                        if batch.len() == context.config.batch_size
-                            && children_elements.len() > context.config.batch_size
-                        {
-                            thread::sleep(Duration::from_millis(1));
+                            // Small yield between large batches to maintain responsiveness
+                            if child_count > batch_size && (child_index + 1) % batch_size == 0 {
+                                thread::sleep(Duration::from_millis(1));
+                            }
                         }
                     }
                 }
@@ -220,11 +286,84 @@ pub(crate) fn build_ui_node_tree_configurable(
     ))
 }
 
+/// Build sibling subtrees on up to `parallel_subtrees` threads, keeping their order.
+///
+/// The engine creates its UIA objects in the multithreaded apartment, so each worker
+/// joins the MTA before touching them and the elements can be used directly without
+/// marshaling. Children are split into contiguous chunks, each built serially.
+fn build_children_parallel(
+    children: Vec<UIElement>,
+    depth: usize,
+    context: &mut TreeBuildingContext,
+    selector_path: &[String],
+) -> Vec<crate::UINode> {
+    let workers = context.config.parallel_subtrees.min(children.len()).max(1);
+    let chunk_size = children.len().div_ceil(workers);
+    let mut remaining = children.into_iter();
+    let chunks: Vec<Vec<UIElement>> = (0..workers)
+        .map(|_| remaining.by_ref().take(chunk_size).collect())
+        .filter(|chunk: &Vec<UIElement>| !chunk.is_empty())
+        .collect();
+
+    let results = thread::scope(|scope| {
+        let handles: Vec<_> = chunks
+            .into_iter()
+            .map(|chunk| {
+                let mut worker_context = context.for_worker();
+                scope.spawn(move || {
+                    let _apartment = MtaGuard::enter();
+                    let mut nodes = Vec::with_capacity(chunk.len());
+                    for child_element in chunk {
+                        match build_ui_node_tree_configurable(
+                            &child_element,
+                            depth,
+                            &mut worker_context,
+                            selector_path.to_vec(),
+                        ) {
+                            Ok(child_node) => nodes.push(child_node),
+                            Err(e) => {
+                                debug!(
+                                    "Failed to process child element: {}. Continuing with next child.",
+                                    e
+                                );
+                                worker_context.increment_errors();
+                            }
+                        }
+                        child_element.release();
+                    }
+                    (nodes, worker_context)
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .map(|handle| handle.join())
+            .collect::<Vec<_>>()
+    });
+
+    let mut nodes = Vec::new();
+    for result in results {
+        match result {
+            Ok((worker_nodes, worker_context)) => {
+                context.merge_worker_stats(&worker_context);
+                nodes.extend(worker_nodes);
+            }
+            Err(_) => {
+                warn!("Tree worker thread panicked; its subtrees are missing from the result");
+                context.increment_errors();
+            }
+        }
+    }
+    nodes
+}
+
 /// Get element attributes based on the configured property loading mode
 fn get_configurable_attributes(
     element: &UIElement,
     property_mode: &crate::platforms::PropertyLoadingMode,
     include_all_bounds: bool,
+    include_shortcuts: bool,
+    include_relationships: bool,
 ) -> UIElementAttributes {
     let mut attrs = match property_mode {
         crate::platforms::PropertyLoadingMode::Fast => {
@@ -290,6 +429,26 @@ fn get_configurable_attributes(
         attrs.is_selected = Some(is_selected);
     }
 
+    if include_shortcuts {
+        attrs.shortcut = element.shortcut();
+    }
+
+    if include_relationships {
+        let names = |elements: Vec<UIElement>| -> Vec<String> {
+            elements
+                .iter()
+                .filter_map(|e| e.name())
+                .filter(|n| !n.is_empty())
+                .collect()
+        };
+        attrs.labeled_by = element
+            .labeled_by()
+            .and_then(|label| label.name())
+            .filter(|n| !n.is_empty());
+        attrs.described_by = names(element.described_by());
+        attrs.controller_for = names(element.controller_for());
+    }
+
     // NOTE: child_count and index_in_parent were removed - they added 3 extra IPC calls per element
     // (~3000 wasted calls per 1000 elements) and were NEVER displayed in the UI tree output:
     // - child_count: only shown when node.children.is_none(), but tree building always populates children
@@ -385,12 +544,16 @@ pub(crate) fn get_element_children_with_timeout(
 /// instead of making ~15 IPC calls per element.
 ///
 /// Performance improvement: ~30-50x faster for large trees (e.g., 6.5s -> 200ms for 245 elements)
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn build_tree_with_cache(
     automation: &UIAutomation,
     root_element: &uiautomation::UIElement,
     max_depth: Option<usize>,
     application_name: Option<String>,
     include_all_bounds: bool,
+    include_shortcuts: bool,
+    include_relationships: bool,
+    stable_ids: bool,
 ) -> Result<crate::UINode, AutomationError> {
     info!("[CACHED_TREE] Starting cached tree build");
     let start_time = std::time::Instant::now();
@@ -401,7 +564,7 @@ pub(crate) fn build_tree_with_cache(
     })?;
 
     // Add properties to cache - these will be fetched in ONE IPC call
-    let properties = [
+    let mut properties = vec![
         UIProperty::ControlType,
         UIProperty::Name,
         UIProperty::BoundingRectangle,
@@ -410,6 +573,16 @@ pub(crate) fn build_tree_with_cache(
         UIProperty::HasKeyboardFocus,
         UIProperty::AutomationId,
     ];
+    if include_shortcuts {
+        properties.extend([UIProperty::AcceleratorKey, UIProperty::AccessKey]);
+    }
+    if include_relationships {
+        properties.extend([
+            UIProperty::LabeledBy,
+            UIProperty::DescribedBy,
+            UIProperty::ControllerFor,
+        ]);
+    }
 
     for prop in &properties {
         cache_request.add_property(*prop).map_err(|e| {
@@ -452,6 +625,9 @@ pub(crate) fn build_tree_with_cache(
         max_depth,
         &application_name,
         include_all_bounds,
+        include_shortcuts,
+        include_relationships,
+        stable_ids,
         &mut elements_count,
         vec![],
     )?;
@@ -468,13 +644,36 @@ pub(crate) fn build_tree_with_cache(
     Ok(result)
 }
 
+/// Name of an element reached through a relationship property. Related elements don't
+/// always carry the cached Name, so this falls back to a live lookup.
+fn related_element_name(element: &uiautomation::UIElement) -> Option<String> {
+    element
+        .get_cached_name()
+        .or_else(|_| element.get_name())
+        .ok()
+        .filter(|n| !n.is_empty())
+}
+
+/// Names of the elements in a cached relationship array, skipping unnamed ones
+fn related_element_names(
+    elements: uiautomation::Result<Vec<uiautomation::UIElement>>,
+) -> Vec<String> {
+    elements
+        .map(|elements| elements.iter().filter_map(related_element_name).collect())
+        .unwrap_or_default()
+}
+
 /// Build a UINode from a cached UIElement - all property access is instant (no IPC)
+#[allow(clippy::too_many_arguments)]
 fn build_node_from_cached_element(
     element: &uiautomation::UIElement,
     depth: usize,
     max_depth: Option<usize>,
     application_name: &Option<String>,
     include_all_bounds: bool,
+    include_shortcuts: bool,
+    include_relationships: bool,
+    stable_ids: bool,
     elements_count: &mut usize,
     selector_path: Vec<String>,
 ) -> Result<crate::UINode, AutomationError> {
@@ -517,6 +716,40 @@ fn build_node_from_cached_element(
     let enabled = element.is_cached_enabled().ok();
     let is_keyboard_focusable = element.is_cached_keyboard_focusable().ok().filter(|&f| f);
     let is_focused = element.has_cached_keyboard_focus().ok().filter(|&f| f);
+    let shortcut = if include_shortcuts {
+        let non_empty = |key: uiautomation::Result<String>| key.ok().filter(|k| !k.is_empty());
+        non_empty(element.get_cached_accelerator_key())
+            .or_else(|| non_empty(element.get_cached_access_key()))
+    } else {
+        None
+    };
+    let (labeled_by, described_by, controller_for) = if include_relationships {
+        (
+            element
+                .get_cached_labeled_by()
+                .ok()
+                .and_then(|label| related_element_name(&label)),
+            related_element_names(element.get_cached_described_by()),
+            related_element_names(element.get_cached_controller_for()),
+        )
+    } else {
+        (None, Vec::new(), Vec::new())
+    };
+
+    // Like the uncached attributes, only unnamed elements carry their automation id
+    let mut properties = std::collections::HashMap::new();
+    if name.is_none() {
+        if let Some(automation_id) = element
+            .get_cached_automation_id()
+            .ok()
+            .filter(|id| !id.is_empty())
+        {
+            properties.insert(
+                "AutomationId".to_string(),
+                Some(serde_json::Value::String(automation_id)),
+            );
+        }
+    }
 
     // Build selector segment for this node
     let current_segment = build_selector_segment(&role, name.as_deref());
@@ -524,11 +757,6 @@ fn build_node_from_cached_element(
     current_selector_path.push(current_segment);
     let selector = build_chained_selector(&current_selector_path);
 
-    // Generate element ID (same logic as WindowsUIElement::id())
-    let id = super::utils::generate_element_id(element)
-        .ok()
-        .map(|oid| oid.to_string().chars().take(6).collect());
-
     let attributes = UIElementAttributes {
         role,
         name,
@@ -537,7 +765,7 @@ fn build_node_from_cached_element(
         value: None,
         description: None,
         application_name: application_name.clone(),
-        properties: std::collections::HashMap::new(),
+        properties,
         is_keyboard_focusable,
         is_focused,
         is_toggled: None,
@@ -546,6 +774,18 @@ fn build_node_from_cached_element(
         is_selected: None,
         child_count: None,     // Not fetching - was wasteful anyway
         index_in_parent: None, // Not fetching - was wasteful anyway
+        shortcut,
+        labeled_by,
+        described_by,
+        controller_for,
+    };
+
+    // Generate element ID (same logic as WindowsUIElement::id()) unless stable ids are wanted
+    let id = match &selector {
+        Some(path) if stable_ids => Some(crate::UINode::stable_id(path, &attributes)),
+        _ => super::utils::generate_element_id(element)
+            .ok()
+            .map(|oid| oid.to_string().chars().take(6).collect()),
     };
 
     let mut node = crate::UINode {
@@ -568,6 +808,9 @@ fn build_node_from_cached_element(
                     max_depth,
                     application_name,
                     include_all_bounds,
+                    include_shortcuts,
+                    include_relationships,
+                    stable_ids,
                     elements_count,
                     current_selector_path.clone(),
                 ) {
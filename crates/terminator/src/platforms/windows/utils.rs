@@ -1,10 +1,11 @@
 //! Utility functions and type conversions for Windows platform
 
 use super::types::ThreadSafeWinUIElement;
-use crate::{AutomationError, UIElement};
+use crate::{AutomationError, ControlPattern, UIElement};
 use std::sync::Arc;
 use uiautomation::controls::ControlType;
 use uiautomation::types::UIProperty;
+use uiautomation::variants::Value;
 use uiautomation::UIAutomation;
 use windows::core::HRESULT;
 use windows::Win32::System::Com::{CoInitializeEx, COINIT_MULTITHREADED};
@@ -162,6 +163,74 @@ pub(crate) fn map_generic_role_to_win_roles(role: &str) -> ControlType {
     }
 }
 
+/// Convert a UIA property value to JSON.
+///
+/// Returns None for values with no JSON form: object references (other elements, or the
+/// "not supported" sentinel UIA returns for properties an element lacks), decimals,
+/// raw safe arrays and error codes.
+pub(crate) fn variant_value_to_json(value: Value) -> Option<serde_json::Value> {
+    use serde_json::Value as Json;
+    Some(match value {
+        Value::EMPTY | Value::NULL | Value::VOID => Json::Null,
+        Value::I1(v) => v.into(),
+        Value::I2(v) => v.into(),
+        Value::I4(v) | Value::INT(v) => v.into(),
+        Value::I8(v) => v.into(),
+        Value::UI1(v) => v.into(),
+        Value::UI2(v) => v.into(),
+        Value::UI4(v) | Value::UINT(v) => v.into(),
+        Value::UI8(v) => v.into(),
+        Value::R4(v) => f64::from(v).into(),
+        Value::R8(v) | Value::DATE(v) => v.into(),
+        // CY is a fixed-point number scaled by 10,000
+        Value::CURRENCY(v) => (v as f64 / 10_000.0).into(),
+        Value::STRING(v) => v.into(),
+        Value::BOOL(v) => v.into(),
+        Value::VARIANT(inner) => return variant_value_to_json(inner.get_value().ok()?),
+        Value::ArrayBool(v) => v.into(),
+        Value::ArrayR8(v) => v.into(),
+        Value::ArrayI2(v) => v.into(),
+        Value::ArrayI4(v) => v.into(),
+        Value::ArrayI8(v) => v.into(),
+        Value::ArrayUI2(v) => v.into(),
+        Value::ArrayUI4(v) => v.into(),
+        Value::ArrayUI8(v) => v.into(),
+        Value::ArrayString(v) => v.into(),
+        Value::UNKNOWN(_)
+        | Value::DISPATCH(_)
+        | Value::DECIMAL(_)
+        | Value::SAFEARRAY(_)
+        | Value::ERROR(_)
+        | Value::HRESULT(_) => return None,
+    })
+}
+
+/// The `Is*PatternAvailable` property that reports whether an element implements `pattern`
+pub(crate) fn pattern_available_property(pattern: ControlPattern) -> UIProperty {
+    match pattern {
+        ControlPattern::Invoke => UIProperty::IsInvokePatternAvailable,
+        ControlPattern::Toggle => UIProperty::IsTogglePatternAvailable,
+        ControlPattern::Value => UIProperty::IsValuePatternAvailable,
+        ControlPattern::RangeValue => UIProperty::IsRangeValuePatternAvailable,
+        ControlPattern::ExpandCollapse => UIProperty::IsExpandCollapsePatternAvailable,
+        ControlPattern::Selection => UIProperty::IsSelectionPatternAvailable,
+        ControlPattern::SelectionItem => UIProperty::IsSelectionItemPatternAvailable,
+        ControlPattern::Grid => UIProperty::IsGridPatternAvailable,
+        ControlPattern::GridItem => UIProperty::IsGridItemPatternAvailable,
+        ControlPattern::Table => UIProperty::IsTablePatternAvailable,
+        ControlPattern::TableItem => UIProperty::IsTableItemPatternAvailable,
+        ControlPattern::Scroll => UIProperty::IsScrollPatternAvailable,
+        ControlPattern::ScrollItem => UIProperty::IsScrollItemPatternAvailable,
+        ControlPattern::Text => UIProperty::IsTextPatternAvailable,
+        ControlPattern::Window => UIProperty::IsWindowPatternAvailable,
+        ControlPattern::Transform => UIProperty::IsTransformPatternAvailable,
+        ControlPattern::Dock => UIProperty::IsDockPatternAvailable,
+        ControlPattern::MultipleView => UIProperty::IsMultipleViewPatternAvailable,
+        ControlPattern::ItemContainer => UIProperty::IsItemContainerPatternAvailable,
+        ControlPattern::LegacyIAccessible => UIProperty::IsLegacyIAccessiblePatternAvailable,
+    }
+}
+
 /// Centralized function to map string attribute keys to UIProperty variants
 pub(crate) fn string_to_ui_property(key: &str) -> Option<UIProperty> {
     match key {
@@ -10,20 +10,60 @@
 
 use std::collections::HashMap;
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use tokio::sync::Mutex;
 use tracing::{debug, info, warn};
-use windows::Win32::Foundation::{HWND, LPARAM, WPARAM};
+use windows::Win32::Foundation::{COLORREF, HWND, LPARAM, WPARAM};
 use windows::Win32::System::Threading::{
     OpenProcess, QueryFullProcessImageNameW, PROCESS_NAME_WIN32, PROCESS_QUERY_LIMITED_INFORMATION,
 };
 use windows::Win32::UI::WindowsAndMessaging::{
-    GetTopWindow, GetWindow, GetWindowLongPtrW, GetWindowPlacement, GetWindowThreadProcessId,
-    IsIconic, IsWindowVisible, IsZoomed, SendMessageTimeoutW, SetWindowPlacement, ShowWindow,
-    GWL_EXSTYLE, GW_HWNDNEXT, SMTO_ABORTIFHUNG, SMTO_BLOCK, SW_MAXIMIZE, SW_MINIMIZE, SW_RESTORE,
-    WINDOWPLACEMENT, WM_GETTEXT, WS_EX_TOPMOST,
+    GetClassNameW, GetForegroundWindow, GetLayeredWindowAttributes, GetTopWindow, GetWindow,
+    GetWindowLongPtrW, GetWindowPlacement, GetWindowRect, GetWindowThreadProcessId, IsIconic,
+    IsWindowVisible, IsZoomed, SendMessageTimeoutW, SetLayeredWindowAttributes, SetWindowLongPtrW,
+    SetWindowPlacement, SetWindowPos, ShowWindow, GWL_EXSTYLE, GWL_STYLE, GW_HWNDNEXT, GW_OWNER,
+    HWND_NOTOPMOST, HWND_TOPMOST, LAYERED_WINDOW_ATTRIBUTES_FLAGS, LWA_ALPHA, SMTO_ABORTIFHUNG,
+    SMTO_BLOCK, SWP_NOACTIVATE, SWP_NOMOVE, SWP_NOSIZE, SW_MAXIMIZE, SW_MINIMIZE, SW_RESTORE,
+    WINDOWPLACEMENT, WM_GETTEXT, WS_CAPTION, WS_EX_LAYERED, WS_EX_TOOLWINDOW, WS_EX_TOPMOST,
+    WS_EX_TRANSPARENT, WS_POPUP,
 };
 
+/// Window classes of menus, tooltips and dropdown lists that float over other windows
+const TRANSIENT_POPUP_CLASSES: &[&str] = &[
+    "tooltips_class32",
+    "#32768", // context and drop-down menus
+    "Auto-Suggest Dropdown",
+    "ComboLBox",
+    "DropDown",
+    "Xaml_WindowedPopupClass",
+];
+
+/// Whether a window looks like a transient popup (tooltip, menu, autocomplete list) that
+/// could intercept a click meant for the window underneath it
+pub(crate) fn is_transient_popup(
+    class_name: &str,
+    style: u32,
+    ex_style: u32,
+    has_owner: bool,
+) -> bool {
+    // Click-through windows, such as our own overlays, never intercept a click
+    if ex_style & WS_EX_TRANSPARENT.0 != 0 {
+        return false;
+    }
+    if TRANSIENT_POPUP_CLASSES
+        .iter()
+        .any(|class| class.eq_ignore_ascii_case(class_name))
+    {
+        return true;
+    }
+    // Owned, caption-less tool windows: custom tooltips, autocomplete and dropdown lists.
+    // The owner check keeps the taskbar and other shell windows out
+    has_owner
+        && style & WS_POPUP.0 != 0
+        && style & WS_CAPTION.0 == 0
+        && ex_style & WS_EX_TOOLWINDOW.0 != 0
+}
+
 /// Information about a window
 #[derive(Clone, Debug)]
 pub struct WindowInfo {
@@ -45,6 +85,17 @@ pub struct WindowInfo {
     pub placement: WindowPlacement,
     /// Window title
     pub title: String,
+    /// Window rectangle in screen coordinates (x, y, width, height)
+    pub bounds: (f64, f64, f64, f64),
+    /// Whether the window has the WS_VISIBLE style
+    pub is_visible: bool,
+    /// Whether this is the foreground window
+    pub is_foreground: bool,
+    /// Window class name (e.g., "Notepad", "tooltips_class32")
+    pub class_name: String,
+    /// Whether the window looks like a tooltip, menu, autocomplete list or other
+    /// transient popup that could intercept clicks
+    pub is_transient_popup: bool,
 }
 
 /// Window placement information for state restoration
@@ -115,8 +166,29 @@ pub struct WindowCache {
     pub minimized_windows: Vec<isize>,
     /// Target window that was maximized (needs restoration too)
     pub target_window: Option<isize>,
-    /// Timestamp of last cache update
-    pub last_updated: Instant,
+    /// Timestamp of last cache update; None until the first refresh finishes
+    pub last_updated: Option<Instant>,
+}
+
+/// Keeps a background window-cache refresh running; dropping it stops the refresh.
+///
+/// Returned by [`WindowManager::start_background_refresh`].
+pub struct WindowCacheRefreshGuard {
+    task: tokio::task::JoinHandle<()>,
+    interval: Duration,
+}
+
+impl WindowCacheRefreshGuard {
+    /// How often the cache is refreshed
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+}
+
+impl Drop for WindowCacheRefreshGuard {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
 }
 
 /// Window manager for controlling window states
@@ -146,13 +218,48 @@ impl WindowManager {
                 original_states: Vec::new(),
                 minimized_windows: Vec::new(),
                 target_window: None,
-                last_updated: Instant::now(),
+                last_updated: None,
             })),
         }
     }
 
     /// Update window cache with current window information
     pub async fn update_window_cache(&self) -> Result<(), String> {
+        Self::refresh_cache(&self.window_cache).await
+    }
+
+    /// Refresh the window cache every `interval` on a background task, so callers can
+    /// read a warm cache instead of updating it on the critical path of each action.
+    ///
+    /// The refresh runs until the returned guard is dropped. Must be called from within
+    /// a Tokio runtime.
+    pub fn start_background_refresh(&self, interval: Duration) -> WindowCacheRefreshGuard {
+        let cache = Arc::clone(&self.window_cache);
+        let task = tokio::spawn(async move {
+            loop {
+                if let Err(e) = Self::refresh_cache(&cache).await {
+                    warn!("Background window cache refresh failed: {}", e);
+                }
+                tokio::time::sleep(interval).await;
+            }
+        });
+        info!(
+            "Started background window cache refresh every {:?}",
+            interval
+        );
+        WindowCacheRefreshGuard { task, interval }
+    }
+
+    /// Time since the window cache was last updated; None if it never has been
+    pub async fn cache_age(&self) -> Option<Duration> {
+        self.window_cache
+            .lock()
+            .await
+            .last_updated
+            .map(|updated| updated.elapsed())
+    }
+
+    async fn refresh_cache(window_cache: &Mutex<WindowCache>) -> Result<(), String> {
         let windows = Self::enumerate_windows_in_z_order()?;
 
         // Build process -> windows map (already sorted by Z-order)
@@ -173,10 +280,10 @@ impl WindowManager {
             .cloned()
             .collect();
 
-        let mut cache = self.window_cache.lock().await;
+        let mut cache = window_cache.lock().await;
         cache.process_windows = process_windows;
         cache.visible_windows = visible_windows;
-        cache.last_updated = Instant::now();
+        cache.last_updated = Some(Instant::now());
 
         Ok(())
     }
@@ -348,6 +455,69 @@ impl WindowManager {
         }
     }
 
+    /// Make a window always-on-top (`HWND_TOPMOST`) or return it to the normal Z-order band,
+    /// without moving, resizing or activating it
+    pub async fn set_topmost(&self, hwnd: isize, topmost: bool) -> Result<(), String> {
+        let insert_after = if topmost {
+            HWND_TOPMOST
+        } else {
+            HWND_NOTOPMOST
+        };
+        unsafe {
+            SetWindowPos(
+                HWND(hwnd as *mut _),
+                Some(insert_after),
+                0,
+                0,
+                0,
+                0,
+                SWP_NOMOVE | SWP_NOSIZE | SWP_NOACTIVATE,
+            )
+            .map_err(|e| format!("Failed to set topmost={topmost} on window {hwnd}: {e}"))
+        }
+    }
+
+    /// Whether a window has the WS_EX_TOPMOST style
+    pub async fn is_topmost(&self, hwnd: isize) -> bool {
+        let ex_style = unsafe { GetWindowLongPtrW(HWND(hwnd as *mut _), GWL_EXSTYLE) };
+        (ex_style & WS_EX_TOPMOST.0 as isize) != 0
+    }
+
+    /// Set a window's opacity, from 0 (fully transparent) to 255 (opaque).
+    ///
+    /// Makes the window layered (WS_EX_LAYERED) first if it isn't already.
+    pub async fn set_window_opacity(&self, hwnd: isize, alpha: u8) -> Result<(), String> {
+        unsafe {
+            let hwnd_win = HWND(hwnd as *mut _);
+            let ex_style = GetWindowLongPtrW(hwnd_win, GWL_EXSTYLE);
+            if ex_style & WS_EX_LAYERED.0 as isize == 0 {
+                SetWindowLongPtrW(hwnd_win, GWL_EXSTYLE, ex_style | WS_EX_LAYERED.0 as isize);
+            }
+            SetLayeredWindowAttributes(hwnd_win, COLORREF(0), alpha, LWA_ALPHA)
+                .map_err(|e| format!("Failed to set opacity {alpha} on window {hwnd}: {e}"))
+        }
+    }
+
+    /// A window's opacity from 0 (fully transparent) to 255 (opaque). Windows that aren't
+    /// layered, or are layered without an alpha value, are reported as opaque.
+    pub async fn get_opacity(&self, hwnd: isize) -> Result<u8, String> {
+        unsafe {
+            let hwnd_win = HWND(hwnd as *mut _);
+            if GetWindowLongPtrW(hwnd_win, GWL_EXSTYLE) & WS_EX_LAYERED.0 as isize == 0 {
+                return Ok(255);
+            }
+            let mut alpha = 255u8;
+            let mut flags = LAYERED_WINDOW_ATTRIBUTES_FLAGS(0);
+            GetLayeredWindowAttributes(hwnd_win, None, Some(&mut alpha), Some(&mut flags))
+                .map_err(|e| format!("Failed to read opacity of window {hwnd}: {e}"))?;
+            Ok(if flags.contains(LWA_ALPHA) {
+                alpha
+            } else {
+                255
+            })
+        }
+    }
+
     /// Restore windows that were minimized (only always-on-top windows) and target window
     pub async fn restore_all_windows(&self) -> Result<u32, String> {
         let cache = self.window_cache.lock().await;
@@ -552,12 +722,39 @@ impl WindowManager {
         cache.target_window = None;
     }
 
+    /// Every top-level window owned by `pid` (main window, dialogs, tool windows,
+    /// tooltips and popups) in Z-order, topmost first, including hidden ones
+    pub fn list_windows_for_pid(pid: u32) -> Result<Vec<WindowInfo>, String> {
+        Self::enumerate_top_level_windows(true, Some(pid))
+    }
+
+    /// Visible tooltips, menus, autocomplete lists and other transient popups, topmost first
+    pub fn list_transient_popups() -> Result<Vec<WindowInfo>, String> {
+        Ok(Self::enumerate_top_level_windows(false, None)?
+            .into_iter()
+            .filter(|w| {
+                w.is_transient_popup && !w.is_minimized && w.bounds.2 > 0.0 && w.bounds.3 > 0.0
+            })
+            .collect())
+    }
+
     /// Enumerate all windows in Z-order (topmost first)
     fn enumerate_windows_in_z_order() -> Result<Vec<WindowInfo>, String> {
+        Self::enumerate_top_level_windows(false, None)
+    }
+
+    /// Enumerate top-level windows in Z-order (topmost first), optionally keeping hidden ones.
+    /// With `only_pid`, other processes' windows are skipped before their title, class and
+    /// process name are queried.
+    fn enumerate_top_level_windows(
+        include_hidden: bool,
+        only_pid: Option<u32>,
+    ) -> Result<Vec<WindowInfo>, String> {
         let mut windows = Vec::new();
         let mut z_order = 0u32;
 
         unsafe {
+            let foreground = GetForegroundWindow();
             let mut hwnd = match GetTopWindow(None) {
                 Ok(h) => h,
                 Err(_) => return Ok(windows),
@@ -569,7 +766,8 @@ impl WindowManager {
                 }
 
                 // Skip invisible windows
-                if !IsWindowVisible(hwnd).as_bool() {
+                let is_visible = IsWindowVisible(hwnd).as_bool();
+                if !is_visible && !include_hidden {
                     hwnd = match GetWindow(hwnd, GW_HWNDNEXT) {
                         Ok(h) => h,
                         Err(_) => break,
@@ -580,7 +778,7 @@ impl WindowManager {
                 let mut pid = 0u32;
                 GetWindowThreadProcessId(hwnd, Some(&mut pid));
 
-                if pid > 0 {
+                if pid > 0 && only_pid.is_none_or(|only| only == pid) {
                     let process_name = Self::get_process_name(pid).unwrap_or_default();
                     let title = Self::get_window_title(hwnd);
                     let is_minimized = IsIconic(hwnd).as_bool();
@@ -590,12 +788,26 @@ impl WindowManager {
                     let ex_style = GetWindowLongPtrW(hwnd, GWL_EXSTYLE);
                     let is_always_on_top = (ex_style & WS_EX_TOPMOST.0 as isize) != 0;
 
+                    let mut class_buf = [0u16; 256];
+                    let class_len = GetClassNameW(hwnd, &mut class_buf).max(0) as usize;
+                    let class_name = String::from_utf16_lossy(&class_buf[..class_len]);
+                    let has_owner = GetWindow(hwnd, GW_OWNER).is_ok_and(|owner| !owner.0.is_null());
+                    let is_transient_popup = is_transient_popup(
+                        &class_name,
+                        GetWindowLongPtrW(hwnd, GWL_STYLE) as u32,
+                        ex_style as u32,
+                        has_owner,
+                    );
+
                     let mut placement = WINDOWPLACEMENT {
                         length: std::mem::size_of::<WINDOWPLACEMENT>() as u32,
                         ..Default::default()
                     };
                     let _ = GetWindowPlacement(hwnd, &mut placement);
 
+                    let mut rect = windows::Win32::Foundation::RECT::default();
+                    let _ = GetWindowRect(hwnd, &mut rect);
+
                     windows.push(WindowInfo {
                         hwnd: hwnd.0 as isize,
                         process_name: process_name.clone(),
@@ -606,6 +818,16 @@ impl WindowManager {
                         is_always_on_top,
                         placement: placement.into(),
                         title,
+                        bounds: (
+                            rect.left as f64,
+                            rect.top as f64,
+                            (rect.right - rect.left) as f64,
+                            (rect.bottom - rect.top) as f64,
+                        ),
+                        is_visible,
+                        is_foreground: hwnd == foreground,
+                        class_name,
+                        is_transient_popup,
                     });
                 }
 
@@ -198,6 +198,10 @@ mod performance_benchmarks {
                 show_overlay: false,
                 overlay_display_mode: None,
                 from_selector: None,
+                include_shortcuts: false,
+                include_relationships: false,
+                stable_ids: false,
+                parallel_subtrees: None,
             };
 
             match engine.get_window_tree(pid, Some(&window_title), config) {
@@ -155,6 +155,156 @@ fn test_get_process_name_by_pid_system_process() {
     }
 }
 
+#[test]
+fn test_foreground_window_is_a_top_level_window() {
+    let engine = match WindowsEngine::new(false, false) {
+        Ok(engine) => engine,
+        Err(_) => {
+            println!("Cannot create WindowsEngine, skipping foreground window test");
+            return;
+        }
+    };
+
+    // CI sessions without an interactive desktop have no foreground window
+    match engine.get_foreground_window() {
+        Ok(window) => {
+            let attrs = window.attributes();
+            println!(
+                "Foreground window - Role: {}, Name: {:?}",
+                attrs.role, attrs.name
+            );
+            assert!(window.process_id().is_ok(), "Window should have a process");
+        }
+        Err(e) => println!("No foreground window: {e} (this might be expected)"),
+    }
+}
+
+#[test]
+fn test_interactable_ancestor_of_element_at_point() {
+    let engine = match WindowsEngine::new(false, false) {
+        Ok(engine) => engine,
+        Err(_) => {
+            println!("Cannot create WindowsEngine, skipping element at point test");
+            return;
+        }
+    };
+
+    // Probe the middle of the foreground window, whatever it is
+    let Ok((x, y, width, height)) = engine.get_foreground_window().and_then(|w| w.bounds()) else {
+        println!("No foreground window, skipping element at point test");
+        return;
+    };
+    let hit = match engine.get_element_at_point(x + width / 2.0, y + height / 2.0) {
+        Ok(hit) => hit,
+        Err(e) => {
+            println!("No element at point: {e} (this might be expected)");
+            return;
+        }
+    };
+    println!(
+        "Element at point - Role: {}, Name: {:?}",
+        hit.role(),
+        hit.name()
+    );
+
+    if let Some(target) = crate::find_interactable_ancestor(&hit) {
+        let patterns = target.supported_patterns();
+        println!(
+            "Interactable ancestor - Role: {}, Patterns: {patterns:?}",
+            target.role()
+        );
+        assert!(patterns
+            .iter()
+            .any(|p| crate::INTERACTABLE_PATTERNS.contains(p)));
+    }
+}
+
+#[test]
+fn test_supported_patterns_of_foreground_window() {
+    let engine = match WindowsEngine::new(false, false) {
+        Ok(engine) => engine,
+        Err(_) => {
+            println!("Cannot create WindowsEngine, skipping supported patterns test");
+            return;
+        }
+    };
+    let window = match engine.get_foreground_window() {
+        Ok(window) => window,
+        Err(e) => {
+            println!("No foreground window: {e} (this might be expected)");
+            return;
+        }
+    };
+
+    let patterns = window.supported_patterns();
+    println!("Foreground window patterns: {patterns:?}");
+    if window.role() == "Window" {
+        assert!(patterns.contains(&crate::ControlPattern::Window));
+    }
+    assert_eq!(
+        serde_json::to_value(crate::ControlPattern::ExpandCollapse).unwrap(),
+        "ExpandCollapse"
+    );
+}
+
+#[test]
+fn test_window_tree_by_hwnd() {
+    let engine = match WindowsEngine::new(false, false) {
+        Ok(engine) => engine,
+        Err(_) => {
+            println!("Cannot create WindowsEngine, skipping window tree by HWND test");
+            return;
+        }
+    };
+    let Ok(window) = engine.get_foreground_window() else {
+        println!("No foreground window, skipping window tree by HWND test");
+        return;
+    };
+    let Ok(hwnd) = window.get_native_window_handle() else {
+        println!("Foreground window has no HWND, skipping window tree by HWND test");
+        return;
+    };
+
+    let config = crate::platforms::TreeBuildConfig {
+        max_depth: Some(2),
+        ..Default::default()
+    };
+    let tree = engine
+        .get_window_tree_by_hwnd(hwnd, config)
+        .expect("tree for a live HWND");
+    assert_eq!(tree.attributes.name, window.name());
+
+    assert!(engine
+        .get_window_tree_by_hwnd(0x7FFF_FFF0, Default::default())
+        .is_err());
+}
+
+#[test]
+fn test_get_property_by_uia_id() {
+    let engine = match WindowsEngine::new(false, false) {
+        Ok(engine) => engine,
+        Err(_) => {
+            println!("Cannot create WindowsEngine, skipping get_property test");
+            return;
+        }
+    };
+    let root = engine.get_root_element();
+
+    // UIA_NamePropertyId and UIA_IsEnabledPropertyId
+    let name = root.get_property(30005).expect("Name is always readable");
+    assert_eq!(
+        name.as_str().unwrap_or_default(),
+        root.name().unwrap_or_default()
+    );
+    let enabled = root
+        .get_property(30010)
+        .expect("IsEnabled is always readable");
+    assert!(enabled.is_boolean());
+
+    // Not a UIA property id
+    assert!(root.get_property(1).is_err());
+}
+
 #[test]
 fn test_open_regular_application() {
     let engine = match WindowsEngine::new(false, false) {
@@ -451,6 +601,10 @@ fn test_unified_tree_api_with_config() {
         show_overlay: false,
         overlay_display_mode: None,
         from_selector: None,
+        include_shortcuts: false,
+        include_relationships: false,
+        stable_ids: false,
+        parallel_subtrees: None,
     };
 
     let start_fast = std::time::Instant::now();
@@ -470,6 +624,10 @@ fn test_unified_tree_api_with_config() {
         show_overlay: false,
         overlay_display_mode: None,
         from_selector: None,
+        include_shortcuts: false,
+        include_relationships: false,
+        stable_ids: false,
+        parallel_subtrees: None,
     };
 
     let start_full = std::time::Instant::now();
@@ -532,6 +690,52 @@ fn test_unified_tree_api_with_config() {
     let _ = app.close();
 }
 
+#[test]
+fn test_parallel_subtrees_matches_serial_build() {
+    let engine = match WindowsEngine::new(false, false) {
+        Ok(engine) => engine,
+        Err(_) => {
+            println!("Cannot create WindowsEngine, skipping parallel tree test");
+            return;
+        }
+    };
+
+    // The desktop root usually has enough top-level windows to be split across workers
+    let root = engine.get_root_element();
+    let config = |parallel_subtrees| crate::platforms::TreeBuildConfig {
+        max_depth: Some(1),
+        parallel_subtrees,
+        ..Default::default()
+    };
+
+    let serial = engine.get_tree_from_element(&root, config(None));
+    let parallel = engine.get_tree_from_element(&root, config(Some(4)));
+    let (serial, parallel) = match (serial, parallel) {
+        (Ok(serial), Ok(parallel)) => (serial, parallel),
+        (serial, parallel) => {
+            println!("Cannot build desktop tree ({serial:?}, {parallel:?}), skipping test");
+            return;
+        }
+    };
+
+    let roles = |node: &crate::UINode| -> Vec<String> {
+        node.children
+            .iter()
+            .map(|child| child.attributes.role.clone())
+            .collect()
+    };
+    println!(
+        "Serial: {} children, parallel: {} children",
+        serial.children.len(),
+        parallel.children.len()
+    );
+    assert_eq!(
+        roles(&serial),
+        roles(&parallel),
+        "Parallel build should keep children in order"
+    );
+}
+
 #[test]
 #[ignore] // does not work in ci cd it seems
 fn test_window_transparency() {
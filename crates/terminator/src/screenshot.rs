@@ -1,4 +1,4 @@
-use crate::Monitor;
+use crate::{Monitor, Rect};
 use base64::{engine::general_purpose, Engine};
 use image::imageops::FilterType;
 use image::{ImageBuffer, ImageFormat, Rgba};
@@ -7,6 +7,31 @@ use std::io::{Cursor, Seek, Write};
 /// Default maximum dimension for screenshot resizing (width or height)
 pub const DEFAULT_MAX_DIMENSION: u32 = 1920;
 
+/// PNG compression effort, trading encode time against file size.
+///
+/// Per-step screenshots of large displays are CPU-bound on encoding, so
+/// latency-sensitive callers should pick `Fast`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CompressionLevel {
+    /// Quickest encode, largest files
+    Fast,
+    /// Balanced encode time and size; what `to_png` and friends use
+    #[default]
+    Default,
+    /// Smallest files, slowest encode
+    Best,
+}
+
+impl From<CompressionLevel> for image::codecs::png::CompressionType {
+    fn from(level: CompressionLevel) -> Self {
+        match level {
+            CompressionLevel::Fast => Self::Fast,
+            CompressionLevel::Default => Self::Default,
+            CompressionLevel::Best => Self::Best,
+        }
+    }
+}
+
 /// Holds the screenshot data
 #[derive(Debug, Clone)]
 pub struct ScreenshotResult {
@@ -83,8 +108,16 @@ impl ScreenshotResult {
     /// # Returns
     /// PNG-encoded bytes
     pub fn to_png(&self) -> Result<Vec<u8>, ScreenshotError> {
+        self.to_png_with(CompressionLevel::default())
+    }
+
+    /// Like [`ScreenshotResult::to_png`], with an explicit compression effort.
+    pub fn to_png_with(
+        &self,
+        png_compression: CompressionLevel,
+    ) -> Result<Vec<u8>, ScreenshotError> {
         let rgba_data = self.bgra_to_rgba();
-        encode_rgba_to_png(&rgba_data, self.width, self.height)
+        encode_rgba_to_png(&rgba_data, self.width, self.height, png_compression)
     }
 
     /// Encode the screenshot as PNG bytes with optional resizing.
@@ -98,27 +131,18 @@ impl ScreenshotResult {
     /// # Returns
     /// PNG-encoded bytes (potentially resized)
     pub fn to_png_resized(&self, max_dimension: Option<u32>) -> Result<Vec<u8>, ScreenshotError> {
-        let max_dim = max_dimension.unwrap_or(DEFAULT_MAX_DIMENSION);
-        let rgba_data = self.bgra_to_rgba();
-
-        // Check if resize is needed
-        if self.width <= max_dim && self.height <= max_dim {
-            return encode_rgba_to_png(&rgba_data, self.width, self.height);
-        }
-
-        // Calculate new dimensions maintaining aspect ratio
-        let scale = (max_dim as f32 / self.width.max(self.height) as f32).min(1.0);
-        let new_width = (self.width as f32 * scale).round() as u32;
-        let new_height = (self.height as f32 * scale).round() as u32;
-
-        // Create image buffer and resize
-        let img = ImageBuffer::<Rgba<u8>, _>::from_raw(self.width, self.height, rgba_data)
-            .ok_or_else(|| {
-                ScreenshotError::ImageProcessing("Failed to create image buffer".into())
-            })?;
+        self.to_png_resized_with(max_dimension, CompressionLevel::default())
+    }
 
-        let resized = image::imageops::resize(&img, new_width, new_height, FilterType::Lanczos3);
-        encode_rgba_to_png(&resized.into_raw(), new_width, new_height)
+    /// Like [`ScreenshotResult::to_png_resized`], with an explicit compression effort;
+    /// `Fast` keeps per-step captures cheap.
+    pub fn to_png_resized_with(
+        &self,
+        max_dimension: Option<u32>,
+        png_compression: CompressionLevel,
+    ) -> Result<Vec<u8>, ScreenshotError> {
+        let (rgba_data, width, height) = self.rgba_resized(max_dimension)?;
+        encode_rgba_to_png(&rgba_data, width, height, png_compression)
     }
 
     /// Encode the screenshot as JPEG bytes with optional resizing.
@@ -138,19 +162,42 @@ impl ScreenshotResult {
         max_dimension: Option<u32>,
         quality: Option<u8>,
     ) -> Result<Vec<u8>, ScreenshotError> {
-        let max_dim = max_dimension.unwrap_or(DEFAULT_MAX_DIMENSION);
         let jpeg_quality = quality.unwrap_or(DEFAULT_JPEG_QUALITY);
+        let (rgba_data, width, height) = self.rgba_resized(max_dimension)?;
+        encode_rgba_to_jpeg(&rgba_data, width, height, jpeg_quality)
+    }
+
+    /// Encode the screenshot as lossless WebP bytes with optional resizing.
+    ///
+    /// If the image exceeds `max_dimension` in either width or height,
+    /// it will be resized while maintaining aspect ratio.
+    ///
+    /// # Arguments
+    /// * `max_dimension` - Maximum width or height. If None, uses DEFAULT_MAX_DIMENSION (1920).
+    ///
+    /// # Returns
+    /// WebP-encoded bytes (potentially resized)
+    pub fn to_webp_resized(&self, max_dimension: Option<u32>) -> Result<Vec<u8>, ScreenshotError> {
+        let (rgba_data, width, height) = self.rgba_resized(max_dimension)?;
+        encode_rgba_to_webp(&rgba_data, width, height)
+    }
+
+    /// Convert to RGBA and downscale so neither side exceeds `max_dimension`.
+    /// Returns the RGBA data along with its (possibly new) width and height.
+    fn rgba_resized(
+        &self,
+        max_dimension: Option<u32>,
+    ) -> Result<(Vec<u8>, u32, u32), ScreenshotError> {
+        let max_dim = max_dimension.unwrap_or(DEFAULT_MAX_DIMENSION);
         let rgba_data = self.bgra_to_rgba();
 
         // Check if resize is needed
         if self.width <= max_dim && self.height <= max_dim {
-            return encode_rgba_to_jpeg(&rgba_data, self.width, self.height, jpeg_quality);
+            return Ok((rgba_data, self.width, self.height));
         }
 
         // Calculate new dimensions maintaining aspect ratio
-        let scale = (max_dim as f32 / self.width.max(self.height) as f32).min(1.0);
-        let new_width = (self.width as f32 * scale).round() as u32;
-        let new_height = (self.height as f32 * scale).round() as u32;
+        let (new_width, new_height) = self.resized_dimensions(max_dim);
 
         // Create image buffer and resize
         let img = ImageBuffer::<Rgba<u8>, _>::from_raw(self.width, self.height, rgba_data)
@@ -159,7 +206,7 @@ impl ScreenshotResult {
             })?;
 
         let resized = image::imageops::resize(&img, new_width, new_height, FilterType::Lanczos3);
-        encode_rgba_to_jpeg(&resized.into_raw(), new_width, new_height, jpeg_quality)
+        Ok((resized.into_raw(), new_width, new_height))
     }
 
     /// Encode the screenshot as base64-encoded PNG string.
@@ -187,7 +234,16 @@ impl ScreenshotResult {
         &self,
         max_dimension: Option<u32>,
     ) -> Result<String, ScreenshotError> {
-        let png_data = self.to_png_resized(max_dimension)?;
+        self.to_base64_png_resized_with(max_dimension, CompressionLevel::default())
+    }
+
+    /// Like [`ScreenshotResult::to_base64_png_resized`], with an explicit compression effort.
+    pub fn to_base64_png_resized_with(
+        &self,
+        max_dimension: Option<u32>,
+        png_compression: CompressionLevel,
+    ) -> Result<String, ScreenshotError> {
+        let png_data = self.to_png_resized_with(max_dimension, png_compression)?;
         Ok(general_purpose::STANDARD.encode(&png_data))
     }
 
@@ -208,6 +264,34 @@ impl ScreenshotResult {
         (new_width, new_height)
     }
 
+    /// Copy out the `width` x `height` region starting at (`x`, `y`).
+    ///
+    /// The region is clipped to the image, so the result may be smaller than requested
+    /// (or empty if the region lies entirely outside the image).
+    pub fn crop(&self, x: u32, y: u32, width: u32, height: u32) -> ScreenshotResult {
+        let x = x.min(self.width);
+        let y = y.min(self.height);
+        let width = width.min(self.width - x);
+        let height = height.min(self.height - y);
+
+        let row_len = (width * 4) as usize;
+        let mut image_data = Vec::with_capacity(row_len * height as usize);
+        for row in y..y + height {
+            let start = ((row * self.width + x) * 4) as usize;
+            match self.image_data.get(start..start + row_len) {
+                Some(pixels) => image_data.extend_from_slice(pixels),
+                None => break,
+            }
+        }
+
+        ScreenshotResult {
+            image_data,
+            width,
+            height,
+            monitor: self.monitor.clone(),
+        }
+    }
+
     /// Draw a cursor arrow on the screenshot at the specified position.
     ///
     /// The cursor is drawn as a red arrow with white outline, scaled based on image size.
@@ -340,6 +424,274 @@ impl ScreenshotResult {
     }
 }
 
+/// Find the bounding box of all pixels that differ between two screenshots.
+///
+/// Pixels count as changed when any channel differs by more than `tolerance`, which
+/// absorbs anti-aliasing and compression noise. If the images differ in size the
+/// whole `after` image is treated as changed.
+///
+/// # Returns
+/// `(x, y, width, height)` of the changed region in image coordinates, or `None` if
+/// nothing changed
+pub fn changed_region(
+    before: &ScreenshotResult,
+    after: &ScreenshotResult,
+    tolerance: u8,
+) -> Option<(u32, u32, u32, u32)> {
+    if before.width != after.width
+        || before.height != after.height
+        || before.image_data.len() != after.image_data.len()
+    {
+        return (after.width > 0 && after.height > 0).then_some((0, 0, after.width, after.height));
+    }
+
+    let mut min_x = u32::MAX;
+    let mut min_y = u32::MAX;
+    let mut max_x = 0;
+    let mut max_y = 0;
+
+    let pixels = before
+        .image_data
+        .chunks_exact(4)
+        .zip(after.image_data.chunks_exact(4));
+    for (i, (a, b)) in pixels.enumerate() {
+        if a.iter().zip(b).any(|(a, b)| a.abs_diff(*b) > tolerance) {
+            let x = i as u32 % after.width;
+            let y = i as u32 / after.width;
+            min_x = min_x.min(x);
+            min_y = min_y.min(y);
+            max_x = max_x.max(x);
+            max_y = max_y.max(y);
+        }
+    }
+
+    (min_x != u32::MAX).then(|| (min_x, min_y, max_x - min_x + 1, max_y - min_y + 1))
+}
+
+/// RGB color used when drawing annotations onto screenshots
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Color {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl Color {
+    pub const RED: Color = Color::new(255, 0, 0);
+    pub const GREEN: Color = Color::new(0, 200, 0);
+    pub const BLUE: Color = Color::new(0, 90, 255);
+    pub const YELLOW: Color = Color::new(255, 215, 0);
+    pub const MAGENTA: Color = Color::new(255, 0, 255);
+    pub const CYAN: Color = Color::new(0, 220, 220);
+    pub const WHITE: Color = Color::new(255, 255, 255);
+    pub const BLACK: Color = Color::new(0, 0, 0);
+
+    pub const fn new(r: u8, g: u8, b: u8) -> Self {
+        Self { r, g, b }
+    }
+
+    /// Pixel value in the BGRA layout used by `ScreenshotResult::image_data`
+    fn to_bgra(self) -> Rgba<u8> {
+        Rgba([self.b, self.g, self.r, 255])
+    }
+
+    /// Black or white, whichever reads better on top of this color
+    fn contrasting(self) -> Color {
+        let luma = 0.299 * self.r as f32 + 0.587 * self.g as f32 + 0.114 * self.b as f32;
+        if luma > 150.0 {
+            Color::BLACK
+        } else {
+            Color::WHITE
+        }
+    }
+}
+
+/// Draw labelled boxes onto a copy of a screenshot.
+///
+/// Useful for checking that bounding boxes returned by vision models (OmniParser,
+/// Gemini, OCR) line up with what is actually on screen. Rectangles are in image
+/// coordinates, i.e. relative to the top-left of the screenshot. Labels are drawn
+/// with a small built-in bitmap font above each box, or inside it when there is no
+/// room above. Boxes that fall partly outside the image are clipped.
+///
+/// # Arguments
+/// * `image` - The screenshot to annotate (left untouched)
+/// * `annotations` - `(rect, label, color)` triples; an empty label draws just the box
+///
+/// # Returns
+/// A new `ScreenshotResult` with the annotations drawn
+pub fn annotate(
+    image: &ScreenshotResult,
+    annotations: &[(Rect, String, Color)],
+) -> ScreenshotResult {
+    let Some(mut canvas) =
+        ImageBuffer::<Rgba<u8>, _>::from_raw(image.width, image.height, image.image_data.clone())
+    else {
+        tracing::warn!(
+            "[annotate] image data does not match {}x{}, returning screenshot unannotated",
+            image.width,
+            image.height
+        );
+        return image.clone();
+    };
+
+    // Scale strokes and text with the image so annotations stay legible on 4K captures
+    let stroke = (image.width.max(image.height) / 800).max(2) as i64;
+    let text_scale = (image.width.max(image.height) / 600).max(2) as i64;
+
+    for (rect, label, color) in annotations {
+        let x0 = rect.x.round() as i64;
+        let y0 = rect.y.round() as i64;
+        let x1 = rect.right().round() as i64;
+        let y1 = rect.bottom().round() as i64;
+        let pixel = color.to_bgra();
+
+        fill_rect(&mut canvas, x0, y0, x1, y0 + stroke, pixel);
+        fill_rect(&mut canvas, x0, y1 - stroke, x1, y1, pixel);
+        fill_rect(&mut canvas, x0, y0, x0 + stroke, y1, pixel);
+        fill_rect(&mut canvas, x1 - stroke, y0, x1, y1, pixel);
+
+        if label.is_empty() {
+            continue;
+        }
+
+        let padding = text_scale;
+        let label_w = text_width(label, text_scale) + padding * 2;
+        let label_h = GLYPH_HEIGHT * text_scale + padding * 2;
+        let label_y = if y0 - label_h >= 0 { y0 - label_h } else { y0 };
+
+        fill_rect(
+            &mut canvas,
+            x0,
+            label_y,
+            x0 + label_w,
+            label_y + label_h,
+            pixel,
+        );
+        draw_text(
+            &mut canvas,
+            label,
+            x0 + padding,
+            label_y + padding,
+            text_scale,
+            color.contrasting().to_bgra(),
+        );
+    }
+
+    ScreenshotResult {
+        image_data: canvas.into_raw(),
+        width: image.width,
+        height: image.height,
+        monitor: image.monitor.clone(),
+    }
+}
+
+const GLYPH_WIDTH: i64 = 3;
+const GLYPH_HEIGHT: i64 = 5;
+
+/// Fill the half-open pixel range `[x0, x1) x [y0, y1)`, clipped to the canvas
+fn fill_rect(
+    canvas: &mut ImageBuffer<Rgba<u8>, Vec<u8>>,
+    x0: i64,
+    y0: i64,
+    x1: i64,
+    y1: i64,
+    pixel: Rgba<u8>,
+) {
+    let (w, h) = (canvas.width() as i64, canvas.height() as i64);
+    for y in y0.max(0)..y1.min(h) {
+        for x in x0.max(0)..x1.min(w) {
+            canvas.put_pixel(x as u32, y as u32, pixel);
+        }
+    }
+}
+
+fn text_width(text: &str, scale: i64) -> i64 {
+    let chars = text.chars().count() as i64;
+    (chars * (GLYPH_WIDTH + 1) - 1).max(0) * scale
+}
+
+fn draw_text(
+    canvas: &mut ImageBuffer<Rgba<u8>, Vec<u8>>,
+    text: &str,
+    x: i64,
+    y: i64,
+    scale: i64,
+    pixel: Rgba<u8>,
+) {
+    for (i, c) in text.chars().enumerate() {
+        let gx = x + i as i64 * (GLYPH_WIDTH + 1) * scale;
+        for (row, bits) in glyph(c).iter().enumerate() {
+            for col in 0..GLYPH_WIDTH {
+                if bits & (0b100 >> col) != 0 {
+                    let px = gx + col * scale;
+                    let py = y + row as i64 * scale;
+                    fill_rect(canvas, px, py, px + scale, py + scale, pixel);
+                }
+            }
+        }
+    }
+}
+
+/// 3x5 bitmap glyphs; each row is 3 bits, most significant bit on the left.
+/// Lowercase letters render as uppercase and unknown characters as a solid block.
+fn glyph(c: char) -> [u8; 5] {
+    match c.to_ascii_uppercase() {
+        'A' => [0b010, 0b101, 0b111, 0b101, 0b101],
+        'B' => [0b110, 0b101, 0b110, 0b101, 0b110],
+        'C' => [0b011, 0b100, 0b100, 0b100, 0b011],
+        'D' => [0b110, 0b101, 0b101, 0b101, 0b110],
+        'E' => [0b111, 0b100, 0b110, 0b100, 0b111],
+        'F' => [0b111, 0b100, 0b110, 0b100, 0b100],
+        'G' => [0b011, 0b100, 0b101, 0b101, 0b011],
+        'H' => [0b101, 0b101, 0b111, 0b101, 0b101],
+        'I' => [0b111, 0b010, 0b010, 0b010, 0b111],
+        'J' => [0b001, 0b001, 0b001, 0b101, 0b010],
+        'K' => [0b101, 0b101, 0b110, 0b101, 0b101],
+        'L' => [0b100, 0b100, 0b100, 0b100, 0b111],
+        'M' => [0b101, 0b111, 0b111, 0b101, 0b101],
+        'N' => [0b110, 0b101, 0b101, 0b101, 0b101],
+        'O' => [0b010, 0b101, 0b101, 0b101, 0b010],
+        'P' => [0b110, 0b101, 0b110, 0b100, 0b100],
+        'Q' => [0b010, 0b101, 0b101, 0b110, 0b011],
+        'R' => [0b110, 0b101, 0b110, 0b101, 0b101],
+        'S' => [0b011, 0b100, 0b010, 0b001, 0b110],
+        'T' => [0b111, 0b010, 0b010, 0b010, 0b010],
+        'U' => [0b101, 0b101, 0b101, 0b101, 0b111],
+        'V' => [0b101, 0b101, 0b101, 0b101, 0b010],
+        'W' => [0b101, 0b101, 0b111, 0b111, 0b101],
+        'X' => [0b101, 0b101, 0b010, 0b101, 0b101],
+        'Y' => [0b101, 0b101, 0b010, 0b010, 0b010],
+        'Z' => [0b111, 0b001, 0b010, 0b100, 0b111],
+        '0' => [0b111, 0b101, 0b101, 0b101, 0b111],
+        '1' => [0b010, 0b110, 0b010, 0b010, 0b111],
+        '2' => [0b110, 0b001, 0b010, 0b100, 0b111],
+        '3' => [0b110, 0b001, 0b010, 0b001, 0b110],
+        '4' => [0b101, 0b101, 0b111, 0b001, 0b001],
+        '5' => [0b111, 0b100, 0b110, 0b001, 0b110],
+        '6' => [0b011, 0b100, 0b111, 0b101, 0b111],
+        '7' => [0b111, 0b001, 0b010, 0b010, 0b010],
+        '8' => [0b111, 0b101, 0b111, 0b101, 0b111],
+        '9' => [0b111, 0b101, 0b111, 0b001, 0b110],
+        ' ' => [0b000, 0b000, 0b000, 0b000, 0b000],
+        '.' => [0b000, 0b000, 0b000, 0b000, 0b010],
+        ',' => [0b000, 0b000, 0b000, 0b010, 0b100],
+        ':' => [0b000, 0b010, 0b000, 0b010, 0b000],
+        '-' => [0b000, 0b000, 0b111, 0b000, 0b000],
+        '_' => [0b000, 0b000, 0b000, 0b000, 0b111],
+        '+' => [0b000, 0b010, 0b111, 0b010, 0b000],
+        '=' => [0b000, 0b111, 0b000, 0b111, 0b000],
+        '(' => [0b001, 0b010, 0b010, 0b010, 0b001],
+        ')' => [0b100, 0b010, 0b010, 0b010, 0b100],
+        '/' => [0b001, 0b001, 0b010, 0b100, 0b100],
+        '#' => [0b101, 0b111, 0b101, 0b111, 0b101],
+        '%' => [0b101, 0b001, 0b010, 0b100, 0b101],
+        '\'' => [0b010, 0b010, 0b000, 0b000, 0b000],
+        '?' => [0b110, 0b001, 0b010, 0b000, 0b010],
+        _ => [0b111, 0b111, 0b111, 0b111, 0b111],
+    }
+}
+
 /// Get the current mouse cursor position on screen.
 /// Returns (x, y) in screen coordinates, or None if unable to get position.
 #[cfg(windows)]
@@ -372,6 +724,8 @@ pub enum ScreenshotError {
     PngEncoding(String),
     #[error("JPEG encoding error: {0}")]
     JpegEncoding(String),
+    #[error("WebP encoding error: {0}")]
+    WebpEncoding(String),
 }
 
 /// Helper function to encode RGBA data to PNG bytes
@@ -379,12 +733,17 @@ fn encode_rgba_to_png(
     rgba_data: &[u8],
     width: u32,
     height: u32,
+    compression: CompressionLevel,
 ) -> Result<Vec<u8>, ScreenshotError> {
-    use image::codecs::png::PngEncoder;
+    use image::codecs::png::{self, PngEncoder};
     use image::{ExtendedColorType, ImageEncoder};
 
     let mut png_data = Vec::new();
-    let encoder = PngEncoder::new(Cursor::new(&mut png_data));
+    let encoder = PngEncoder::new_with_quality(
+        Cursor::new(&mut png_data),
+        compression.into(),
+        png::FilterType::Adaptive,
+    );
     encoder
         .write_image(rgba_data, width, height, ExtendedColorType::Rgba8)
         .map_err(|e| ScreenshotError::PngEncoding(e.to_string()))?;
@@ -419,3 +778,21 @@ fn encode_rgba_to_jpeg(
 
     Ok(jpeg_data)
 }
+
+/// Helper function to encode RGBA data to lossless WebP bytes
+fn encode_rgba_to_webp(
+    rgba_data: &[u8],
+    width: u32,
+    height: u32,
+) -> Result<Vec<u8>, ScreenshotError> {
+    use image::codecs::webp::WebPEncoder;
+    use image::{ExtendedColorType, ImageEncoder};
+
+    let mut webp_data = Vec::new();
+    let encoder = WebPEncoder::new_lossless(Cursor::new(&mut webp_data));
+    encoder
+        .write_image(rgba_data, width, height, ExtendedColorType::Rgba8)
+        .map_err(|e| ScreenshotError::WebpEncoding(e.to_string()))?;
+
+    Ok(webp_data)
+}
@@ -1,4 +1,49 @@
-use std::collections::BTreeMap;
+use once_cell::sync::Lazy;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Mutex;
+
+/// Maximum number of parsed selectors kept in the parse cache
+const PARSE_CACHE_CAPACITY: usize = 256;
+
+/// Least-recently-used cache of parsed selectors, keyed by the trimmed selector string
+struct ParseCache {
+    entries: HashMap<String, (Selector, u64)>,
+    tick: u64,
+}
+
+impl ParseCache {
+    fn get(&mut self, key: &str) -> Option<Selector> {
+        self.tick += 1;
+        let tick = self.tick;
+        self.entries.get_mut(key).map(|(selector, last_used)| {
+            *last_used = tick;
+            selector.clone()
+        })
+    }
+
+    fn insert(&mut self, key: String, selector: Selector) {
+        if self.entries.len() >= PARSE_CACHE_CAPACITY && !self.entries.contains_key(&key) {
+            // Evict the least recently used entry
+            if let Some(oldest) = self
+                .entries
+                .iter()
+                .min_by_key(|(_, (_, last_used))| *last_used)
+                .map(|(k, _)| k.clone())
+            {
+                self.entries.remove(&oldest);
+            }
+        }
+        self.tick += 1;
+        self.entries.insert(key, (selector, self.tick));
+    }
+}
+
+static PARSE_CACHE: Lazy<Mutex<ParseCache>> = Lazy::new(|| {
+    Mutex::new(ParseCache {
+        entries: HashMap::new(),
+        tick: 0,
+    })
+});
 
 /// Represents ways to locate a UI element
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -29,6 +74,10 @@ pub enum Selector {
     LocalizedRole(String),
     /// Select by process name (e.g., "chrome", "notepad", "chrome.exe")
     Process(String),
+    /// Select an application's top-level windows by process name, like `Process`, or by
+    /// pid when the value is a number. Starts a chain to scope the search to one app,
+    /// e.g. `app:notepad >> role:Edit`
+    App(String),
     /// Select elements to the right of an anchor element
     RightOf(Box<Selector>),
     /// Select elements to the left of an anchor element
@@ -41,6 +90,9 @@ pub enum Selector {
     Near(Box<Selector>),
     /// Select the n-th element from the matches
     Nth(i32),
+    /// Select a slice of the matches by zero-based index, end exclusive: `range:1..5` keeps
+    /// the 2nd through 5th, `range:2..` everything from the 3rd on
+    Range { start: usize, end: Option<usize> },
     /// Select elements that have at least one descendant matching the inner selector (Playwright-style :has())
     Has(Box<Selector>),
     /// Navigate to parent element (Playwright-style ..)
@@ -51,10 +103,111 @@ pub enum Selector {
     Or(Vec<Selector>),
     /// Logical NOT: element must not match the selector
     Not(Box<Selector>),
+    /// Match elements by a runtime state (e.g., `state:disabled`, `state:focused`)
+    State { state: ElementState, expected: bool },
     /// Represents an invalid selector string, with a reason.
     Invalid(String),
 }
 
+/// Element states that can be checked by `state:` predicates
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ElementState {
+    /// The element accepts user input (`IsEnabled`)
+    Enabled,
+    /// The element has keyboard focus (`HasKeyboardFocus`)
+    Focused,
+    /// The element is scrolled or positioned off screen (`IsOffscreen`)
+    Offscreen,
+}
+
+/// Parse a `range:` value such as `1..5`, `2..` or `..3` into its start and optional end
+fn parse_range(value: &str) -> Result<(usize, Option<usize>), String> {
+    let invalid =
+        || format!("Invalid range selector: 'range:{value}'. Expected 'start..end' or 'start..'");
+    let (start, end) = value.trim().split_once("..").ok_or_else(invalid)?;
+    let start = match start.trim() {
+        "" => 0,
+        start => start.parse().map_err(|_| invalid())?,
+    };
+    let end = match end.trim() {
+        "" => None,
+        end => Some(end.parse::<usize>().map_err(|_| invalid())?),
+    };
+    if end.is_some_and(|end| end < start) {
+        return Err(format!(
+            "Invalid range selector: 'range:{value}' ends before it starts"
+        ));
+    }
+    Ok((start, end))
+}
+
+/// The part of `matches` a `Range` selector keeps. Bounds past the end give a shorter or
+/// empty slice rather than an error.
+pub(crate) fn apply_range<T>(matches: Vec<T>, start: usize, end: Option<usize>) -> Vec<T> {
+    let len = end.map_or(usize::MAX, |end| end - start);
+    matches.into_iter().skip(start).take(len).collect()
+}
+
+/// Build a selector segment for a single element (e.g., "role:Button && name:Submit")
+/// Only includes name if it's non-empty and meaningful
+pub(crate) fn build_selector_segment(role: &str, name: Option<&str>) -> String {
+    match name {
+        Some(n) if !n.is_empty() => format!("role:{} && name:{}", role, n),
+        _ => format!("role:{}", role),
+    }
+}
+
+/// Build a chained selector from a list of segments (e.g., "role:Window && name:App >> role:Button && name:Submit")
+pub(crate) fn build_chained_selector(segments: &[String]) -> Option<String> {
+    if segments.is_empty() {
+        None
+    } else {
+        Some(segments.join(" >> "))
+    }
+}
+
+/// Parse a `state:` predicate value into the state and expected value it checks
+fn parse_state_predicate(value: &str) -> Result<(ElementState, bool), String> {
+    match value.trim().to_lowercase().as_str() {
+        "enabled" => Ok((ElementState::Enabled, true)),
+        "disabled" => Ok((ElementState::Enabled, false)),
+        "focused" => Ok((ElementState::Focused, true)),
+        "offscreen" => Ok((ElementState::Offscreen, true)),
+        "onscreen" => Ok((ElementState::Offscreen, false)),
+        other => Err(format!(
+            "Unknown state predicate: 'state:{other}'. Supported states: enabled, disabled, focused, offscreen, onscreen"
+        )),
+    }
+}
+
+/// Parse the value of a boolean state predicate such as `enabled:true` or `focused:false`
+///
+/// Supported keys and the attribute each one checks:
+/// - `enabled:` - `UIElementAttributes::enabled` (UIA `IsEnabled`)
+/// - `focused:` - `UIElementAttributes::is_focused` (UIA `HasKeyboardFocus`)
+/// - `visible:` - on-screen visibility (UIA `IsOffscreen` negated)
+fn parse_bool_predicate(key: &str, value: &str) -> Result<bool, String> {
+    match value.trim().to_lowercase().as_str() {
+        "true" => Ok(true),
+        "false" => Ok(false),
+        other => Err(format!(
+            "Invalid value for '{key}:' predicate: '{other}'. Expected 'true' or 'false'"
+        )),
+    }
+}
+
+impl Selector {
+    /// Clear the internal cache of parsed selector strings.
+    ///
+    /// `Selector::from(&str)` memoizes parse results so repeated locators in loops skip
+    /// re-parsing. This is mainly useful for tests that need a cold cache.
+    pub fn clear_parse_cache() {
+        if let Ok(mut cache) = PARSE_CACHE.lock() {
+            cache.entries.clear();
+        }
+    }
+}
+
 impl std::fmt::Display for Selector {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{self:?}")
@@ -105,6 +258,13 @@ fn tokenize(input: &str) -> Result<Vec<Token>, String> {
         match ch {
             // Parentheses - these are operators/delimiters (unless inside text:)
             '(' if !in_text_selector => {
+                // CSS-style negation: ":not(...)" is the same as "!(...)"
+                if current.trim() == ":not" {
+                    current.clear();
+                    tokens.push(Token::Not);
+                    tokens.push(Token::LParen);
+                    continue;
+                }
                 if !current.is_empty() {
                     tokens.push(Token::Selector(current.trim().to_string()));
                     current.clear();
@@ -220,8 +380,11 @@ fn parse_boolean_expression(tokens: Vec<Token>) -> Result<Selector, String> {
     for token in tokens {
         match token {
             Token::Selector(s) => {
-                // Parse the atomic selector
-                output_queue.push(parse_atomic_selector(&s));
+                // Parse the atomic selector, surfacing invalid predicates as parse errors
+                match parse_atomic_selector(&s) {
+                    Selector::Invalid(reason) => return Err(reason),
+                    selector => output_queue.push(selector),
+                }
             }
             Token::LParen => {
                 operator_stack.push(token);
@@ -389,9 +552,33 @@ fn parse_atomic_selector(s: &str) -> Selector {
             Selector::NativeId(parts[1].trim().to_string())
         }
         _ if s.to_lowercase().starts_with("visible:") => {
-            let value = s[8..].trim().to_lowercase();
-            Selector::Visible(value == "true")
+            match parse_bool_predicate("visible", &s["visible:".len()..]) {
+                Ok(value) => Selector::Visible(value),
+                Err(reason) => Selector::Invalid(reason),
+            }
         }
+        _ if s.to_lowercase().starts_with("enabled:") => {
+            match parse_bool_predicate("enabled", &s["enabled:".len()..]) {
+                Ok(expected) => Selector::State {
+                    state: ElementState::Enabled,
+                    expected,
+                },
+                Err(reason) => Selector::Invalid(reason),
+            }
+        }
+        _ if s.to_lowercase().starts_with("focused:") => {
+            match parse_bool_predicate("focused", &s["focused:".len()..]) {
+                Ok(expected) => Selector::State {
+                    state: ElementState::Focused,
+                    expected,
+                },
+                Err(reason) => Selector::Invalid(reason),
+            }
+        }
+        _ if s.to_lowercase().starts_with("state:") => match parse_state_predicate(&s[6..]) {
+            Ok((state, expected)) => Selector::State { state, expected },
+            Err(reason) => Selector::Invalid(reason),
+        },
         _ if s.to_lowercase().starts_with("process:") || s.to_lowercase().starts_with("processname:") => {
             let prefix_len = if s.to_lowercase().starts_with("process:") {
                 "process:".len()
@@ -401,6 +588,7 @@ fn parse_atomic_selector(s: &str) -> Selector {
             let process_name = s[prefix_len..].trim().to_string();
             Selector::Process(process_name)
         }
+        _ if s.to_lowercase().starts_with("app:") => Selector::App(s[4..].trim().to_string()),
         _ if s.to_lowercase().starts_with("attr:") => {
             let attr_part = &s["attr:".len()..];
             let mut attributes = BTreeMap::new();
@@ -453,6 +641,10 @@ fn parse_atomic_selector(s: &str) -> Selector {
                 Selector::Invalid(format!("Invalid index for nth selector: '{index_str}'"))
             }
         }
+        _ if s.to_lowercase().starts_with("range:") => match parse_range(&s["range:".len()..]) {
+            Ok((start, end)) => Selector::Range { start, end },
+            Err(reason) => Selector::Invalid(reason),
+        },
         _ if s.starts_with("id:") => Selector::Id(s[3..].to_string()),
         _ if s.starts_with("text:") => Selector::Text(s[5..].to_string()),
         _ if s.contains(':') => {
@@ -466,7 +658,7 @@ fn parse_atomic_selector(s: &str) -> Selector {
         _ if s.starts_with('/') => Selector::Path(s.to_string()),
         ".." => Selector::Parent,
         _ => Selector::Invalid(format!(
-            "Unknown selector format: \"{s}\". Use prefixes like 'role:', 'name:', 'id:', 'text:', 'nativeid:', 'classname:', 'process:', 'attr:', 'visible:', or 'has:' to specify the selector type."
+            "Unknown selector format: \"{s}\". Use prefixes like 'role:', 'name:', 'id:', 'text:', 'nativeid:', 'classname:', 'process:', 'app:', 'attr:', 'visible:', 'enabled:', 'focused:', 'state:', or 'has:' to specify the selector type."
         )),
     }
 }
@@ -475,60 +667,83 @@ impl From<&str> for Selector {
     fn from(s: &str) -> Self {
         let s = s.trim();
 
-        // Handle chained selectors first (>> has highest priority)
-        if s.contains(">>") {
-            let parts: Vec<&str> = s.split(">>").map(|p| p.trim()).collect();
-            if parts.len() > 1 {
-                // Strip outer parentheses from each part if present
-                let cleaned_parts: Vec<Selector> = parts
-                    .into_iter()
-                    .map(|part| {
-                        let trimmed = part.trim();
-                        // Check if the part is wrapped in parentheses
-                        if trimmed.starts_with('(') && trimmed.ends_with(')') {
-                            // Check if these are truly outer parentheses (balanced)
-                            let inner = &trimmed[1..trimmed.len() - 1];
-                            // Only strip if the parentheses are balanced at this level
-                            if !has_unbalanced_parens(inner) {
-                                return Selector::from(inner);
-                            }
+        // The lock is released before parsing since parsing recurses into Selector::from
+        if let Some(cached) = PARSE_CACHE.lock().ok().and_then(|mut c| c.get(s)) {
+            return cached;
+        }
+
+        let selector = parse_selector(s);
+        if let Ok(mut cache) = PARSE_CACHE.lock() {
+            cache.insert(s.to_string(), selector.clone());
+        }
+        selector
+    }
+}
+
+#[cfg(test)]
+thread_local! {
+    /// Cache misses on this thread, i.e. calls to `parse_selector`, for cache tests
+    static PARSE_COUNT: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+}
+
+/// Parse a (trimmed) selector string without consulting the parse cache
+fn parse_selector(s: &str) -> Selector {
+    #[cfg(test)]
+    PARSE_COUNT.with(|count| count.set(count.get() + 1));
+
+    // Handle chained selectors first (>> has highest priority)
+    if s.contains(">>") {
+        let parts: Vec<&str> = s.split(">>").map(|p| p.trim()).collect();
+        if parts.len() > 1 {
+            // Strip outer parentheses from each part if present
+            let cleaned_parts: Vec<Selector> = parts
+                .into_iter()
+                .map(|part| {
+                    let trimmed = part.trim();
+                    // Check if the part is wrapped in parentheses
+                    if trimmed.starts_with('(') && trimmed.ends_with(')') {
+                        // Check if these are truly outer parentheses (balanced)
+                        let inner = &trimmed[1..trimmed.len() - 1];
+                        // Only strip if the parentheses are balanced at this level
+                        if !has_unbalanced_parens(inner) {
+                            return Selector::from(inner);
                         }
-                        Selector::from(trimmed)
-                    })
-                    .collect();
-                return Selector::Chain(cleaned_parts);
-            }
-        }
-
-        // Check if this contains boolean operators (&&, ||, !, parentheses, or comma for OR)
-        let has_boolean_ops = s.contains("&&")
-            || s.contains("||")
-            || s.contains('(')
-            || s.contains(')')
-            || s.contains('!')
-            || (s.contains(',') && !s.starts_with("attr:")); // comma is OR unless in attr: context
-
-        if has_boolean_ops {
-            // Check if this is ONLY a text: selector with special characters (not a boolean expression)
-            // text: is special because the value after the colon can legitimately contain these characters
-            if s.starts_with("text:") && !s[5..].contains("text:") {
-                // This is a simple text: selector with special chars in the text, not a boolean expression
-                return parse_atomic_selector(s);
-            }
-
-            // Use boolean expression parser
-            match tokenize(s) {
-                Ok(tokens) => match parse_boolean_expression(tokens) {
-                    Ok(selector) => return selector,
-                    Err(e) => return Selector::Invalid(format!("Parse error: {e}")),
-                },
-                Err(e) => return Selector::Invalid(format!("Tokenization error: {e}")),
-            }
+                    }
+                    Selector::from(trimmed)
+                })
+                .collect();
+            return Selector::Chain(cleaned_parts);
         }
+    }
 
-        // No boolean operators - parse as atomic selector
-        parse_atomic_selector(s)
+    // Check if this contains boolean operators (&&, ||, !, parentheses, or comma for OR)
+    let has_boolean_ops = s.contains("&&")
+        || s.contains("||")
+        || s.contains('(')
+        || s.contains(')')
+        || s.contains('!')
+        || (s.contains(',') && !s.starts_with("attr:")); // comma is OR unless in attr: context
+
+    if has_boolean_ops {
+        // Check if this is ONLY a text: selector with special characters (not a boolean expression)
+        // text: is special because the value after the colon can legitimately contain these characters
+        if s.starts_with("text:") && !s[5..].contains("text:") {
+            // This is a simple text: selector with special chars in the text, not a boolean expression
+            return parse_atomic_selector(s);
+        }
+
+        // Use boolean expression parser
+        match tokenize(s) {
+            Ok(tokens) => match parse_boolean_expression(tokens) {
+                Ok(selector) => return selector,
+                Err(e) => return Selector::Invalid(format!("Parse error: {e}")),
+            },
+            Err(e) => return Selector::Invalid(format!("Tokenization error: {e}")),
+        }
     }
+
+    // No boolean operators - parse as atomic selector
+    parse_atomic_selector(s)
 }
 // Comprehensive unit tests for selector parsing and behavior
 
@@ -611,6 +826,28 @@ mod debug_selector_test {
         }
     }
 
+    #[test]
+    fn test_app_selector_by_name_and_pid() {
+        assert_eq!(
+            Selector::from("app:notepad"),
+            Selector::App("notepad".to_string())
+        );
+        assert_eq!(
+            Selector::from("App: 4242"),
+            Selector::App("4242".to_string())
+        );
+        assert_eq!(
+            Selector::from("app:notepad >> role:Edit"),
+            Selector::Chain(vec![
+                Selector::App("notepad".to_string()),
+                Selector::Role {
+                    role: "Edit".to_string(),
+                    name: None
+                },
+            ])
+        );
+    }
+
     #[test]
     fn test_process_selector_with_extension() {
         let input = "processname:notepad.exe";
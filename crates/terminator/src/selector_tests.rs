@@ -213,6 +213,42 @@ fn test_nth_selector() {
     }
 }
 
+#[test]
+fn test_range_selector() {
+    let selector = Selector::from("role:DataItem >> range:1..5");
+    match selector {
+        Selector::Chain(selectors) => assert_eq!(
+            selectors[1],
+            Selector::Range {
+                start: 1,
+                end: Some(5)
+            }
+        ),
+        _ => panic!("Expected Chain selector"),
+    }
+
+    assert_eq!(
+        Selector::from("range:2.."),
+        Selector::Range {
+            start: 2,
+            end: None
+        }
+    );
+    assert_eq!(
+        Selector::from("range:..3"),
+        Selector::Range {
+            start: 0,
+            end: Some(3)
+        }
+    );
+    for invalid in ["range:5..1", "range:a..b", "range:3"] {
+        assert!(
+            matches!(Selector::from(invalid), Selector::Invalid(_)),
+            "{invalid} should be invalid"
+        );
+    }
+}
+
 #[test]
 fn test_not_selector() {
     let selector = Selector::from("!name:Cancel");
@@ -541,3 +577,152 @@ fn test_chain_with_multiple_operators() {
         _ => panic!("Expected Chain selector"),
     }
 }
+
+fn parse_count() -> usize {
+    PARSE_COUNT.with(|count| count.get())
+}
+
+#[test]
+fn test_parse_cache_returns_identical_selector() {
+    let input = "role:Window && name:Cache Test >> (role:Button && name:OK) >> nth:0";
+    let before = parse_count();
+    let first = Selector::from(input);
+    assert!(parse_count() > before, "first lookup should parse");
+
+    let after_first = parse_count();
+    let second = Selector::from(input);
+    assert_eq!(first, second);
+    assert_eq!(
+        parse_count(),
+        after_first,
+        "second lookup should be a cache hit"
+    );
+
+    Selector::clear_parse_cache();
+    let after_clear = Selector::from(input);
+    assert_eq!(first, after_clear);
+    assert!(
+        parse_count() > after_first,
+        "lookup after clearing should miss"
+    );
+}
+
+#[test]
+fn test_parse_cache_ignores_surrounding_whitespace() {
+    assert_eq!(
+        Selector::from("  role:Button && name:Trim  "),
+        Selector::from("role:Button && name:Trim")
+    );
+}
+
+#[test]
+fn test_state_selector() {
+    assert_eq!(
+        Selector::from("state:disabled"),
+        Selector::State {
+            state: ElementState::Enabled,
+            expected: false
+        }
+    );
+    assert_eq!(
+        Selector::from("state:Focused"),
+        Selector::State {
+            state: ElementState::Focused,
+            expected: true
+        }
+    );
+    assert_eq!(
+        Selector::from("state:onscreen"),
+        Selector::State {
+            state: ElementState::Offscreen,
+            expected: false
+        }
+    );
+}
+
+#[test]
+fn test_css_not_predicate_with_and() {
+    let selector = Selector::from("role:Button && :not(state:disabled)");
+    match selector {
+        Selector::And(parts) => {
+            assert_eq!(parts.len(), 2);
+            assert!(matches!(&parts[0], Selector::Role { role, .. } if role == "Button"));
+            assert_eq!(
+                parts[1],
+                Selector::Not(Box::new(Selector::State {
+                    state: ElementState::Enabled,
+                    expected: false
+                }))
+            );
+        }
+        _ => panic!("Expected And selector, got: {selector:?}"),
+    }
+}
+
+#[test]
+fn test_css_not_matches_bang_syntax() {
+    assert_eq!(
+        Selector::from("role:Button && :not(state:focused)"),
+        Selector::from("role:Button && !(state:focused)")
+    );
+}
+
+#[test]
+fn test_unknown_state_predicate_is_invalid() {
+    assert!(matches!(
+        Selector::from("state:sleepy"),
+        Selector::Invalid(_)
+    ));
+    match Selector::from("role:Button && :not(state:sleepy)") {
+        Selector::Invalid(msg) => assert!(msg.contains("state:sleepy"), "msg: {msg}"),
+        other => panic!("Expected Invalid selector, got: {other:?}"),
+    }
+}
+
+#[test]
+fn test_boolean_state_predicates() {
+    assert_eq!(
+        Selector::from("enabled:true"),
+        Selector::State {
+            state: ElementState::Enabled,
+            expected: true
+        }
+    );
+    assert_eq!(
+        Selector::from("focused:false"),
+        Selector::State {
+            state: ElementState::Focused,
+            expected: false
+        }
+    );
+    assert_eq!(Selector::from("visible:FALSE"), Selector::Visible(false));
+}
+
+#[test]
+fn test_state_predicate_combined_with_role() {
+    let selector = Selector::from("role:Button && name:Save && enabled:true && visible:true");
+    match selector {
+        Selector::And(parts) => {
+            assert_eq!(parts.len(), 4);
+            assert_eq!(
+                parts[2],
+                Selector::State {
+                    state: ElementState::Enabled,
+                    expected: true
+                }
+            );
+            assert_eq!(parts[3], Selector::Visible(true));
+        }
+        _ => panic!("Expected And selector, got: {selector:?}"),
+    }
+}
+
+#[test]
+fn test_state_predicate_rejects_non_boolean_values() {
+    for input in ["enabled:yes", "focused:1", "visible:maybe"] {
+        match Selector::from(input) {
+            Selector::Invalid(msg) => assert!(msg.contains("Expected 'true' or 'false'")),
+            other => panic!("Expected Invalid for {input}, got: {other:?}"),
+        }
+    }
+}
@@ -0,0 +1,37 @@
+//! Tests for the list_browser_tabs / activate_browser_tab types
+
+use crate::extension_bridge::{BrowserTab, TabTarget};
+
+#[test]
+fn test_browser_tab_parses_extension_payload() {
+    let json = r#"[
+        {"id":11,"index":0,"title":"Inbox","url":"https://mail.example.com","active":false,"windowId":1},
+        {"id":12,"index":1,"title":"Docs","url":"https://docs.example.com","active":true,"windowId":1}
+    ]"#;
+    let tabs: Vec<BrowserTab> = serde_json::from_str(json).expect("Should parse tab list");
+
+    assert_eq!(tabs.len(), 2);
+    assert_eq!(tabs[1].id, 12);
+    assert_eq!(tabs[1].index, 1);
+    assert!(tabs[1].active);
+    assert_eq!(tabs[1].window_id, Some(1));
+}
+
+#[test]
+fn test_browser_tab_with_null_fields() {
+    let json = r#"{"id":5,"index":3,"title":null,"url":null,"active":false,"windowId":null}"#;
+    let tab: BrowserTab = serde_json::from_str(json).expect("Should parse with nulls");
+
+    assert!(tab.title.is_none());
+    assert!(tab.url.is_none());
+    assert!(tab.window_id.is_none());
+}
+
+#[test]
+fn test_tab_target_conversions() {
+    assert_eq!(TabTarget::from(2usize), TabTarget::Index(2));
+    assert_eq!(
+        TabTarget::from("example.com"),
+        TabTarget::Url("example.com".to_string())
+    );
+}
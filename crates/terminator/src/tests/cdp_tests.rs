@@ -0,0 +1,149 @@
+use crate::cdp::BrowserSession;
+use crate::AutomationError;
+use futures_util::{SinkExt, StreamExt};
+use serde_json::{json, Value};
+use tokio::net::TcpListener;
+use tokio_tungstenite::{accept_async, tungstenite::Message};
+
+/// Minimal DevTools endpoint that answers the commands `BrowserSession` uses.
+async fn spawn_mock_cdp() -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    tokio::spawn(async move {
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut ws = accept_async(stream).await.unwrap();
+
+        while let Some(Ok(Message::Text(text))) = ws.next().await {
+            let command: Value = serde_json::from_str(&text).unwrap();
+            let id = command["id"].clone();
+            let session = command.get("sessionId").cloned().unwrap_or(Value::Null);
+            let params = &command["params"];
+
+            let reply = match command["method"].as_str().unwrap() {
+                "Target.getTargets" => json!({ "id": id, "result": { "targetInfos": [
+                    { "type": "service_worker", "targetId": "SW" },
+                    { "type": "page", "targetId": "PAGE1" },
+                ]}}),
+                "Target.attachToTarget" => {
+                    assert_eq!(params["targetId"], "PAGE1");
+                    json!({ "id": id, "result": { "sessionId": "SESSION1" } })
+                }
+                "Page.navigate" if params["url"] == "https://bad.invalid/" => {
+                    json!({ "id": id, "result": { "frameId": "F", "errorText": "net::ERR_NAME_NOT_RESOLVED" } })
+                }
+                "Page.navigate" => json!({ "id": id, "result": { "frameId": "F" } }),
+                "Runtime.evaluate" => {
+                    let expression = params["expression"].as_str().unwrap();
+                    let value = if expression == "document.title" {
+                        json!("Mock Page")
+                    } else if expression == "document.readyState" {
+                        json!("complete")
+                    } else if expression == "sessionId" {
+                        session.clone()
+                    } else if expression == "({a: 1})" {
+                        json!({ "a": 1 })
+                    } else if expression.contains("querySelectorAll(\"button.primary\")") {
+                        json!([{
+                            "tag": "button",
+                            "text": "Submit",
+                            "attributes": { "class": "primary", "type": "submit" },
+                            "bounds": [10.0, 20.0, 80.0, 30.0],
+                        }])
+                    } else {
+                        let reply = json!({ "id": id, "result": {
+                            "result": { "type": "object" },
+                            "exceptionDetails": { "text": "Uncaught", "exception": { "description": "ReferenceError: boom is not defined" } },
+                        }});
+                        ws.send(Message::Text(reply.to_string())).await.unwrap();
+                        continue;
+                    };
+                    json!({ "id": id, "result": { "result": { "value": value } } })
+                }
+                _ => {
+                    json!({ "id": id, "error": { "code": -32601, "message": "method not found" } })
+                }
+            };
+
+            // Interleave an event to make sure it is ignored
+            ws.send(Message::Text(
+                json!({ "method": "Page.loadEventFired", "params": {} }).to_string(),
+            ))
+            .await
+            .unwrap();
+            ws.send(Message::Text(reply.to_string())).await.unwrap();
+        }
+    });
+
+    format!("ws://{addr}")
+}
+
+#[tokio::test]
+async fn test_cdp_script_execution() {
+    let url = spawn_mock_cdp().await;
+    let session = BrowserSession::connect(&format!("{url}/devtools/page/PAGE1"))
+        .await
+        .unwrap();
+
+    assert_eq!(
+        session
+            .execute_browser_script("document.title")
+            .await
+            .unwrap(),
+        "Mock Page"
+    );
+    assert_eq!(
+        session.execute_browser_script("({a: 1})").await.unwrap(),
+        r#"{"a":1}"#
+    );
+
+    let err = session.execute_browser_script("boom").await.unwrap_err();
+    assert!(err.to_string().contains("ReferenceError"), "{err}");
+
+    // Page endpoints talk to the page directly, without a target session
+    assert_eq!(
+        session.execute_browser_script("sessionId").await.unwrap(),
+        ""
+    );
+}
+
+#[tokio::test]
+async fn test_cdp_browser_endpoint_attaches_to_page() {
+    let url = spawn_mock_cdp().await;
+    let session = BrowserSession::connect(&format!("{url}/devtools/browser/B1"))
+        .await
+        .unwrap();
+
+    assert_eq!(
+        session.execute_browser_script("sessionId").await.unwrap(),
+        "SESSION1"
+    );
+}
+
+#[tokio::test]
+async fn test_cdp_query_dom_and_navigation() {
+    let url = spawn_mock_cdp().await;
+    let session = BrowserSession::connect(&format!("{url}/devtools/page/PAGE1"))
+        .await
+        .unwrap();
+
+    let buttons = session.query_dom("button.primary").await.unwrap();
+    assert_eq!(buttons.len(), 1);
+    assert_eq!(buttons[0].tag, "button");
+    assert_eq!(buttons[0].text, "Submit");
+    assert_eq!(buttons[0].attributes["type"], "submit");
+    assert_eq!(buttons[0].bounds, (10.0, 20.0, 80.0, 30.0));
+
+    session.navigate("https://example.com/").await.unwrap();
+    let err = session.navigate("https://bad.invalid/").await.unwrap_err();
+    assert!(err.to_string().contains("ERR_NAME_NOT_RESOLVED"), "{err}");
+
+    let err = session.send("Nope.unknown", json!({})).await.unwrap_err();
+    assert!(err.to_string().contains("method not found"), "{err}");
+}
+
+#[tokio::test]
+async fn test_cdp_rejects_non_websocket_url() {
+    let result = BrowserSession::connect("http://127.0.0.1:9222").await;
+    assert!(matches!(result, Err(AutomationError::InvalidArgument(_))));
+}
@@ -0,0 +1,295 @@
+use super::node;
+use crate::clock::{Clock, FakeClock};
+use crate::locator::WaitCondition;
+use crate::platforms::mock::MockEngine;
+use crate::{AutomationError, Desktop, SettleStrategy, UINode, UiDiffOptions};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+fn desktop_with_fake_clock() -> (Arc<FakeClock>, Desktop) {
+    let mut window = node(
+        "editor",
+        "Window",
+        "Editor",
+        vec![node("save", "Button", "Save", vec![])],
+    );
+    window.attributes.properties =
+        HashMap::from([("ProcessId".to_string(), Some(serde_json::json!(42)))]);
+    let engine = Arc::new(MockEngine::new(node(
+        "desktop",
+        "Pane",
+        "Desktop",
+        vec![window],
+    )));
+    let clock = Arc::new(FakeClock::new());
+    let desktop = Desktop::with_engine(engine).with_clock(clock.clone());
+    (clock, desktop)
+}
+
+#[tokio::test]
+async fn test_fake_clock_sleep_advances_instantly() {
+    let clock = FakeClock::new();
+    let start = clock.now();
+
+    clock.sleep(Duration::from_secs(3600)).await;
+    clock.advance(Duration::from_millis(5));
+
+    assert_eq!(clock.now() - start, Duration::from_millis(3_600_005));
+    assert_eq!(clock.elapsed(), Duration::from_millis(3_600_005));
+}
+
+#[tokio::test]
+async fn test_wait_for_times_out_on_the_injected_clock() {
+    let (clock, desktop) = desktop_with_fake_clock();
+    let real_start = Instant::now();
+
+    let result = desktop
+        .locator("role:Button|name:Delete")
+        .wait_for(WaitCondition::Exists, Some(Duration::from_secs(30)))
+        .await;
+
+    assert!(matches!(result, Err(AutomationError::Timeout(_))));
+    // Polls every 100ms and gives up on the first check past the timeout
+    assert_eq!(clock.elapsed(), Duration::from_millis(30_100));
+    assert!(real_start.elapsed() < Duration::from_secs(5));
+}
+
+#[tokio::test]
+async fn test_wait_for_found_element_does_not_sleep() {
+    let (clock, desktop) = desktop_with_fake_clock();
+
+    let element = desktop
+        .locator("role:Button|name:Save")
+        .wait_for(WaitCondition::Exists, Some(Duration::from_secs(30)))
+        .await
+        .unwrap();
+
+    assert_eq!(element.name().as_deref(), Some("Save"));
+    assert_eq!(clock.elapsed(), Duration::ZERO);
+}
+
+fn desktop_with_disabled_submit() -> (Arc<FakeClock>, Desktop) {
+    let mut submit = node("submit", "Button", "Submit", vec![]);
+    submit.attributes.enabled = Some(false);
+    let window = node(
+        "form",
+        "Window",
+        "Form",
+        vec![submit, node("cancel", "Button", "Cancel", vec![])],
+    );
+    let engine = Arc::new(MockEngine::new(node(
+        "desktop",
+        "Pane",
+        "Desktop",
+        vec![window],
+    )));
+    let clock = Arc::new(FakeClock::new());
+    let desktop = Desktop::with_engine(engine).with_clock(clock.clone());
+    (clock, desktop)
+}
+
+#[tokio::test]
+async fn test_wait_enabled_returns_enabled_element() {
+    let (clock, desktop) = desktop_with_disabled_submit();
+
+    let element = desktop
+        .locator("role:Button|name:Cancel")
+        .wait_enabled(Some(Duration::from_secs(5)))
+        .await
+        .unwrap();
+
+    assert_eq!(element.name().as_deref(), Some("Cancel"));
+    assert_eq!(clock.elapsed(), Duration::ZERO);
+}
+
+#[tokio::test]
+async fn test_wait_enabled_reports_found_but_disabled() {
+    let (clock, desktop) = desktop_with_disabled_submit();
+
+    let result = desktop
+        .locator("role:Button|name:Submit")
+        .wait_enabled(Some(Duration::from_secs(5)))
+        .await;
+
+    match result {
+        Err(AutomationError::Timeout(message)) => {
+            assert!(message.contains("found but never Enabled"), "{message}")
+        }
+        other => panic!("expected a timeout, got {other:?}"),
+    }
+    assert_eq!(clock.elapsed(), Duration::from_millis(5_100));
+}
+
+#[tokio::test]
+async fn test_wait_enabled_reports_never_found() {
+    let (_clock, desktop) = desktop_with_disabled_submit();
+
+    let result = desktop
+        .locator("role:Button|name:Delete")
+        .wait_enabled(Some(Duration::from_secs(5)))
+        .await;
+
+    match result {
+        Err(AutomationError::Timeout(message)) => {
+            assert!(message.contains("never found"), "{message}")
+        }
+        other => panic!("expected a timeout, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn test_ui_diff_settle_delay_uses_the_injected_clock() {
+    let (clock, desktop) = desktop_with_fake_clock();
+    let options = UiDiffOptions {
+        settle_delay_ms: Some(2500),
+        ..Default::default()
+    };
+
+    let (_, element, diff) = desktop
+        .execute_with_ui_diff("role:Button|name:Save", |el| el.click(), Some(options))
+        .await
+        .unwrap();
+
+    assert_eq!(element.name().as_deref(), Some("Save"));
+    assert!(diff.is_some_and(|diff| !diff.has_changes));
+    assert_eq!(clock.elapsed(), Duration::from_millis(2500));
+}
+
+async fn settle_time(settle: SettleStrategy) -> Duration {
+    let (clock, desktop) = desktop_with_fake_clock();
+    let options = UiDiffOptions {
+        settle: Some(settle),
+        ..Default::default()
+    };
+
+    desktop
+        .execute_with_ui_diff("role:Button|name:Save", |el| el.click(), Some(options))
+        .await
+        .unwrap();
+    clock.elapsed()
+}
+
+#[tokio::test]
+async fn test_settle_until_idle_waits_only_for_the_quiet_period() {
+    let settled = settle_time(SettleStrategy::UntilIdle {
+        quiet_for: Duration::from_millis(300),
+        timeout: Duration::from_secs(10),
+    })
+    .await;
+
+    assert_eq!(settled, Duration::from_millis(300));
+}
+
+#[tokio::test]
+async fn test_settle_until_selector_returns_once_it_matches() {
+    let present = settle_time(SettleStrategy::UntilSelector {
+        selector: "role:Button|name:Save".to_string(),
+        timeout: Duration::from_secs(10),
+    })
+    .await;
+    let missing = settle_time(SettleStrategy::UntilSelector {
+        selector: "role:Dialog".to_string(),
+        timeout: Duration::from_secs(1),
+    })
+    .await;
+
+    assert_eq!(present, Duration::ZERO);
+    assert_eq!(missing, Duration::from_millis(1100));
+}
+
+#[tokio::test]
+async fn test_settle_strategy_overrides_settle_delay_ms() {
+    let (clock, desktop) = desktop_with_fake_clock();
+    let options = UiDiffOptions {
+        settle_delay_ms: Some(2500),
+        settle: Some(SettleStrategy::Fixed(Duration::from_millis(200))),
+        ..Default::default()
+    };
+
+    desktop
+        .execute_with_ui_diff("role:Button|name:Save", |el| el.click(), Some(options))
+        .await
+        .unwrap();
+
+    assert_eq!(clock.elapsed(), Duration::from_millis(200));
+}
+
+fn two_app_desktop() -> (Arc<FakeClock>, Arc<MockEngine>, Desktop) {
+    let app = |id: &str, name: &str, pid: u32| {
+        let mut window = node(id, "Window", name, vec![]);
+        window.attributes.properties =
+            HashMap::from([("ProcessId".to_string(), Some(serde_json::json!(pid)))]);
+        window
+    };
+    let engine = Arc::new(MockEngine::new(node(
+        "desktop",
+        "Pane",
+        "Desktop",
+        vec![app("editor", "Editor", 42), app("terminal", "Terminal", 7)],
+    )));
+    let clock = Arc::new(FakeClock::new());
+    let desktop = Desktop::with_engine(engine.clone()).with_clock(clock.clone());
+    (clock, engine, desktop)
+}
+
+#[tokio::test]
+async fn test_activate_application_and_wait_returns_once_foreground() {
+    let (clock, engine, desktop) = two_app_desktop();
+    engine.set_focused("editor").unwrap();
+
+    desktop
+        .activate_application_and_wait("editor", Duration::from_secs(5))
+        .await
+        .unwrap();
+
+    assert_eq!(clock.elapsed(), Duration::ZERO);
+    assert_eq!(engine.actions()[0].action, "activate_application");
+}
+
+#[tokio::test]
+async fn test_activate_application_and_wait_times_out_while_another_app_is_foreground() {
+    let (clock, engine, desktop) = two_app_desktop();
+    engine.set_focused("terminal").unwrap();
+
+    let result = desktop
+        .activate_application_and_wait("editor", Duration::from_secs(2))
+        .await;
+
+    assert!(matches!(result, Err(AutomationError::Timeout(_))));
+    assert_eq!(clock.elapsed(), Duration::from_secs(2));
+}
+
+#[tokio::test]
+async fn test_activate_application_and_wait_matches_the_top_level_window() {
+    // Like a UWP app, the focused window belongs to another process than the
+    // application's frame; the shared top-level window ties them together
+    let with_window = |mut window: UINode, pid: u32, handle: isize| {
+        window.attributes.properties = HashMap::from([
+            ("ProcessId".to_string(), Some(serde_json::json!(pid))),
+            (
+                "NativeWindowHandle".to_string(),
+                Some(serde_json::json!(handle)),
+            ),
+        ]);
+        window
+    };
+    let core = with_window(node("core", "Window", "Calculator", vec![]), 42, 200);
+    let frame = with_window(node("frame", "Window", "Calculator", vec![core]), 900, 100);
+    let engine = Arc::new(MockEngine::new(node(
+        "desktop",
+        "Pane",
+        "Desktop",
+        vec![frame],
+    )));
+    let clock = Arc::new(FakeClock::new());
+    let desktop = Desktop::with_engine(engine.clone()).with_clock(clock.clone());
+    engine.set_focused("core").unwrap();
+
+    desktop
+        .activate_application_and_wait("calculator", Duration::from_secs(5))
+        .await
+        .unwrap();
+
+    assert_eq!(clock.elapsed(), Duration::ZERO);
+}
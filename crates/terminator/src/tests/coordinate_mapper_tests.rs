@@ -0,0 +1,50 @@
+//! Tests for mapping capture-space rects back to screen coordinates
+
+use crate::{CoordinateConverter, CoordinateMapper, Rect};
+
+#[test]
+fn test_identity_mapping_only_offsets_by_origin() {
+    let mapper = CoordinateMapper::for_capture((100.0, 50.0, 800.0, 600.0), 800, 600);
+    assert_eq!(mapper.dpi_scale, (1.0, 1.0));
+    assert_eq!(
+        mapper.physical_to_screen(Rect::new(10.0, 20.0, 30.0, 40.0)),
+        Rect::new(110.0, 70.0, 30.0, 40.0)
+    );
+}
+
+#[test]
+fn test_dpi_scale_converts_physical_to_logical() {
+    // 150% scaling: an 800x600 logical window is captured at 1200x900
+    let mapper = CoordinateMapper::for_capture((100.0, 50.0, 800.0, 600.0), 1200, 900);
+    assert_eq!(mapper.dpi_scale, (1.5, 1.5));
+    assert_eq!(
+        mapper.physical_to_screen(Rect::new(300.0, 150.0, 60.0, 30.0)),
+        Rect::new(300.0, 150.0, 40.0, 20.0)
+    );
+}
+
+#[test]
+fn test_resize_scale_is_undone_before_dpi() {
+    // 4K capture of a 1920x1080 logical window at 200%, downscaled by half for the model
+    let mapper = CoordinateMapper::for_capture((0.0, 0.0, 1920.0, 1080.0), 3840, 2160)
+        .with_resize_scale(0.5);
+    assert_eq!(
+        mapper.physical_to_screen(Rect::new(960.0, 540.0, 100.0, 50.0)),
+        Rect::new(960.0, 540.0, 100.0, 50.0)
+    );
+}
+
+#[test]
+fn test_box_to_screen_maps_corners() {
+    let mapper = CoordinateMapper::new((10.0, 20.0), (2.0, 2.0), 1.0);
+    assert_eq!(
+        mapper.box_to_screen([0.0, 0.0, 100.0, 50.0]),
+        [10.0, 20.0, 60.0, 45.0]
+    );
+}
+
+#[test]
+fn test_zero_sized_window_falls_back_to_unit_scale() {
+    let mapper = CoordinateMapper::for_capture((0.0, 0.0, 0.0, 0.0), 640, 480);
+    assert_eq!(mapper.dpi_scale, (1.0, 1.0));
+}
@@ -0,0 +1,78 @@
+use crate::platforms::{retry_init, EngineInitConfig};
+use crate::AutomationError;
+use std::cell::{Cell, RefCell};
+use std::time::Duration;
+
+fn config(attempts: u32) -> EngineInitConfig {
+    EngineInitConfig {
+        attempts,
+        initial_backoff: Duration::from_millis(100),
+        max_backoff: Duration::from_millis(300),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_retries_with_capped_exponential_backoff_until_success() {
+    let calls = Cell::new(0);
+    let sleeps = RefCell::new(Vec::new());
+    let result = retry_init(
+        &config(5),
+        |d| sleeps.borrow_mut().push(d),
+        || {
+            calls.set(calls.get() + 1);
+            if calls.get() < 4 {
+                Err(AutomationError::PlatformError("not ready".to_string()))
+            } else {
+                Ok(calls.get())
+            }
+        },
+    );
+    assert_eq!(result.unwrap(), 4);
+    assert_eq!(
+        *sleeps.borrow(),
+        vec![
+            Duration::from_millis(100),
+            Duration::from_millis(200),
+            Duration::from_millis(300)
+        ]
+    );
+}
+
+#[test]
+fn test_gives_up_after_the_configured_attempts() {
+    let calls = Cell::new(0);
+    let result: Result<(), _> = retry_init(
+        &config(3),
+        |_| {},
+        || {
+            calls.set(calls.get() + 1);
+            Err(AutomationError::PlatformError("not ready".to_string()))
+        },
+    );
+    assert!(matches!(result, Err(AutomationError::PlatformError(_))));
+    assert_eq!(calls.get(), 3);
+}
+
+#[test]
+fn test_default_config_and_unsupported_platform_do_not_retry() {
+    let not_ready = || AutomationError::PlatformError("not ready".to_string());
+    let unsupported = || AutomationError::UnsupportedPlatform("nope".to_string());
+    let cases: [(EngineInitConfig, &dyn Fn() -> AutomationError); 2] = [
+        (EngineInitConfig::default(), &not_ready),
+        (config(5), &unsupported),
+    ];
+    for (config, error) in cases {
+        let calls = Cell::new(0);
+        let result: Result<(), _> = retry_init(
+            &config,
+            |_| panic!("should not sleep"),
+            || {
+                calls.set(calls.get() + 1);
+                Err(error())
+            },
+        );
+        assert!(result.is_err());
+        assert_eq!(calls.get(), 1);
+    }
+}
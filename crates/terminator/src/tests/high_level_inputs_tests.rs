@@ -121,3 +121,111 @@ async fn test_calendar_selection() -> Result<(), AutomationError> {
     app.close()?;
     Ok(())
 }
+
+#[tokio::test]
+#[ignore]
+async fn test_press_key_combo_select_all() -> Result<(), AutomationError> {
+    let desktop = Desktop::new(false, false)?;
+    let app = desktop.open_application("notepad")?;
+    tokio::time::sleep(Duration::from_millis(1000)).await;
+
+    let editor = app
+        .locator(Selector::Role {
+            role: "document".to_string(),
+            name: None,
+        })?
+        .first(Some(Duration::from_secs(5)))
+        .await?;
+    editor.type_text("hello world", false)?;
+
+    // Ctrl+A then typing replaces everything; a stuck Ctrl would turn "x" into a shortcut
+    desktop
+        .press_key_combo(&[crate::Modifier::Ctrl], "a")
+        .await?;
+    editor.type_text("x", false)?;
+    let text = editor.get_value()?.unwrap_or_default();
+
+    let _ = app.close();
+    assert_eq!(text, "x", "Ctrl+A should select all and be released");
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_scroll_until_visible() -> Result<(), AutomationError> {
+    let desktop = Desktop::new(false, false)?;
+    let app = desktop.open_url(
+        "https://en.wikipedia.org/wiki/Rust_(programming_language)",
+        None,
+    )?;
+    tokio::time::sleep(Duration::from_millis(3000)).await;
+
+    let document = app
+        .locator(Selector::Role {
+            role: "document".to_string(),
+            name: None,
+        })?
+        .first(Some(Duration::from_secs(10)))
+        .await?;
+
+    // The "External links" heading sits far below the fold
+    let heading = document.scroll_until_visible(
+        "role:Heading|External links",
+        crate::ScrollDirection::Down,
+        50,
+    )?;
+    assert!(heading.is_visible()?);
+
+    // A target that never appears errors once the scroll limit is hit
+    let missing = document.scroll_until_visible(
+        "role:Heading|Does not exist",
+        crate::ScrollDirection::Down,
+        2,
+    );
+    assert!(matches!(missing, Err(AutomationError::ElementNotFound(_))));
+
+    let _ = app.close();
+    Ok(())
+}
+
+#[test]
+fn test_resolve_range_value_snaps_to_step() {
+    use crate::element::utils::resolve_range_value;
+
+    assert_eq!(resolve_range_value(42.3, 0.0, 100.0, 5.0).unwrap(), 40.0);
+    assert_eq!(resolve_range_value(43.0, 0.0, 100.0, 5.0).unwrap(), 45.0);
+    // Steps count from the minimum, not from zero
+    assert_eq!(resolve_range_value(4.0, 1.0, 10.0, 2.0).unwrap(), 5.0);
+    assert_eq!(resolve_range_value(0.3, 0.0, 1.0, 0.1).unwrap(), 0.3);
+    // Snapping never overshoots the maximum
+    assert_eq!(resolve_range_value(9.9, 0.0, 10.0, 3.0).unwrap(), 9.0);
+    // No step leaves the value alone
+    assert_eq!(resolve_range_value(12.34, 0.0, 100.0, 0.0).unwrap(), 12.34);
+}
+
+#[test]
+fn test_resolve_range_value_rejects_out_of_range() {
+    use crate::element::utils::resolve_range_value;
+
+    for value in [-0.5, 100.5, f64::NAN, f64::INFINITY] {
+        assert!(matches!(
+            resolve_range_value(value, 0.0, 100.0, 1.0),
+            Err(AutomationError::InvalidArgument(_))
+        ));
+    }
+    assert_eq!(resolve_range_value(0.0, 0.0, 100.0, 1.0).unwrap(), 0.0);
+    assert_eq!(resolve_range_value(100.0, 0.0, 100.0, 1.0).unwrap(), 100.0);
+}
+
+#[test]
+fn test_typed_value_matches() {
+    use crate::element::utils::typed_value_matches;
+
+    assert!(typed_value_matches("hello", "hello"));
+    assert!(typed_value_matches("hello", "hello\r\n"));
+    assert!(typed_value_matches("hello\n", "hello "));
+    // A dropped first keystroke or an auto-formatter's change is a mismatch
+    assert!(!typed_value_matches("hello", "ello"));
+    assert!(!typed_value_matches("5551234", "555-1234"));
+    assert!(!typed_value_matches("hello", " hello"));
+}
@@ -0,0 +1,45 @@
+use crate::hotkey::Hotkey;
+use crate::{AutomationError, Modifier};
+
+#[test]
+fn test_parse_modifiers_and_key() {
+    let hotkey = Hotkey::parse("ctrl+shift+c").unwrap();
+    assert_eq!(hotkey.modifiers, vec![Modifier::Ctrl, Modifier::Shift]);
+    assert_eq!(hotkey.key, "C");
+    assert_eq!(hotkey.to_string(), "Ctrl+Shift+C");
+}
+
+#[test]
+fn test_parse_is_case_and_space_insensitive() {
+    let hotkey: Hotkey = " Control + ALT + f5 ".parse().unwrap();
+    assert_eq!(hotkey.modifiers, vec![Modifier::Ctrl, Modifier::Alt]);
+    assert_eq!(hotkey.key, "F5");
+}
+
+#[test]
+fn test_parse_named_keys_and_aliases() {
+    assert_eq!(Hotkey::parse("win+esc").unwrap().key, "Escape");
+    assert_eq!(Hotkey::parse("super+pgdn").unwrap().key, "PageDown");
+    assert_eq!(Hotkey::parse("space").unwrap().modifiers, vec![]);
+}
+
+#[test]
+fn test_parse_rejects_invalid_combos() {
+    for combo in [
+        "",
+        "ctrl+",
+        "ctrl+shift",
+        "ctrl+ctrl+a",
+        "a+b",
+        "ctrl+f25",
+        "ctrl+nosuchkey",
+    ] {
+        assert!(
+            matches!(
+                Hotkey::parse(combo),
+                Err(AutomationError::InvalidArgument(_))
+            ),
+            "'{combo}' should be rejected"
+        );
+    }
+}
@@ -0,0 +1,26 @@
+use crate::index_cache::IndexCache;
+
+#[test]
+fn test_index_cache_replace_all_keeps_lowest_indices() {
+    let mut cache = IndexCache::new(3);
+    cache.replace_all([(99, 990)]);
+    cache.replace_all((1..=5).map(|i| (i, i * 10)));
+
+    assert_eq!(cache.len(), 3);
+    assert_eq!(cache.get(1), Some(&10));
+    assert_eq!(cache.get(3), Some(&30));
+    assert_eq!(cache.get(4), None);
+    assert_eq!(cache.get(99), None);
+}
+
+#[test]
+fn test_index_cache_shrinking_capacity_drops_highest_indices() {
+    let mut cache = IndexCache::new(10);
+    cache.replace_all((1..=4).map(|i| (i, ())));
+    cache.set_capacity(2);
+
+    assert_eq!(cache.len(), 2);
+    assert!(cache.get(1).is_some());
+    assert!(cache.get(2).is_some());
+    assert!(cache.get(3).is_none());
+}
@@ -0,0 +1,41 @@
+use crate::element::utils::{join_in_reading_order, TextFragment};
+
+fn frag(text: &str, x: f64, y: f64) -> TextFragment {
+    TextFragment {
+        text: text.to_string(),
+        bounds: Some((x, y, 40.0, 20.0)),
+    }
+}
+
+#[test]
+fn test_same_line_fragments_join_with_spaces_left_to_right() {
+    let fragments = vec![frag("world", 50.0, 10.0), frag("Hello", 0.0, 12.0)];
+    assert_eq!(join_in_reading_order(fragments), "Hello world");
+}
+
+#[test]
+fn test_separate_lines_join_with_newlines_top_to_bottom() {
+    let fragments = vec![
+        frag("second", 0.0, 40.0),
+        frag("first", 0.0, 0.0),
+        frag("line", 50.0, 1.0),
+    ];
+    assert_eq!(join_in_reading_order(fragments), "first line\nsecond");
+}
+
+#[test]
+fn test_fragments_without_bounds_are_appended_in_order() {
+    let fragments = vec![
+        TextFragment {
+            text: "tail".to_string(),
+            bounds: None,
+        },
+        frag("head", 0.0, 0.0),
+    ];
+    assert_eq!(join_in_reading_order(fragments), "head\ntail");
+}
+
+#[test]
+fn test_empty_fragments_produce_empty_string() {
+    assert_eq!(join_in_reading_order(Vec::new()), "");
+}
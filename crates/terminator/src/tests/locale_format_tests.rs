@@ -0,0 +1,53 @@
+use crate::locale_format::{format_date, format_number};
+use crate::AutomationError;
+use chrono::NaiveDate;
+
+#[test]
+fn test_format_number_per_locale() {
+    assert_eq!(format_number(1000.5, "en-US").unwrap(), "1,000.5");
+    assert_eq!(format_number(1000.5, "de-DE").unwrap(), "1.000,5");
+    assert_eq!(format_number(1000.5, "de_de").unwrap(), "1.000,5");
+    assert_eq!(format_number(1234567.25, "fr-FR").unwrap(), "1 234 567,25");
+    // Regional tags override their language
+    assert_eq!(format_number(1234567.25, "de-CH").unwrap(), "1'234'567.25");
+    assert_eq!(format_number(-42.0, "de").unwrap(), "-42");
+    assert_eq!(format_number(999.0, "en").unwrap(), "999");
+    assert_eq!(format_number(0.125, "es-ES").unwrap(), "0,125");
+}
+
+#[test]
+fn test_format_number_rejects_bad_input() {
+    assert!(matches!(
+        format_number(1.0, "xx-YY"),
+        Err(AutomationError::InvalidArgument(_))
+    ));
+    assert!(matches!(
+        format_number(f64::NAN, "en-US"),
+        Err(AutomationError::InvalidArgument(_))
+    ));
+}
+
+#[test]
+fn test_format_date_patterns() {
+    let date = NaiveDate::from_ymd_opt(2024, 3, 7).unwrap();
+    assert_eq!(format_date(date, "dd.MM.yyyy").unwrap(), "07.03.2024");
+    assert_eq!(format_date(date, "M/d/yy").unwrap(), "3/7/24");
+    assert_eq!(format_date(date, "YYYY-MM-DD").unwrap(), "2024-03-07");
+    assert_eq!(format_date(date, "%d %B %Y").unwrap(), "07 March 2024");
+    // A locale tag uses that locale's short date format
+    assert_eq!(format_date(date, "en-US").unwrap(), "03/07/2024");
+    assert_eq!(format_date(date, "de-DE").unwrap(), "07.03.2024");
+}
+
+#[test]
+fn test_format_date_rejects_unknown_tokens() {
+    let date = NaiveDate::from_ymd_opt(2024, 3, 7).unwrap();
+    assert!(matches!(
+        format_date(date, "dd.MMM.yyyy"),
+        Err(AutomationError::InvalidArgument(_))
+    ));
+    assert!(matches!(
+        format_date(date, "%Q"),
+        Err(AutomationError::InvalidArgument(_))
+    ));
+}
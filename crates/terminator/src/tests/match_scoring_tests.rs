@@ -0,0 +1,101 @@
+use crate::locator::{
+    closest_matches, name_distance, selector_name_query, suggestion_hint, MatchScoring,
+};
+use crate::selector::Selector;
+
+#[test]
+fn test_name_match_ordering() {
+    let scoring = MatchScoring::default();
+    let exact = scoring.score(Some("Save"), Some("save"), false, false);
+    let prefix = scoring.score(Some("Save"), Some("Save As..."), false, false);
+    let substring = scoring.score(Some("Save"), Some("Autosave"), false, false);
+    let none = scoring.score(Some("Save"), Some("Open"), false, false);
+
+    assert!(exact > prefix);
+    assert!(prefix > substring);
+    assert!(substring > none);
+    assert_eq!(none, 0.0);
+}
+
+#[test]
+fn test_visibility_and_enabled_add_to_score() {
+    let scoring = MatchScoring::default();
+    let base = scoring.score(Some("OK"), Some("OK"), false, false);
+    assert_eq!(
+        scoring.score(Some("OK"), Some("OK"), true, false),
+        base + scoring.visible
+    );
+    assert_eq!(
+        scoring.score(Some("OK"), Some("OK"), true, true),
+        base + scoring.visible + scoring.enabled
+    );
+}
+
+#[test]
+fn test_custom_weights_bias_ranking() {
+    let scoring = MatchScoring {
+        enabled: 500.0,
+        ..MatchScoring::default()
+    };
+    // An enabled prefix match now beats a disabled exact match.
+    let disabled_exact = scoring.score(Some("Submit"), Some("Submit"), true, false);
+    let enabled_prefix = scoring.score(Some("Submit"), Some("Submit form"), true, true);
+    assert!(enabled_prefix > disabled_exact);
+}
+
+#[test]
+fn test_score_without_query_uses_state_only() {
+    let scoring = MatchScoring::default();
+    assert_eq!(
+        scoring.score(None, Some("Anything"), true, true),
+        scoring.visible + scoring.enabled
+    );
+    assert_eq!(scoring.score(Some(""), Some("Anything"), false, false), 0.0);
+}
+
+#[test]
+fn test_selector_name_query() {
+    assert_eq!(
+        selector_name_query(&Selector::from("role:Button|Submit")),
+        Some("Submit".to_string())
+    );
+    assert_eq!(
+        selector_name_query(&Selector::from("name:Save")),
+        Some("Save".to_string())
+    );
+    assert_eq!(
+        selector_name_query(&Selector::from("role:Window|Notepad >> role:Button|OK")),
+        Some("OK".to_string())
+    );
+    assert_eq!(selector_name_query(&Selector::from("role:Button")), None);
+}
+
+#[test]
+fn test_name_distance_is_case_insensitive() {
+    assert_eq!(name_distance("Save", "save"), 0);
+    assert_eq!(name_distance("Save", "Save As"), 3);
+    assert_eq!(name_distance("kitten", "sitting"), 3);
+    assert_eq!(name_distance("", "abc"), 3);
+}
+
+#[test]
+fn test_closest_matches_ranks_and_dedupes() {
+    let candidates = vec![
+        ("Button".to_string(), "Open".to_string()),
+        ("Button".to_string(), "Save As".to_string()),
+        ("Button".to_string(), "Sav".to_string()),
+        ("Button".to_string(), "Sav".to_string()),
+        ("Pane".to_string(), String::new()),
+        ("Button".to_string(), "xyz".to_string()),
+    ];
+    let matches = closest_matches("Save", candidates, 5);
+    let names: Vec<&str> = matches.iter().map(|(_, name)| name.as_str()).collect();
+    // "Open" and "xyz" share nothing with "Save"; the unnamed pane and duplicate are dropped
+    assert_eq!(names, vec!["Sav", "Save As"]);
+
+    assert_eq!(
+        suggestion_hint(&matches[..1]),
+        " Did you mean: Button \"Sav\"?"
+    );
+    assert_eq!(suggestion_hint(&[]), "");
+}
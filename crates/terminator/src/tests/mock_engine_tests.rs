@@ -0,0 +1,688 @@
+use super::node;
+use crate::locator::WaitCondition;
+use crate::platforms::mock::{MockAction, MockEngine};
+use crate::platforms::{AccessibilityEngine, TreeBuildConfig};
+use crate::{AutomationError, Desktop, MultiSelectMode, NameNormalization, Selector, UINode};
+use std::sync::Arc;
+use std::time::Duration;
+
+fn editor_tree() -> UINode {
+    node(
+        "desktop",
+        "Pane",
+        "Desktop",
+        vec![
+            node(
+                "editor",
+                "Window",
+                "Editor",
+                vec![
+                    node("body", "Edit", "Body", vec![]),
+                    node("save", "Button", "Save", vec![]),
+                    node("save-as", "Button", "Save As", vec![]),
+                ],
+            ),
+            node(
+                "dialog",
+                "Window",
+                "Confirm",
+                vec![node("ok", "Button", "OK", vec![])],
+            ),
+        ],
+    )
+}
+
+fn desktop() -> (Arc<MockEngine>, Desktop) {
+    let engine = Arc::new(MockEngine::new(editor_tree()));
+    let desktop = Desktop::with_engine(engine.clone());
+    (engine, desktop)
+}
+
+#[tokio::test]
+async fn test_click_records_the_resolved_element() {
+    let (engine, desktop) = desktop();
+
+    let button = desktop
+        .locator("role:Button|name:Save")
+        .first(None)
+        .await
+        .unwrap();
+    button.click().unwrap();
+
+    assert_eq!(
+        engine.actions(),
+        vec![MockAction {
+            element_id: Some("save".to_string()),
+            element_name: Some("Save".to_string()),
+            action: "click".to_string(),
+            argument: None,
+        }]
+    );
+}
+
+#[tokio::test]
+async fn test_chained_locator_searches_within_previous_match() {
+    let (_engine, desktop) = desktop();
+
+    let ok = desktop
+        .locator("role:Window|name:Confirm >> role:Button")
+        .first(None)
+        .await
+        .unwrap();
+    assert_eq!(ok.id().as_deref(), Some("ok"));
+
+    let second = desktop
+        .locator("role:Window|name:Editor >> role:Button >> nth=1")
+        .first(None)
+        .await
+        .unwrap();
+    assert_eq!(second.id().as_deref(), Some("save-as"));
+}
+
+#[tokio::test]
+async fn test_missing_element_times_out() {
+    let (engine, desktop) = desktop();
+
+    let result = desktop
+        .locator("role:Button|name:Delete")
+        .first(Some(std::time::Duration::from_millis(10)))
+        .await;
+
+    assert!(matches!(result, Err(AutomationError::Timeout(_))));
+    assert!(engine.actions().is_empty());
+}
+
+#[tokio::test]
+async fn test_missing_element_behind_uac_prompt_requires_elevation() {
+    let (engine, desktop) = desktop();
+    engine.set_elevation_prompt_active(true);
+    assert!(desktop.is_elevation_prompt_active());
+
+    let result = desktop
+        .locator("role:Button|name:Delete")
+        .first(Some(std::time::Duration::from_millis(10)))
+        .await;
+    assert!(matches!(result, Err(AutomationError::ElevationRequired(_))));
+
+    // Elements that are reachable are still found
+    assert!(desktop
+        .locator("role:Button|name:Save")
+        .first(Some(std::time::Duration::from_millis(10)))
+        .await
+        .is_ok());
+}
+
+#[tokio::test]
+async fn test_locate_all_keeps_error_kinds_for_duplicates() {
+    let (engine, desktop) = desktop();
+    engine.set_elevation_prompt_active(true);
+
+    let results = desktop
+        .locate_all(
+            &[
+                "role:Button|name:Delete",
+                "role:Button|name:Save",
+                "role:Button|name:Delete",
+            ],
+            Duration::from_millis(10),
+        )
+        .await;
+
+    assert!(matches!(
+        results[0],
+        Err(AutomationError::ElevationRequired(_))
+    ));
+    assert_eq!(results[1].as_ref().unwrap().id().as_deref(), Some("save"));
+    assert!(matches!(
+        results[2],
+        Err(AutomationError::ElevationRequired(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_typing_updates_the_value() {
+    let (engine, desktop) = desktop();
+
+    let body = desktop.locator("role:Edit").first(None).await.unwrap();
+    body.type_text("hello", false).unwrap();
+    body.type_text(" world", false).unwrap();
+
+    assert_eq!(
+        engine
+            .element("body")
+            .unwrap()
+            .get_value()
+            .unwrap()
+            .as_deref(),
+        Some("hello world")
+    );
+    assert_eq!(engine.actions()[1].argument.as_deref(), Some(" world"));
+}
+
+#[test]
+fn test_navigation_follows_the_scripted_tree() {
+    let engine = MockEngine::new(editor_tree());
+    let save = engine.element("save").unwrap();
+
+    assert_eq!(
+        save.parent().unwrap().unwrap().id().as_deref(),
+        Some("editor")
+    );
+    assert_eq!(
+        save.window().unwrap().unwrap().id().as_deref(),
+        Some("editor")
+    );
+    let applications = engine.get_applications().unwrap();
+    assert_eq!(applications.len(), 2);
+    assert_eq!(
+        engine
+            .get_application_by_name("confirm")
+            .unwrap()
+            .id()
+            .as_deref(),
+        Some("dialog")
+    );
+}
+
+#[test]
+fn test_unsupported_selector_is_reported() {
+    let engine = MockEngine::new(editor_tree());
+
+    let result = engine.find_elements(&Selector::Visible(true), None, None, None);
+
+    assert!(matches!(
+        result,
+        Err(AutomationError::UnsupportedOperation(_))
+    ));
+}
+
+#[test]
+fn test_tree_respects_max_depth() {
+    let engine = MockEngine::new(editor_tree());
+    let config = TreeBuildConfig {
+        max_depth: Some(1),
+        ..Default::default()
+    };
+
+    let tree = engine
+        .get_tree_from_element(&engine.get_root_element(), config)
+        .unwrap();
+
+    assert_eq!(tree.children.len(), 2);
+    assert!(tree.children.iter().all(|child| child.children.is_empty()));
+}
+
+#[tokio::test]
+async fn test_scan_codes_are_recorded_with_extended_prefix() {
+    let (engine, desktop) = desktop();
+
+    desktop.press_scan_code(0x1C, false).await.unwrap();
+    desktop.press_scan_code(0x48, true).await.unwrap();
+
+    let codes: Vec<_> = engine
+        .actions()
+        .into_iter()
+        .map(|a| (a.action, a.argument.unwrap_or_default()))
+        .collect();
+    assert_eq!(
+        codes,
+        vec![
+            ("press_scan_code".to_string(), "1C".to_string()),
+            ("press_scan_code".to_string(), "E0 48".to_string()),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_to_selector_disambiguates_and_round_trips() {
+    let (engine, desktop) = desktop();
+
+    let save = engine.element("save").unwrap();
+    let selector = save.to_selector().await.unwrap();
+
+    // "name:Save" also matches "Save As", so the step needs an index
+    assert_eq!(
+        selector,
+        "role:Window && name:Editor >> role:Button && name:Save >> nth=0"
+    );
+    let resolved = desktop
+        .locator(selector.as_str())
+        .first(None)
+        .await
+        .unwrap();
+    assert_eq!(resolved.id().as_deref(), Some("save"));
+
+    let save_as = engine.element("save-as").unwrap().to_selector().await;
+    assert_eq!(
+        save_as.unwrap(),
+        "role:Window && name:Editor >> role:Button && name:Save As"
+    );
+}
+
+#[tokio::test]
+async fn test_to_selector_only_indexes_the_last_step() {
+    // Two identical windows: the ambiguity is in an ancestor, not the button itself
+    let window = |id: &str, button: &str| {
+        node(
+            id,
+            "Window",
+            "Editor",
+            vec![node(button, "Button", "Close", vec![])],
+        )
+    };
+    let engine = Arc::new(MockEngine::new(node(
+        "desktop",
+        "Pane",
+        "Desktop",
+        vec![window("first", "close-1"), window("second", "close-2")],
+    )));
+    let desktop = Desktop::with_engine(engine.clone());
+
+    let selector = engine
+        .element("close-2")
+        .unwrap()
+        .to_selector()
+        .await
+        .unwrap();
+    // A mid-chain nth= means "nth child" on Windows, so only a trailing one is emitted
+    assert_eq!(
+        selector,
+        "role:Window && name:Editor >> role:Button && name:Close >> nth=1"
+    );
+    let resolved = desktop
+        .locator(selector.as_str())
+        .first(None)
+        .await
+        .unwrap();
+    assert_eq!(resolved.id().as_deref(), Some("close-2"));
+}
+
+#[tokio::test]
+async fn test_to_selector_prefers_automation_id() {
+    let mut field = node("field", "Edit", "Search", vec![]);
+    field.attributes.properties = std::collections::HashMap::from([(
+        "AutomationId".to_string(),
+        Some(serde_json::json!("SearchBox")),
+    )]);
+    let engine = Arc::new(MockEngine::new(node(
+        "desktop",
+        "Pane",
+        "Desktop",
+        vec![node("app", "Window", "App", vec![field])],
+    )));
+
+    let selector = engine
+        .element("field")
+        .unwrap()
+        .to_selector()
+        .await
+        .unwrap();
+    assert_eq!(selector, "role:Window && name:App >> nativeid:SearchBox");
+
+    let root = engine.element("desktop").unwrap().to_selector().await;
+    assert!(matches!(root, Err(AutomationError::InvalidArgument(_))));
+}
+
+#[tokio::test]
+async fn test_app_prefix_scopes_the_chain_to_one_application() {
+    let mut notepad = node(
+        "notepad",
+        "Window",
+        "Notepad",
+        vec![node("notepad-edit", "Edit", "Text Editor", vec![])],
+    );
+    notepad.attributes.properties =
+        std::collections::HashMap::from([("ProcessId".to_string(), Some(serde_json::json!(7)))]);
+    let mut root = editor_tree();
+    root.children.push(notepad);
+    let desktop = Desktop::with_engine(Arc::new(MockEngine::new(root)));
+
+    let by_name = desktop
+        .locator("app:notepad >> role:Edit")
+        .all(None, None)
+        .await
+        .unwrap();
+    assert_eq!(by_name.len(), 1);
+    assert_eq!(by_name[0].id().as_deref(), Some("notepad-edit"));
+
+    let by_pid = desktop
+        .locator("app:7 >> role:Edit")
+        .first(None)
+        .await
+        .unwrap();
+    assert_eq!(by_pid.id().as_deref(), Some("notepad-edit"));
+}
+
+#[test]
+fn test_is_hittable_detects_occlusion_and_zero_area() {
+    fn bounded(
+        id: &str,
+        role: &str,
+        bounds: (f64, f64, f64, f64),
+        children: Vec<UINode>,
+    ) -> UINode {
+        let mut node = node(id, role, id, children);
+        node.attributes.bounds = Some(bounds);
+        node
+    }
+    // The popup comes first, so it is on top of the left half of the editor
+    let root = node(
+        "desktop",
+        "Pane",
+        "Desktop",
+        vec![
+            bounded("popup", "Window", (0.0, 0.0, 200.0, 200.0), vec![]),
+            bounded(
+                "editor",
+                "Window",
+                (0.0, 0.0, 400.0, 400.0),
+                vec![
+                    bounded("covered", "Button", (50.0, 50.0, 20.0, 20.0), vec![]),
+                    bounded(
+                        "toolbar",
+                        "ToolBar",
+                        (250.0, 250.0, 100.0, 100.0),
+                        vec![bounded("icon", "Image", (290.0, 290.0, 20.0, 20.0), vec![])],
+                    ),
+                    bounded("empty", "Button", (300.0, 50.0, 0.0, 0.0), vec![]),
+                ],
+            ),
+        ],
+    );
+    let engine = MockEngine::new(root);
+
+    assert!(!engine.element("covered").unwrap().is_hittable());
+    // A click on the toolbar's center lands on its icon, which is still the toolbar
+    assert!(engine.element("toolbar").unwrap().is_hittable());
+    assert!(engine.element("icon").unwrap().is_hittable());
+    assert!(!engine.element("empty").unwrap().is_hittable());
+}
+
+#[test]
+fn test_invoke_pattern_method_records_the_call() {
+    let engine = MockEngine::new(editor_tree());
+
+    let result = engine
+        .element("body")
+        .unwrap()
+        .invoke_pattern_method(
+            crate::ControlPattern::Value,
+            "SetValue",
+            &[serde_json::json!("hello")],
+        )
+        .unwrap();
+
+    assert_eq!(result, serde_json::Value::Null);
+    let actions = engine.actions();
+    assert_eq!(actions[0].action, "invoke_pattern_method");
+    assert_eq!(actions[0].element_id.as_deref(), Some("body"));
+    assert_eq!(
+        actions[0].argument.as_deref(),
+        Some(r#"Value.SetValue["hello"]"#)
+    );
+}
+
+#[test]
+fn test_drag_path_sends_every_point_and_rejects_short_paths() {
+    let (engine, desktop) = desktop();
+
+    desktop
+        .drag_path(&[(10.0, 10.0), (20.5, 30.0), (40.0, 10.0)], 0)
+        .unwrap();
+    assert!(matches!(
+        desktop.drag_path(&[(10.0, 10.0)], 0),
+        Err(AutomationError::InvalidArgument(_))
+    ));
+
+    assert_eq!(
+        engine.actions(),
+        vec![MockAction {
+            element_id: None,
+            element_name: None,
+            action: "drag_path".to_string(),
+            argument: Some("10,10 20.5,30 40,10".to_string()),
+        }]
+    );
+}
+
+fn selectable_list(can_select_multiple: bool) -> UINode {
+    let mut list = node(
+        "list",
+        "List",
+        "Files",
+        ["a", "b", "c", "d"]
+            .iter()
+            .map(|id| node(id, "ListItem", id, vec![]))
+            .collect(),
+    );
+    list.attributes.properties = std::collections::HashMap::from([(
+        "CanSelectMultiple".to_string(),
+        Some(serde_json::json!(can_select_multiple)),
+    )]);
+    list
+}
+
+fn selection_actions(engine: &MockEngine) -> Vec<(String, String, Option<String>)> {
+    engine
+        .actions()
+        .into_iter()
+        .map(|a| (a.element_id.unwrap_or_default(), a.action, a.argument))
+        .collect()
+}
+
+#[test]
+fn test_multi_select_clicks_with_modifiers_without_selection_pattern() {
+    let engine = MockEngine::new(selectable_list(false));
+    let list = engine.element("list").unwrap();
+    let (a, c, d) = (
+        engine.element("a").unwrap(),
+        engine.element("c").unwrap(),
+        engine.element("d").unwrap(),
+    );
+
+    list.multi_select(&[&a, &c], MultiSelectMode::Add).unwrap();
+    list.multi_select(&[&a, &d], MultiSelectMode::Range)
+        .unwrap();
+    assert!(matches!(
+        list.multi_select(&[], MultiSelectMode::Add),
+        Err(AutomationError::InvalidArgument(_))
+    ));
+
+    let ctrl = Some("Ctrl".to_string());
+    assert_eq!(
+        selection_actions(&engine),
+        vec![
+            ("a".into(), "click_with_modifiers".into(), ctrl.clone()),
+            ("c".into(), "click_with_modifiers".into(), ctrl),
+            ("a".into(), "click".into(), None),
+            (
+                "d".into(),
+                "click_with_modifiers".into(),
+                Some("Shift".into())
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_multi_select_uses_selection_pattern_when_supported() {
+    let engine = MockEngine::new(selectable_list(true));
+    let list = engine.element("list").unwrap();
+    let (b, d) = (engine.element("b").unwrap(), engine.element("d").unwrap());
+
+    list.multi_select(&[&d, &b], MultiSelectMode::Range)
+        .unwrap();
+
+    assert_eq!(
+        selection_actions(&engine),
+        vec![
+            ("d".into(), "set_selected".into(), Some("true".into())),
+            ("b".into(), "add_to_selection".into(), None),
+            ("c".into(), "add_to_selection".into(), None),
+        ]
+    );
+    assert!(!engine.element("a").unwrap().is_selected().unwrap());
+    assert!(engine.element("c").unwrap().is_selected().unwrap());
+}
+
+#[tokio::test]
+async fn test_range_selector_slices_matches() {
+    let engine = Arc::new(MockEngine::new(selectable_list(false)));
+    let desktop = Desktop::with_engine(engine.clone());
+    let ids = |elements: Vec<crate::UIElement>| -> Vec<String> {
+        elements.iter().filter_map(|e| e.id()).collect()
+    };
+
+    let middle = desktop
+        .locator("role:ListItem >> range:1..3")
+        .all(None, None)
+        .await
+        .unwrap();
+    let tail = desktop
+        .locator("role:ListItem >> range:2..")
+        .all(None, None)
+        .await
+        .unwrap();
+    let past_end = desktop
+        .locator("role:ListItem >> range:7..9")
+        .all(None, None)
+        .await
+        .unwrap();
+
+    assert_eq!(ids(middle), ["b", "c"]);
+    assert_eq!(ids(tail), ["c", "d"]);
+    assert!(past_end.is_empty());
+}
+
+#[test]
+fn test_sibling_index_is_position_under_parent() {
+    let engine = MockEngine::new(selectable_list(false));
+
+    assert_eq!(engine.element("a").unwrap().sibling_index(), Some(0));
+    assert_eq!(engine.element("c").unwrap().sibling_index(), Some(2));
+    assert_eq!(engine.element("list").unwrap().sibling_index(), None);
+    // The mock has no native windows to stack
+    assert_eq!(engine.element("c").unwrap().z_order(), None);
+}
+
+fn menu_tree() -> UINode {
+    node(
+        "menu",
+        "Menu",
+        "File",
+        vec![
+            node("open", "MenuItem", "&Open...", vec![]),
+            node("save-as", "MenuItem", "Save  &As\tCtrl+Shift+S", vec![]),
+            node("export", "MenuItem", "Export(&E)…", vec![]),
+        ],
+    )
+}
+
+#[test]
+fn test_name_normalization_rules() {
+    let all = NameNormalization::default();
+    assert_eq!(all.normalize("&Open..."), "Open");
+    assert_eq!(all.normalize("Save  &As\tCtrl+Shift+S"), "Save As");
+    assert_eq!(all.normalize("Export(&E)…"), "Export");
+    assert_eq!(all.normalize("Find && Replace"), "Find & Replace");
+
+    let mnemonics_only = NameNormalization {
+        strip_mnemonics: true,
+        collapse_whitespace: false,
+        strip_ellipsis: false,
+        strip_access_key_hints: false,
+    };
+    assert_eq!(mnemonics_only.normalize("&Open..."), "Open...");
+}
+
+#[tokio::test]
+async fn test_locator_name_normalization_matches_decorated_names() {
+    let engine = Arc::new(MockEngine::new(menu_tree()));
+    let desktop = Desktop::with_engine(engine.clone());
+
+    let plain = desktop
+        .locator("role:MenuItem|Save As...")
+        .all(None, None)
+        .await
+        .unwrap();
+    assert!(plain.is_empty());
+
+    let save_as = desktop
+        .locator("role:MenuItem|Save As...")
+        .with_name_normalization(NameNormalization::default())
+        .first(None)
+        .await
+        .unwrap();
+    let export = desktop
+        .locator("role:Menu|File >> role:MenuItem|&Export")
+        .with_name_normalization(NameNormalization::default())
+        .all(None, None)
+        .await
+        .unwrap();
+
+    assert_eq!(save_as.id().as_deref(), Some("save-as"));
+    assert_eq!(export.len(), 1);
+    assert_eq!(export[0].id().as_deref(), Some("export"));
+}
+
+#[tokio::test]
+async fn test_name_normalization_stays_within_the_locator_root() {
+    let tree = node(
+        "root",
+        "Window",
+        "App",
+        vec![
+            node("edit", "Menu", "Edit", vec![]),
+            node(
+                "file",
+                "Menu",
+                "File",
+                vec![node("save", "MenuItem", "&Save", vec![])],
+            ),
+            node("toolbar-save", "MenuItem", "Save...", vec![]),
+        ],
+    );
+    let desktop = Desktop::with_engine(Arc::new(MockEngine::new(tree)));
+    let edit = desktop.locator("role:Menu|Edit").first(None).await.unwrap();
+    let file = desktop.locator("role:Menu|File").first(None).await.unwrap();
+
+    let in_edit = desktop
+        .locator("role:MenuItem|Save")
+        .within(edit)
+        .with_name_normalization(NameNormalization::default())
+        .all(None, None)
+        .await
+        .unwrap();
+    let in_file = desktop
+        .locator("role:MenuItem|Save")
+        .within(file)
+        .with_name_normalization(NameNormalization::default())
+        .all(None, None)
+        .await
+        .unwrap();
+
+    assert!(in_edit.is_empty());
+    assert_eq!(in_file.len(), 1);
+    assert_eq!(in_file[0].id().as_deref(), Some("save"));
+}
+
+#[tokio::test]
+async fn test_wait_enabled_uses_name_normalization() {
+    let desktop = Desktop::with_engine(Arc::new(MockEngine::new(menu_tree())));
+    let locator = desktop
+        .locator("role:MenuItem|Open")
+        .with_name_normalization(NameNormalization::default());
+
+    let open = locator
+        .wait_enabled(Some(Duration::from_millis(300)))
+        .await
+        .unwrap();
+    assert_eq!(open.id().as_deref(), Some("open"));
+
+    let missing = desktop
+        .locator("role:MenuItem|Close")
+        .with_name_normalization(NameNormalization::default())
+        .wait_for(WaitCondition::Exists, Some(Duration::from_millis(300)))
+        .await;
+    assert!(matches!(missing, Err(AutomationError::Timeout(_))));
+}
@@ -1,23 +1,83 @@
 mod boolean_selector_tests;
 #[cfg(test)]
+mod browser_tabs_tests;
+#[cfg(test)]
+mod cdp_tests;
+#[cfg(test)]
+mod clock_tests;
+#[cfg(test)]
 mod close_tab_tests;
+#[cfg(test)]
+mod coordinate_mapper_tests;
 mod e2e_tests;
+#[cfg(test)]
+mod engine_init_tests;
 mod firefox_window_tests;
 mod functional_verification_tests;
 mod get_applications_tests;
 #[cfg(test)]
 mod high_level_inputs_tests;
+#[cfg(test)]
+mod hotkey_tests;
 #[cfg(all(test, target_os = "windows"))]
 mod id_stability_tests;
+#[cfg(test)]
+mod index_cache_tests;
+#[cfg(test)]
+mod inner_text_tests;
+#[cfg(test)]
+mod locale_format_tests;
+#[cfg(test)]
+mod match_scoring_tests;
+#[cfg(test)]
+mod mock_engine_tests;
+#[cfg(test)]
+mod modal_tests;
 mod notepad_selector_test;
 #[cfg(test)]
 mod parent_navigation_tests;
 #[cfg(test)]
 mod performance_tests;
 #[cfg(all(test, target_os = "windows"))]
+mod popup_classification_tests;
+#[cfg(all(test, target_os = "windows"))]
+mod process_wait_tests;
+#[cfg(test)]
+mod read_form_tests;
+#[cfg(test)]
+mod read_interleaved_tests;
+#[cfg(test)]
+mod rect_tests;
+#[cfg(test)]
+mod screenshot_annotate_tests;
+#[cfg(test)]
+mod screenshot_diff_tests;
+#[cfg(test)]
+mod screenshot_encoding_tests;
+#[cfg(all(test, target_os = "windows"))]
 mod selector_tests;
+#[cfg(test)]
+mod stable_id_tests;
+#[cfg(test)]
+mod stale_element_tests;
 mod test_serialization;
 
+use crate::{UIElementAttributes, UINode};
+
+/// A mock-engine tree node with the given id, role and name
+pub fn node(id: &str, role: &str, name: &str, children: Vec<UINode>) -> UINode {
+    UINode {
+        id: Some(id.to_string()),
+        attributes: UIElementAttributes {
+            role: role.to_string(),
+            name: Some(name.to_string()),
+            ..Default::default()
+        },
+        children,
+        selector: None,
+    }
+}
+
 // Initialize tracing for tests
 pub fn init_tracing() {
     use tracing_subscriber::{fmt, EnvFilter};
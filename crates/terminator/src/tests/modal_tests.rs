@@ -0,0 +1,51 @@
+use super::node;
+use crate::modal::{containing_window, ModalMatcher};
+use crate::platforms::mock::MockEngine;
+
+#[test]
+fn test_title_matcher_is_case_insensitive_substring() {
+    let matcher = ModalMatcher::title("save changes");
+    assert!(matcher.matches("Notepad - Save Changes?", None));
+    assert!(!matcher.matches("Untitled - Notepad", None));
+}
+
+#[test]
+fn test_process_matcher_ignores_exe_suffix() {
+    let matcher = ModalMatcher::title("Allow").with_process("Chrome.exe");
+    assert!(matcher.matches("Allow notifications?", Some("chrome")));
+    assert!(!matcher.matches("Allow notifications?", Some("msedge")));
+    assert!(!matcher.matches("Allow notifications?", None));
+}
+
+#[test]
+fn test_process_only_matcher() {
+    let matcher = ModalMatcher {
+        process: Some("notepad".to_string()),
+        ..ModalMatcher::default()
+    };
+    assert!(matcher.matches("Anything", Some("notepad.exe")));
+}
+
+#[test]
+fn test_empty_matcher_never_matches() {
+    assert!(!ModalMatcher::default().matches("Save changes?", Some("notepad")));
+}
+
+#[test]
+fn test_containing_window_skips_panes() {
+    let engine = MockEngine::new(node(
+        "dialog",
+        "Window",
+        "Save changes?",
+        vec![node(
+            "body",
+            "Pane",
+            "Content",
+            vec![node("save", "Button", "Save", vec![])],
+        )],
+    ));
+
+    let window = containing_window(&engine.element("save").unwrap()).unwrap();
+    assert_eq!(window.id().as_deref(), Some("dialog"));
+    assert_eq!(window.name().as_deref(), Some("Save changes?"));
+}
@@ -268,3 +268,77 @@ fn test_parent_navigation_consistency() {
         panic!("Failed to open Notepad for testing");
     }
 }
+
+#[test]
+#[cfg(target_os = "windows")]
+fn test_ancestor_relationships() {
+    println!("\n🔍 Testing ancestor/descendant relationship checks");
+
+    let fixture = ParentTestFixture::new().expect("Failed to create test fixture");
+
+    if let Some(ref app) = fixture.app {
+        let children = app.children().expect("Failed to get Notepad children");
+        let child = children.first().expect("Notepad should have children");
+
+        assert!(child.runtime_id().is_some(), "UIA exposes a runtime id");
+        assert!(child.is_descendant_of(app), "Child should descend from app");
+        assert!(!app.is_descendant_of(child), "App is not inside its child");
+        assert!(
+            !child.is_descendant_of(child),
+            "Element is not its own descendant"
+        );
+
+        let common = child
+            .common_ancestor(app)
+            .expect("Child and app share an ancestor");
+        assert_eq!(common.runtime_id(), app.runtime_id());
+
+        if let Some(sibling) = children.get(1) {
+            let common = child
+                .common_ancestor(sibling)
+                .expect("Siblings share an ancestor");
+            assert!(child.is_descendant_of(&common));
+            assert!(sibling.is_descendant_of(&common));
+            println!("  ✅ Siblings share ancestor role='{}'", common.role());
+        }
+    } else {
+        panic!("Failed to open Notepad for testing");
+    }
+}
+
+#[test]
+#[cfg(target_os = "windows")]
+fn test_find_parent_window_with_limit() {
+    println!("\n🔍 Testing find_parent_window iteration limits");
+
+    let fixture = ParentTestFixture::new().expect("Failed to create test fixture");
+
+    if let Some(ref app) = fixture.app {
+        let children = app.children().expect("Failed to get Notepad children");
+        let child = children
+            .iter()
+            .find(|c| c.role() != "Window" && c.role() != "Pane")
+            .expect("Notepad should have a non-window child");
+
+        let window = crate::find_parent_window_with_limit(child, 100)
+            .expect("Walk should succeed")
+            .expect("Notepad child should have a parent window");
+        assert!(window.role() == "Window" || window.role() == "Pane");
+
+        match crate::find_parent_window_with_limit(child, 1) {
+            Err(AutomationError::TraversalLimitExceeded(msg)) => {
+                println!("  ✅ Limit reported: {msg}");
+            }
+            other => panic!("Expected TraversalLimitExceeded, got {other:?}"),
+        }
+
+        // The Option-returning wrapper swallows the limit error
+        assert_eq!(
+            crate::parent_window_max_iterations(),
+            crate::DEFAULT_PARENT_WINDOW_MAX_ITERATIONS
+        );
+        assert!(crate::find_parent_window(child).is_some());
+    } else {
+        panic!("Failed to open Notepad for testing");
+    }
+}
@@ -0,0 +1,57 @@
+use crate::platforms::windows::window_manager::is_transient_popup;
+use windows::Win32::UI::WindowsAndMessaging::{
+    WS_CAPTION, WS_EX_LAYERED, WS_EX_TOOLWINDOW, WS_EX_TOPMOST, WS_EX_TRANSPARENT,
+    WS_OVERLAPPEDWINDOW, WS_POPUP,
+};
+
+#[test]
+fn test_known_popup_classes_are_transient() {
+    for class in [
+        "tooltips_class32",
+        "#32768",
+        "Auto-Suggest Dropdown",
+        "ComboLBox",
+    ] {
+        assert!(is_transient_popup(class, WS_POPUP.0, 0, false), "{class}");
+    }
+}
+
+#[test]
+fn test_owned_captionless_tool_windows_are_transient() {
+    let style = WS_POPUP.0;
+    let ex_style = WS_EX_TOOLWINDOW.0 | WS_EX_TOPMOST.0;
+
+    assert!(is_transient_popup(
+        "Chrome_WidgetWin_1",
+        style,
+        ex_style,
+        true
+    ));
+    // Unowned, like the taskbar
+    assert!(!is_transient_popup("Shell_TrayWnd", style, ex_style, false));
+    // Has a title bar, like a floating palette
+    assert!(!is_transient_popup(
+        "Chrome_WidgetWin_1",
+        style | WS_CAPTION.0,
+        ex_style,
+        true
+    ));
+}
+
+#[test]
+fn test_regular_and_click_through_windows_are_not_transient() {
+    assert!(!is_transient_popup(
+        "Notepad",
+        WS_OVERLAPPEDWINDOW.0,
+        0,
+        false
+    ));
+    // Highlight and action overlays are click-through
+    let overlay = WS_EX_LAYERED.0 | WS_EX_TRANSPARENT.0 | WS_EX_TOPMOST.0 | WS_EX_TOOLWINDOW.0;
+    assert!(!is_transient_popup(
+        "tooltips_class32",
+        WS_POPUP.0,
+        overlay,
+        true
+    ));
+}
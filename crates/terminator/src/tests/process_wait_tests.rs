@@ -0,0 +1,47 @@
+use crate::{AutomationError, Desktop};
+use std::time::Duration;
+
+#[tokio::test]
+async fn test_wait_for_process_exit_returns_exit_code() -> Result<(), AutomationError> {
+    let desktop = Desktop::new(false, false)?;
+    let mut child = std::process::Command::new("cmd")
+        .args(["/c", "exit", "3"])
+        .spawn()
+        .expect("spawn cmd");
+
+    let result = desktop
+        .wait_for_process_exit(child.id(), Duration::from_secs(10))
+        .await;
+    let _ = child.wait();
+    assert_eq!(result?, 3);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_wait_for_process_exit_times_out() -> Result<(), AutomationError> {
+    let desktop = Desktop::new(false, false)?;
+    let mut child = std::process::Command::new("ping")
+        .args(["-n", "10", "127.0.0.1"])
+        .stdout(std::process::Stdio::null())
+        .spawn()
+        .expect("spawn ping");
+
+    let result = desktop
+        .wait_for_process_exit(child.id(), Duration::from_millis(200))
+        .await;
+    let _ = child.kill();
+    let _ = child.wait();
+    assert!(matches!(result, Err(AutomationError::Timeout(_))));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_wait_for_process_exit_unknown_pid() -> Result<(), AutomationError> {
+    let desktop = Desktop::new(false, false)?;
+    // PIDs are multiples of 4 on Windows, so this one can never exist
+    let result = desktop
+        .wait_for_process_exit(u32::MAX - 2, Duration::from_millis(100))
+        .await;
+    assert!(matches!(result, Err(AutomationError::ElementNotFound(_))));
+    Ok(())
+}
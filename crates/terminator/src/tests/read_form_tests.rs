@@ -0,0 +1,95 @@
+use super::node;
+use crate::platforms::mock::MockEngine;
+use crate::{Desktop, FormField, UINode};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+fn boxed(id: &str, role: &str, name: &str, bounds: (f64, f64, f64, f64)) -> UINode {
+    let mut element = node(id, role, name, vec![]);
+    element.attributes.bounds = Some(bounds);
+    element
+}
+
+fn signup_form() -> (Arc<MockEngine>, Desktop) {
+    let mut name = boxed("name", "Edit", "", (80.0, 10.0, 200.0, 20.0));
+    name.attributes.value = Some("Ada".to_string());
+    let mut email = boxed("email", "Edit", "", (80.0, 62.0, 200.0, 20.0));
+    email.attributes.value = Some("ada@example.com".to_string());
+    let mut subscribe = boxed("subscribe", "CheckBox", "", (80.0, 100.0, 20.0, 20.0));
+    subscribe.attributes.is_toggled = Some(true);
+    subscribe.attributes.properties = HashMap::from([(
+        "LabeledBy".to_string(),
+        Some(serde_json::json!("news-label")),
+    )]);
+    let notes = boxed("notes", "Edit", "Notes", (500.0, 350.0, 80.0, 20.0));
+
+    let mut group = boxed("group", "Group", "", (0.0, 0.0, 300.0, 130.0));
+    group.children = vec![
+        boxed("name-label", "Text", "Name:", (10.0, 10.0, 60.0, 20.0)),
+        name,
+        boxed("email-label", "Text", "Email", (80.0, 40.0, 100.0, 20.0)),
+        email,
+        subscribe,
+    ];
+    let mut window = boxed("signup", "Window", "Signup", (0.0, 0.0, 600.0, 400.0));
+    window.children = vec![
+        group,
+        boxed(
+            "news-label",
+            "Text",
+            "Send me news",
+            (400.0, 300.0, 100.0, 20.0),
+        ),
+        notes,
+    ];
+    let mut root = boxed("desktop", "Pane", "Desktop", (0.0, 0.0, 1920.0, 1080.0));
+    root.children = vec![window];
+
+    let engine = Arc::new(MockEngine::new(root));
+    let desktop = Desktop::with_engine(engine.clone());
+    (engine, desktop)
+}
+
+fn summary(fields: &[FormField]) -> Vec<(Option<&str>, &str, Option<&str>)> {
+    fields
+        .iter()
+        .map(|f| (f.label.as_deref(), f.role.as_str(), f.value.as_deref()))
+        .collect()
+}
+
+#[tokio::test]
+async fn test_read_form_pairs_inputs_with_labels() {
+    let (engine, _desktop) = signup_form();
+
+    let fields = engine.element("signup").unwrap().read_form().await;
+
+    assert_eq!(
+        summary(&fields),
+        vec![
+            // Label on the same row, to the left
+            (Some("Name:"), "Edit", Some("Ada")),
+            // Label directly above
+            (Some("Email"), "Edit", Some("ada@example.com")),
+            // Declared LabeledBy wins over the spatially closer "Email"
+            (Some("Send me news"), "CheckBox", Some("true")),
+            // Nothing nearby
+            (None, "Edit", None),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_read_form_selectors_resolve_to_the_inputs() {
+    let (engine, _desktop) = signup_form();
+
+    let form = engine.element("signup").unwrap();
+    let fields = form.read_form().await;
+
+    // Selectors are relative to the form, so only its subtree is searched
+    for (field, id) in fields.iter().zip(["name", "email", "subscribe", "notes"]) {
+        let selector = field.selector.as_deref().unwrap();
+        assert!(!selector.contains("name:Signup"), "{selector}");
+        let resolved = form.locator(selector).unwrap().first(None).await.unwrap();
+        assert_eq!(resolved.id().as_deref(), Some(id), "{selector}");
+    }
+}
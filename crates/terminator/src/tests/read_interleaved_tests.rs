@@ -0,0 +1,60 @@
+use crate::utils::read_interleaved;
+use std::time::Duration;
+use tokio::io::{duplex, AsyncWriteExt, DuplexStream};
+
+/// Write `text` and give the reader a moment to pick it up, so the order of
+/// writes across the two streams is the order the reader sees them in
+async fn write(stream: &mut DuplexStream, text: &str) {
+    stream.write_all(text.as_bytes()).await.unwrap();
+    tokio::time::sleep(Duration::from_millis(20)).await;
+}
+
+#[tokio::test]
+async fn test_lines_are_interleaved_in_arrival_order() {
+    let (mut out, out_reader) = duplex(64);
+    let (mut err, err_reader) = duplex(64);
+    let reader = tokio::spawn(read_interleaved(out_reader, err_reader));
+
+    write(&mut out, "compiling\n").await;
+    write(&mut err, "warning: unused\n").await;
+    write(&mut out, "done\n").await;
+    drop((out, err));
+
+    let (stdout, stderr, combined) = reader.await.unwrap().unwrap();
+    assert_eq!(stdout, "compiling\ndone\n");
+    assert_eq!(stderr, "warning: unused\n");
+    assert_eq!(combined, "compiling\nwarning: unused\ndone\n");
+}
+
+#[tokio::test]
+async fn test_unterminated_last_line_is_kept() {
+    let (mut out, out_reader) = duplex(64);
+    let (err, err_reader) = duplex(64);
+    let reader = tokio::spawn(read_interleaved(out_reader, err_reader));
+
+    write(&mut out, "first\nno newline").await;
+    drop((out, err));
+
+    let (stdout, stderr, combined) = reader.await.unwrap().unwrap();
+    assert_eq!(stdout, "first\nno newline");
+    assert_eq!(stderr, "");
+    assert_eq!(combined, "first\nno newline");
+}
+
+#[tokio::test]
+async fn test_one_stream_closing_early_keeps_reading_the_other() {
+    let (mut out, out_reader) = duplex(64);
+    let (mut err, err_reader) = duplex(64);
+    let reader = tokio::spawn(read_interleaved(out_reader, err_reader));
+
+    write(&mut err, "fatal: retrying\n").await;
+    drop(err);
+    write(&mut out, "attempt 2\n").await;
+    write(&mut out, "ok\n").await;
+    drop(out);
+
+    let (stdout, stderr, combined) = reader.await.unwrap().unwrap();
+    assert_eq!(stdout, "attempt 2\nok\n");
+    assert_eq!(stderr, "fatal: retrying\n");
+    assert_eq!(combined, "fatal: retrying\nattempt 2\nok\n");
+}
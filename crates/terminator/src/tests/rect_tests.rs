@@ -0,0 +1,49 @@
+use crate::Rect;
+
+#[test]
+fn test_rect_edges_and_center() {
+    let r = Rect::new(10.0, 20.0, 100.0, 50.0);
+    assert_eq!(r.right(), 110.0);
+    assert_eq!(r.bottom(), 70.0);
+    assert_eq!(r.center(), (60.0, 45.0));
+    assert_eq!(Rect::from((10.0, 20.0, 100.0, 50.0)), r);
+}
+
+#[test]
+fn test_rect_intersection() {
+    let a = Rect::new(0.0, 0.0, 100.0, 100.0);
+    let b = Rect::new(50.0, 50.0, 100.0, 100.0);
+    assert_eq!(a.intersection(&b), Some(Rect::new(50.0, 50.0, 50.0, 50.0)));
+    assert!(a.overlaps(&b));
+    assert!(b.overlaps(&a));
+
+    // Sharing an edge is not an overlap
+    let touching = Rect::new(100.0, 0.0, 50.0, 50.0);
+    assert_eq!(a.intersection(&touching), None);
+    assert!(!a.overlaps(&touching));
+}
+
+#[test]
+fn test_rect_contains() {
+    let outer = Rect::new(0.0, 0.0, 100.0, 100.0);
+    assert!(outer.contains(&Rect::new(10.0, 10.0, 20.0, 20.0)));
+    assert!(outer.contains(&outer));
+    assert!(!outer.contains(&Rect::new(90.0, 90.0, 20.0, 20.0)));
+}
+
+#[test]
+fn test_rect_relative_position() {
+    let field = Rect::new(0.0, 0.0, 200.0, 30.0);
+    let error = Rect::new(0.0, 34.0, 200.0, 20.0);
+    assert!(field.is_above(&error));
+    assert!(!error.is_above(&field));
+
+    // Vertically adjacent rects still count as above
+    assert!(field.is_above(&Rect::new(0.0, 30.0, 10.0, 10.0)));
+
+    let label = Rect::new(0.0, 0.0, 50.0, 30.0);
+    let input = Rect::new(60.0, 0.0, 100.0, 30.0);
+    assert!(label.is_left_of(&input));
+    assert!(!input.is_left_of(&label));
+    assert!(!label.is_above(&input));
+}
@@ -0,0 +1,94 @@
+//! Tests for drawing vision-debugging annotations onto screenshots
+
+use crate::screenshot::{annotate, Color};
+use crate::{Rect, ScreenshotResult};
+
+fn blank(width: u32, height: u32) -> ScreenshotResult {
+    ScreenshotResult {
+        image_data: vec![0; (width * height * 4) as usize],
+        width,
+        height,
+        monitor: None,
+    }
+}
+
+fn pixel(shot: &ScreenshotResult, x: u32, y: u32) -> [u8; 4] {
+    let idx = ((y * shot.width + x) * 4) as usize;
+    shot.image_data[idx..idx + 4].try_into().unwrap()
+}
+
+#[test]
+fn test_annotate_draws_box_outline_in_bgra() {
+    let shot = blank(200, 100);
+    let annotated = annotate(
+        &shot,
+        &[(Rect::new(50.0, 40.0, 60.0, 30.0), String::new(), Color::RED)],
+    );
+
+    // Red is stored as BGRA like the rest of the capture pipeline
+    assert_eq!(pixel(&annotated, 50, 40), [0, 0, 255, 255]);
+    assert_eq!(pixel(&annotated, 109, 69), [0, 0, 255, 255]);
+    // Interior and exterior are untouched
+    assert_eq!(pixel(&annotated, 80, 55), [0, 0, 0, 0]);
+    assert_eq!(pixel(&annotated, 10, 10), [0, 0, 0, 0]);
+    // The source screenshot is not modified
+    assert!(shot.image_data.iter().all(|&b| b == 0));
+}
+
+#[test]
+fn test_annotate_places_label_above_box_when_room() {
+    let shot = blank(200, 100);
+    let annotated = annotate(
+        &shot,
+        &[(
+            Rect::new(20.0, 50.0, 100.0, 40.0),
+            "OK 1".into(),
+            Color::YELLOW,
+        )],
+    );
+
+    // Label background sits directly above the box
+    assert_eq!(pixel(&annotated, 21, 49), [0, 215, 255, 255]);
+    // Text on a light background is drawn in black
+    let label_row: Vec<[u8; 4]> = (20..60).map(|x| pixel(&annotated, x, 40)).collect();
+    assert!(label_row.contains(&[0, 0, 0, 255]));
+}
+
+#[test]
+fn test_annotate_places_label_inside_box_at_top_edge() {
+    let shot = blank(200, 100);
+    let annotated = annotate(
+        &shot,
+        &[(Rect::new(10.0, 0.0, 150.0, 60.0), "TOP".into(), Color::BLUE)],
+    );
+
+    // No room above, so the label fills the top of the box: the gap after
+    // the first glyph is background and the glyph itself is white text
+    assert_eq!(pixel(&annotated, 19, 3), [255, 90, 0, 255]);
+    assert_eq!(pixel(&annotated, 12, 3), [255, 255, 255, 255]);
+}
+
+#[test]
+fn test_annotate_clips_out_of_bounds_boxes() {
+    let shot = blank(50, 50);
+    let annotated = annotate(
+        &shot,
+        &[(
+            Rect::new(-20.0, -20.0, 200.0, 200.0),
+            "OFF SCREEN".into(),
+            Color::YELLOW,
+        )],
+    );
+    assert_eq!(annotated.image_data.len(), shot.image_data.len());
+}
+
+#[test]
+fn test_annotate_returns_copy_for_mismatched_data() {
+    let mut shot = blank(10, 10);
+    shot.image_data.truncate(12);
+    let annotated = annotate(
+        &shot,
+        &[(Rect::new(0.0, 0.0, 5.0, 5.0), "X".into(), Color::RED)],
+    );
+    assert_eq!(annotated.image_data, shot.image_data);
+}
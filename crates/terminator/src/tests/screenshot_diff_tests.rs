@@ -0,0 +1,69 @@
+//! Tests for pixel change detection and cropping of screenshots
+
+use crate::screenshot::changed_region;
+use crate::ScreenshotResult;
+
+fn solid(width: u32, height: u32, value: u8) -> ScreenshotResult {
+    ScreenshotResult {
+        image_data: vec![value; (width * height * 4) as usize],
+        width,
+        height,
+        monitor: None,
+    }
+}
+
+fn set_pixel(shot: &mut ScreenshotResult, x: u32, y: u32, bgra: [u8; 4]) {
+    let idx = ((y * shot.width + x) * 4) as usize;
+    shot.image_data[idx..idx + 4].copy_from_slice(&bgra);
+}
+
+#[test]
+fn test_identical_screenshots_have_no_changed_region() {
+    let before = solid(20, 10, 200);
+    assert_eq!(changed_region(&before, &before.clone(), 0), None);
+}
+
+#[test]
+fn test_changed_region_bounds_all_changed_pixels() {
+    let before = solid(20, 10, 200);
+    let mut after = before.clone();
+    set_pixel(&mut after, 3, 2, [0, 0, 255, 255]);
+    set_pixel(&mut after, 12, 7, [0, 255, 0, 255]);
+
+    assert_eq!(changed_region(&before, &after, 0), Some((3, 2, 10, 6)));
+}
+
+#[test]
+fn test_changed_region_ignores_noise_within_tolerance() {
+    let before = solid(8, 8, 100);
+    let mut after = before.clone();
+    set_pixel(&mut after, 4, 4, [110, 95, 100, 100]);
+
+    assert_eq!(changed_region(&before, &after, 16), None);
+    assert_eq!(changed_region(&before, &after, 4), Some((4, 4, 1, 1)));
+}
+
+#[test]
+fn test_resized_capture_counts_as_fully_changed() {
+    let before = solid(8, 8, 0);
+    let after = solid(10, 6, 0);
+    assert_eq!(changed_region(&before, &after, 0), Some((0, 0, 10, 6)));
+}
+
+#[test]
+fn test_crop_copies_region_and_clips_to_image() {
+    let mut shot = solid(6, 4, 0);
+    set_pixel(&mut shot, 2, 1, [1, 2, 3, 4]);
+
+    let crop = shot.crop(2, 1, 3, 2);
+    assert_eq!((crop.width, crop.height), (3, 2));
+    assert_eq!(crop.image_data.len(), 3 * 2 * 4);
+    assert_eq!(&crop.image_data[0..4], &[1, 2, 3, 4]);
+
+    let clipped = shot.crop(4, 3, 10, 10);
+    assert_eq!((clipped.width, clipped.height), (2, 1));
+
+    let outside = shot.crop(10, 10, 5, 5);
+    assert_eq!((outside.width, outside.height), (0, 0));
+    assert!(outside.image_data.is_empty());
+}
@@ -0,0 +1,79 @@
+//! Tests for PNG compression levels in screenshot encoding
+
+use crate::{CompressionLevel, ScreenshotResult};
+
+/// A gradient with some structure, so compression levels actually differ
+fn gradient(width: u32, height: u32) -> ScreenshotResult {
+    let mut image_data = Vec::with_capacity((width * height * 4) as usize);
+    for y in 0..height {
+        for x in 0..width {
+            image_data.extend_from_slice(&[
+                (x % 256) as u8,
+                (y % 256) as u8,
+                ((x ^ y) % 7) as u8,
+                255,
+            ]);
+        }
+    }
+    ScreenshotResult {
+        image_data,
+        width,
+        height,
+        monitor: None,
+    }
+}
+
+fn decode(png: &[u8]) -> image::RgbaImage {
+    image::load_from_memory_with_format(png, image::ImageFormat::Png)
+        .unwrap()
+        .to_rgba8()
+}
+
+#[test]
+fn test_every_compression_level_is_lossless() {
+    let shot = gradient(300, 200);
+    let fast = decode(
+        &shot
+            .to_png_resized_with(None, CompressionLevel::Fast)
+            .unwrap(),
+    );
+
+    for level in [CompressionLevel::Default, CompressionLevel::Best] {
+        let decoded = decode(&shot.to_png_resized_with(None, level).unwrap());
+        assert_eq!(decoded, fast, "{level:?} decodes to different pixels");
+    }
+    // BGRA input comes out as RGBA
+    assert_eq!(fast.get_pixel(10, 20).0, [(10 ^ 20) % 7, 20, 10, 255]);
+}
+
+#[test]
+fn test_best_compression_is_not_larger_than_fast() {
+    let shot = gradient(300, 200);
+    let fast = shot
+        .to_png_resized_with(None, CompressionLevel::Fast)
+        .unwrap();
+    let best = shot
+        .to_png_resized_with(None, CompressionLevel::Best)
+        .unwrap();
+    assert!(
+        best.len() <= fast.len(),
+        "best {} > fast {}",
+        best.len(),
+        fast.len()
+    );
+}
+
+#[test]
+fn test_default_encoding_is_unchanged() {
+    let shot = gradient(300, 200);
+    assert_eq!(CompressionLevel::default(), CompressionLevel::Default);
+    assert_eq!(
+        shot.to_png().unwrap(),
+        shot.to_png_with(CompressionLevel::Default).unwrap()
+    );
+    assert_eq!(
+        shot.to_png_resized(None).unwrap(),
+        shot.to_png_resized_with(None, CompressionLevel::Default)
+            .unwrap()
+    );
+}
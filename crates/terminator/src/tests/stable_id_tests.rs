@@ -0,0 +1,101 @@
+use super::node;
+use crate::platforms::mock::MockEngine;
+use crate::platforms::TreeBuildConfig;
+use crate::{Desktop, UINode};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+fn editor(buttons: Vec<UINode>) -> Desktop {
+    let engine = Arc::new(MockEngine::new(node(
+        "desktop",
+        "Pane",
+        "Desktop",
+        vec![node(
+            "editor",
+            "Window",
+            "Editor",
+            vec![node("toolbar", "ToolBar", "Tools", buttons)],
+        )],
+    )));
+    Desktop::with_engine(engine)
+}
+
+fn stable_tree(desktop: &Desktop) -> UINode {
+    desktop
+        .get_window_tree(
+            0,
+            None,
+            Some(TreeBuildConfig {
+                stable_ids: true,
+                ..Default::default()
+            }),
+        )
+        .unwrap()
+}
+
+fn button_ids(tree: &UINode) -> Vec<String> {
+    tree.children[0]
+        .children
+        .iter()
+        .map(|button| button.id.clone().unwrap())
+        .collect()
+}
+
+#[test]
+fn test_stable_ids_survive_recapture_and_reordering() {
+    let first = editor(vec![
+        node("a1", "Button", "Save", vec![]),
+        node("a2", "Button", "Open", vec![]),
+    ]);
+    // A later session: different element ids, siblings in another order
+    let second = editor(vec![
+        node("b1", "Button", "Open", vec![]),
+        node("b2", "Button", "Save", vec![]),
+    ]);
+
+    let first_ids = button_ids(&stable_tree(&first));
+    let second_ids = button_ids(&stable_tree(&second));
+
+    assert_eq!(first_ids[0], second_ids[1]);
+    assert_eq!(first_ids[1], second_ids[0]);
+    assert_ne!(first_ids[0], first_ids[1]);
+}
+
+#[test]
+fn test_stable_ids_use_automation_id_for_unnamed_elements() {
+    let unnamed = |element_id: &str, automation_id: &str| {
+        let mut button = node(element_id, "Button", "", vec![]);
+        button.attributes.properties = HashMap::from([(
+            "AutomationId".to_string(),
+            Some(serde_json::json!(automation_id)),
+        )]);
+        button
+    };
+    let desktop = editor(vec![unnamed("a1", "bold"), unnamed("a2", "italic")]);
+
+    let ids = button_ids(&stable_tree(&desktop));
+    assert_ne!(ids[0], ids[1]);
+}
+
+#[test]
+fn test_element_ids_are_kept_by_default() {
+    let desktop = editor(vec![node("a1", "Button", "Save", vec![])]);
+
+    let tree = desktop.get_window_tree(0, None, None).unwrap();
+    assert_eq!(tree.id.as_deref(), Some("editor"));
+}
+
+#[test]
+fn test_stable_ids_match_per_node_ids_from_selector_paths() {
+    // Tree builders that assign ids as they go hash each node's chained selector
+    let desktop = editor(vec![node("a1", "Button", "Save", vec![])]);
+
+    let tree = stable_tree(&desktop);
+    let button = &tree.children[0].children[0];
+    let path =
+        "role:Window && name:Editor >> role:ToolBar && name:Tools >> role:Button && name:Save";
+    assert_eq!(
+        button.id.as_deref(),
+        Some(UINode::stable_id(path, &button.attributes).as_str())
+    );
+}
@@ -0,0 +1,101 @@
+use super::node;
+use crate::platforms::mock::MockEngine;
+use crate::{AutomationError, Desktop, StaleHandling};
+use std::sync::Arc;
+
+fn inbox() -> (Arc<MockEngine>, Desktop) {
+    let engine = Arc::new(MockEngine::new(node(
+        "desktop",
+        "Pane",
+        "Desktop",
+        vec![node(
+            "inbox",
+            "Window",
+            "Inbox",
+            vec![
+                node("first", "ListItem", "First", vec![]),
+                node("second", "ListItem", "Second", vec![]),
+            ],
+        )],
+    )));
+    let desktop = Desktop::with_engine(engine.clone());
+    (engine, desktop)
+}
+
+#[tokio::test]
+async fn live_elements_are_returned_as_found() {
+    let (_engine, desktop) = inbox();
+    let mut items = desktop
+        .locator("role:ListItem")
+        .all_tracked(None, None)
+        .await
+        .unwrap();
+
+    assert_eq!(items.len(), 2);
+    assert!(!items[1].is_stale());
+    assert_eq!(
+        items[1].resolve().await.unwrap().id().as_deref(),
+        Some("second")
+    );
+}
+
+#[tokio::test]
+async fn stale_element_fails_by_default() {
+    let (engine, desktop) = inbox();
+    let mut items = desktop
+        .locator("role:ListItem")
+        .all_tracked(None, None)
+        .await
+        .unwrap();
+
+    engine
+        .replace_element("second", node("second-v2", "ListItem", "Second", vec![]))
+        .unwrap();
+
+    assert!(items[1].is_stale());
+    assert!(matches!(
+        items[1].resolve().await,
+        Err(AutomationError::ElementNotFound(_))
+    ));
+}
+
+#[tokio::test]
+async fn refresh_reacquires_the_replacement() {
+    let (engine, desktop) = inbox();
+    let mut items = desktop
+        .locator("role:ListItem")
+        .with_stale_handling(StaleHandling::Refresh)
+        .all_tracked(None, None)
+        .await
+        .unwrap();
+
+    engine
+        .replace_element("second", node("second-v2", "ListItem", "Second", vec![]))
+        .unwrap();
+
+    let element = items[1].resolve().await.unwrap();
+    assert_eq!(element.id().as_deref(), Some("second-v2"));
+    assert!(!items[1].is_stale());
+    element.click().unwrap();
+    assert_eq!(engine.actions()[0].element_id.as_deref(), Some("second-v2"));
+}
+
+#[tokio::test]
+async fn refresh_fails_when_nothing_takes_the_place() {
+    let (engine, desktop) = inbox();
+    let mut items = desktop
+        .locator("role:ListItem")
+        .with_stale_handling(StaleHandling::Refresh)
+        .all_tracked(None, None)
+        .await
+        .unwrap();
+
+    engine
+        .replace_element("first", node("first-v2", "ListItem", "Renamed", vec![]))
+        .unwrap();
+
+    assert!(matches!(
+        items[0].resolve().await,
+        Err(AutomationError::ElementNotFound(_))
+    ));
+}
@@ -9,6 +9,7 @@ use crate::element::{SerializableUIElement, UIElementAttributes};
 use crate::types::{OmniparserItem, VisionElement};
 use crate::OcrElement;
 use crate::UINode;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 /// Result of UI tree formatting - includes both the formatted string and bounds mapping
@@ -133,6 +134,10 @@ pub fn serializable_to_ui_node(element: &SerializableUIElement) -> UINode {
             is_selected: element.is_selected,
             child_count: element.child_count,
             index_in_parent: element.index_in_parent,
+            shortcut: element.shortcut.clone(),
+            labeled_by: element.labeled_by.clone(),
+            described_by: element.described_by.clone(),
+            controller_for: element.controller_for.clone(),
         },
         children: element
             .children
@@ -171,6 +176,10 @@ fn ui_node_to_serializable(node: &UINode) -> SerializableUIElement {
         is_selected: node.attributes.is_selected,
         child_count: node.attributes.child_count,
         index_in_parent: node.attributes.index_in_parent,
+        shortcut: node.attributes.shortcut.clone(),
+        labeled_by: node.attributes.labeled_by.clone(),
+        described_by: node.attributes.described_by.clone(),
+        controller_for: node.attributes.controller_for.clone(),
         selector: node.selector.clone(),
     }
 }
@@ -293,6 +302,26 @@ fn format_node(
         context_parts.push("toggled".to_string());
     }
 
+    if let Some(ref shortcut) = node.shortcut {
+        if !shortcut.is_empty() {
+            context_parts.push(format!("shortcut: {shortcut}"));
+        }
+    }
+
+    if let Some(ref labeled_by) = node.labeled_by {
+        if !labeled_by.is_empty() {
+            context_parts.push(format!("labeled by: {labeled_by}"));
+        }
+    }
+
+    if !node.described_by.is_empty() {
+        context_parts.push(format!("described by: {}", node.described_by.join(", ")));
+    }
+
+    if !node.controller_for.is_empty() {
+        context_parts.push(format!("controls: {}", node.controller_for.join(", ")));
+    }
+
     // Add value if present
     if let Some(ref value) = node.value {
         if !value.is_empty() {
@@ -719,6 +748,62 @@ pub fn format_clustered_tree_from_caches(
     }
 }
 
+// ============================================================================
+// Flat Tree Output - One row per element with parent pointers
+// ============================================================================
+
+/// A UI tree node without its children, linked to its parent by id
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FlatNode {
+    /// Position in pre-order traversal (root = 0), stable for a given tree
+    pub id: usize,
+    /// `id` of the parent node, None for the root
+    pub parent_id: Option<usize>,
+    /// Depth below the root (root = 0)
+    pub depth: usize,
+    /// Platform element id, if the node has one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub element_id: Option<String>,
+    pub role: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// (x, y, width, height)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bounds: Option<(f64, f64, f64, f64)>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub selector: Option<String>,
+}
+
+/// Flatten a UI tree into a list of nodes in pre-order, each pointing at its parent.
+///
+/// Parents always come before their children, so the list can be inserted into a
+/// database or graph library in order.
+pub fn flatten(tree: &UINode) -> Vec<FlatNode> {
+    let mut nodes = Vec::new();
+    // (node, parent id, depth); children are pushed in reverse to pop in order
+    let mut stack: Vec<(&UINode, Option<usize>, usize)> = vec![(tree, None, 0)];
+    while let Some((node, parent_id, depth)) = stack.pop() {
+        let id = nodes.len();
+        nodes.push(FlatNode {
+            id,
+            parent_id,
+            depth,
+            element_id: node.id.clone(),
+            role: node.attributes.role.clone(),
+            name: node.attributes.name.clone(),
+            bounds: node.attributes.bounds,
+            selector: node.selector.clone(),
+        });
+        stack.extend(
+            node.children
+                .iter()
+                .rev()
+                .map(|child| (child, Some(id), depth + 1)),
+        );
+    }
+    nodes
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -747,6 +832,10 @@ mod tests {
             is_selected: None,
             child_count: None,
             index_in_parent: None,
+            shortcut: None,
+            labeled_by: None,
+            described_by: Vec::new(),
+            controller_for: Vec::new(),
             selector: None,
         };
 
@@ -758,6 +847,40 @@ mod tests {
         assert!(result.index_to_bounds.contains_key(&1));
     }
 
+    #[test]
+    fn test_shortcut_formatting() {
+        let mut node = SerializableUIElement::new("MenuItem".to_string());
+        node.name = Some("Save".to_string());
+        node.bounds = Some((0.0, 0.0, 80.0, 20.0));
+        node.shortcut = Some("Ctrl+S".to_string());
+
+        let result = format_tree_as_compact_yaml(&node, 0);
+        assert!(result.formatted.contains("#1 [MenuItem] Save"));
+        assert!(result.formatted.contains("shortcut: Ctrl+S"));
+
+        let round_trip = ui_node_to_serializable(&serializable_to_ui_node(&node));
+        assert_eq!(round_trip.shortcut.as_deref(), Some("Ctrl+S"));
+    }
+
+    #[test]
+    fn test_labeled_by_formatting() {
+        let mut node = SerializableUIElement::new("Edit".to_string());
+        node.bounds = Some((80.0, 10.0, 200.0, 20.0));
+        node.labeled_by = Some("First name".to_string());
+        node.described_by = vec!["Required".to_string()];
+        node.controller_for = vec!["Suggestions".to_string()];
+
+        let result = format_tree_as_compact_yaml(&node, 0);
+        assert!(result.formatted.contains("labeled by: First name"));
+        assert!(result.formatted.contains("described by: Required"));
+        assert!(result.formatted.contains("controls: Suggestions"));
+
+        let round_trip = ui_node_to_serializable(&serializable_to_ui_node(&node));
+        assert_eq!(round_trip.labeled_by.as_deref(), Some("First name"));
+        assert_eq!(round_trip.described_by, vec!["Required".to_string()]);
+        assert_eq!(round_trip.controller_for, vec!["Suggestions".to_string()]);
+    }
+
     #[test]
     fn test_nested_formatting() {
         let child = SerializableUIElement {
@@ -782,6 +905,10 @@ mod tests {
             is_selected: None,
             child_count: None,
             index_in_parent: None,
+            shortcut: None,
+            labeled_by: None,
+            described_by: Vec::new(),
+            controller_for: Vec::new(),
             selector: None,
         };
 
@@ -807,6 +934,10 @@ mod tests {
             is_selected: None,
             child_count: None,
             index_in_parent: None,
+            shortcut: None,
+            labeled_by: None,
+            described_by: Vec::new(),
+            controller_for: Vec::new(),
             selector: None,
         };
 
@@ -840,6 +971,10 @@ mod tests {
             is_selected: None,
             child_count: None,
             index_in_parent: None,
+            shortcut: None,
+            labeled_by: None,
+            described_by: Vec::new(),
+            controller_for: Vec::new(),
             selector: Some("role:Button && name:Click Me".to_string()),
         };
 
@@ -865,6 +1000,10 @@ mod tests {
             is_selected: None,
             child_count: None,
             index_in_parent: None,
+            shortcut: None,
+            labeled_by: None,
+            described_by: Vec::new(),
+            controller_for: Vec::new(),
             selector: None,
         };
 
@@ -890,6 +1029,10 @@ mod tests {
             is_selected: None,
             child_count: None,
             index_in_parent: None,
+            shortcut: None,
+            labeled_by: None,
+            described_by: Vec::new(),
+            controller_for: Vec::new(),
             selector: None,
         };
 
@@ -906,4 +1049,45 @@ mod tests {
             Some("role:Button && name:Click Me".to_string())
         );
     }
+
+    #[test]
+    fn test_flatten_links_children_to_parents_in_order() {
+        let node = |role: &str, name: &str, children: Vec<UINode>| UINode {
+            attributes: UIElementAttributes {
+                role: role.to_string(),
+                name: Some(name.to_string()),
+                ..Default::default()
+            },
+            children,
+            ..Default::default()
+        };
+        let tree = node(
+            "Window",
+            "App",
+            vec![
+                node("Pane", "Toolbar", vec![node("Button", "Save", vec![])]),
+                node("Button", "Close", vec![]),
+            ],
+        );
+
+        let flat = flatten(&tree);
+        let rows: Vec<(usize, Option<usize>, usize, &str)> = flat
+            .iter()
+            .map(|n| (n.id, n.parent_id, n.depth, n.name.as_deref().unwrap()))
+            .collect();
+        assert_eq!(
+            rows,
+            vec![
+                (0, None, 0, "App"),
+                (1, Some(0), 1, "Toolbar"),
+                (2, Some(1), 2, "Save"),
+                (3, Some(0), 1, "Close"),
+            ]
+        );
+        assert_eq!(
+            flat,
+            flatten(&tree),
+            "ids should be stable for the same tree"
+        );
+    }
 }
@@ -37,6 +37,169 @@ impl Default for FontStyle {
     }
 }
 
+/// Axis-aligned screen rectangle in physical pixels
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct Rect {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+impl Rect {
+    pub fn new(x: f64, y: f64, width: f64, height: f64) -> Self {
+        Self {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    pub fn right(&self) -> f64 {
+        self.x + self.width
+    }
+
+    pub fn bottom(&self) -> f64 {
+        self.y + self.height
+    }
+
+    pub fn center(&self) -> (f64, f64) {
+        (self.x + self.width / 2.0, self.y + self.height / 2.0)
+    }
+
+    /// Overlapping area of the two rects, or None if they only touch or are apart
+    pub fn intersection(&self, other: &Rect) -> Option<Rect> {
+        let x = self.x.max(other.x);
+        let y = self.y.max(other.y);
+        let right = self.right().min(other.right());
+        let bottom = self.bottom().min(other.bottom());
+        (right > x && bottom > y).then(|| Rect::new(x, y, right - x, bottom - y))
+    }
+
+    /// True if the rects share a non-empty area (shared edges don't count)
+    pub fn overlaps(&self, other: &Rect) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    /// True if `other` lies entirely inside this rect
+    pub fn contains(&self, other: &Rect) -> bool {
+        other.x >= self.x
+            && other.y >= self.y
+            && other.right() <= self.right()
+            && other.bottom() <= self.bottom()
+    }
+
+    /// True if this rect ends at or above the top edge of `other`
+    pub fn is_above(&self, other: &Rect) -> bool {
+        self.bottom() <= other.y
+    }
+
+    /// True if this rect ends at or left of the left edge of `other`
+    pub fn is_left_of(&self, other: &Rect) -> bool {
+        self.right() <= other.x
+    }
+}
+
+impl From<(f64, f64, f64, f64)> for Rect {
+    fn from((x, y, width, height): (f64, f64, f64, f64)) -> Self {
+        Self::new(x, y, width, height)
+    }
+}
+
+/// Converts rects found in a window capture into logical screen coordinates.
+///
+/// Implement this for capture pipelines whose pixel space differs from the
+/// standard "window screenshot, optionally downscaled" case.
+pub trait CoordinateConverter {
+    /// Map a rect in capture pixel space to logical screen coordinates
+    fn physical_to_screen(&self, rect: Rect) -> Rect;
+
+    /// Map a `[x_min, y_min, x_max, y_max]` box in capture pixel space to screen coordinates
+    fn box_to_screen(&self, box_2d: [f64; 4]) -> [f64; 4] {
+        let rect = self.physical_to_screen(Rect::new(
+            box_2d[0],
+            box_2d[1],
+            box_2d[2] - box_2d[0],
+            box_2d[3] - box_2d[1],
+        ));
+        [rect.x, rect.y, rect.right(), rect.bottom()]
+    }
+}
+
+/// Maps vision/OCR results from a window screenshot back to screen coordinates.
+///
+/// Screenshots are captured in physical pixels and may be downscaled before being
+/// sent to a model, while window bounds and click targets are in logical pixels.
+/// A capture-space point is mapped as `origin + point / resize_scale / dpi_scale`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CoordinateMapper {
+    /// Top-left corner of the captured window in logical screen coordinates
+    pub window_origin: (f64, f64),
+    /// Physical pixels per logical pixel on the (x, y) axes
+    pub dpi_scale: (f64, f64),
+    /// Factor the screenshot was resized by before analysis (1.0 when not resized)
+    pub resize_scale: f64,
+}
+
+impl CoordinateMapper {
+    pub fn new(window_origin: (f64, f64), dpi_scale: (f64, f64), resize_scale: f64) -> Self {
+        Self {
+            window_origin,
+            dpi_scale,
+            resize_scale,
+        }
+    }
+
+    /// Build a mapper for a capture of a window with the given logical bounds.
+    ///
+    /// The DPI scale is derived from the capture size relative to the window size,
+    /// falling back to 1.0 on an axis where the window reports no extent.
+    pub fn for_capture(
+        window_bounds: (f64, f64, f64, f64),
+        capture_width: u32,
+        capture_height: u32,
+    ) -> Self {
+        let (x, y, width, height) = window_bounds;
+        let scale = |physical: u32, logical: f64| {
+            if logical > 0.0 {
+                physical as f64 / logical
+            } else {
+                1.0
+            }
+        };
+        Self::new(
+            (x, y),
+            (scale(capture_width, width), scale(capture_height, height)),
+            1.0,
+        )
+    }
+
+    /// Set the factor the capture was resized by before analysis
+    pub fn with_resize_scale(mut self, resize_scale: f64) -> Self {
+        self.resize_scale = resize_scale;
+        self
+    }
+
+    /// Map a rect in capture pixel space to logical screen coordinates
+    pub fn physical_to_screen(&self, rect: Rect) -> Rect {
+        let sx = self.resize_scale * self.dpi_scale.0;
+        let sy = self.resize_scale * self.dpi_scale.1;
+        Rect::new(
+            self.window_origin.0 + rect.x / sx,
+            self.window_origin.1 + rect.y / sy,
+            rect.width / sx,
+            rect.height / sy,
+        )
+    }
+}
+
+impl CoordinateConverter for CoordinateMapper {
+    fn physical_to_screen(&self, rect: Rect) -> Rect {
+        CoordinateMapper::physical_to_screen(self, rect)
+    }
+}
+
 /// Handle for managing active highlights with cleanup
 pub struct HighlightHandle {
     pub(crate) should_close: Arc<AtomicBool>,
@@ -72,3 +72,43 @@ pub fn find_pid_for_process(desktop: &Desktop, process_name: &str) -> Result<u32
             ))
         })
 }
+
+/// Read two pipes to the end concurrently. Returns each stream on its own plus both
+/// interleaved line by line in the order the lines arrived.
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+pub(crate) async fn read_interleaved(
+    stdout: impl tokio::io::AsyncRead + Unpin,
+    stderr: impl tokio::io::AsyncRead + Unpin,
+) -> std::io::Result<(String, String, String)> {
+    use tokio::io::{AsyncBufReadExt, BufReader};
+
+    let (mut stdout, mut stderr) = (BufReader::new(stdout), BufReader::new(stderr));
+    let (mut out, mut err, mut combined) = (Vec::new(), Vec::new(), Vec::new());
+    // Partial lines survive a cancelled read_until, so the buffers outlive each select
+    let (mut out_line, mut err_line) = (Vec::new(), Vec::new());
+    let (mut out_open, mut err_open) = (true, true);
+
+    while out_open || err_open {
+        tokio::select! {
+            read = stdout.read_until(b'\n', &mut out_line), if out_open => {
+                if read? == 0 {
+                    out_open = false;
+                } else {
+                    out.extend_from_slice(&out_line);
+                    combined.append(&mut out_line);
+                }
+            }
+            read = stderr.read_until(b'\n', &mut err_line), if err_open => {
+                if read? == 0 {
+                    err_open = false;
+                } else {
+                    err.extend_from_slice(&err_line);
+                    combined.append(&mut err_line);
+                }
+            }
+        }
+    }
+
+    let text = |bytes: Vec<u8>| String::from_utf8_lossy(&bytes).into_owned();
+    Ok((text(out), text(err), text(combined)))
+}
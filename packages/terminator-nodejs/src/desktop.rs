@@ -1,6 +1,6 @@
 use crate::types::{
     ClickResult, ClickType, ComputerUseResult, ComputerUseStep, Monitor, MonitorScreenshotPair,
-    ResizedDimensions, TreeOutputFormat, VisionType, WindowTreeResult,
+    PngCompression, ResizedDimensions, TreeOutputFormat, VisionType, WindowTreeResult,
 };
 use crate::Selector;
 use crate::{
@@ -199,6 +199,25 @@ impl Desktop {
         self.inner.activate_application(&name).map_err(map_error)
     }
 
+    /// (async) Activate an application and wait until its window is foreground.
+    ///
+    /// Use instead of sleeping after `activateApplication` before sending input.
+    ///
+    /// @param {string} name - The name of the application to activate.
+    /// @param {number} [timeoutMs=5000] - How long to wait for the window to become foreground.
+    #[napi]
+    pub async fn activate_application_and_wait(
+        &self,
+        name: String,
+        timeout_ms: Option<f64>,
+    ) -> napi::Result<()> {
+        let timeout = std::time::Duration::from_millis(timeout_ms.unwrap_or(5000.0) as u64);
+        self.inner
+            .activate_application_and_wait(&name, timeout)
+            .await
+            .map_err(map_error)
+    }
+
     /// Click within element bounds at a specified position.
     ///
     /// This is useful for clicking on elements from UI tree, OCR, omniparser, gemini vision, or DOM
@@ -351,6 +370,7 @@ impl Desktop {
                 exit_status: r.exit_status,
                 stdout: r.stdout,
                 stderr: r.stderr,
+                combined: r.combined,
             })
             .map_err(map_error)
     }
@@ -360,6 +380,7 @@ impl Desktop {
     /// @param {string} command - The command to run (can be single or multi-line).
     /// @param {string} [shell] - Optional shell to use (defaults to PowerShell on Windows, bash on Unix).
     /// @param {string} [workingDirectory] - Optional working directory for the command.
+    /// @param {boolean} [captureCombined] - Also return stdout and stderr interleaved in the order they were written, in `combined`. Defaults to false.
     /// @returns {Promise<CommandOutput>} The command output.
     #[napi]
     pub async fn run(
@@ -367,18 +388,23 @@ impl Desktop {
         command: String,
         shell: Option<String>,
         working_directory: Option<String>,
+        capture_combined: Option<bool>,
     ) -> napi::Result<CommandOutput> {
         self.inner
-            .run(
+            .run_with_options(
                 command.as_str(),
                 shell.as_deref(),
                 working_directory.as_deref(),
+                terminator::RunCommandOptions {
+                    capture_combined: capture_combined.unwrap_or(false),
+                },
             )
             .await
             .map(|r| CommandOutput {
                 exit_status: r.exit_status,
                 stdout: r.stdout,
                 stderr: r.stderr,
+                combined: r.combined,
             })
             .map_err(map_error)
     }
@@ -1896,6 +1922,9 @@ impl Desktop {
                 include_omniparser: None,
                 include_ocr: None,
                 include_browser_dom: None,
+                parallel_subtrees: c.parallel_subtrees,
+                include_relationships: c.include_relationships,
+                stable_ids: c.stable_ids,
             };
             if matches!(output_format, TreeOutputFormat::VerboseJson) {
                 c_clone.format_output = Some(false);
@@ -2308,16 +2337,21 @@ impl Desktop {
     ///
     /// @param {ScreenshotResult} screenshot - The screenshot to convert.
     /// @param {number} [maxDimension] - Maximum width or height. Defaults to 1920.
+    /// @param {PngCompression} [pngCompression='Default'] - Compression effort: 'Fast', 'Default' or 'Best'.
     /// @returns {Buffer} PNG-encoded bytes (potentially resized).
     #[napi(js_name = "screenshotToPngResized")]
     pub fn screenshot_to_png_resized(
         &self,
         screenshot: ScreenshotResult,
         max_dimension: Option<u32>,
+        png_compression: Option<PngCompression>,
     ) -> napi::Result<Vec<u8>> {
         screenshot
             .to_inner()
-            .to_png_resized(max_dimension)
+            .to_png_resized_with(
+                max_dimension,
+                png_compression.map(Into::into).unwrap_or_default(),
+            )
             .map_err(|e| napi::Error::from_reason(e.to_string()))
     }
 
@@ -2340,16 +2374,21 @@ impl Desktop {
     ///
     /// @param {ScreenshotResult} screenshot - The screenshot to convert.
     /// @param {number} [maxDimension] - Maximum width or height. Defaults to 1920.
+    /// @param {PngCompression} [pngCompression='Default'] - Compression effort: 'Fast', 'Default' or 'Best'.
     /// @returns {string} Base64-encoded PNG string (potentially resized).
     #[napi(js_name = "screenshotToBase64PngResized")]
     pub fn screenshot_to_base64_png_resized(
         &self,
         screenshot: ScreenshotResult,
         max_dimension: Option<u32>,
+        png_compression: Option<PngCompression>,
     ) -> napi::Result<String> {
         screenshot
             .to_inner()
-            .to_base64_png_resized(max_dimension)
+            .to_base64_png_resized_with(
+                max_dimension,
+                png_compression.map(Into::into).unwrap_or_default(),
+            )
             .map_err(|e| napi::Error::from_reason(e.to_string()))
     }
 
@@ -52,6 +52,8 @@ pub struct ActionOptions {
     pub ui_diff_before_after: Option<bool>,
     /// Max depth for tree capture when doing UI diff.
     pub ui_diff_max_depth: Option<u32>,
+    /// Whether to also screenshot the window before/after the action and return a crop of the changed region. Only used with uiDiffBeforeAfter. Defaults to false.
+    pub capture_visual_diff: Option<bool>,
     /// Click position within element bounds. If not specified, clicks at center.
     pub click_position: Option<ClickPosition>,
     /// Type of click: 'Left', 'Double', or 'Right'. Defaults to 'Left'.
@@ -222,6 +224,9 @@ impl Element {
                 width,
                 height,
             }),
+            labeled_by: attrs.labeled_by,
+            described_by: attrs.described_by,
+            controller_for: attrs.controller_for,
         }
     }
 
@@ -305,7 +310,9 @@ impl Element {
             let diff_options = terminator::UiDiffOptions {
                 max_depth: opts.ui_diff_max_depth.map(|d| d as usize),
                 settle_delay_ms: Some(1500),
+                settle: None,
                 include_detailed_attributes: Some(true),
+                capture_visual_diff: opts.capture_visual_diff.unwrap_or(false),
             };
 
             // Get desktop to call execute_on_element_with_ui_diff
@@ -335,6 +342,7 @@ impl Element {
                     let ui_diff_converted = ui_diff.map(|d| crate::types::UiDiffResult {
                         diff: d.diff,
                         has_changes: d.has_changes,
+                        visual_diff: d.visual_diff.map(crate::types::VisualDiff::from),
                     });
                     ClickResult {
                         method: click_result.method,
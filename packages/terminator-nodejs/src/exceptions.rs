@@ -61,5 +61,12 @@ pub fn map_error(err: AutomationError) -> napi::Error {
             Status::GenericFailure,
             format!("VERIFICATION_FAILED: {msg}"),
         ),
+        AutomationError::TraversalLimitExceeded(msg) => napi::Error::new(
+            Status::GenericFailure,
+            format!("TRAVERSAL_LIMIT_EXCEEDED: {msg}"),
+        ),
+        AutomationError::ElevationRequired(msg) => {
+            napi::Error::new(Status::GenericFailure, format!("ELEVATION_REQUIRED: {msg}"))
+        }
     }
 }
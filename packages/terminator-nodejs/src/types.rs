@@ -24,6 +24,47 @@ pub struct UiDiffResult {
     pub diff: String,
     /// Whether any UI changes were detected
     pub has_changes: bool,
+    /// Crop of the visually changed region if captureVisualDiff was enabled and pixels changed
+    pub visual_diff: Option<VisualDiff>,
+}
+
+/// Screenshot of the region that visually changed during an action
+#[napi(object, js_name = "VisualDiff")]
+pub struct VisualDiff {
+    /// The after-action window screenshot cropped to the changed region
+    pub screenshot: ScreenshotResult,
+    /// Changed region in window screenshot pixels
+    pub region: Bounds,
+    /// Changed region in screen coordinates, for clicking or highlighting
+    pub screen_bounds: Bounds,
+}
+
+impl From<terminator::VisualDiff> for VisualDiff {
+    fn from(visual_diff: terminator::VisualDiff) -> Self {
+        let (x, y, width, height) = visual_diff.region;
+        let screenshot = visual_diff.screenshot;
+        let screen_bounds = visual_diff.screen_bounds;
+        VisualDiff {
+            screenshot: ScreenshotResult {
+                width: screenshot.width,
+                height: screenshot.height,
+                image_data: screenshot.image_data,
+                monitor: screenshot.monitor.map(Monitor::from),
+            },
+            region: Bounds {
+                x: x as f64,
+                y: y as f64,
+                width: width as f64,
+                height: height as f64,
+            },
+            screen_bounds: Bounds {
+                x: screen_bounds.x,
+                y: screen_bounds.y,
+                width: screen_bounds.width,
+                height: screen_bounds.height,
+            },
+        }
+    }
 }
 
 #[napi(object, js_name = "ClickResult")]
@@ -73,6 +114,27 @@ impl From<ClickType> for terminator::ClickType {
     }
 }
 
+/// PNG compression effort, trading encode time against file size
+#[napi(string_enum, js_name = "PngCompression")]
+pub enum PngCompression {
+    /// Quickest encode, largest files
+    Fast,
+    /// Balanced encode time and size (default)
+    Default,
+    /// Smallest files, slowest encode
+    Best,
+}
+
+impl From<PngCompression> for terminator::CompressionLevel {
+    fn from(level: PngCompression) -> Self {
+        match level {
+            PngCompression::Fast => terminator::CompressionLevel::Fast,
+            PngCompression::Default => terminator::CompressionLevel::Default,
+            PngCompression::Best => terminator::CompressionLevel::Best,
+        }
+    }
+}
+
 /// Source of indexed elements for click targeting
 #[napi(string_enum, js_name = "VisionType")]
 pub enum VisionType {
@@ -105,6 +167,9 @@ pub struct CommandOutput {
     pub exit_status: Option<i32>,
     pub stdout: String,
     pub stderr: String,
+    /// stdout and stderr interleaved line by line in the order they were written.
+    /// Only set when `run` is called with `captureCombined`.
+    pub combined: Option<String>,
 }
 
 #[derive(Clone)]
@@ -174,6 +239,12 @@ pub struct UIElementAttributes {
     pub properties: HashMap<String, Option<String>>,
     pub is_keyboard_focusable: Option<bool>,
     pub bounds: Option<Bounds>,
+    /// Name of the element that labels this one. Only set when the tree is built with `includeRelationships`.
+    pub labeled_by: Option<String>,
+    /// Names of the elements that describe this one. Only set with `includeRelationships`.
+    pub described_by: Vec<String>,
+    /// Names of the elements this one controls. Only set with `includeRelationships`.
+    pub controller_for: Vec<String>,
 }
 
 #[derive(Serialize)]
@@ -509,6 +580,12 @@ pub struct TreeBuildConfig {
     pub include_ocr: Option<bool>,
     /// Include browser DOM elements (requires Terminator Bridge extension). Elements prefixed with #d1, #d2, etc.
     pub include_browser_dom: Option<bool>,
+    /// Maximum threads used to build sibling subtrees in parallel when walking element by element. Defaults to serial.
+    pub parallel_subtrees: Option<i32>,
+    /// Add "labeled by", "described by" and "controls" relationships to each element of the formatted tree. Costs extra calls per element. Defaults to false.
+    pub include_relationships: Option<bool>,
+    /// Replace element ids with ids derived from each element's position, role, name and automation id, so the same element keeps its id across captures. These ids cannot be used in #id selectors. Defaults to false.
+    pub stable_ids: Option<bool>,
 }
 
 impl From<(f64, f64, f64, f64)> for Bounds {
@@ -647,6 +724,9 @@ impl From<terminator::UIElementAttributes> for UIElementAttributes {
                 width,
                 height,
             }),
+            labeled_by: attrs.labeled_by,
+            described_by: attrs.described_by,
+            controller_for: attrs.controller_for,
         }
     }
 }
@@ -784,6 +864,10 @@ impl From<TreeBuildConfig> for terminator::platforms::TreeBuildConfig {
             show_overlay: false, // Use Desktop.showInspectOverlay() method instead
             overlay_display_mode: None,
             from_selector: config.tree_from_selector, // Pass through to core SDK
+            include_shortcuts: false,
+            include_relationships: config.include_relationships.unwrap_or(false),
+            stable_ids: config.stable_ids.unwrap_or(false),
+            parallel_subtrees: config.parallel_subtrees.map(|x| x as usize),
         }
     }
 }
@@ -804,6 +888,9 @@ pub(crate) fn serializable_to_ui_node(elem: &terminator::SerializableUIElement)
             width: w,
             height: h,
         }),
+        labeled_by: elem.labeled_by.clone(),
+        described_by: elem.described_by.clone(),
+        controller_for: elem.controller_for.clone(),
     };
 
     let children = elem
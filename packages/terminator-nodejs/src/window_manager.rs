@@ -256,6 +256,75 @@ impl WindowManager {
         }
     }
 
+    /// Make a window always-on-top, or return it to the normal Z-order band
+    #[napi]
+    pub async fn set_topmost(&self, hwnd: i64, topmost: bool) -> napi::Result<()> {
+        #[cfg(target_os = "windows")]
+        {
+            self.inner
+                .set_topmost(hwnd as isize, topmost)
+                .await
+                .map_err(|e| napi::Error::from_reason(format!("Failed to set topmost: {}", e)))
+        }
+        #[cfg(not(target_os = "windows"))]
+        {
+            Err(napi::Error::from_reason(
+                "WindowManager is only supported on Windows",
+            ))
+        }
+    }
+
+    /// Check if a window is always-on-top
+    #[napi]
+    pub async fn is_topmost(&self, hwnd: i64) -> napi::Result<bool> {
+        #[cfg(target_os = "windows")]
+        {
+            Ok(self.inner.is_topmost(hwnd as isize).await)
+        }
+        #[cfg(not(target_os = "windows"))]
+        {
+            Err(napi::Error::from_reason(
+                "WindowManager is only supported on Windows",
+            ))
+        }
+    }
+
+    /// Set a window's opacity, from 0 (fully transparent) to 255 (opaque)
+    #[napi]
+    pub async fn set_window_opacity(&self, hwnd: i64, alpha: u8) -> napi::Result<()> {
+        #[cfg(target_os = "windows")]
+        {
+            self.inner
+                .set_window_opacity(hwnd as isize, alpha)
+                .await
+                .map_err(|e| napi::Error::from_reason(format!("Failed to set opacity: {}", e)))
+        }
+        #[cfg(not(target_os = "windows"))]
+        {
+            Err(napi::Error::from_reason(
+                "WindowManager is only supported on Windows",
+            ))
+        }
+    }
+
+    /// Get a window's opacity, from 0 (fully transparent) to 255 (opaque)
+    #[napi]
+    pub async fn get_opacity(&self, hwnd: i64) -> napi::Result<u8> {
+        #[cfg(target_os = "windows")]
+        {
+            self.inner
+                .get_opacity(hwnd as isize)
+                .await
+                .map_err(|e| napi::Error::from_reason(format!("Failed to get opacity: {}", e)))
+        }
+        #[cfg(not(target_os = "windows"))]
+        {
+            Err(napi::Error::from_reason(
+                "WindowManager is only supported on Windows",
+            ))
+        }
+    }
+
     /// Capture current state before workflow
     #[napi]
     pub async fn capture_initial_state(&self) -> napi::Result<()> {
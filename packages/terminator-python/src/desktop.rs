@@ -159,14 +159,16 @@ impl Desktop {
 
     #[pyo3(
         name = "run",
-        text_signature = "($self, command, shell=None, working_directory=None)"
+        text_signature = "($self, command, shell=None, working_directory=None, capture_combined=False)"
     )]
+    #[pyo3(signature = (command, shell=None, working_directory=None, capture_combined=false))]
     /// (async) Execute a shell command using GitHub Actions-style syntax.
     ///
     /// Args:
     ///     command (str): The command to run (can be single or multi-line).
     ///     shell (Optional[str]): Optional shell to use (defaults to PowerShell on Windows, bash on Unix).
     ///     working_directory (Optional[str]): Optional working directory for the command.
+    ///     capture_combined (bool): Also return stdout and stderr interleaved in the order they were written, in `combined`. Defaults to False.
     ///
     /// Returns:
     ///     CommandOutput: The command output.
@@ -176,14 +178,16 @@ impl Desktop {
         command: String,
         shell: Option<String>,
         working_directory: Option<String>,
+        capture_combined: bool,
     ) -> PyResult<Bound<'py, PyAny>> {
         let desktop = self.inner.clone();
         pyo3_tokio::future_into_py_with_locals(py, TaskLocals::with_running_loop(py)?, async move {
             let result = desktop
-                .run(
+                .run_with_options(
                     command.as_str(),
                     shell.as_deref(),
                     working_directory.as_deref(),
+                    ::terminator_core::RunCommandOptions { capture_combined },
                 )
                 .await
                 .map_err(automation_error_to_pyerr)?;
@@ -86,6 +86,9 @@ impl UIElement {
                 width,
                 height,
             }),
+            labeled_by: attrs.labeled_by,
+            described_by: attrs.described_by,
+            controller_for: attrs.controller_for,
         })
     }
 
@@ -69,6 +69,16 @@ create_exception!(
     OperationCancelledError,
     pyo3::exceptions::PyRuntimeError
 );
+create_exception!(
+    terminator,
+    TraversalLimitExceededError,
+    pyo3::exceptions::PyRuntimeError
+);
+create_exception!(
+    terminator,
+    ElevationRequiredError,
+    pyo3::exceptions::PyRuntimeError
+);
 
 use ::terminator_core::errors::AutomationError;
 
@@ -94,5 +104,7 @@ pub fn automation_error_to_pyerr(e: AutomationError) -> pyo3::PyErr {
         AutomationError::ScrollFailed(_) => ScrollFailedError::new_err(msg),
         AutomationError::OperationCancelled(_) => OperationCancelledError::new_err(msg),
         AutomationError::VerificationFailed(_) => InternalError::new_err(msg),
+        AutomationError::TraversalLimitExceeded(_) => TraversalLimitExceededError::new_err(msg),
+        AutomationError::ElevationRequired(_) => ElevationRequiredError::new_err(msg),
     }
 }
@@ -69,6 +69,10 @@ pub struct CommandOutput {
     pub stdout: String,
     #[pyo3(get)]
     pub stderr: String,
+    /// stdout and stderr interleaved line by line in the order they were written.
+    /// Only set when run is called with capture_combined=True.
+    #[pyo3(get)]
+    pub combined: Option<String>,
 }
 
 /// UI Element attributes
@@ -92,6 +96,16 @@ pub struct UIElementAttributes {
     pub is_keyboard_focusable: Option<bool>,
     #[pyo3(get)]
     pub bounds: Option<Bounds>,
+    /// Name of the element that labels this one. Only set when the tree is built with
+    /// include_relationships.
+    #[pyo3(get)]
+    pub labeled_by: Option<String>,
+    /// Names of the elements that describe this one. Only set with include_relationships.
+    #[pyo3(get)]
+    pub described_by: Vec<String>,
+    /// Names of the elements this one controls. Only set with include_relationships.
+    #[pyo3(get)]
+    pub controller_for: Vec<String>,
 }
 
 /// Coordinates for mouse operations
@@ -193,6 +207,15 @@ pub struct TreeBuildConfig {
     pub yield_every_n_elements: Option<usize>,
     #[pyo3(get, set)]
     pub batch_size: Option<usize>,
+    /// Populate labeled_by/described_by/controller_for from UI Automation relationships.
+    /// Costs extra calls per element. Defaults to False.
+    #[pyo3(get, set)]
+    pub include_relationships: bool,
+    /// Replace element ids with ids derived from each element's position, role, name and
+    /// automation id, so the same element keeps its id across captures. These ids cannot
+    /// be used in #id selectors. Defaults to False.
+    #[pyo3(get, set)]
+    pub stable_ids: bool,
 }
 
 /// Position options for text overlays in highlighting
@@ -394,6 +417,7 @@ impl From<CoreCommandOutput> for CommandOutput {
             exit_status: r.exit_status,
             stdout: r.stdout,
             stderr: r.stderr,
+            combined: r.combined,
         }
     }
 }
@@ -431,6 +455,9 @@ impl From<::terminator_core::UIElementAttributes> for UIElementAttributes {
                 width,
                 height,
             }),
+            labeled_by: attrs.labeled_by,
+            described_by: attrs.described_by,
+            controller_for: attrs.controller_for,
         }
     }
 }
@@ -456,6 +483,10 @@ impl From<TreeBuildConfig> for ::terminator_core::platforms::TreeBuildConfig {
             show_overlay: false,
             overlay_display_mode: None,
             from_selector: None,
+            include_shortcuts: false,
+            include_relationships: config.include_relationships,
+            stable_ids: config.stable_ids,
+            parallel_subtrees: None,
         }
     }
 }
@@ -661,6 +692,8 @@ impl TreeBuildConfig {
             timeout_per_operation_ms: None,
             yield_every_n_elements: None,
             batch_size: None,
+            include_relationships: false,
+            stable_ids: false,
         }
     }
 
@@ -32,6 +32,11 @@ class CommandOutput:
     exit_status: typing.Optional[builtins.int]
     stdout: builtins.str
     stderr: builtins.str
+    combined: typing.Optional[builtins.str]
+    r"""
+    stdout and stderr interleaved line by line in the order they were written.
+    Only set when run is called with capture_combined=True.
+    """
     def __repr__(self) -> builtins.str: ...
     def __str__(self) -> builtins.str: ...
 
@@ -119,7 +124,7 @@ class Desktop:
         Returns:
             CommandOutput: The command output.
         """
-    async def run(self, command:builtins.str, shell:typing.Optional[builtins.str]=None, working_directory:typing.Optional[builtins.str]=None) -> CommandOutput:
+    async def run(self, command:builtins.str, shell:typing.Optional[builtins.str]=None, working_directory:typing.Optional[builtins.str]=None, capture_combined:builtins.bool=False) -> CommandOutput:
         r"""
         (async) Execute a shell command using GitHub Actions-style syntax.
 
@@ -127,6 +132,7 @@ class Desktop:
             command (str): The command to run (can be single or multi-line).
             shell (Optional[str]): Optional shell to use (defaults to PowerShell on Windows, bash on Unix).
             working_directory (Optional[str]): Optional working directory for the command.
+            capture_combined (bool): Also return stdout and stderr interleaved in the order they were written, in `combined`. Defaults to False.
 
         Returns:
             CommandOutput: The command output.
@@ -434,6 +440,17 @@ class TreeBuildConfig:
     timeout_per_operation_ms: typing.Optional[builtins.int]
     yield_every_n_elements: typing.Optional[builtins.int]
     batch_size: typing.Optional[builtins.int]
+    include_relationships: builtins.bool
+    r"""
+    Populate labeled_by/described_by/controller_for from UI Automation relationships.
+    Costs extra calls per element. Defaults to False.
+    """
+    stable_ids: builtins.bool
+    r"""
+    Replace element ids with ids derived from each element's position, role, name and
+    automation id, so the same element keeps its id across captures. These ids cannot
+    be used in #id selectors. Defaults to False.
+    """
     def __repr__(self) -> builtins.str: ...
     def __str__(self) -> builtins.str: ...
 
@@ -850,6 +867,19 @@ class UIElementAttributes:
     properties: builtins.dict[builtins.str, typing.Optional[builtins.str]]
     is_keyboard_focusable: typing.Optional[builtins.bool]
     bounds: typing.Optional[Bounds]
+    labeled_by: typing.Optional[builtins.str]
+    r"""
+    Name of the element that labels this one. Only set when the tree is built with
+    include_relationships.
+    """
+    described_by: builtins.list[builtins.str]
+    r"""
+    Names of the elements that describe this one. Only set with include_relationships.
+    """
+    controller_for: builtins.list[builtins.str]
+    r"""
+    Names of the elements this one controls. Only set with include_relationships.
+    """
     def __repr__(self) -> builtins.str: ...
     def __str__(self) -> builtins.str: ...
 